@@ -3,6 +3,7 @@ use argon2::{
     Argon2,
 };
 use serde::{Deserialize, Serialize};
+use shared::auth::Role;
 use std::sync::OnceLock;
 
 pub fn init_dummy_hash() {
@@ -25,6 +26,9 @@ pub fn get_dummy_hash() -> &'static str {
 pub struct Claims {
     pub sub: String,
     pub exp: usize,
+    pub jti: String,
+    #[serde(default)]
+    pub role: Role,
 }
 
 #[derive(Deserialize)]
@@ -33,11 +37,6 @@ pub struct LoginRequest {
     pub password: String,
 }
 
-#[derive(Serialize)]
-pub struct LoginResponse {
-    pub token: String,
-}
-
 #[derive(Serialize, Deserialize)]
 pub struct ChangePasswordRequest {
     pub current_password: String,
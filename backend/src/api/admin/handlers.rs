@@ -1,15 +1,85 @@
 use crate::api::admin::auth::{
     get_dummy_hash, hash_password, verify_password, ChangePasswordRequest, Claims, LoginRequest,
-    LoginResponse, UserRow,
+    UserRow,
 };
+use crate::api::admin::throttle;
+use crate::api::admin::tokens::{
+    clear_session_cookies, format_db_time, issue_access_token, new_refresh_token,
+    rotate_refresh_token, session_cookies, store_refresh_token, RefreshOutcome, SESSION_TTL_DAYS,
+};
+use crate::api::admin::totp;
+use crate::api::admin::users::{self, UserChangeError};
+use crate::api::admin::webauthn;
 use axum::body::to_bytes;
 use axum::body::Body;
-use axum::extract::State;
+use axum::extract::{ConnectInfo, Path, Query, State};
 use axum::http::{header, HeaderMap, Request, StatusCode};
 use axum::response::{IntoResponse, Json, Response};
-use chrono::{Duration, Utc};
-use jsonwebtoken::{encode, EncodingKey, Header};
+use chrono::{DateTime, Duration, Utc};
+use frontend::api::audit::{ssr as audit, AuditAction, AuditEvent, AuditFilter};
+use frontend::api::auth::ssr_utils::{cookie_value, token_from_headers};
+use frontend::api::sessions::ssr as sessions;
+use shared::auth::{
+    CreateUserRequest, LockoutInfo, PasskeyAssertion, PasskeyInfo, PasskeyLoginOptions,
+    PasskeyRegistration, PasskeyRegistrationOptions, RecoveryCodes, Role, SessionExpiry,
+    TotpChallenge, TotpCodeRequest, TotpLoginRequest, TotpSetup, TotpStatus, UnlockRequest,
+    UpdateRoleRequest, UserInfo,
+};
 use sqlx::SqlitePool;
+use std::net::{IpAddr, SocketAddr};
+
+fn database_error(context: &str, e: sqlx::Error) -> (StatusCode, String) {
+    tracing::error!("Database error {}: {}", context, e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "Database error".to_string(),
+    )
+}
+
+/// Decodes the access token (cookie or bearer) and checks that its session is still active.
+async fn authenticate(
+    pool: &SqlitePool,
+    headers: &HeaderMap,
+) -> Result<Claims, (StatusCode, String)> {
+    let token = token_from_headers(headers)
+        .ok_or((StatusCode::UNAUTHORIZED, "Missing token".to_string()))?;
+
+    let validation = jsonwebtoken::Validation::new(jsonwebtoken::Algorithm::HS256);
+    let token_data = jsonwebtoken::decode::<Claims>(
+        token,
+        &jsonwebtoken::DecodingKey::from_secret(shared::auth::get_jwt_secret()),
+        &validation,
+    )
+    .map_err(|_| (StatusCode::UNAUTHORIZED, "Invalid token".to_string()))?;
+
+    let active = sessions::touch_session(pool, &token_data.claims.jti, &token_data.claims.sub)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error checking session: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Database error".to_string(),
+            )
+        })?;
+    if !active {
+        return Err((StatusCode::UNAUTHORIZED, "Session revoked".to_string()));
+    }
+
+    Ok(token_data.claims)
+}
+
+/// Like `authenticate`, but the token's role must also be at least `min`.
+async fn authorize(
+    pool: &SqlitePool,
+    headers: &HeaderMap,
+    min: Role,
+) -> Result<Claims, (StatusCode, String)> {
+    let claims = authenticate(pool, headers).await?;
+    if claims.role < min {
+        return Err((StatusCode::FORBIDDEN, format!("Requires the {} role", min)));
+    }
+    Ok(claims)
+}
 
 pub async fn login(
     State(pool): State<SqlitePool>,
@@ -44,6 +114,15 @@ pub async fn login(
         return Err((StatusCode::BAD_REQUEST, "Username too long".to_string()));
     }
 
+    let peer_ip = parts
+        .extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ci| ci.0.ip());
+    let client_ip = client_ip_key(&parts.headers, peer_ip);
+    if let Some(response) = check_lockout(&pool, &client_ip, &req.username).await? {
+        return Ok(response);
+    }
+
     let user: Option<UserRow> =
         sqlx::query_as("SELECT id, password_hash FROM users WHERE username = ?")
             .bind(&req.username)
@@ -75,21 +154,237 @@ pub async fn login(
     let is_invalid = !is_valid_user || !password_match;
 
     if is_invalid {
+        record_login_failure(&pool, &client_ip, &req.username, "wrong password").await?;
         return Err((StatusCode::UNAUTHORIZED, "Invalid credentials".to_string()));
     }
 
-    let exp = (Utc::now() + Duration::hours(24)).timestamp() as usize;
-    let claims = Claims {
-        sub: user.expect("is_valid_user guarantees Some").id,
-        exp,
+    let user_id = user.expect("is_valid_user guarantees Some").id;
+
+    // With two-factor enabled the password only earns a short-lived challenge
+    let totp_enabled = totp::is_enabled(&pool, &user_id)
+        .await
+        .map_err(|e| database_error("checking two-factor status", e))?;
+    if totp_enabled {
+        let challenge = totp::create_challenge(&pool, &user_id)
+            .await
+            .map_err(|e| database_error("creating login challenge", e))?;
+        return Ok((StatusCode::ACCEPTED, Json(TotpChallenge { challenge })).into_response());
+    }
+
+    clear_login_failures(&pool, &req.username).await?;
+    start_session(&pool, &parts.headers, peer_ip, &user_id, "password").await
+}
+
+/// Rate-limit and lockout key for the request's client.
+fn client_ip_key(headers: &HeaderMap, peer_ip: Option<IpAddr>) -> String {
+    crate::api::proxy::extract_client_ip(headers, peer_ip).unwrap_or_else(|| "unknown".to_string())
+}
+
+/// `429` if the client IP or the account is locked out after too many failed logins.
+/// Checked before the password so a locked account can't be used to test guesses.
+async fn check_lockout(
+    pool: &SqlitePool,
+    client_ip: &str,
+    username: &str,
+) -> Result<Option<Response>, (StatusCode, String)> {
+    let keys = [
+        (throttle::LockKind::Ip, client_ip),
+        (throttle::LockKind::Account, username),
+    ];
+    let locked_for = throttle::locked_for(pool, &keys, Utc::now())
+        .await
+        .map_err(|e| database_error("checking lockout", e))?;
+    Ok(locked_for.map(|secs| {
+        throttle::too_many_requests(
+            secs,
+            &format!(
+                "Too many failed sign-in attempts; try again in {} seconds",
+                secs
+            ),
+        )
+    }))
+}
+
+/// Counts a failed sign-in towards lockouts and adds it to the audit log.
+async fn record_login_failure(
+    pool: &SqlitePool,
+    client_ip: &str,
+    username: &str,
+    reason: &str,
+) -> Result<(), (StatusCode, String)> {
+    audit::record_event(
+        pool,
+        AuditAction::LoginFailed,
+        audit::NewAuditEvent {
+            ip: Some(client_ip),
+            target: Some(username),
+            after: Some(reason.to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(|e| database_error("recording audit event", e))?;
+
+    let now = Utc::now();
+    for (kind, key) in [
+        (throttle::LockKind::Ip, client_ip),
+        (throttle::LockKind::Account, username),
+    ] {
+        let locked_until = throttle::record_failure(pool, throttle::settings(), kind, key, now)
+            .await
+            .map_err(|e| database_error("recording failed login", e))?;
+        if let Some(until) = locked_until {
+            tracing::warn!(
+                "Locked {} {} until {} after repeated failed logins",
+                kind.as_str(),
+                key.replace(['\n', '\r'], " "),
+                format_db_time(until)
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn clear_login_failures(
+    pool: &SqlitePool,
+    username: &str,
+) -> Result<(), (StatusCode, String)> {
+    throttle::clear_failures(pool, throttle::LockKind::Account, username)
+        .await
+        .map_err(|e| database_error("clearing failed logins", e))?;
+    Ok(())
+}
+
+async fn username_of(pool: &SqlitePool, user_id: &str) -> Result<String, (StatusCode, String)> {
+    sqlx::query_scalar("SELECT username FROM users WHERE id = ?")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| database_error("fetching user", e))?
+        .ok_or((StatusCode::UNAUTHORIZED, "Unknown user".to_string()))
+}
+
+/// Second login step: exchanges the challenge from `login` plus a TOTP or recovery code
+/// for a session.
+pub async fn login_totp(
+    State(pool): State<SqlitePool>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(req): Json<TotpLoginRequest>,
+) -> Result<Response, (StatusCode, String)> {
+    if req.code.len() > 64 || req.challenge.len() > 128 {
+        return Err((StatusCode::BAD_REQUEST, "Code too long".to_string()));
+    }
+
+    let user_id = totp::challenge_attempt(&pool, &req.challenge)
+        .await
+        .map_err(|e| database_error("checking login challenge", e))?
+        .ok_or((
+            StatusCode::GONE,
+            "Login challenge expired; sign in again".to_string(),
+        ))?;
+
+    let peer_ip = connect_info.map(|ci| ci.0.ip());
+    let client_ip = client_ip_key(&headers, peer_ip);
+    let username = username_of(&pool, &user_id).await?;
+    if let Some(response) = check_lockout(&pool, &client_ip, &username).await? {
+        return Ok(response);
+    }
+
+    let valid = totp::verify_second_factor(&pool, &user_id, &req.code, Utc::now().timestamp())
+        .await
+        .map_err(|e| database_error("verifying second factor", e))?;
+    if !valid {
+        record_login_failure(&pool, &client_ip, &username, "wrong second-factor code").await?;
+        return Err((StatusCode::UNAUTHORIZED, "Invalid code".to_string()));
+    }
+
+    totp::finish_challenge(&pool, &req.challenge)
+        .await
+        .map_err(|e| database_error("finishing login challenge", e))?;
+    clear_login_failures(&pool, &username).await?;
+    start_session(
+        &pool,
+        &headers,
+        peer_ip,
+        &user_id,
+        "password and second factor",
+    )
+    .await
+}
+
+/// Records a new session for a fully authenticated user and sets its cookies.
+async fn start_session(
+    pool: &SqlitePool,
+    headers: &HeaderMap,
+    peer_ip: Option<IpAddr>,
+    user_id: &str,
+    method: &str,
+) -> Result<Response, (StatusCode, String)> {
+    let jti = uuid::Uuid::new_v4().to_string();
+    let session_expires_at = Utc::now() + Duration::days(SESSION_TTL_DAYS);
+
+    let client_ip = crate::api::proxy::extract_client_ip(headers, peer_ip)
+        .or_else(|| peer_ip.map(|ip| ip.to_string()));
+    let user_agent = headers
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .map(|ua| ua.chars().take(512).collect::<String>());
+
+    let refresh_token = new_refresh_token();
+    let db_error = |e: sqlx::Error| {
+        tracing::error!("Failed to record session: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Database error".to_string(),
+        )
     };
+    sessions::create_session(
+        pool,
+        &jti,
+        user_id,
+        client_ip.as_deref(),
+        user_agent.as_deref(),
+        &format_db_time(session_expires_at),
+    )
+    .await
+    .map_err(db_error)?;
+    store_refresh_token(pool, &jti, &refresh_token)
+        .await
+        .map_err(db_error)?;
 
-    let token = encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(shared::auth::get_jwt_secret()),
+    let username = username_of(pool, user_id).await?;
+    audit::record_event(
+        pool,
+        AuditAction::Login,
+        audit::NewAuditEvent {
+            actor: Some(user_id),
+            ip: client_ip.as_deref(),
+            target: Some(&username),
+            after: Some(format!("signed in with {}", method)),
+            ..Default::default()
+        },
     )
-    .map_err(|e| {
+    .await
+    .map_err(db_error)?;
+
+    session_response(pool, user_id, &jti, &refresh_token, session_expires_at).await
+}
+
+/// Issues an access token carrying the user's current role and sets both auth cookies,
+/// returning their expiry times.
+async fn session_response(
+    pool: &SqlitePool,
+    sub: &str,
+    jti: &str,
+    refresh_token: &str,
+    session_expires_at: DateTime<Utc>,
+) -> Result<Response, (StatusCode, String)> {
+    let role = users::role_of(pool, sub)
+        .await
+        .map_err(|e| database_error("fetching user role", e))?
+        .ok_or((StatusCode::UNAUTHORIZED, "Unknown user".to_string()))?;
+    let (access_token, access_expires_at) = issue_access_token(sub, jti, role).map_err(|e| {
         tracing::error!("Token generation failed: {}", e);
         (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -97,58 +392,150 @@ pub async fn login(
         )
     })?;
 
-    Ok(Json(LoginResponse { token }).into_response())
+    let mut response = Json(SessionExpiry {
+        access_expires_at,
+        refresh_expires_at: session_expires_at.timestamp(),
+        role,
+    })
+    .into_response();
+    for cookie in session_cookies(&access_token, refresh_token, session_expires_at) {
+        response.headers_mut().append(header::SET_COOKIE, cookie);
+    }
+    Ok(response)
+}
+
+fn response_clearing_cookies(status: StatusCode, message: &str) -> Response {
+    let mut response = (status, message.to_string()).into_response();
+    for cookie in clear_session_cookies() {
+        response.headers_mut().append(header::SET_COOKIE, cookie);
+    }
+    response
+}
+
+/// Rotates the refresh token cookie and issues a new access token. Replaying an old
+/// refresh token revokes its whole session.
+pub async fn refresh(
+    State(pool): State<SqlitePool>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, String)> {
+    let Some(token) =
+        cookie_value(&headers, shared::auth::REFRESH_COOKIE).filter(|t| !t.is_empty())
+    else {
+        return Ok(response_clearing_cookies(
+            StatusCode::UNAUTHORIZED,
+            "Missing refresh token",
+        ));
+    };
+
+    let outcome = rotate_refresh_token(&pool, token).await.map_err(|e| {
+        tracing::error!("Database error rotating refresh token: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Database error".to_string(),
+        )
+    })?;
+
+    match outcome {
+        RefreshOutcome::Rotated {
+            sub,
+            jti,
+            refresh_token,
+            session_expires_at,
+        } => session_response(&pool, &sub, &jti, &refresh_token, session_expires_at).await,
+        RefreshOutcome::Reused { sub, jti } => {
+            tracing::warn!(
+                "Refresh token reuse detected for user {}; revoked session {}",
+                sub,
+                jti
+            );
+            Ok(response_clearing_cookies(
+                StatusCode::UNAUTHORIZED,
+                "Refresh token reused",
+            ))
+        }
+        RefreshOutcome::Invalid => Ok(response_clearing_cookies(
+            StatusCode::UNAUTHORIZED,
+            "Invalid refresh token",
+        )),
+    }
 }
 
 pub async fn me(
+    State(pool): State<SqlitePool>,
     headers: HeaderMap,
     connect_info: Option<axum::extract::ConnectInfo<std::net::SocketAddr>>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
-    // Design Note: The /me endpoint validates the JWT cryptographically but does not query the database.
-    // This means a deleted user's JWT remains valid until expiration (24h). For a single-admin personal site,
-    // this is an acceptable performance trade-off. `change_password` does perform a DB lookup.
+    // Design Note: The token is checked against the `sessions` table, so logging out,
+    // revoking a session or deleting the user invalidates it without waiting for it to expire.
 
-    let token = headers
-        .get("Authorization")
-        .and_then(|h| h.to_str().ok())
-        .and_then(|s| s.strip_prefix("Bearer "))
-        .ok_or(StatusCode::UNAUTHORIZED)?;
-
-    let validation = jsonwebtoken::Validation::new(jsonwebtoken::Algorithm::HS256);
-    let _token_data = jsonwebtoken::decode::<Claims>(
-        token,
-        &jsonwebtoken::DecodingKey::from_secret(shared::auth::get_jwt_secret()),
-        &validation,
-    )
-    .map_err(|e| {
-        let proxy_ip = connect_info
-            .as_ref()
-            .map(|ci| ci.0.ip().to_string())
-            .unwrap_or_else(|| "unknown".to_string());
+    authenticate(&pool, &headers)
+        .await
+        .map_err(|(status, reason)| {
+            let proxy_ip = connect_info
+                .as_ref()
+                .map(|ci| ci.0.ip().to_string())
+                .unwrap_or_else(|| "unknown".to_string());
 
-        let client_ip =
-            crate::api::proxy::extract_client_ip(&headers, connect_info.map(|ci| ci.0.ip()))
-                .unwrap_or_else(|| proxy_ip.clone());
-        let safe_client_ip = client_ip.replace(['\n', '\r'], " ");
+            let client_ip =
+                crate::api::proxy::extract_client_ip(&headers, connect_info.map(|ci| ci.0.ip()))
+                    .unwrap_or_else(|| proxy_ip.clone());
+            let safe_client_ip = client_ip.replace(['\n', '\r'], " ");
 
-        tracing::warn!(
-            "Invalid token on /me from client IP {} (via proxy {}): {}",
-            safe_client_ip,
-            proxy_ip,
-            e
-        );
-        StatusCode::UNAUTHORIZED
-    })?;
+            tracing::warn!(
+                "Invalid token on /me from client IP {} (via proxy {}): {}",
+                safe_client_ip,
+                proxy_ip,
+                reason
+            );
+            status
+        })?;
 
     Ok(Json(serde_json::json!({
         "authenticated": true
     })))
 }
 
+/// Revokes the session of the presented token.
+pub async fn logout(
+    State(pool): State<SqlitePool>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, String)> {
+    let claims = authenticate(&pool, &headers).await?;
+    sessions::revoke_session(&pool, &claims.sub, &claims.jti)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database update failed for logout: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Database update failed".to_string(),
+            )
+        })?;
+    Ok(response_clearing_cookies(StatusCode::NO_CONTENT, ""))
+}
+
+/// Revokes every session of the token's user, including the current one.
+pub async fn logout_all(
+    State(pool): State<SqlitePool>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, String)> {
+    let claims = authenticate(&pool, &headers).await?;
+    let revoked = sessions::revoke_all_sessions(&pool, &claims.sub)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database update failed for logout-all: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Database update failed".to_string(),
+            )
+        })?;
+    tracing::info!("Revoked {} sessions for user {}", revoked, claims.sub);
+    Ok(response_clearing_cookies(StatusCode::NO_CONTENT, ""))
+}
+
 pub async fn change_password(
     State(pool): State<SqlitePool>,
     req: Request<Body>,
-) -> Result<StatusCode, (StatusCode, String)> {
+) -> Result<Response, (StatusCode, String)> {
     let (parts, body) = req.into_parts();
     let content_type = parts
         .headers
@@ -163,21 +550,7 @@ pub async fn change_password(
         ));
     }
 
-    let token = parts
-        .headers
-        .get("Authorization")
-        .and_then(|h| h.to_str().ok())
-        .and_then(|s| s.strip_prefix("Bearer "))
-        .ok_or((StatusCode::UNAUTHORIZED, "Missing token".to_string()))?;
-
-    // Verify token (simple check, ideally decode claims)
-    let validation = jsonwebtoken::Validation::new(jsonwebtoken::Algorithm::HS256);
-    let token_data = jsonwebtoken::decode::<Claims>(
-        token,
-        &jsonwebtoken::DecodingKey::from_secret(shared::auth::get_jwt_secret()),
-        &validation,
-    )
-    .map_err(|_| (StatusCode::UNAUTHORIZED, "Invalid token".to_string()))?;
+    let claims = authenticate(&pool, &parts.headers).await?;
 
     let bytes = to_bytes(body, 16 * 1024)
         .await
@@ -203,7 +576,7 @@ pub async fn change_password(
         ));
     }
 
-    let user_id_str = &token_data.claims.sub;
+    let user_id_str = &claims.sub;
     if uuid::Uuid::parse_str(user_id_str).is_err() {
         tracing::error!("Valid JWT contained invalid UUID string: {}", user_id_str);
         return Err((
@@ -212,6 +585,17 @@ pub async fn change_password(
         ));
     }
 
+    // A stolen session shouldn't allow unlimited guesses at the current password
+    let peer_ip = parts
+        .extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ci| ci.0.ip());
+    let client_ip = client_ip_key(&parts.headers, peer_ip);
+    let username = username_of(&pool, user_id_str).await?;
+    if let Some(response) = check_lockout(&pool, &client_ip, &username).await? {
+        return Ok(response);
+    }
+
     // Verify current password
     let user: Option<UserRow> = sqlx::query_as("SELECT id, password_hash FROM users WHERE id = ?")
         .bind(user_id_str)
@@ -237,6 +621,7 @@ pub async fn change_password(
         .unwrap_or(false);
 
     if !is_valid_user || !password_match {
+        record_login_failure(&pool, &client_ip, &username, "wrong current password").await?;
         return Err((
             StatusCode::FORBIDDEN,
             "Invalid current password".to_string(),
@@ -269,5 +654,534 @@ pub async fn change_password(
             )
         })?;
 
-    Ok(StatusCode::OK)
+    audit::record_event(
+        &pool,
+        AuditAction::PasswordChange,
+        audit::NewAuditEvent {
+            actor: Some(user_id_str),
+            ip: Some(&client_ip),
+            target: Some(&username),
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(|e| database_error("recording audit event", e))?;
+
+    Ok(StatusCode::OK.into_response())
+}
+
+pub async fn totp_status(
+    State(pool): State<SqlitePool>,
+    headers: HeaderMap,
+) -> Result<Json<TotpStatus>, (StatusCode, String)> {
+    let claims = authenticate(&pool, &headers).await?;
+    let enabled = totp::is_enabled(&pool, &claims.sub)
+        .await
+        .map_err(|e| database_error("checking two-factor status", e))?;
+    let recovery_codes_remaining = totp::remaining_recovery_codes(&pool, &claims.sub)
+        .await
+        .map_err(|e| database_error("counting recovery codes", e))?;
+    Ok(Json(TotpStatus {
+        enabled,
+        recovery_codes_remaining,
+    }))
+}
+
+/// Starts enrollment with a fresh secret. It only takes effect once confirmed through
+/// `totp_enable`.
+pub async fn totp_setup(
+    State(pool): State<SqlitePool>,
+    headers: HeaderMap,
+) -> Result<Json<TotpSetup>, (StatusCode, String)> {
+    let claims = authenticate(&pool, &headers).await?;
+    let username: String = sqlx::query_scalar("SELECT username FROM users WHERE id = ?")
+        .bind(&claims.sub)
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| database_error("fetching user for two-factor setup", e))?;
+
+    let secret = totp::begin_enrollment(&pool, &claims.sub)
+        .await
+        .map_err(|e| database_error("starting two-factor enrollment", e))?
+        .ok_or((
+            StatusCode::CONFLICT,
+            "Two-factor authentication is already enabled".to_string(),
+        ))?;
+
+    let provisioning_uri = totp::provisioning_uri(totp::issuer(), &username, &secret);
+    let qr_code_svg = totp::provisioning_qr_svg(&provisioning_uri).ok_or((
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "Could not draw the QR code".to_string(),
+    ))?;
+    Ok(Json(TotpSetup {
+        secret,
+        provisioning_uri,
+        qr_code_svg,
+    }))
+}
+
+pub async fn totp_enable(
+    State(pool): State<SqlitePool>,
+    headers: HeaderMap,
+    Json(req): Json<TotpCodeRequest>,
+) -> Result<Json<RecoveryCodes>, (StatusCode, String)> {
+    let claims = authenticate(&pool, &headers).await?;
+    let codes = totp::confirm_enrollment(&pool, &claims.sub, &req.code, Utc::now().timestamp())
+        .await
+        .map_err(|e| database_error("confirming two-factor enrollment", e))?
+        .ok_or((StatusCode::BAD_REQUEST, "Invalid code".to_string()))?;
+    tracing::info!("Two-factor authentication enabled for user {}", claims.sub);
+    Ok(Json(RecoveryCodes { codes }))
+}
+
+/// Checks a TOTP or recovery code before a change to the user's two-factor settings.
+async fn require_second_factor(
+    pool: &SqlitePool,
+    user_id: &str,
+    code: &str,
+) -> Result<(), (StatusCode, String)> {
+    if code.len() > 64 {
+        return Err((StatusCode::BAD_REQUEST, "Code too long".to_string()));
+    }
+    let valid = totp::verify_second_factor(pool, user_id, code, Utc::now().timestamp())
+        .await
+        .map_err(|e| database_error("verifying second factor", e))?;
+    if valid {
+        Ok(())
+    } else {
+        Err((StatusCode::FORBIDDEN, "Invalid code".to_string()))
+    }
+}
+
+pub async fn totp_disable(
+    State(pool): State<SqlitePool>,
+    headers: HeaderMap,
+    Json(req): Json<TotpCodeRequest>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let claims = authenticate(&pool, &headers).await?;
+    require_second_factor(&pool, &claims.sub, &req.code).await?;
+    totp::disable(&pool, &claims.sub)
+        .await
+        .map_err(|e| database_error("disabling two-factor", e))?;
+    tracing::info!("Two-factor authentication disabled for user {}", claims.sub);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn totp_recovery_codes(
+    State(pool): State<SqlitePool>,
+    headers: HeaderMap,
+    Json(req): Json<TotpCodeRequest>,
+) -> Result<Json<RecoveryCodes>, (StatusCode, String)> {
+    let claims = authenticate(&pool, &headers).await?;
+    require_second_factor(&pool, &claims.sub, &req.code).await?;
+    let codes = totp::regenerate_recovery_codes(&pool, &claims.sub)
+        .await
+        .map_err(|e| database_error("regenerating recovery codes", e))?;
+    Ok(Json(RecoveryCodes { codes }))
+}
+
+/// Starts passkey registration for the signed-in user.
+pub async fn passkey_register_options(
+    State(pool): State<SqlitePool>,
+    headers: HeaderMap,
+) -> Result<Json<PasskeyRegistrationOptions>, (StatusCode, String)> {
+    let claims = authenticate(&pool, &headers).await?;
+    let username: String = sqlx::query_scalar("SELECT username FROM users WHERE id = ?")
+        .bind(&claims.sub)
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| database_error("fetching user for passkey registration", e))?;
+    let exclude_credentials = webauthn::list_credentials(&pool, &claims.sub)
+        .await
+        .map_err(|e| database_error("listing passkeys", e))?
+        .into_iter()
+        .map(|c| c.id)
+        .collect();
+    let challenge = webauthn::create_challenge(&pool, "register", Some(&claims.sub))
+        .await
+        .map_err(|e| database_error("creating passkey challenge", e))?;
+
+    let rp = webauthn::RelyingParty::current();
+    Ok(Json(PasskeyRegistrationOptions {
+        challenge,
+        rp_name: rp.id.clone(),
+        rp_id: rp.id,
+        user_id: webauthn::encode(claims.sub.as_bytes()),
+        user_name: username,
+        exclude_credentials,
+    }))
+}
+
+pub async fn passkey_register(
+    State(pool): State<SqlitePool>,
+    headers: HeaderMap,
+    Json(registration): Json<PasskeyRegistration>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let claims = authenticate(&pool, &headers).await?;
+    let name = registration.name.trim();
+    let name = if name.is_empty() { "Passkey" } else { name };
+    if name.chars().count() > 64 {
+        return Err((StatusCode::BAD_REQUEST, "Name too long".to_string()));
+    }
+
+    let verified = webauthn::verify_registration(&webauthn::RelyingParty::current(), &registration)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let issued_to = webauthn::take_challenge(&pool, &verified.challenge, "register")
+        .await
+        .map_err(|e| database_error("checking passkey challenge", e))?;
+    if issued_to != Some(Some(claims.sub.clone())) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Registration challenge expired".to_string(),
+        ));
+    }
+
+    webauthn::store_credential(&pool, &claims.sub, name, &verified)
+        .await
+        .map_err(|e| database_error("storing passkey", e))?;
+    tracing::info!("Passkey registered for user {}", claims.sub);
+    Ok(StatusCode::CREATED)
+}
+
+pub async fn passkey_login_options(
+    State(pool): State<SqlitePool>,
+) -> Result<Json<PasskeyLoginOptions>, (StatusCode, String)> {
+    let challenge = webauthn::create_challenge(&pool, "login", None)
+        .await
+        .map_err(|e| database_error("creating passkey challenge", e))?;
+    Ok(Json(PasskeyLoginOptions {
+        challenge,
+        rp_id: webauthn::RelyingParty::current().id,
+    }))
+}
+
+/// Signs in with a passkey. A user-verified passkey already combines possession and a
+/// PIN or biometric, so it is not followed by the TOTP step.
+pub async fn passkey_login(
+    State(pool): State<SqlitePool>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(assertion): Json<PasskeyAssertion>,
+) -> Result<Response, (StatusCode, String)> {
+    let rejected = |reason: &str| (StatusCode::UNAUTHORIZED, reason.to_string());
+    let stored = webauthn::find_credential(&pool, &assertion.credential_id)
+        .await
+        .map_err(|e| database_error("fetching passkey", e))?
+        .ok_or_else(|| rejected("Unknown passkey"))?;
+
+    let verified = webauthn::verify_assertion(
+        &webauthn::RelyingParty::current(),
+        &assertion,
+        &stored.public_key,
+        stored.sign_count,
+    )
+    .map_err(|reason| {
+        tracing::warn!(
+            "Passkey assertion rejected for user {}: {}",
+            stored.user_id,
+            reason
+        );
+        rejected(reason)
+    })?;
+
+    let challenge = webauthn::take_challenge(&pool, &verified.challenge, "login")
+        .await
+        .map_err(|e| database_error("checking passkey challenge", e))?;
+    if challenge.is_none() {
+        return Err(rejected("Login challenge expired"));
+    }
+
+    webauthn::record_use(&pool, &assertion.credential_id, verified.sign_count)
+        .await
+        .map_err(|e| database_error("updating passkey", e))?;
+    start_session(
+        &pool,
+        &headers,
+        connect_info.map(|ci| ci.0.ip()),
+        &stored.user_id,
+        "passkey",
+    )
+    .await
+}
+
+pub async fn passkey_list(
+    State(pool): State<SqlitePool>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<PasskeyInfo>>, (StatusCode, String)> {
+    let claims = authenticate(&pool, &headers).await?;
+    let passkeys = webauthn::list_credentials(&pool, &claims.sub)
+        .await
+        .map_err(|e| database_error("listing passkeys", e))?;
+    Ok(Json(passkeys))
+}
+
+pub async fn passkey_delete(
+    State(pool): State<SqlitePool>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let claims = authenticate(&pool, &headers).await?;
+    let deleted = webauthn::delete_credential(&pool, &claims.sub, &id)
+        .await
+        .map_err(|e| database_error("deleting passkey", e))?;
+    if deleted {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err((StatusCode::NOT_FOUND, "Passkey not found".to_string()))
+    }
+}
+
+fn user_change_error(e: UserChangeError) -> (StatusCode, String) {
+    match e {
+        UserChangeError::NotFound => (StatusCode::NOT_FOUND, "User not found".to_string()),
+        UserChangeError::LastAdmin => (
+            StatusCode::CONFLICT,
+            "There must always be at least one admin".to_string(),
+        ),
+        UserChangeError::Database(e) => {
+            tracing::error!("Database error changing user: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Database error".to_string(),
+            )
+        }
+    }
+}
+
+pub async fn users_list(
+    State(pool): State<SqlitePool>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<UserInfo>>, (StatusCode, String)> {
+    authorize(&pool, &headers, Role::Admin).await?;
+    let users = users::list_users(&pool)
+        .await
+        .map_err(|e| database_error("listing users", e))?;
+    Ok(Json(users))
+}
+
+pub async fn user_create(
+    State(pool): State<SqlitePool>,
+    headers: HeaderMap,
+    Json(req): Json<CreateUserRequest>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let claims = authorize(&pool, &headers, Role::Admin).await?;
+    let username = req.username.trim();
+    if username.is_empty() || username.len() > 64 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Username must be between 1 and 64 bytes".to_string(),
+        ));
+    }
+    if !(12..=128).contains(&req.password.len()) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Password length must be at least 12 bytes and no more than 128 bytes (policy limit)."
+                .to_string(),
+        ));
+    }
+
+    let pw = req.password;
+    let password_hash = tokio::task::spawn_blocking(move || hash_password(&pw))
+        .await
+        .unwrap_or_else(|_| Err("Task join failed".to_string()))
+        .map_err(|e| {
+            tracing::error!("Failed to hash password for new user: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to hash password".to_string(),
+            )
+        })?;
+    let id = users::create_user(&pool, username, &password_hash, req.role)
+        .await
+        .map_err(|e| database_error("creating user", e))?
+        .ok_or((
+            StatusCode::CONFLICT,
+            "That username is already taken".to_string(),
+        ))?;
+    tracing::info!(
+        "User {} created {} account {} ({})",
+        claims.sub,
+        req.role,
+        id,
+        username
+    );
+    Ok(StatusCode::CREATED)
+}
+
+pub async fn user_update_role(
+    State(pool): State<SqlitePool>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateRoleRequest>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let claims = authorize(&pool, &headers, Role::Admin).await?;
+    if id == claims.sub {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "You can't change your own role".to_string(),
+        ));
+    }
+    users::set_role(&pool, &id, req.role)
+        .await
+        .map_err(user_change_error)?;
+    // Their access tokens still carry the old role until they sign in again
+    sessions::revoke_all_sessions(&pool, &id)
+        .await
+        .map_err(|e| database_error("revoking sessions", e))?;
+    tracing::info!("User {} set the role of {} to {}", claims.sub, id, req.role);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn user_delete(
+    State(pool): State<SqlitePool>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let claims = authorize(&pool, &headers, Role::Admin).await?;
+    if id == claims.sub {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "You can't delete your own account".to_string(),
+        ));
+    }
+    users::delete_user(&pool, &id)
+        .await
+        .map_err(user_change_error)?;
+    tracing::info!("User {} deleted account {}", claims.sub, id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn lockouts_list(
+    State(pool): State<SqlitePool>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<LockoutInfo>>, (StatusCode, String)> {
+    authorize(&pool, &headers, Role::Admin).await?;
+    let lockouts = throttle::list_lockouts(&pool, Utc::now())
+        .await
+        .map_err(|e| database_error("listing lockouts", e))?;
+    Ok(Json(lockouts))
+}
+
+pub async fn lockout_unlock(
+    State(pool): State<SqlitePool>,
+    headers: HeaderMap,
+    Json(req): Json<UnlockRequest>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let claims = authorize(&pool, &headers, Role::Admin).await?;
+    let kind: throttle::LockKind = req
+        .kind
+        .parse()
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Unknown lockout kind".to_string()))?;
+    let cleared = throttle::unlock(&pool, kind, &req.key)
+        .await
+        .map_err(|e| database_error("unlocking", e))?;
+    if !cleared {
+        return Err((StatusCode::NOT_FOUND, "Lockout not found".to_string()));
+    }
+    tracing::info!(
+        "User {} unlocked {} {}",
+        claims.sub,
+        kind.as_str(),
+        req.key.replace(['\n', '\r'], " ")
+    );
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// The audit log as a JSON download, narrowed by the same filters as the audit page.
+pub async fn audit_export(
+    State(pool): State<SqlitePool>,
+    headers: HeaderMap,
+    Query(filter): Query<AuditFilter>,
+) -> Result<Response, (StatusCode, String)> {
+    authorize(&pool, &headers, Role::Admin).await?;
+    let events: Vec<AuditEvent> = audit::list_events(&pool, &filter, i64::MAX)
+        .await
+        .map_err(|e| database_error("exporting audit log", e))?;
+    let filename = format!("audit-log-{}.json", Utc::now().format("%Y-%m-%d"));
+    Ok((
+        [(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", filename),
+        )],
+        Json(events),
+    )
+        .into_response())
+}
+
+/// Downloads one of the backup archives made from the admin backups page.
+pub async fn backup_download(
+    State(pool): State<SqlitePool>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+    req: Request<Body>,
+) -> Result<Response, (StatusCode, String)> {
+    use tower::ServiceExt;
+
+    authorize(&pool, &headers, Role::Admin).await?;
+    let path = frontend::api::archive::ssr::backup_path(&name)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    if !path.is_file() {
+        return Err((StatusCode::NOT_FOUND, "Backup not found".to_string()));
+    }
+    // Streams the file rather than reading archives with media into memory
+    let mut response = tower_http::services::ServeFile::new(path)
+        .oneshot(req)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .into_response();
+    response.headers_mut().insert(
+        header::CONTENT_DISPOSITION,
+        format!("attachment; filename=\"{}\"", name)
+            .parse()
+            .expect("backup names are valid header values"),
+    );
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_role_change_revokes_sessions() {
+        std::env::set_var("JWT_SECRET", "test-secret-that-is-at-least-32-bytes-long");
+        let pool = frontend::api::test_pool().await;
+        let admin = users::create_user(&pool, "admin", "x", Role::Admin)
+            .await
+            .unwrap()
+            .unwrap();
+        let demoted = users::create_user(&pool, "demoted", "x", Role::Admin)
+            .await
+            .unwrap()
+            .unwrap();
+        for (jti, user) in [("admin-session", &admin), ("demoted-session", &demoted)] {
+            sessions::create_session(&pool, jti, user, None, None, "2999-01-01T00:00:00.000Z")
+                .await
+                .unwrap();
+        }
+
+        let (token, _) = issue_access_token(&admin, "admin-session", Role::Admin).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            format!("Bearer {}", token).parse().unwrap(),
+        );
+        let status = user_update_role(
+            State(pool.clone()),
+            headers,
+            Path(demoted.clone()),
+            Json(UpdateRoleRequest { role: Role::Editor }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, StatusCode::NO_CONTENT);
+
+        // The demoted admin's token, which still says admin, no longer gets through
+        assert!(!sessions::touch_session(&pool, "demoted-session", &demoted)
+            .await
+            .unwrap());
+        assert!(sessions::touch_session(&pool, "admin-session", &admin)
+            .await
+            .unwrap());
+    }
 }
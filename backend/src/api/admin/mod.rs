@@ -1,54 +1,146 @@
 pub mod auth;
 pub mod handlers;
+pub mod throttle;
+pub mod tokens;
+pub mod totp;
+pub mod users;
+pub mod webauthn;
 
 pub use auth::init_dummy_hash;
-use handlers::{change_password, login, me};
+use handlers::{
+    audit_export, backup_download, change_password, lockout_unlock, lockouts_list, login,
+    login_totp, logout, logout_all, me, passkey_delete, passkey_list, passkey_login,
+    passkey_login_options, passkey_register, passkey_register_options, refresh, totp_disable,
+    totp_enable, totp_recovery_codes, totp_setup, totp_status, user_create, user_delete,
+    user_update_role, users_list,
+};
 
-use axum::routing::{get, post};
+use axum::routing::{delete, get, post};
 use axum::Router;
 
 pub fn router(state: crate::state::AppState) -> Router<crate::state::AppState> {
-    // KNOWN LIMITATION: tower_governor uses in-memory state. A server restart will reset all rate limit counters.
-    // Burst windows completely refresh across restarts. Therefore, the effective rate limiting
-    // window ONLY covers uptime, not absolute calendar time. An attacker who can trigger or observe
-    // restarts could reset their login throttle window. For a low-traffic personal site, this is an
-    // acceptable trade-off to avoid the complexity of a distributed rate limiter like Redis. It is REQUIRED
-    // to pair this with an OS-level fail2ban or log-based alerting to compensate for the login endpoint.
-    tracing::info!("Initializing rate limiters. Warning: In-memory rate limiter state resets on restart. Frequent restarts may bypass burst limits.");
-    let shared_auth_governor_config = std::sync::Arc::new(
-        tower_governor::governor::GovernorConfigBuilder::default()
-            .key_extractor(crate::api::proxy::TrustedProxyIpKeyExtractor)
-            .per_second(1)
-            .burst_size(1)
-            .finish()
-            .unwrap(),
-    );
+    let settings = throttle::settings();
+    tracing::info!("Initializing rate limiters: {:?}", settings);
 
-    let login_governor_layer = tower_governor::GovernorLayer {
-        config: shared_auth_governor_config.clone(),
-    };
-
-    let password_governor_layer = tower_governor::GovernorLayer {
-        config: shared_auth_governor_config,
+    // The sign-in endpoints share one persistent limit per client IP, stored in SQLite so
+    // restarting the server doesn't reset an attacker's window. Failed attempts also count
+    // towards per-IP and per-account lockouts (see `throttle`).
+    let auth_limit = throttle::PersistentLimit {
+        pool: state.pool.clone(),
+        bucket: "auth",
+        limit: settings.login,
     };
+    let auth_limit_layer =
+        || axum::middleware::from_fn_with_state(auth_limit.clone(), throttle::enforce_limit);
 
+    // Everything else only needs protection from floods, so the in-memory governor is enough
     let me_governor_layer = tower_governor::GovernorLayer {
         config: std::sync::Arc::new(
             tower_governor::governor::GovernorConfigBuilder::default()
                 .key_extractor(crate::api::proxy::TrustedProxyIpKeyExtractor)
-                .per_second(5)
-                .burst_size(10)
+                .per_second(settings.api_per_second)
+                .burst_size(settings.api_burst)
                 .finish()
                 .unwrap(),
         ),
     };
 
     Router::new()
-        .route("/login", post(login).route_layer(login_governor_layer))
+        .route("/login", post(login).route_layer(auth_limit_layer()))
+        .route(
+            "/login/totp",
+            post(login_totp).route_layer(auth_limit_layer()),
+        )
         .route(
             "/password",
-            post(change_password).route_layer(password_governor_layer),
+            post(change_password).route_layer(auth_limit_layer()),
+        )
+        .route("/me", get(me).route_layer(me_governor_layer.clone()))
+        .route(
+            "/refresh",
+            post(refresh).route_layer(me_governor_layer.clone()),
+        )
+        .route(
+            "/logout",
+            post(logout).route_layer(me_governor_layer.clone()),
+        )
+        .route(
+            "/logout-all",
+            post(logout_all).route_layer(me_governor_layer.clone()),
+        )
+        .route(
+            "/totp",
+            get(totp_status).route_layer(me_governor_layer.clone()),
+        )
+        .route(
+            "/totp/setup",
+            post(totp_setup).route_layer(me_governor_layer.clone()),
+        )
+        .route(
+            "/totp/enable",
+            post(totp_enable).route_layer(me_governor_layer.clone()),
+        )
+        .route(
+            "/totp/disable",
+            post(totp_disable).route_layer(me_governor_layer.clone()),
+        )
+        .route(
+            "/totp/recovery-codes",
+            post(totp_recovery_codes).route_layer(me_governor_layer.clone()),
+        )
+        .route(
+            "/webauthn/register/options",
+            post(passkey_register_options).route_layer(me_governor_layer.clone()),
+        )
+        .route(
+            "/webauthn/register",
+            post(passkey_register).route_layer(me_governor_layer.clone()),
+        )
+        .route(
+            "/webauthn/login/options",
+            post(passkey_login_options).route_layer(me_governor_layer.clone()),
+        )
+        .route(
+            "/webauthn/login",
+            post(passkey_login).route_layer(me_governor_layer.clone()),
+        )
+        .route(
+            "/webauthn/credentials",
+            get(passkey_list).route_layer(me_governor_layer.clone()),
+        )
+        .route(
+            "/webauthn/credentials/:id",
+            delete(passkey_delete).route_layer(me_governor_layer.clone()),
+        )
+        .route(
+            "/api/users",
+            get(users_list)
+                .post(user_create)
+                .route_layer(me_governor_layer.clone()),
+        )
+        .route(
+            "/api/users/:id",
+            delete(user_delete).route_layer(me_governor_layer.clone()),
+        )
+        .route(
+            "/api/users/:id/role",
+            post(user_update_role).route_layer(me_governor_layer.clone()),
+        )
+        .route(
+            "/api/lockouts",
+            get(lockouts_list).route_layer(me_governor_layer.clone()),
+        )
+        .route(
+            "/api/lockouts/unlock",
+            post(lockout_unlock).route_layer(me_governor_layer.clone()),
+        )
+        .route(
+            "/audit/export",
+            get(audit_export).route_layer(me_governor_layer.clone()),
+        )
+        .route(
+            "/backups/:name",
+            get(backup_download).route_layer(me_governor_layer),
         )
-        .route("/me", get(me).route_layer(me_governor_layer))
         .with_state(state)
 }
@@ -0,0 +1,422 @@
+//! Login throttling that survives restarts: fixed-window request limits per client IP,
+//! and lockouts with exponential backoff after repeated failed logins from one IP or
+//! against one account.
+
+use crate::api::admin::tokens::format_db_time;
+use crate::api::proxy::TrustedProxyIpKeyExtractor;
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Duration, Utc};
+use shared::auth::LockoutInfo;
+use sqlx::{Row, SqlitePool};
+use std::sync::OnceLock;
+use tower_governor::key_extractor::KeyExtractor;
+
+/// At most `requests` per `window_secs` from one client.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    pub requests: i64,
+    pub window_secs: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThrottleSettings {
+    /// Persistent limit shared by the password, second-factor and password-change
+    /// endpoints (`LOGIN_RATE_LIMIT_REQUESTS` per `LOGIN_RATE_LIMIT_WINDOW_SECS`).
+    pub login: RateLimit,
+    /// In-memory governor for the other admin endpoints (`ADMIN_RATE_PER_SECOND`,
+    /// `ADMIN_RATE_BURST`).
+    pub api_per_second: u64,
+    pub api_burst: u32,
+    /// Failed logins before an IP or account is locked (`LOCKOUT_THRESHOLD`).
+    pub lockout_threshold: i64,
+    /// Length of the first lockout; each further failure doubles it, up to the maximum
+    /// (`LOCKOUT_BASE_SECS`, `LOCKOUT_MAX_SECS`). Failures older than the maximum are
+    /// forgotten.
+    pub lockout_base_secs: i64,
+    pub lockout_max_secs: i64,
+}
+
+impl Default for ThrottleSettings {
+    fn default() -> Self {
+        Self {
+            login: RateLimit {
+                requests: 10,
+                window_secs: 60,
+            },
+            api_per_second: 5,
+            api_burst: 10,
+            lockout_threshold: 5,
+            lockout_base_secs: 60,
+            lockout_max_secs: 24 * 60 * 60,
+        }
+    }
+}
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(default)
+}
+
+impl ThrottleSettings {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            login: RateLimit {
+                requests: env_or("LOGIN_RATE_LIMIT_REQUESTS", defaults.login.requests).max(1),
+                window_secs: env_or("LOGIN_RATE_LIMIT_WINDOW_SECS", defaults.login.window_secs)
+                    .max(1),
+            },
+            api_per_second: env_or("ADMIN_RATE_PER_SECOND", defaults.api_per_second).max(1),
+            api_burst: env_or("ADMIN_RATE_BURST", defaults.api_burst).max(1),
+            lockout_threshold: env_or("LOCKOUT_THRESHOLD", defaults.lockout_threshold).max(1),
+            lockout_base_secs: env_or("LOCKOUT_BASE_SECS", defaults.lockout_base_secs).max(1),
+            lockout_max_secs: env_or("LOCKOUT_MAX_SECS", defaults.lockout_max_secs).max(1),
+        }
+    }
+
+    /// How long to lock out after `failures` consecutive failures, if at all.
+    pub fn lockout_duration(&self, failures: i64) -> Option<Duration> {
+        if failures < self.lockout_threshold {
+            return None;
+        }
+        let doublings = (failures - self.lockout_threshold).min(32) as u32;
+        let secs = self
+            .lockout_base_secs
+            .saturating_mul(1i64 << doublings)
+            .min(self.lockout_max_secs);
+        Some(Duration::seconds(secs))
+    }
+}
+
+pub fn settings() -> &'static ThrottleSettings {
+    static SETTINGS: OnceLock<ThrottleSettings> = OnceLock::new();
+    SETTINGS.get_or_init(ThrottleSettings::from_env)
+}
+
+/// What a failed login is counted against.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LockKind {
+    Ip,
+    Account,
+}
+
+impl LockKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LockKind::Ip => "ip",
+            LockKind::Account => "account",
+        }
+    }
+}
+
+impl std::str::FromStr for LockKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ip" => Ok(LockKind::Ip),
+            "account" => Ok(LockKind::Account),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Counts a request against `key`'s current window in `bucket`. Returns the seconds
+/// until the window resets if the limit is exceeded.
+pub async fn hit(
+    pool: &SqlitePool,
+    bucket: &str,
+    key: &str,
+    limit: RateLimit,
+    now: DateTime<Utc>,
+) -> Result<Option<i64>, sqlx::Error> {
+    let now = now.timestamp();
+    let window_start = now - now.rem_euclid(limit.window_secs);
+    // Old windows are only useful until they expire
+    sqlx::query("DELETE FROM rate_limit_windows WHERE bucket = ? AND window_start < ?")
+        .bind(bucket)
+        .bind(window_start)
+        .execute(pool)
+        .await?;
+    let hits: i64 = sqlx::query_scalar(
+        "INSERT INTO rate_limit_windows (bucket, key, window_start, hits) VALUES (?, ?, ?, 1) \
+         ON CONFLICT(bucket, key) DO UPDATE SET \
+            hits = CASE WHEN window_start = excluded.window_start THEN hits + 1 ELSE 1 END, \
+            window_start = excluded.window_start \
+         RETURNING hits",
+    )
+    .bind(bucket)
+    .bind(key)
+    .bind(window_start)
+    .fetch_one(pool)
+    .await?;
+
+    Ok((hits > limit.requests).then(|| window_start + limit.window_secs - now))
+}
+
+/// Seconds left on the longest lockout among `keys`, if any of them is locked.
+pub async fn locked_for(
+    pool: &SqlitePool,
+    keys: &[(LockKind, &str)],
+    now: DateTime<Utc>,
+) -> Result<Option<i64>, sqlx::Error> {
+    let mut longest: Option<i64> = None;
+    for (kind, key) in keys {
+        let locked_until: Option<String> = sqlx::query_scalar(
+            "SELECT locked_until FROM login_failures WHERE kind = ? AND key = ? AND locked_until > ?",
+        )
+        .bind(kind.as_str())
+        .bind(key)
+        .bind(format_db_time(now))
+        .fetch_optional(pool)
+        .await?
+        .flatten();
+        if let Some(until) = locked_until.and_then(|t| DateTime::parse_from_rfc3339(&t).ok()) {
+            // Round up so a client told to wait N seconds isn't refused again
+            let secs = ((until.to_utc() - now).num_milliseconds() + 999) / 1000;
+            longest = Some(longest.map_or(secs, |l| l.max(secs)));
+        }
+    }
+    Ok(longest)
+}
+
+/// Counts a failed login against `key`, locking it once the threshold is reached.
+/// Returns the end of the lockout if one is now in force.
+pub async fn record_failure(
+    pool: &SqlitePool,
+    settings: &ThrottleSettings,
+    kind: LockKind,
+    key: &str,
+    now: DateTime<Utc>,
+) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let previous: i64 = sqlx::query_scalar(
+        "SELECT failures FROM login_failures WHERE kind = ? AND key = ? AND last_failure_at > ?",
+    )
+    .bind(kind.as_str())
+    .bind(key)
+    .bind(format_db_time(
+        now - Duration::seconds(settings.lockout_max_secs),
+    ))
+    .fetch_optional(&mut *tx)
+    .await?
+    .unwrap_or(0);
+
+    let failures = previous + 1;
+    let locked_until = settings.lockout_duration(failures).map(|d| now + d);
+    sqlx::query(
+        "INSERT INTO login_failures (kind, key, failures, last_failure_at, locked_until) VALUES (?, ?, ?, ?, ?) \
+         ON CONFLICT(kind, key) DO UPDATE SET \
+            failures = excluded.failures, \
+            last_failure_at = excluded.last_failure_at, \
+            locked_until = excluded.locked_until",
+    )
+    .bind(kind.as_str())
+    .bind(key)
+    .bind(failures)
+    .bind(format_db_time(now))
+    .bind(locked_until.map(format_db_time))
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(locked_until)
+}
+
+/// Forgets failed logins against `key`, e.g. after a successful sign-in.
+pub async fn clear_failures(
+    pool: &SqlitePool,
+    kind: LockKind,
+    key: &str,
+) -> Result<bool, sqlx::Error> {
+    let deleted = sqlx::query("DELETE FROM login_failures WHERE kind = ? AND key = ?")
+        .bind(kind.as_str())
+        .bind(key)
+        .execute(pool)
+        .await?;
+    Ok(deleted.rows_affected() > 0)
+}
+
+/// Lifts a lockout from the admin page. Unlocking an IP also resets its request windows.
+pub async fn unlock(pool: &SqlitePool, kind: LockKind, key: &str) -> Result<bool, sqlx::Error> {
+    let cleared = clear_failures(pool, kind, key).await?;
+    if kind == LockKind::Ip {
+        sqlx::query("DELETE FROM rate_limit_windows WHERE key = ?")
+            .bind(key)
+            .execute(pool)
+            .await?;
+    }
+    Ok(cleared)
+}
+
+/// IPs and accounts with failed logins, locked ones first.
+pub async fn list_lockouts(
+    pool: &SqlitePool,
+    now: DateTime<Utc>,
+) -> Result<Vec<LockoutInfo>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT kind, key, failures, last_failure_at, \
+            CASE WHEN locked_until > ?1 THEN locked_until END AS locked_until \
+         FROM login_failures \
+         ORDER BY locked_until > ?1 DESC, last_failure_at DESC",
+    )
+    .bind(format_db_time(now))
+    .fetch_all(pool)
+    .await?;
+    rows.iter()
+        .map(|row| {
+            Ok(LockoutInfo {
+                kind: row.try_get("kind")?,
+                key: row.try_get("key")?,
+                failures: row.try_get("failures")?,
+                locked_until: row.try_get("locked_until")?,
+                last_failure_at: row.try_get("last_failure_at")?,
+            })
+        })
+        .collect()
+}
+
+/// `429 Too Many Requests` telling the client when to come back.
+pub fn too_many_requests(retry_after_secs: i64, message: &str) -> Response {
+    let mut response = (StatusCode::TOO_MANY_REQUESTS, message.to_string()).into_response();
+    response.headers_mut().insert(
+        header::RETRY_AFTER,
+        HeaderValue::from(retry_after_secs.max(1)),
+    );
+    response
+}
+
+/// A persistent rate limit for one group of routes.
+#[derive(Clone)]
+pub struct PersistentLimit {
+    pub pool: SqlitePool,
+    pub bucket: &'static str,
+    pub limit: RateLimit,
+}
+
+/// Middleware enforcing a [`PersistentLimit`] per client IP. Like the governor it fails
+/// closed when the client IP can't be determined.
+pub async fn enforce_limit(
+    State(limit): State<PersistentLimit>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let Ok(key) = TrustedProxyIpKeyExtractor.extract(&req) else {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Unable to determine client IP",
+        )
+            .into_response();
+    };
+    match hit(&limit.pool, limit.bucket, &key, limit.limit, Utc::now()).await {
+        Ok(None) => next.run(req).await,
+        Ok(Some(retry_after)) => {
+            tracing::warn!(
+                "Rate limit exceeded on {} by client IP {}",
+                limit.bucket,
+                key.replace(['\n', '\r'], " ")
+            );
+            too_many_requests(retry_after, "Too many requests")
+        }
+        Err(e) => {
+            tracing::error!("Database error checking rate limit: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lockout_backoff() {
+        let settings = ThrottleSettings::default();
+        assert_eq!(settings.lockout_duration(4), None);
+        assert_eq!(settings.lockout_duration(5), Some(Duration::seconds(60)));
+        assert_eq!(settings.lockout_duration(7), Some(Duration::seconds(240)));
+        assert_eq!(
+            settings.lockout_duration(500),
+            Some(Duration::seconds(settings.lockout_max_secs))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_windows() {
+        let pool = frontend::api::test_pool().await;
+        let limit = RateLimit {
+            requests: 2,
+            window_secs: 60,
+        };
+        let start = DateTime::from_timestamp(6_000, 0).unwrap();
+        assert_eq!(
+            hit(&pool, "auth", "10.0.0.1", limit, start).await.unwrap(),
+            None
+        );
+        assert_eq!(
+            hit(&pool, "auth", "10.0.0.1", limit, start).await.unwrap(),
+            None
+        );
+        let later = start + Duration::seconds(15);
+        assert_eq!(
+            hit(&pool, "auth", "10.0.0.1", limit, later).await.unwrap(),
+            Some(45)
+        );
+        // Other clients and the next window start fresh
+        assert_eq!(
+            hit(&pool, "auth", "10.0.0.2", limit, later).await.unwrap(),
+            None
+        );
+        let next = start + Duration::seconds(60);
+        assert_eq!(
+            hit(&pool, "auth", "10.0.0.1", limit, next).await.unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lockout_and_unlock() {
+        let pool = frontend::api::test_pool().await;
+        let settings = ThrottleSettings::default();
+        let now = Utc::now();
+        let keys = [(LockKind::Ip, "10.0.0.1"), (LockKind::Account, "admin")];
+
+        for _ in 0..4 {
+            assert_eq!(
+                record_failure(&pool, &settings, LockKind::Account, "admin", now)
+                    .await
+                    .unwrap(),
+                None
+            );
+        }
+        assert_eq!(locked_for(&pool, &keys, now).await.unwrap(), None);
+        assert!(
+            record_failure(&pool, &settings, LockKind::Account, "admin", now)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert_eq!(locked_for(&pool, &keys, now).await.unwrap(), Some(60));
+        assert_eq!(
+            locked_for(&pool, &keys, now + Duration::seconds(61))
+                .await
+                .unwrap(),
+            None
+        );
+
+        let listed = list_lockouts(&pool, now).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(
+            (listed[0].kind.as_str(), listed[0].failures),
+            ("account", 5)
+        );
+        assert!(listed[0].locked_until.is_some());
+
+        assert!(unlock(&pool, LockKind::Account, "admin").await.unwrap());
+        assert_eq!(locked_for(&pool, &keys, now).await.unwrap(), None);
+    }
+}
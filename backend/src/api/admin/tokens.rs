@@ -0,0 +1,258 @@
+use crate::api::admin::auth::Claims;
+use axum::http::HeaderValue;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use jsonwebtoken::{encode, EncodingKey, Header};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use shared::auth::{Role, ACCESS_COOKIE, REFRESH_COOKIE};
+use sqlx::{Row, SqlitePool};
+
+/// Lifetime of an access token; the client refreshes shortly before it runs out.
+pub const ACCESS_TOKEN_TTL_MINUTES: i64 = 15;
+/// Lifetime of a session (token family). Rotation does not extend it.
+pub const SESSION_TTL_DAYS: i64 = 7;
+
+/// Signs an access token for the session, returning it with its expiry timestamp.
+pub fn issue_access_token(
+    sub: &str,
+    jti: &str,
+    role: Role,
+) -> Result<(String, i64), jsonwebtoken::errors::Error> {
+    let exp = (Utc::now() + Duration::minutes(ACCESS_TOKEN_TTL_MINUTES)).timestamp();
+    let claims = Claims {
+        sub: sub.to_string(),
+        exp: exp as usize,
+        jti: jti.to_string(),
+        role,
+    };
+    let token = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(shared::auth::get_jwt_secret()),
+    )?;
+    Ok((token, exp))
+}
+
+/// A fresh random refresh token (256 bits, hex-encoded).
+pub fn new_refresh_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Only hashes are stored so a database leak doesn't hand out live refresh tokens.
+pub fn hash_refresh_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+pub async fn store_refresh_token<'e, E>(
+    executor: E,
+    session_jti: &str,
+    token: &str,
+) -> Result<(), sqlx::Error>
+where
+    E: sqlx::SqliteExecutor<'e>,
+{
+    sqlx::query("INSERT INTO refresh_tokens (token_hash, session_jti) VALUES (?, ?)")
+        .bind(hash_refresh_token(token))
+        .bind(session_jti)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RefreshOutcome {
+    /// The token was valid and has been replaced by `refresh_token`.
+    Rotated {
+        sub: String,
+        jti: String,
+        refresh_token: String,
+        session_expires_at: DateTime<Utc>,
+    },
+    /// A rotated token was presented again; the whole session has been revoked.
+    Reused { sub: String, jti: String },
+    /// Unknown token, or its session is revoked or expired.
+    Invalid,
+}
+
+/// Exchanges a refresh token for a new one in the same family.
+pub async fn rotate_refresh_token(
+    pool: &SqlitePool,
+    token: &str,
+) -> Result<RefreshOutcome, sqlx::Error> {
+    let token_hash = hash_refresh_token(token);
+    let mut tx = pool.begin().await?;
+
+    let row = sqlx::query(
+        "SELECT r.session_jti, r.rotated_at, s.user_id, s.expires_at, \
+            (s.revoked_at IS NULL AND s.expires_at > strftime('%Y-%m-%dT%H:%M:%fZ', 'now')) AS active \
+         FROM refresh_tokens r JOIN sessions s ON s.jti = r.session_jti \
+         WHERE r.token_hash = ?",
+    )
+    .bind(&token_hash)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(row) = row else {
+        return Ok(RefreshOutcome::Invalid);
+    };
+    let jti: String = row.try_get("session_jti")?;
+    let sub: String = row.try_get("user_id")?;
+    if !row.try_get::<bool, _>("active")? {
+        return Ok(RefreshOutcome::Invalid);
+    }
+
+    // A second use of a rotated token means it was copied, however soon it comes
+    let claimed = if row.try_get::<Option<String>, _>("rotated_at")?.is_none() {
+        sqlx::query(
+            "UPDATE refresh_tokens SET rotated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE token_hash = ? AND rotated_at IS NULL",
+        )
+        .bind(&token_hash)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected()
+            == 1
+    } else {
+        false
+    };
+    if !claimed {
+        sqlx::query(
+            "UPDATE sessions SET revoked_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE jti = ? AND revoked_at IS NULL",
+        )
+        .bind(&jti)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        return Ok(RefreshOutcome::Reused { sub, jti });
+    }
+
+    let refresh_token = new_refresh_token();
+    store_refresh_token(&mut *tx, &jti, &refresh_token).await?;
+    tx.commit().await?;
+
+    let expires_at: String = row.try_get("expires_at")?;
+    let session_expires_at = DateTime::parse_from_rfc3339(&expires_at)
+        .map(|dt| dt.to_utc())
+        .unwrap_or_else(|_| Utc::now());
+    Ok(RefreshOutcome::Rotated {
+        sub,
+        jti,
+        refresh_token,
+        session_expires_at,
+    })
+}
+
+pub fn format_db_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// `Secure` is left off outside production so the cookies work over plain HTTP locally.
+fn cookie_secure() -> bool {
+    std::env::var("ENVIRONMENT").as_deref() == Ok("production")
+}
+
+fn build_cookie(name: &str, value: &str, path: &str, max_age: i64) -> HeaderValue {
+    let mut cookie = format!(
+        "{}={}; Path={}; Max-Age={}; HttpOnly; SameSite=Strict",
+        name,
+        value,
+        path,
+        max_age.max(0)
+    );
+    if cookie_secure() {
+        cookie.push_str("; Secure");
+    }
+    HeaderValue::from_str(&cookie).expect("cookie values are hex or base64url")
+}
+
+/// `Set-Cookie` values for a freshly issued access/refresh token pair.
+pub fn session_cookies(
+    access_token: &str,
+    refresh_token: &str,
+    session_expires_at: DateTime<Utc>,
+) -> [HeaderValue; 2] {
+    [
+        build_cookie(
+            ACCESS_COOKIE,
+            access_token,
+            "/",
+            ACCESS_TOKEN_TTL_MINUTES * 60,
+        ),
+        build_cookie(
+            REFRESH_COOKIE,
+            refresh_token,
+            "/admin",
+            (session_expires_at - Utc::now()).num_seconds(),
+        ),
+    ]
+}
+
+/// `Set-Cookie` values that delete both auth cookies.
+pub fn clear_session_cookies() -> [HeaderValue; 2] {
+    [
+        build_cookie(ACCESS_COOKIE, "", "/", 0),
+        build_cookie(REFRESH_COOKIE, "", "/admin", 0),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_refresh_rotation_and_reuse() {
+        let pool = frontend::api::test_pool().await;
+        sqlx::query("INSERT INTO users (id, username, password_hash) VALUES ('u1', 'tester', 'x')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        frontend::api::sessions::ssr::create_session(
+            &pool,
+            "s1",
+            "u1",
+            None,
+            None,
+            &format_db_time(Utc::now() + Duration::days(1)),
+        )
+        .await
+        .unwrap();
+        store_refresh_token(&pool, "s1", "first").await.unwrap();
+
+        let second = match rotate_refresh_token(&pool, "first").await.unwrap() {
+            RefreshOutcome::Rotated {
+                sub,
+                jti,
+                refresh_token,
+                ..
+            } => {
+                assert_eq!((sub.as_str(), jti.as_str()), ("u1", "s1"));
+                refresh_token
+            }
+            other => panic!("expected rotation, got {:?}", other),
+        };
+
+        // Presenting the old token again, even straight away, revokes the whole family
+        assert_eq!(
+            rotate_refresh_token(&pool, "first").await.unwrap(),
+            RefreshOutcome::Reused {
+                sub: "u1".to_string(),
+                jti: "s1".to_string(),
+            }
+        );
+        let revoked: Option<String> =
+            sqlx::query_scalar("SELECT revoked_at FROM sessions WHERE jti = 's1'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert!(revoked.is_some());
+        assert_eq!(
+            rotate_refresh_token(&pool, &second).await.unwrap(),
+            RefreshOutcome::Invalid
+        );
+        assert_eq!(
+            rotate_refresh_token(&pool, "unknown").await.unwrap(),
+            RefreshOutcome::Invalid
+        );
+    }
+}
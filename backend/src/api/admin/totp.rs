@@ -0,0 +1,486 @@
+//! RFC 6238 time-based one-time passwords for the optional second login step, plus the
+//! one-time recovery codes that stand in for a lost authenticator.
+
+use crate::api::admin::tokens::new_refresh_token;
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use sqlx::{Row, SqlitePool};
+
+pub const TOTP_STEP_SECS: i64 = 30;
+pub const TOTP_DIGITS: u32 = 6;
+/// Steps accepted either side of the current one, to tolerate clock drift.
+pub const TOTP_SKEW_STEPS: i64 = 1;
+pub const RECOVERY_CODE_COUNT: usize = 10;
+pub const CHALLENGE_TTL_MINUTES: i64 = 5;
+/// Wrong codes allowed per challenge before the password has to be entered again.
+pub const MAX_CHALLENGE_ATTEMPTS: i64 = 5;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// RFC 4648 base32 without padding, the format authenticator apps expect for secrets.
+pub fn base32_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(5) * 8);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &byte in data {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    out
+}
+
+/// Decodes base32, ignoring case, spaces and padding. Returns `None` on any other character.
+pub fn base32_decode(input: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(input.len() * 5 / 8);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in input.chars().filter(|c| !c.is_whitespace() && *c != '=') {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|&a| a as char == c.to_ascii_uppercase())? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+/// A new 160-bit secret, base32-encoded.
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; 20];
+    rand::thread_rng().fill(&mut bytes);
+    base32_encode(&bytes)
+}
+
+/// RFC 4226 HOTP value for `counter`, truncated to `TOTP_DIGITS` digits.
+pub fn hotp(secret: &[u8], counter: u64) -> u32 {
+    let mut mac = Hmac::<Sha1>::new_from_slice(secret).expect("HMAC accepts keys of any size");
+    mac.update(&counter.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+    binary % 10u32.pow(TOTP_DIGITS)
+}
+
+pub fn time_step(unix_time: i64) -> i64 {
+    unix_time.div_euclid(TOTP_STEP_SECS)
+}
+
+/// Checks `code` against the steps around `unix_time`, skipping any step at or before
+/// `last_used_step`. Returns the matching step, which the caller records as used.
+pub fn verify_code(
+    secret: &str,
+    code: &str,
+    unix_time: i64,
+    last_used_step: Option<i64>,
+) -> Option<i64> {
+    let code = code.trim();
+    if code.len() != TOTP_DIGITS as usize || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let code: u32 = code.parse().ok()?;
+    let secret = base32_decode(secret)?;
+    let current = time_step(unix_time);
+    (current - TOTP_SKEW_STEPS..=current + TOTP_SKEW_STEPS)
+        .filter(|step| *step >= 0 && last_used_step.is_none_or(|last| *step > last))
+        .find(|step| hotp(&secret, *step as u64) == code)
+}
+
+/// The `otpauth://` URI authenticator apps scan from a QR code.
+pub fn provisioning_uri(issuer: &str, account: &str, secret: &str) -> String {
+    use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+    let issuer = utf8_percent_encode(issuer, NON_ALPHANUMERIC).to_string();
+    let account = utf8_percent_encode(account, NON_ALPHANUMERIC);
+    format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        issuer, account, secret, issuer, TOTP_DIGITS, TOTP_STEP_SECS
+    )
+}
+
+/// The provisioning URI drawn as an inline SVG QR code, or `None` if it's too long to fit.
+pub fn provisioning_qr_svg(uri: &str) -> Option<String> {
+    let svg = qrcode::QrCode::new(uri.as_bytes())
+        .ok()?
+        .render::<qrcode::render::svg::Color>()
+        .min_dimensions(200, 200)
+        .build();
+    // The XML declaration has no place inside an HTML page
+    Some(
+        svg.split_once("?>")
+            .map_or(svg.as_str(), |(_, rest)| rest)
+            .to_string(),
+    )
+}
+
+/// Issuer label shown in authenticator apps: the site's host name.
+pub fn issuer() -> &'static str {
+    let site = crate::api::site::get_site_url();
+    site.split_once("://").map_or(site, |(_, host)| host)
+}
+
+/// 80-bit codes formatted as `xxxx-xxxx-xxxx-xxxx` for readability.
+pub fn generate_recovery_codes() -> Vec<String> {
+    let mut rng = rand::thread_rng();
+    (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            let chars: Vec<char> = (0..16)
+                .map(|_| BASE32_ALPHABET[rng.gen_range(0..32)].to_ascii_lowercase() as char)
+                .collect();
+            chars
+                .chunks(4)
+                .map(|c| c.iter().collect::<String>())
+                .collect::<Vec<_>>()
+                .join("-")
+        })
+        .collect()
+}
+
+/// Hash of a recovery code, ignoring case and separators so it can be typed loosely.
+pub fn hash_recovery_code(code: &str) -> String {
+    let normalized: String = code
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    format!("{:x}", Sha256::digest(normalized.as_bytes()))
+}
+
+fn hash_challenge(challenge: &str) -> String {
+    format!("{:x}", Sha256::digest(challenge.as_bytes()))
+}
+
+pub async fn is_enabled(pool: &SqlitePool, user_id: &str) -> Result<bool, sqlx::Error> {
+    let row = sqlx::query("SELECT 1 FROM user_totp WHERE user_id = ? AND enabled_at IS NOT NULL")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.is_some())
+}
+
+pub async fn remaining_recovery_codes(
+    pool: &SqlitePool,
+    user_id: &str,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar("SELECT COUNT(*) FROM recovery_codes WHERE user_id = ? AND used_at IS NULL")
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+}
+
+/// Starts (or restarts) an enrollment with a new secret. Returns `None` if two-factor is
+/// already enabled; it has to be disabled first.
+pub async fn begin_enrollment(
+    pool: &SqlitePool,
+    user_id: &str,
+) -> Result<Option<String>, sqlx::Error> {
+    let secret = generate_secret();
+    let result = sqlx::query(
+        "INSERT INTO user_totp (user_id, secret) VALUES (?, ?) \
+         ON CONFLICT(user_id) DO UPDATE SET secret = excluded.secret, last_used_step = NULL, \
+            created_at = excluded.created_at \
+         WHERE user_totp.enabled_at IS NULL",
+    )
+    .bind(user_id)
+    .bind(&secret)
+    .execute(pool)
+    .await?;
+    Ok((result.rows_affected() == 1).then_some(secret))
+}
+
+async fn replace_recovery_codes(
+    conn: &mut sqlx::SqliteConnection,
+    user_id: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query("DELETE FROM recovery_codes WHERE user_id = ?")
+        .bind(user_id)
+        .execute(&mut *conn)
+        .await?;
+    let codes = generate_recovery_codes();
+    for code in &codes {
+        sqlx::query("INSERT INTO recovery_codes (code_hash, user_id) VALUES (?, ?)")
+            .bind(hash_recovery_code(code))
+            .bind(user_id)
+            .execute(&mut *conn)
+            .await?;
+    }
+    Ok(codes)
+}
+
+/// Enables a pending enrollment once the user proves their app produces valid codes.
+/// Returns the new recovery codes, or `None` if the code is wrong or nothing is pending.
+pub async fn confirm_enrollment(
+    pool: &SqlitePool,
+    user_id: &str,
+    code: &str,
+    unix_time: i64,
+) -> Result<Option<Vec<String>>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let secret: Option<String> =
+        sqlx::query_scalar("SELECT secret FROM user_totp WHERE user_id = ? AND enabled_at IS NULL")
+            .bind(user_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+    let Some(step) = secret.and_then(|s| verify_code(&s, code, unix_time, None)) else {
+        return Ok(None);
+    };
+
+    sqlx::query(
+        "UPDATE user_totp SET enabled_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), last_used_step = ? \
+         WHERE user_id = ?",
+    )
+    .bind(step)
+    .bind(user_id)
+    .execute(&mut *tx)
+    .await?;
+    let codes = replace_recovery_codes(&mut tx, user_id).await?;
+    tx.commit().await?;
+    Ok(Some(codes))
+}
+
+/// Accepts either a TOTP code (each time step only once) or an unused recovery code,
+/// which is then spent.
+pub async fn verify_second_factor(
+    pool: &SqlitePool,
+    user_id: &str,
+    code: &str,
+    unix_time: i64,
+) -> Result<bool, sqlx::Error> {
+    let row = sqlx::query(
+        "SELECT secret, last_used_step FROM user_totp WHERE user_id = ? AND enabled_at IS NOT NULL",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+    let Some(row) = row else {
+        return Ok(false);
+    };
+    let secret: String = row.try_get("secret")?;
+    let last_used_step: Option<i64> = row.try_get("last_used_step")?;
+
+    if let Some(step) = verify_code(&secret, code, unix_time, last_used_step) {
+        // Conditional so two concurrent logins can't both spend the same step
+        let claimed = sqlx::query(
+            "UPDATE user_totp SET last_used_step = ? \
+             WHERE user_id = ? AND (last_used_step IS NULL OR last_used_step < ?)",
+        )
+        .bind(step)
+        .bind(user_id)
+        .bind(step)
+        .execute(pool)
+        .await?;
+        return Ok(claimed.rows_affected() == 1);
+    }
+
+    let spent = sqlx::query(
+        "UPDATE recovery_codes SET used_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') \
+         WHERE code_hash = ? AND user_id = ? AND used_at IS NULL",
+    )
+    .bind(hash_recovery_code(code))
+    .bind(user_id)
+    .execute(pool)
+    .await?;
+    Ok(spent.rows_affected() == 1)
+}
+
+/// Issues a fresh set of recovery codes, invalidating the old ones.
+pub async fn regenerate_recovery_codes(
+    pool: &SqlitePool,
+    user_id: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let codes = replace_recovery_codes(&mut tx, user_id).await?;
+    tx.commit().await?;
+    Ok(codes)
+}
+
+pub async fn disable(pool: &SqlitePool, user_id: &str) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM user_totp WHERE user_id = ?")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("DELETE FROM recovery_codes WHERE user_id = ?")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await
+}
+
+/// Records that `user_id` passed the password step and returns the value the client
+/// presents with its code.
+pub async fn create_challenge(pool: &SqlitePool, user_id: &str) -> Result<String, sqlx::Error> {
+    let challenge = new_refresh_token();
+    sqlx::query(
+        "INSERT INTO login_challenges (challenge_hash, user_id, expires_at) \
+         VALUES (?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now', ?))",
+    )
+    .bind(hash_challenge(&challenge))
+    .bind(user_id)
+    .bind(format!("+{} minutes", CHALLENGE_TTL_MINUTES))
+    .execute(pool)
+    .await?;
+    // Expired challenges are only useful until they expire; tidy up on the way
+    sqlx::query(
+        "DELETE FROM login_challenges WHERE expires_at <= strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+    )
+    .execute(pool)
+    .await?;
+    Ok(challenge)
+}
+
+/// Counts an attempt against a live challenge and returns its user. `None` once the
+/// challenge is unknown, expired or out of attempts.
+pub async fn challenge_attempt(
+    pool: &SqlitePool,
+    challenge: &str,
+) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        "UPDATE login_challenges SET attempts = attempts + 1 \
+         WHERE challenge_hash = ? AND attempts < ? \
+           AND expires_at > strftime('%Y-%m-%dT%H:%M:%fZ', 'now') \
+         RETURNING user_id",
+    )
+    .bind(hash_challenge(challenge))
+    .bind(MAX_CHALLENGE_ATTEMPTS)
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn finish_challenge(pool: &SqlitePool, challenge: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM login_challenges WHERE challenge_hash = ?")
+        .bind(hash_challenge(challenge))
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 6238 appendix B, SHA-1 seed, truncated to six digits
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn test_rfc6238_vectors() {
+        let secret = base32_encode(RFC_SECRET);
+        assert_eq!(base32_decode(&secret).unwrap(), RFC_SECRET);
+        for (time, code) in [
+            (59, "287082"),
+            (1111111109, "081804"),
+            (1111111111, "050471"),
+            (1234567890, "005924"),
+            (2000000000, "279037"),
+        ] {
+            assert_eq!(
+                format!("{:06}", hotp(RFC_SECRET, time_step(time) as u64)),
+                code
+            );
+            assert_eq!(
+                verify_code(&secret, code, time, None),
+                Some(time_step(time))
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify_code_window_and_replay() {
+        let secret = base32_encode(RFC_SECRET);
+        // 287082 belongs to step 1 (t = 30..59)
+        assert!(verify_code(&secret, "287082", 59 + TOTP_STEP_SECS, None).is_some());
+        assert!(verify_code(&secret, "287082", 59 + 2 * TOTP_STEP_SECS, None).is_none());
+        assert!(verify_code(&secret, "287082", 59, Some(1)).is_none());
+        assert!(verify_code(&secret, "28708", 59, None).is_none());
+        assert!(verify_code(&secret, "abcdef", 59, None).is_none());
+    }
+
+    #[test]
+    fn test_provisioning_qr_svg() {
+        let uri = provisioning_uri("example.com", "tester", &generate_secret());
+        let svg = provisioning_qr_svg(&uri).unwrap();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>"));
+        assert!(svg.contains("<path"));
+        assert!(provisioning_qr_svg(&"x".repeat(8_000)).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_enrollment_and_recovery_codes() {
+        let pool = frontend::api::test_pool().await;
+        sqlx::query("INSERT INTO users (id, username, password_hash) VALUES ('u1', 'tester', 'x')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let now = 1_700_000_000;
+        let secret = begin_enrollment(&pool, "u1").await.unwrap().unwrap();
+        let code = |t: i64| {
+            format!(
+                "{:06}",
+                hotp(&base32_decode(&secret).unwrap(), time_step(t) as u64)
+            )
+        };
+        assert!(!is_enabled(&pool, "u1").await.unwrap());
+        let wrong = format!("{:06}", (code(now).parse::<u32>().unwrap() + 1) % 1_000_000);
+        assert!(confirm_enrollment(&pool, "u1", &wrong, now)
+            .await
+            .unwrap()
+            .is_none());
+
+        let codes = confirm_enrollment(&pool, "u1", &code(now), now)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(codes.len(), RECOVERY_CODE_COUNT);
+        assert!(is_enabled(&pool, "u1").await.unwrap());
+        assert!(begin_enrollment(&pool, "u1").await.unwrap().is_none());
+
+        // The enrollment code's step is spent; the next step's code works once
+        let later = now + TOTP_STEP_SECS;
+        assert!(!verify_second_factor(&pool, "u1", &code(now), now)
+            .await
+            .unwrap());
+        assert!(verify_second_factor(&pool, "u1", &code(later), later)
+            .await
+            .unwrap());
+        assert!(!verify_second_factor(&pool, "u1", &code(later), later)
+            .await
+            .unwrap());
+
+        // Recovery codes work once, regardless of case and separators
+        let loose = codes[0].to_uppercase().replace('-', " ");
+        assert!(verify_second_factor(&pool, "u1", &loose, later)
+            .await
+            .unwrap());
+        assert!(!verify_second_factor(&pool, "u1", &codes[0], later)
+            .await
+            .unwrap());
+        assert_eq!(
+            remaining_recovery_codes(&pool, "u1").await.unwrap(),
+            RECOVERY_CODE_COUNT as i64 - 1
+        );
+
+        disable(&pool, "u1").await.unwrap();
+        assert!(!is_enabled(&pool, "u1").await.unwrap());
+        assert!(!verify_second_factor(&pool, "u1", &codes[1], later)
+            .await
+            .unwrap());
+    }
+}
@@ -0,0 +1,166 @@
+//! Admin accounts and their roles.
+
+use shared::auth::{Role, UserInfo};
+use sqlx::{Row, SqlitePool};
+
+/// Why a change to an account was not made.
+#[derive(Debug, PartialEq)]
+pub enum UserChangeError {
+    NotFound,
+    /// The change would leave nobody able to manage users.
+    LastAdmin,
+    Database(String),
+}
+
+impl From<sqlx::Error> for UserChangeError {
+    fn from(e: sqlx::Error) -> Self {
+        UserChangeError::Database(e.to_string())
+    }
+}
+
+fn parse_role(value: &str) -> Result<Role, sqlx::Error> {
+    value
+        .parse()
+        .map_err(|e: String| sqlx::Error::Decode(e.into()))
+}
+
+pub async fn role_of(pool: &SqlitePool, user_id: &str) -> Result<Option<Role>, sqlx::Error> {
+    let role: Option<String> = sqlx::query_scalar("SELECT role FROM users WHERE id = ?")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+    role.as_deref().map(parse_role).transpose()
+}
+
+pub async fn list_users(pool: &SqlitePool) -> Result<Vec<UserInfo>, sqlx::Error> {
+    let rows = sqlx::query("SELECT id, username, role, created_at FROM users ORDER BY username")
+        .fetch_all(pool)
+        .await?;
+    rows.iter()
+        .map(|row| {
+            Ok(UserInfo {
+                id: row.try_get("id")?,
+                username: row.try_get("username")?,
+                role: parse_role(row.try_get("role")?)?,
+                created_at: row.try_get("created_at")?,
+            })
+        })
+        .collect()
+}
+
+/// Creates an account, returning its id, or `None` if the username is taken.
+pub async fn create_user(
+    pool: &SqlitePool,
+    username: &str,
+    password_hash: &str,
+    role: Role,
+) -> Result<Option<String>, sqlx::Error> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let inserted = sqlx::query(
+        "INSERT INTO users (id, username, password_hash, role) VALUES (?, ?, ?, ?) ON CONFLICT(username) DO NOTHING",
+    )
+    .bind(&id)
+    .bind(username)
+    .bind(password_hash)
+    .bind(role.as_str())
+    .execute(pool)
+    .await?;
+    Ok((inserted.rows_affected() == 1).then_some(id))
+}
+
+/// Checks that `user_id` exists and that taking away its admin role (if it has one)
+/// leaves another admin.
+async fn check_change(
+    tx: &mut sqlx::SqliteConnection,
+    user_id: &str,
+    new_role: Option<Role>,
+) -> Result<(), UserChangeError> {
+    let current: Option<String> = sqlx::query_scalar("SELECT role FROM users WHERE id = ?")
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+    let current = parse_role(&current.ok_or(UserChangeError::NotFound)?)?;
+
+    if current == Role::Admin && new_role != Some(Role::Admin) {
+        let admins: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE role = 'admin'")
+            .fetch_one(&mut *tx)
+            .await?;
+        if admins <= 1 {
+            return Err(UserChangeError::LastAdmin);
+        }
+    }
+    Ok(())
+}
+
+/// Changes an account's role. Access tokens carry the role, so callers also revoke the
+/// account's sessions for the new role to apply at once.
+pub async fn set_role(pool: &SqlitePool, user_id: &str, role: Role) -> Result<(), UserChangeError> {
+    let mut tx = pool.begin().await?;
+    check_change(&mut tx, user_id, Some(role)).await?;
+    sqlx::query("UPDATE users SET role = ? WHERE id = ?")
+        .bind(role.as_str())
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Deletes an account; its sessions, passkeys and two-factor settings go with it.
+pub async fn delete_user(pool: &SqlitePool, user_id: &str) -> Result<(), UserChangeError> {
+    let mut tx = pool.begin().await?;
+    check_change(&mut tx, user_id, None).await?;
+    sqlx::query("DELETE FROM users WHERE id = ?")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_last_admin_is_kept() {
+        let pool = frontend::api::test_pool().await;
+
+        let admin = create_user(&pool, "admin", "x", Role::Admin)
+            .await
+            .unwrap()
+            .unwrap();
+        let writer = create_user(&pool, "writer", "x", Role::Contributor)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            create_user(&pool, "writer", "x", Role::Editor)
+                .await
+                .unwrap(),
+            None
+        );
+
+        assert_eq!(
+            set_role(&pool, &admin, Role::Editor).await,
+            Err(UserChangeError::LastAdmin)
+        );
+        assert_eq!(
+            delete_user(&pool, &admin).await,
+            Err(UserChangeError::LastAdmin)
+        );
+
+        set_role(&pool, &writer, Role::Admin).await.unwrap();
+        delete_user(&pool, &admin).await.unwrap();
+        assert_eq!(role_of(&pool, &admin).await.unwrap(), None);
+        assert_eq!(role_of(&pool, &writer).await.unwrap(), Some(Role::Admin));
+        assert_eq!(
+            set_role(&pool, &admin, Role::Editor).await,
+            Err(UserChangeError::NotFound)
+        );
+
+        let users = list_users(&pool).await.unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].username, "writer");
+    }
+}
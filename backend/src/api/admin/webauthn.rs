@@ -0,0 +1,518 @@
+//! Passkey registration and login (WebAuthn level 2, `none` attestation, ES256 only).
+//!
+//! The browser's `getPublicKey()`/`getAuthenticatorData()` accessors are used on
+//! registration, so the server never has to parse CBOR: it checks the client data, the
+//! authenticator data and stores the raw P-256 point for verifying later assertions.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use rand::RngCore;
+use ring::signature::{UnparsedPublicKey, ECDSA_P256_SHA256_ASN1};
+use sha2::{Digest, Sha256};
+use shared::auth::{PasskeyAssertion, PasskeyInfo, PasskeyRegistration};
+use sqlx::{Row, SqlitePool};
+
+pub const CHALLENGE_TTL_MINUTES: i64 = 5;
+/// COSE algorithm identifier for ECDSA with P-256 and SHA-256.
+pub const COSE_ALG_ES256: i64 = -7;
+
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_ATTESTED_CREDENTIAL: u8 = 0x40;
+
+/// DER header of a P-256 SubjectPublicKeyInfo; the 65-byte uncompressed point follows.
+const P256_SPKI_PREFIX: [u8; 26] = [
+    0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a,
+    0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00,
+];
+
+/// Who credentials are scoped to: the RP id is the site's host name and assertions must
+/// come from the site's origin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelyingParty {
+    pub id: String,
+    pub origin: String,
+}
+
+impl RelyingParty {
+    pub fn from_site_url(site_url: &str) -> Self {
+        let origin = site_url.trim_end_matches('/').to_string();
+        let host = origin.split_once("://").map_or(origin.as_str(), |(_, h)| h);
+        let host = host.split('/').next().unwrap_or(host);
+        let id = host.rsplit_once(':').map_or(host, |(h, _)| h).to_string();
+        Self { id, origin }
+    }
+
+    pub fn current() -> Self {
+        Self::from_site_url(crate::api::site::get_site_url())
+    }
+}
+
+pub fn encode(bytes: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn decode(value: &str) -> Result<Vec<u8>, &'static str> {
+    URL_SAFE_NO_PAD
+        .decode(value.trim_end_matches('='))
+        .map_err(|_| "Malformed base64url value")
+}
+
+struct AuthenticatorData {
+    flags: u8,
+    sign_count: u32,
+    credential_id: Option<Vec<u8>>,
+}
+
+fn parse_authenticator_data(
+    data: &[u8],
+    rp: &RelyingParty,
+) -> Result<AuthenticatorData, &'static str> {
+    if data.len() < 37 {
+        return Err("Authenticator data too short");
+    }
+    if data[..32] != Sha256::digest(rp.id.as_bytes())[..] {
+        return Err("Credential is for a different site");
+    }
+    let flags = data[32];
+    if flags & FLAG_USER_PRESENT == 0 || flags & FLAG_USER_VERIFIED == 0 {
+        return Err("User verification is required");
+    }
+    let sign_count = u32::from_be_bytes([data[33], data[34], data[35], data[36]]);
+
+    // Attested credential data: 16-byte AAGUID, 2-byte length, credential id, COSE key
+    let credential_id = if flags & FLAG_ATTESTED_CREDENTIAL != 0 {
+        let len_at = 37 + 16;
+        let id_len = data
+            .get(len_at..len_at + 2)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]) as usize)
+            .ok_or("Attested credential data too short")?;
+        let id = data
+            .get(len_at + 2..len_at + 2 + id_len)
+            .ok_or("Attested credential data too short")?;
+        Some(id.to_vec())
+    } else {
+        None
+    };
+
+    Ok(AuthenticatorData {
+        flags,
+        sign_count,
+        credential_id,
+    })
+}
+
+/// Checks the ceremony type and origin in `clientDataJSON`, returning its challenge.
+fn check_client_data(
+    client_data_json: &[u8],
+    expected_type: &str,
+    rp: &RelyingParty,
+) -> Result<String, &'static str> {
+    let client_data: serde_json::Value =
+        serde_json::from_slice(client_data_json).map_err(|_| "Malformed client data")?;
+    if client_data["type"] != expected_type {
+        return Err("Unexpected ceremony type");
+    }
+    if client_data["origin"] != rp.origin.as_str() {
+        return Err("Unexpected origin");
+    }
+    client_data["challenge"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or("Missing challenge")
+}
+
+#[derive(Debug, PartialEq)]
+pub struct VerifiedRegistration {
+    pub challenge: String,
+    pub credential_id: String,
+    pub public_key: Vec<u8>,
+    pub sign_count: u32,
+}
+
+pub fn verify_registration(
+    rp: &RelyingParty,
+    registration: &PasskeyRegistration,
+) -> Result<VerifiedRegistration, &'static str> {
+    let challenge = check_client_data(
+        &decode(&registration.client_data_json)?,
+        "webauthn.create",
+        rp,
+    )?;
+
+    if registration.public_key_algorithm != COSE_ALG_ES256 {
+        return Err("Only ES256 passkeys are supported");
+    }
+    let spki = decode(&registration.public_key)?;
+    let public_key = spki
+        .strip_prefix(&P256_SPKI_PREFIX[..])
+        .filter(|point| point.len() == 65 && point[0] == 0x04)
+        .ok_or("Malformed P-256 public key")?
+        .to_vec();
+
+    let auth_data = parse_authenticator_data(&decode(&registration.authenticator_data)?, rp)?;
+    if auth_data.flags & FLAG_ATTESTED_CREDENTIAL == 0 {
+        return Err("Missing attested credential data");
+    }
+    let credential_id = decode(&registration.credential_id)?;
+    if auth_data.credential_id.as_deref() != Some(credential_id.as_slice()) {
+        return Err("Credential id mismatch");
+    }
+
+    Ok(VerifiedRegistration {
+        challenge,
+        credential_id: encode(&credential_id),
+        public_key,
+        sign_count: auth_data.sign_count,
+    })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct VerifiedAssertion {
+    pub challenge: String,
+    pub sign_count: u32,
+}
+
+/// Verifies an assertion against the stored key. A signature counter that fails to
+/// increase (when the authenticator keeps one) suggests a cloned key and is rejected.
+pub fn verify_assertion(
+    rp: &RelyingParty,
+    assertion: &PasskeyAssertion,
+    public_key: &[u8],
+    stored_sign_count: u32,
+) -> Result<VerifiedAssertion, &'static str> {
+    let client_data_json = decode(&assertion.client_data_json)?;
+    let challenge = check_client_data(&client_data_json, "webauthn.get", rp)?;
+    let authenticator_data = decode(&assertion.authenticator_data)?;
+    let auth_data = parse_authenticator_data(&authenticator_data, rp)?;
+
+    let mut signed = authenticator_data;
+    signed.extend_from_slice(&Sha256::digest(&client_data_json));
+    UnparsedPublicKey::new(&ECDSA_P256_SHA256_ASN1, public_key)
+        .verify(&signed, &decode(&assertion.signature)?)
+        .map_err(|_| "Invalid signature")?;
+
+    if (auth_data.sign_count != 0 || stored_sign_count != 0)
+        && auth_data.sign_count <= stored_sign_count
+    {
+        return Err("Signature counter did not increase");
+    }
+
+    Ok(VerifiedAssertion {
+        challenge,
+        sign_count: auth_data.sign_count,
+    })
+}
+
+/// Issues a one-time challenge for a ceremony of `kind` (`register` or `login`).
+pub async fn create_challenge(
+    pool: &SqlitePool,
+    kind: &str,
+    user_id: Option<&str>,
+) -> Result<String, sqlx::Error> {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let challenge = encode(&bytes);
+    sqlx::query(
+        "INSERT INTO webauthn_challenges (challenge, kind, user_id, expires_at) \
+         VALUES (?, ?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now', ?))",
+    )
+    .bind(&challenge)
+    .bind(kind)
+    .bind(user_id)
+    .bind(format!("+{} minutes", CHALLENGE_TTL_MINUTES))
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "DELETE FROM webauthn_challenges WHERE expires_at <= strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+    )
+    .execute(pool)
+    .await?;
+    Ok(challenge)
+}
+
+/// Consumes a live challenge. The outer `Option` is `None` if it is unknown or expired;
+/// the inner one is the user it was issued to.
+pub async fn take_challenge(
+    pool: &SqlitePool,
+    challenge: &str,
+    kind: &str,
+) -> Result<Option<Option<String>>, sqlx::Error> {
+    sqlx::query_scalar(
+        "DELETE FROM webauthn_challenges \
+         WHERE challenge = ? AND kind = ? AND expires_at > strftime('%Y-%m-%dT%H:%M:%fZ', 'now') \
+         RETURNING user_id",
+    )
+    .bind(challenge)
+    .bind(kind)
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn store_credential(
+    pool: &SqlitePool,
+    user_id: &str,
+    name: &str,
+    credential: &VerifiedRegistration,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO webauthn_credentials (id, user_id, name, public_key, sign_count) \
+         VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(&credential.credential_id)
+    .bind(user_id)
+    .bind(name)
+    .bind(&credential.public_key)
+    .bind(credential.sign_count as i64)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub struct StoredCredential {
+    pub user_id: String,
+    pub public_key: Vec<u8>,
+    pub sign_count: u32,
+}
+
+pub async fn find_credential(
+    pool: &SqlitePool,
+    credential_id: &str,
+) -> Result<Option<StoredCredential>, sqlx::Error> {
+    let row = sqlx::query(
+        "SELECT user_id, public_key, sign_count FROM webauthn_credentials WHERE id = ?",
+    )
+    .bind(credential_id)
+    .fetch_optional(pool)
+    .await?;
+    row.map(|row| {
+        Ok(StoredCredential {
+            user_id: row.try_get("user_id")?,
+            public_key: row.try_get("public_key")?,
+            sign_count: row.try_get::<i64, _>("sign_count")? as u32,
+        })
+    })
+    .transpose()
+}
+
+pub async fn record_use(
+    pool: &SqlitePool,
+    credential_id: &str,
+    sign_count: u32,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE webauthn_credentials \
+         SET sign_count = ?, last_used_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+    )
+    .bind(sign_count as i64)
+    .bind(credential_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn list_credentials(
+    pool: &SqlitePool,
+    user_id: &str,
+) -> Result<Vec<PasskeyInfo>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT id, name, created_at, last_used_at FROM webauthn_credentials \
+         WHERE user_id = ? ORDER BY created_at",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+    rows.into_iter()
+        .map(|row| {
+            Ok(PasskeyInfo {
+                id: row.try_get("id")?,
+                name: row.try_get("name")?,
+                created_at: row.try_get("created_at")?,
+                last_used_at: row.try_get("last_used_at")?,
+            })
+        })
+        .collect()
+}
+
+pub async fn delete_credential(
+    pool: &SqlitePool,
+    user_id: &str,
+    credential_id: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM webauthn_credentials WHERE id = ? AND user_id = ?")
+        .bind(credential_id)
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_ASN1_SIGNING};
+
+    /// Minimal platform authenticator: one P-256 key and a signature counter.
+    struct SoftwareAuthenticator {
+        key: EcdsaKeyPair,
+        credential_id: Vec<u8>,
+        sign_count: u32,
+        rng: SystemRandom,
+    }
+
+    impl SoftwareAuthenticator {
+        fn new() -> Self {
+            let rng = SystemRandom::new();
+            let pkcs8 =
+                EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng).unwrap();
+            let key =
+                EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref(), &rng)
+                    .unwrap();
+            Self {
+                key,
+                credential_id: vec![7; 16],
+                sign_count: 0,
+                rng,
+            }
+        }
+
+        fn client_data(kind: &str, challenge: &str, origin: &str) -> Vec<u8> {
+            serde_json::json!({ "type": kind, "challenge": challenge, "origin": origin })
+                .to_string()
+                .into_bytes()
+        }
+
+        fn auth_data(&self, rp_id: &str, attested: bool) -> Vec<u8> {
+            let mut data = Sha256::digest(rp_id.as_bytes()).to_vec();
+            let mut flags = FLAG_USER_PRESENT | FLAG_USER_VERIFIED;
+            if attested {
+                flags |= FLAG_ATTESTED_CREDENTIAL;
+            }
+            data.push(flags);
+            data.extend_from_slice(&self.sign_count.to_be_bytes());
+            if attested {
+                data.extend_from_slice(&[0; 16]);
+                data.extend_from_slice(&(self.credential_id.len() as u16).to_be_bytes());
+                data.extend_from_slice(&self.credential_id);
+                // COSE key bytes; not parsed by the server
+                data.extend_from_slice(&[0xa0]);
+            }
+            data
+        }
+
+        fn register(&self, challenge: &str, origin: &str, rp_id: &str) -> PasskeyRegistration {
+            let mut spki = P256_SPKI_PREFIX.to_vec();
+            spki.extend_from_slice(self.key.public_key().as_ref());
+            PasskeyRegistration {
+                name: "Laptop".to_string(),
+                credential_id: encode(&self.credential_id),
+                client_data_json: encode(&Self::client_data("webauthn.create", challenge, origin)),
+                authenticator_data: encode(&self.auth_data(rp_id, true)),
+                public_key: encode(&spki),
+                public_key_algorithm: COSE_ALG_ES256,
+            }
+        }
+
+        fn assert(&mut self, challenge: &str, origin: &str, rp_id: &str) -> PasskeyAssertion {
+            self.sign_count += 1;
+            let client_data = Self::client_data("webauthn.get", challenge, origin);
+            let auth_data = self.auth_data(rp_id, false);
+            let mut signed = auth_data.clone();
+            signed.extend_from_slice(&Sha256::digest(&client_data));
+            let signature = self.key.sign(&self.rng, &signed).unwrap();
+            PasskeyAssertion {
+                credential_id: encode(&self.credential_id),
+                client_data_json: encode(&client_data),
+                authenticator_data: encode(&auth_data),
+                signature: encode(signature.as_ref()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_relying_party_from_site_url() {
+        let rp = RelyingParty::from_site_url("http://localhost:3000/");
+        assert_eq!(rp.id, "localhost");
+        assert_eq!(rp.origin, "http://localhost:3000");
+        assert_eq!(
+            RelyingParty::from_site_url("https://jakewray.dev").id,
+            "jakewray.dev"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_register_and_login_with_software_authenticator() {
+        let pool = frontend::api::test_pool().await;
+        sqlx::query("INSERT INTO users (id, username, password_hash) VALUES ('u1', 'tester', 'x')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let rp = RelyingParty::from_site_url("https://jakewray.dev");
+        let mut authenticator = SoftwareAuthenticator::new();
+
+        let challenge = create_challenge(&pool, "register", Some("u1"))
+            .await
+            .unwrap();
+        let registration = authenticator.register(&challenge, &rp.origin, &rp.id);
+        let verified = verify_registration(&rp, &registration).unwrap();
+        assert_eq!(
+            take_challenge(&pool, &verified.challenge, "register")
+                .await
+                .unwrap(),
+            Some(Some("u1".to_string()))
+        );
+        store_credential(&pool, "u1", &registration.name, &verified)
+            .await
+            .unwrap();
+
+        // Registration from another origin or for another RP id is refused
+        let phished = authenticator.register(&challenge, "https://jakewray.dev.evil", &rp.id);
+        assert!(verify_registration(&rp, &phished).is_err());
+        let other_rp = authenticator.register(&challenge, &rp.origin, "evil.example");
+        assert!(verify_registration(&rp, &other_rp).is_err());
+
+        let challenge = create_challenge(&pool, "login", None).await.unwrap();
+        let assertion = authenticator.assert(&challenge, &rp.origin, &rp.id);
+        let stored = find_credential(&pool, &assertion.credential_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.user_id, "u1");
+        let result =
+            verify_assertion(&rp, &assertion, &stored.public_key, stored.sign_count).unwrap();
+        assert_eq!(result.sign_count, 1);
+        assert_eq!(
+            take_challenge(&pool, &result.challenge, "login")
+                .await
+                .unwrap(),
+            Some(None)
+        );
+        assert_eq!(
+            take_challenge(&pool, &result.challenge, "login")
+                .await
+                .unwrap(),
+            None
+        );
+        record_use(&pool, &assertion.credential_id, result.sign_count)
+            .await
+            .unwrap();
+
+        // Replayed counters and tampered signatures fail
+        assert!(verify_assertion(&rp, &assertion, &stored.public_key, 1).is_err());
+        let mut tampered = authenticator.assert(&challenge, &rp.origin, &rp.id);
+        tampered.client_data_json = encode(&SoftwareAuthenticator::client_data(
+            "webauthn.get",
+            "other",
+            &rp.origin,
+        ));
+        assert!(verify_assertion(&rp, &tampered, &stored.public_key, 1).is_err());
+
+        assert_eq!(list_credentials(&pool, "u1").await.unwrap().len(), 1);
+        assert!(delete_credential(&pool, "u1", &assertion.credential_id)
+            .await
+            .unwrap());
+        assert!(list_credentials(&pool, "u1").await.unwrap().is_empty());
+    }
+}
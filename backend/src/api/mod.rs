@@ -1,8 +1,10 @@
 pub mod admin;
 pub mod proxy;
 pub mod public;
+pub mod site;
 
 pub use proxy::init_trusted_proxies;
+pub use site::init_site_url;
 
 use axum::Router;
 
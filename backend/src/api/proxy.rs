@@ -81,6 +81,15 @@ pub fn extract_client_ip(
     peer_ip.map(|ip| ip.to_string())
 }
 
+/// Client IP of `req` for the audit log, as passed to server functions.
+pub fn request_client_ip<T>(req: &Request<T>) -> Option<String> {
+    let peer_ip = req
+        .extensions()
+        .get::<axum::extract::ConnectInfo<std::net::SocketAddr>>()
+        .map(|ci| ci.0.ip());
+    extract_client_ip(req.headers(), peer_ip)
+}
+
 #[derive(Clone)]
 pub struct TrustedProxyIpKeyExtractor;
 
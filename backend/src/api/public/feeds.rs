@@ -0,0 +1,456 @@
+use crate::api::public::mappers::parse_flexible_datetime;
+use crate::api::site::absolute_url;
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use frontend::utils::html::strip_tags;
+use sqlx::{Row, SqlitePool};
+
+/// Number of most recent items included in each feed.
+const FEED_LIMIT: i64 = 50;
+
+/// Summaries built from body text are cut to roughly this many characters.
+const SUMMARY_CHARS: usize = 300;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedEntry {
+    pub title: String,
+    pub url: String,
+    pub summary: String,
+    pub image: Option<String>,
+    pub categories: Vec<String>,
+    pub published: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Feed {
+    pub title: String,
+    pub description: String,
+    /// HTML page the feed mirrors.
+    pub link: String,
+    /// Absolute URL of the feed document itself.
+    pub self_url: String,
+    pub entries: Vec<FeedEntry>,
+}
+
+impl Feed {
+    fn updated(&self) -> DateTime<Utc> {
+        self.entries
+            .iter()
+            .map(|e| e.updated)
+            .max()
+            .unwrap_or(DateTime::UNIX_EPOCH)
+    }
+}
+
+pub(crate) fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // Control characters other than tab/newline are not allowed in XML 1.0
+            c if c.is_control() && c != '\t' && c != '\n' && c != '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Plain-text summary from an explicit excerpt, falling back to the start of the body.
+fn summarize(excerpt: Option<&str>, html: &str) -> String {
+    if let Some(excerpt) = excerpt.map(str::trim).filter(|e| !e.is_empty()) {
+        return strip_tags(excerpt);
+    }
+    let text = strip_tags(html)
+        .replace("&nbsp;", " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if text.chars().count() <= SUMMARY_CHARS {
+        return text;
+    }
+    let cut: String = text.chars().take(SUMMARY_CHARS).collect();
+    match cut.rfind(' ') {
+        Some(idx) => format!("{}…", &cut[..idx]),
+        None => format!("{}…", cut),
+    }
+}
+
+fn image_mime_type(url: &str) -> &'static str {
+    let path = url.split(['?', '#']).next().unwrap_or(url).to_lowercase();
+    match path.rsplit('.').next() {
+        Some("png") => "image/png",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("avif") => "image/avif",
+        Some("svg") => "image/svg+xml",
+        _ => "image/jpeg",
+    }
+}
+
+pub fn render_rss(feed: &Feed) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n<channel>\n",
+    );
+    xml.push_str(&format!("<title>{}</title>\n", xml_escape(&feed.title)));
+    xml.push_str(&format!("<link>{}</link>\n", xml_escape(&feed.link)));
+    xml.push_str(&format!(
+        "<description>{}</description>\n",
+        xml_escape(&feed.description)
+    ));
+    xml.push_str(&format!(
+        "<atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+        xml_escape(&feed.self_url)
+    ));
+    xml.push_str(&format!(
+        "<lastBuildDate>{}</lastBuildDate>\n",
+        feed.updated().to_rfc2822()
+    ));
+
+    for entry in &feed.entries {
+        xml.push_str("<item>\n");
+        xml.push_str(&format!("<title>{}</title>\n", xml_escape(&entry.title)));
+        xml.push_str(&format!("<link>{}</link>\n", xml_escape(&entry.url)));
+        xml.push_str(&format!(
+            "<guid isPermaLink=\"true\">{}</guid>\n",
+            xml_escape(&entry.url)
+        ));
+        xml.push_str(&format!(
+            "<pubDate>{}</pubDate>\n",
+            entry.published.to_rfc2822()
+        ));
+        xml.push_str(&format!(
+            "<description>{}</description>\n",
+            xml_escape(&entry.summary)
+        ));
+        for category in &entry.categories {
+            xml.push_str(&format!("<category>{}</category>\n", xml_escape(category)));
+        }
+        if let Some(image) = &entry.image {
+            // RSS requires a length; 0 is the accepted value when it isn't known
+            xml.push_str(&format!(
+                "<enclosure url=\"{}\" length=\"0\" type=\"{}\"/>\n",
+                xml_escape(image),
+                image_mime_type(image)
+            ));
+        }
+        xml.push_str("</item>\n");
+    }
+
+    xml.push_str("</channel>\n</rss>\n");
+    xml
+}
+
+pub fn render_atom(feed: &Feed) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n",
+    );
+    xml.push_str(&format!("<title>{}</title>\n", xml_escape(&feed.title)));
+    xml.push_str(&format!(
+        "<subtitle>{}</subtitle>\n",
+        xml_escape(&feed.description)
+    ));
+    xml.push_str(&format!("<id>{}</id>\n", xml_escape(&feed.self_url)));
+    xml.push_str(&format!(
+        "<link href=\"{}\" rel=\"alternate\" type=\"text/html\"/>\n",
+        xml_escape(&feed.link)
+    ));
+    xml.push_str(&format!(
+        "<link href=\"{}\" rel=\"self\" type=\"application/atom+xml\"/>\n",
+        xml_escape(&feed.self_url)
+    ));
+    xml.push_str(&format!(
+        "<updated>{}</updated>\n",
+        feed.updated().to_rfc3339_opts(SecondsFormat::Secs, true)
+    ));
+    xml.push_str("<author><name>Jake Wray</name></author>\n");
+
+    for entry in &feed.entries {
+        xml.push_str("<entry>\n");
+        xml.push_str(&format!("<title>{}</title>\n", xml_escape(&entry.title)));
+        xml.push_str(&format!("<id>{}</id>\n", xml_escape(&entry.url)));
+        xml.push_str(&format!(
+            "<link href=\"{}\" rel=\"alternate\" type=\"text/html\"/>\n",
+            xml_escape(&entry.url)
+        ));
+        xml.push_str(&format!(
+            "<published>{}</published>\n",
+            entry.published.to_rfc3339_opts(SecondsFormat::Secs, true)
+        ));
+        xml.push_str(&format!(
+            "<updated>{}</updated>\n",
+            entry.updated.to_rfc3339_opts(SecondsFormat::Secs, true)
+        ));
+        xml.push_str(&format!(
+            "<summary>{}</summary>\n",
+            xml_escape(&entry.summary)
+        ));
+        for category in &entry.categories {
+            xml.push_str(&format!("<category term=\"{}\"/>\n", xml_escape(category)));
+        }
+        if let Some(image) = &entry.image {
+            xml.push_str(&format!(
+                "<link href=\"{}\" rel=\"enclosure\" type=\"{}\"/>\n",
+                xml_escape(image),
+                image_mime_type(image)
+            ));
+        }
+        xml.push_str("</entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+fn db_error(e: sqlx::Error) -> (StatusCode, String) {
+    tracing::error!("Failed to build feed: {}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "Database error".to_string(),
+    )
+}
+
+async fn article_feed(pool: &SqlitePool, self_path: &str) -> Result<Feed, (StatusCode, String)> {
+    let rows = sqlx::query("SELECT slug, title, excerpt, content, cover_image_url, published_at, updated_at FROM articles WHERE (status IS NULL OR status = 'published' OR (status = 'scheduled' AND published_at <= strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))) AND status != 'draft' ORDER BY published_at DESC LIMIT ?")
+        .bind(FEED_LIMIT)
+        .fetch_all(pool)
+        .await
+        .map_err(db_error)?;
+
+    let mut entries = Vec::with_capacity(rows.len());
+    for row in rows {
+        let slug: String = row.get("slug");
+        let excerpt: Option<String> = row.get("excerpt");
+        let content: String = row.get("content");
+        let cover_image_url: Option<String> = row.get("cover_image_url");
+        let published = parse_flexible_datetime(row.get("published_at")).map_err(db_error)?;
+        let updated = parse_flexible_datetime(row.get("updated_at")).unwrap_or(published);
+        entries.push(FeedEntry {
+            title: row.get("title"),
+            url: absolute_url(&format!("/journalism/{}", slug)),
+            summary: summarize(excerpt.as_deref(), &content),
+            image: cover_image_url
+                .filter(|u| !u.trim().is_empty())
+                .map(|u| absolute_url(&u)),
+            categories: Vec::new(),
+            published,
+            updated,
+        });
+    }
+
+    Ok(Feed {
+        title: "Jake Wray — Journalism".to_string(),
+        description: "Community news reporting by Jake Wray.".to_string(),
+        link: absolute_url("/journalism"),
+        self_url: absolute_url(self_path),
+        entries,
+    })
+}
+
+async fn blog_feed(
+    pool: &SqlitePool,
+    tag: Option<&str>,
+    self_path: &str,
+) -> Result<Feed, (StatusCode, String)> {
+    let rows = sqlx::query("SELECT slug, title, content, tags, published_at, updated_at FROM blog_posts WHERE (status IS NULL OR status = 'published' OR (status = 'scheduled' AND published_at <= strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))) AND status != 'draft' AND (?1 IS NULL OR EXISTS (SELECT 1 FROM json_each(CASE WHEN json_valid(blog_posts.tags) THEN blog_posts.tags ELSE '[]' END) WHERE lower(value) = lower(?1))) ORDER BY published_at DESC LIMIT ?2")
+        .bind(tag)
+        .bind(FEED_LIMIT)
+        .fetch_all(pool)
+        .await
+        .map_err(db_error)?;
+
+    let mut entries = Vec::with_capacity(rows.len());
+    for row in rows {
+        let slug: String = row.get("slug");
+        let content: String = row.get("content");
+        let tags: Option<String> = row.get("tags");
+        let published = parse_flexible_datetime(row.get("published_at")).map_err(db_error)?;
+        let updated = parse_flexible_datetime(row.get("updated_at")).unwrap_or(published);
+        entries.push(FeedEntry {
+            title: row.get("title"),
+            url: absolute_url(&format!("/blog/{}", slug)),
+            summary: summarize(None, &content),
+            image: None,
+            categories: tags
+                .and_then(|t| serde_json::from_str::<Vec<String>>(&t).ok())
+                .unwrap_or_default(),
+            published,
+            updated,
+        });
+    }
+
+    let (title, description) = match tag {
+        Some(tag) => (
+            format!("Jake Wray — Blog: {}", tag),
+            format!("Personal blog posts tagged \"{}\".", tag),
+        ),
+        None => (
+            "Jake Wray — Blog".to_string(),
+            "Personal blog posts by Jake Wray.".to_string(),
+        ),
+    };
+
+    Ok(Feed {
+        title,
+        description,
+        link: absolute_url("/blog"),
+        self_url: absolute_url(self_path),
+        entries,
+    })
+}
+
+fn rss_response(feed: &Feed) -> Response {
+    (
+        [(header::CONTENT_TYPE, "application/rss+xml; charset=utf-8")],
+        render_rss(feed),
+    )
+        .into_response()
+}
+
+fn atom_response(feed: &Feed) -> Response {
+    (
+        [(header::CONTENT_TYPE, "application/atom+xml; charset=utf-8")],
+        render_atom(feed),
+    )
+        .into_response()
+}
+
+fn clean_tag(tag: &str) -> Result<String, (StatusCode, String)> {
+    let tag = tag.trim();
+    if tag.is_empty() || tag.len() > 64 {
+        return Err((StatusCode::BAD_REQUEST, "Invalid tag".to_string()));
+    }
+    Ok(tag.to_string())
+}
+
+pub async fn articles_rss(
+    State(pool): State<SqlitePool>,
+) -> Result<Response, (StatusCode, String)> {
+    Ok(rss_response(&article_feed(&pool, "/feed.xml").await?))
+}
+
+pub async fn articles_atom(
+    State(pool): State<SqlitePool>,
+) -> Result<Response, (StatusCode, String)> {
+    Ok(atom_response(&article_feed(&pool, "/atom.xml").await?))
+}
+
+pub async fn blog_rss(State(pool): State<SqlitePool>) -> Result<Response, (StatusCode, String)> {
+    Ok(rss_response(
+        &blog_feed(&pool, None, "/blog/feed.xml").await?,
+    ))
+}
+
+pub async fn blog_atom(State(pool): State<SqlitePool>) -> Result<Response, (StatusCode, String)> {
+    Ok(atom_response(
+        &blog_feed(&pool, None, "/blog/atom.xml").await?,
+    ))
+}
+
+pub async fn blog_tag_rss(
+    State(pool): State<SqlitePool>,
+    Path(tag): Path<String>,
+) -> Result<Response, (StatusCode, String)> {
+    let tag = clean_tag(&tag)?;
+    let self_path = format!("/blog/tags/{}/feed.xml", urlencode(&tag));
+    Ok(rss_response(
+        &blog_feed(&pool, Some(&tag), &self_path).await?,
+    ))
+}
+
+pub async fn blog_tag_atom(
+    State(pool): State<SqlitePool>,
+    Path(tag): Path<String>,
+) -> Result<Response, (StatusCode, String)> {
+    let tag = clean_tag(&tag)?;
+    let self_path = format!("/blog/tags/{}/atom.xml", urlencode(&tag));
+    Ok(atom_response(
+        &blog_feed(&pool, Some(&tag), &self_path).await?,
+    ))
+}
+
+/// Percent-encodes a single path segment.
+pub(crate) fn urlencode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry() -> FeedEntry {
+        let published = DateTime::parse_from_rfc3339("2026-01-02T03:04:05Z")
+            .unwrap()
+            .to_utc();
+        FeedEntry {
+            title: "Council & <Mayor>".to_string(),
+            url: "https://example.com/journalism/council".to_string(),
+            summary: "Votes \"yes\"".to_string(),
+            image: Some("https://example.com/media/c.png".to_string()),
+            categories: vec!["local".to_string()],
+            published,
+            updated: published + chrono::Duration::hours(1),
+        }
+    }
+
+    fn feed() -> Feed {
+        Feed {
+            title: "Test".to_string(),
+            description: "Desc".to_string(),
+            link: "https://example.com/journalism".to_string(),
+            self_url: "https://example.com/feed.xml".to_string(),
+            entries: vec![entry()],
+        }
+    }
+
+    #[test]
+    fn test_render_rss_escapes_and_includes_enclosure() {
+        let xml = render_rss(&feed());
+        assert!(xml.contains("<title>Council &amp; &lt;Mayor&gt;</title>"));
+        assert!(xml.contains("<description>Votes &quot;yes&quot;</description>"));
+        assert!(xml.contains("<pubDate>Fri, 2 Jan 2026 03:04:05 +0000</pubDate>"));
+        assert!(xml.contains(
+            "<enclosure url=\"https://example.com/media/c.png\" length=\"0\" type=\"image/png\"/>"
+        ));
+        assert!(xml.contains("<category>local</category>"));
+    }
+
+    #[test]
+    fn test_render_atom_uses_latest_updated() {
+        let xml = render_atom(&feed());
+        assert!(xml.contains("<updated>2026-01-02T04:04:05Z</updated>\n<author>"));
+        assert!(xml.contains("<published>2026-01-02T03:04:05Z</published>"));
+        assert!(xml.contains("<category term=\"local\"/>"));
+    }
+
+    #[test]
+    fn test_summarize_prefers_excerpt_and_truncates_body() {
+        assert_eq!(summarize(Some(" Short "), "<p>Body</p>"), "Short");
+        let long = format!("<p>{}</p>", "word ".repeat(100));
+        let summary = summarize(None, &long);
+        assert!(summary.ends_with('…'));
+        assert!(summary.chars().count() <= SUMMARY_CHARS + 1);
+    }
+
+    #[test]
+    fn test_urlencode_tag_segment() {
+        assert_eq!(urlencode("rust"), "rust");
+        assert_eq!(urlencode("web dev/é"), "web%20dev%2F%C3%A9");
+    }
+}
@@ -5,7 +5,7 @@ use axum::{
     Json,
 };
 use serde::Deserialize;
-use shared::{Article, BlogPost, PageContent};
+use shared::{Article, BlogPost, PageContent, SearchResult};
 use sqlx::{Row, SqlitePool};
 
 #[derive(Deserialize)]
@@ -15,6 +15,13 @@ pub struct Pagination {
     pub before: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub struct SearchQuery {
+    pub q: String,
+    pub kind: Option<String>,
+    pub limit: Option<u32>,
+}
+
 pub async fn health_check() -> &'static str {
     "OK"
 }
@@ -156,7 +163,7 @@ pub async fn list_blog_posts(
             })?
             .to_utc();
         let normalized = dt.format("%Y-%m-%dT%H:%M:%3fZ").to_string();
-        sqlx::query("SELECT id, slug, title, content, published_at, tags FROM blog_posts WHERE published_at < ? ORDER BY published_at DESC LIMIT ?")
+        sqlx::query("SELECT id, slug, title, content, published_at, tags FROM blog_posts WHERE (status IS NULL OR status = 'published' OR (status = 'scheduled' AND published_at <= strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))) AND status != 'draft' AND published_at < ? ORDER BY published_at DESC LIMIT ?")
             .bind(normalized)
             .bind(limit)
             .try_map(map_blog_post_row)
@@ -167,7 +174,7 @@ pub async fn list_blog_posts(
         if offset > 10_000 {
             return Err((StatusCode::BAD_REQUEST, "Offset too large".to_string()));
         }
-        sqlx::query("SELECT id, slug, title, content, published_at, tags FROM blog_posts ORDER BY published_at DESC LIMIT ? OFFSET ?")
+        sqlx::query("SELECT id, slug, title, content, published_at, tags FROM blog_posts WHERE (status IS NULL OR status = 'published' OR (status = 'scheduled' AND published_at <= strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))) AND status != 'draft' ORDER BY published_at DESC LIMIT ? OFFSET ?")
             .bind(limit)
             .bind(offset)
             .try_map(map_blog_post_row)
@@ -186,3 +193,32 @@ pub async fn list_blog_posts(
         }
     }
 }
+
+pub async fn search(
+    State(pool): State<SqlitePool>,
+    Query(query): Query<SearchQuery>,
+) -> Result<Json<Vec<SearchResult>>, (StatusCode, String)> {
+    if query.q.len() > 200 {
+        return Err((StatusCode::BAD_REQUEST, "Query too long".to_string()));
+    }
+    if let Some(kind) = query.kind.as_deref() {
+        if !matches!(kind, "article" | "blog" | "page") {
+            return Err((StatusCode::BAD_REQUEST, "Invalid 'kind'".to_string()));
+        }
+    }
+    let limit = query
+        .limit
+        .unwrap_or(frontend::api::search::DEFAULT_SEARCH_LIMIT)
+        .min(frontend::api::search::MAX_SEARCH_LIMIT);
+
+    frontend::api::search::ssr::run_search(&pool, &query.q, query.kind.as_deref(), limit)
+        .await
+        .map(Json)
+        .map_err(|e| {
+            tracing::error!("Search for '{}' failed: {}", query.q, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Database error".to_string(),
+            )
+        })
+}
@@ -1,7 +1,11 @@
+pub mod feeds;
 pub mod handlers;
 pub mod mappers;
+pub mod seo;
 
-use handlers::{get_page_by_slug, health_check, list_articles, list_blog_posts};
+use feeds::{articles_atom, articles_rss, blog_atom, blog_rss, blog_tag_atom, blog_tag_rss};
+use handlers::{get_page_by_slug, health_check, list_articles, list_blog_posts, search};
+use seo::{robots_txt, sitemap_xml};
 
 use axum::routing::get;
 use axum::Router;
@@ -25,6 +29,14 @@ pub fn router(state: crate::state::AppState) -> Router<crate::state::AppState> {
     };
 
     let pages_governor_layer = tower_governor::GovernorLayer {
+        config: public_governor_config.clone(),
+    };
+
+    let search_governor_layer = tower_governor::GovernorLayer {
+        config: public_governor_config.clone(),
+    };
+
+    let feeds_governor_layer = tower_governor::GovernorLayer {
         config: public_governor_config,
     };
 
@@ -42,5 +54,38 @@ pub fn router(state: crate::state::AppState) -> Router<crate::state::AppState> {
             "/api/pages/:slug",
             get(get_page_by_slug).route_layer(pages_governor_layer),
         )
+        .route(
+            "/api/search",
+            get(search).route_layer(search_governor_layer),
+        )
+        .route(
+            "/feed.xml",
+            get(articles_rss).route_layer(feeds_governor_layer.clone()),
+        )
+        .route(
+            "/atom.xml",
+            get(articles_atom).route_layer(feeds_governor_layer.clone()),
+        )
+        .route(
+            "/blog/feed.xml",
+            get(blog_rss).route_layer(feeds_governor_layer.clone()),
+        )
+        .route(
+            "/blog/atom.xml",
+            get(blog_atom).route_layer(feeds_governor_layer.clone()),
+        )
+        .route(
+            "/blog/tags/:tag/feed.xml",
+            get(blog_tag_rss).route_layer(feeds_governor_layer.clone()),
+        )
+        .route(
+            "/blog/tags/:tag/atom.xml",
+            get(blog_tag_atom).route_layer(feeds_governor_layer.clone()),
+        )
+        .route(
+            "/sitemap.xml",
+            get(sitemap_xml).route_layer(feeds_governor_layer),
+        )
+        .route("/robots.txt", get(robots_txt))
         .with_state(state)
 }
@@ -0,0 +1,189 @@
+use crate::api::public::feeds::xml_escape;
+use crate::api::public::mappers::parse_flexible_datetime;
+use crate::api::site::{absolute_url, get_site_url};
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use sqlx::{Row, SqlitePool};
+
+/// Listing pages that aren't backed by a row of their own.
+const STATIC_PATHS: &[&str] = &["/", "/journalism", "/blog", "/writing", "/code"];
+
+/// Rows of `pages` the frontend has a route for. Any other slug would 404.
+const ROUTED_PAGES: &[&str] = &["about"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SitemapUrl {
+    pub loc: String,
+    pub lastmod: Option<DateTime<Utc>>,
+}
+
+pub fn render_sitemap(urls: &[SitemapUrl]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for url in urls {
+        xml.push_str("<url>");
+        xml.push_str(&format!("<loc>{}</loc>", xml_escape(&url.loc)));
+        if let Some(lastmod) = url.lastmod {
+            xml.push_str(&format!(
+                "<lastmod>{}</lastmod>",
+                lastmod.to_rfc3339_opts(SecondsFormat::Secs, true)
+            ));
+        }
+        xml.push_str("</url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+pub fn render_robots(site_url: &str) -> String {
+    format!(
+        "User-agent: *\nDisallow: /admin\nDisallow: /api/\n\nSitemap: {}/sitemap.xml\n",
+        site_url
+    )
+}
+
+/// Appends `(slug, updated_at)` rows from `sql` as `{prefix}{slug}` URLs.
+async fn collect_urls(
+    pool: &SqlitePool,
+    sql: &str,
+    prefix: &str,
+    urls: &mut Vec<SitemapUrl>,
+) -> Result<(), sqlx::Error> {
+    let rows = sqlx::query(sql).fetch_all(pool).await?;
+    for row in rows {
+        let slug: String = row.try_get("slug")?;
+        let updated_at: Option<String> = row.try_get("updated_at")?;
+        urls.push(SitemapUrl {
+            loc: absolute_url(&format!("{}{}", prefix, slug)),
+            lastmod: updated_at.and_then(|s| parse_flexible_datetime(s).ok()),
+        });
+    }
+    Ok(())
+}
+
+async fn build_sitemap(pool: &SqlitePool) -> Result<Vec<SitemapUrl>, sqlx::Error> {
+    let mut urls: Vec<SitemapUrl> = STATIC_PATHS
+        .iter()
+        .map(|path| SitemapUrl {
+            loc: absolute_url(path),
+            lastmod: None,
+        })
+        .collect();
+
+    collect_urls(
+        pool,
+        "SELECT slug, updated_at FROM articles WHERE (status IS NULL OR status = 'published' OR (status = 'scheduled' AND published_at <= strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))) AND status != 'draft' ORDER BY published_at DESC",
+        "/journalism/",
+        &mut urls,
+    )
+    .await?;
+    collect_urls(
+        pool,
+        "SELECT slug, updated_at FROM blog_posts WHERE (status IS NULL OR status = 'published' OR (status = 'scheduled' AND published_at <= strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))) AND status != 'draft' ORDER BY published_at DESC",
+        "/blog/",
+        &mut urls,
+    )
+    .await?;
+    collect_urls(
+        pool,
+        "SELECT slug, updated_at FROM creative_works WHERE (status IS NULL OR status = 'published' OR (status = 'scheduled' AND published_at <= strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))) AND status != 'draft' ORDER BY published_at DESC",
+        "/writing/",
+        &mut urls,
+    )
+    .await?;
+    let routed = ROUTED_PAGES
+        .iter()
+        .map(|slug| format!("'{}'", slug))
+        .collect::<Vec<_>>()
+        .join(", ");
+    collect_urls(
+        pool,
+        &format!(
+            "SELECT slug, updated_at FROM pages WHERE slug IN ({}) ORDER BY slug",
+            routed
+        ),
+        "/",
+        &mut urls,
+    )
+    .await?;
+
+    Ok(urls)
+}
+
+pub async fn sitemap_xml(State(pool): State<SqlitePool>) -> Result<Response, (StatusCode, String)> {
+    let urls = build_sitemap(&pool).await.map_err(|e| {
+        tracing::error!("Failed to build sitemap: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Database error".to_string(),
+        )
+    })?;
+
+    Ok((
+        [(header::CONTENT_TYPE, "application/xml; charset=utf-8")],
+        render_sitemap(&urls),
+    )
+        .into_response())
+}
+
+pub async fn robots_txt() -> Response {
+    (
+        [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+        render_robots(get_site_url()),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_sitemap() {
+        let lastmod = DateTime::parse_from_rfc3339("2026-03-25T10:00:00.123Z")
+            .unwrap()
+            .to_utc();
+        let xml = render_sitemap(&[
+            SitemapUrl {
+                loc: "https://example.com/".to_string(),
+                lastmod: None,
+            },
+            SitemapUrl {
+                loc: "https://example.com/blog/a&b".to_string(),
+                lastmod: Some(lastmod),
+            },
+        ]);
+        assert!(xml.contains("<url><loc>https://example.com/</loc></url>"));
+        assert!(xml.contains(
+            "<url><loc>https://example.com/blog/a&amp;b</loc><lastmod>2026-03-25T10:00:00Z</lastmod></url>"
+        ));
+    }
+
+    #[test]
+    fn test_render_robots() {
+        let robots = render_robots("https://example.com");
+        assert!(robots.contains("Disallow: /admin\n"));
+        assert!(robots.contains("Disallow: /api/\n"));
+        assert!(robots.ends_with("Sitemap: https://example.com/sitemap.xml\n"));
+    }
+
+    #[tokio::test]
+    async fn test_build_sitemap_skips_unrouted_pages() {
+        let pool = frontend::api::test_pool().await;
+        sqlx::query(
+            "INSERT OR IGNORE INTO pages (slug, title, content) VALUES ('about', 'About', ''), ('colophon', 'Colophon', '')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let urls = build_sitemap(&pool).await.unwrap();
+        assert!(urls.iter().any(|u| u.loc == absolute_url("/about")));
+        assert!(!urls.iter().any(|u| u.loc.ends_with("/colophon")));
+    }
+}
@@ -0,0 +1,31 @@
+use std::sync::OnceLock;
+
+pub const DEFAULT_SITE_URL: &str = "https://jakewray.dev";
+
+static SITE_URL: OnceLock<String> = OnceLock::new();
+
+pub fn init_site_url() {
+    tracing::info!("Initialized SITE_URL: {}", get_site_url());
+}
+
+/// Public base URL used to build absolute links in feeds and the sitemap, without a
+/// trailing slash. Comes from `SITE_URL`.
+pub(crate) fn get_site_url() -> &'static str {
+    SITE_URL.get_or_init(|| {
+        std::env::var("SITE_URL")
+            .ok()
+            .map(|u| u.trim().trim_end_matches('/').to_string())
+            .filter(|u| !u.is_empty())
+            .unwrap_or_else(|| DEFAULT_SITE_URL.to_string())
+    })
+}
+
+/// Resolves a site-relative path (e.g. `/media/x.jpg`) against the site URL. Absolute
+/// URLs are returned unchanged.
+pub(crate) fn absolute_url(path: &str) -> String {
+    if path.starts_with("http://") || path.starts_with("https://") {
+        path.to_string()
+    } else {
+        format!("{}/{}", get_site_url(), path.trim_start_matches('/'))
+    }
+}
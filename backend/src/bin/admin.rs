@@ -0,0 +1,75 @@
+//! Admin command-line tool. Run `cargo run --bin admin -- help` for the commands.
+
+use backend::cli;
+use std::io::{BufRead, IsTerminal, Write};
+use std::process::ExitCode;
+
+/// Reads one line from stdin, prompting first when it's a terminal so the password never
+/// appears in shell history or `ps` output.
+fn read_password() -> std::io::Result<String> {
+    let stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        eprint!("Password: ");
+        std::io::stderr().flush()?;
+    }
+    let mut password = String::new();
+    stdin.lock().read_line(&mut password)?;
+    Ok(password.trim_end_matches(['\r', '\n']).to_string())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    dotenvy::dotenv().ok();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if matches!(
+        args.first().map(String::as_str),
+        Some("help" | "--help" | "-h")
+    ) {
+        println!("{}", cli::USAGE);
+        return ExitCode::SUCCESS;
+    }
+    let command = match cli::parse_args(&args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let password = if command.needs_password() {
+        match read_password() {
+            Ok(password) => Some(password),
+            Err(e) => {
+                eprintln!("Failed to read password: {}", e);
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        None
+    };
+
+    let Ok(database_url) = std::env::var("DATABASE_URL") else {
+        eprintln!("DATABASE_URL environment variable must be set");
+        return ExitCode::FAILURE;
+    };
+    let pool = match backend::db::connect(&database_url, 1).await {
+        Ok(pool) => pool,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match cli::run(&pool, command, password).await {
+        Ok(output) => {
+            if !output.is_empty() {
+                println!("{}", output);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,421 @@
+//! The `admin` command-line tool: bootstraps a deployment and manages accounts and content
+//! without a running server or any shell scripts.
+
+use crate::api::admin::{auth::hash_password, throttle, users};
+use frontend::api::archive::{ssr as archive, ArchiveFormat};
+use frontend::api::audit::{ssr as audit, AuditAction};
+use frontend::api::images;
+use frontend::api::importer;
+use frontend::api::sessions::ssr as sessions;
+use shared::auth::Role;
+use sqlx::SqlitePool;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: admin <command>
+
+Commands:
+  migrate                              Apply pending database migrations
+  user list                            List accounts and their roles
+  user create <username> [--role R]    Create an account (role admin, editor or
+                                       contributor; default admin)
+  user delete <username>               Delete an account
+  user set-password <username>         Reset an account's password and sign it out
+  user revoke-sessions <username>      Sign an account out everywhere
+  content export <file> [--format F] [--media]
+                                       Write a backup archive of all content (format
+                                       json or markdown; --media adds media files)
+  content import <file>                Restore a backup archive, upserting by id
+  import json [<path>] [--dry-run]     Import articles from a JSON file or directory
+                                       (default data/articles)
+  import wxr <file> [--dry-run]        Import posts from a WordPress export
+  media variants                       Record stored files missing from the media
+                                       library and make resized copies of images
+                                       that have none yet
+
+Passwords are read from standard input. The database is taken from DATABASE_URL.";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Migrate,
+    ListUsers,
+    CreateUser {
+        username: String,
+        role: Role,
+    },
+    DeleteUser {
+        username: String,
+    },
+    SetPassword {
+        username: String,
+    },
+    RevokeSessions {
+        username: String,
+    },
+    ExportContent {
+        path: PathBuf,
+        format: ArchiveFormat,
+        include_media: bool,
+    },
+    ImportContent {
+        path: PathBuf,
+    },
+    ImportArticles {
+        source: ImportSource,
+        path: PathBuf,
+        dry_run: bool,
+    },
+    MediaVariants,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ImportSource {
+    Json,
+    Wxr,
+}
+
+fn parse_export(path: &str, rest: &[&str]) -> Result<Command, String> {
+    let mut format = ArchiveFormat::Json;
+    let mut include_media = false;
+    let mut rest = rest.iter();
+    while let Some(arg) = rest.next() {
+        match *arg {
+            "--format" => format = rest.next().ok_or("--format needs a value")?.parse()?,
+            "--media" => include_media = true,
+            other => return Err(format!("Unrecognised export option: {}", other)),
+        }
+    }
+    Ok(Command::ExportContent {
+        path: PathBuf::from(path),
+        format,
+        include_media,
+    })
+}
+
+fn parse_import(kind: &str, rest: &[&str]) -> Result<Command, String> {
+    let dry_run = rest.contains(&"--dry-run");
+    let paths: Vec<&str> = rest.iter().copied().filter(|a| *a != "--dry-run").collect();
+    let (source, path) = match (kind, paths.as_slice()) {
+        ("json", []) => (
+            ImportSource::Json,
+            frontend::api::auth::ssr_utils::get_articles_dir(),
+        ),
+        ("json", [path]) => (ImportSource::Json, PathBuf::from(path)),
+        ("wxr", [path]) => (ImportSource::Wxr, PathBuf::from(path)),
+        _ => return Err(format!("Unrecognised import: {} {}", kind, rest.join(" "))),
+    };
+    Ok(Command::ImportArticles {
+        source,
+        path,
+        dry_run,
+    })
+}
+
+impl Command {
+    /// Whether the command needs a password from standard input.
+    pub fn needs_password(&self) -> bool {
+        matches!(
+            self,
+            Command::CreateUser { .. } | Command::SetPassword { .. }
+        )
+    }
+}
+
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let command = match args.as_slice() {
+        ["migrate"] => Command::Migrate,
+        ["user", "list"] => Command::ListUsers,
+        ["user", "create", username] => Command::CreateUser {
+            username: username.to_string(),
+            role: Role::Admin,
+        },
+        ["user", "create", username, "--role", role] => Command::CreateUser {
+            username: username.to_string(),
+            role: role.parse()?,
+        },
+        ["user", "delete", username] => Command::DeleteUser {
+            username: username.to_string(),
+        },
+        ["user", "set-password", username] => Command::SetPassword {
+            username: username.to_string(),
+        },
+        ["user", "revoke-sessions", username] => Command::RevokeSessions {
+            username: username.to_string(),
+        },
+        ["content", "export", path, rest @ ..] => parse_export(path, rest)?,
+        ["content", "import", path] => Command::ImportContent {
+            path: PathBuf::from(path),
+        },
+        ["import", kind, rest @ ..] => parse_import(kind, rest)?,
+        ["media", "variants"] => Command::MediaVariants,
+        [] => return Err("No command given".to_string()),
+        _ => return Err(format!("Unrecognised command: {}", args.join(" "))),
+    };
+    Ok(command)
+}
+
+/// Same policy as the password change endpoint.
+fn check_password(password: &str) -> Result<(), String> {
+    if !(12..=128).contains(&password.len()) {
+        return Err("Password must be between 12 and 128 bytes".to_string());
+    }
+    Ok(())
+}
+
+async fn user_id(pool: &SqlitePool, username: &str) -> Result<String, String> {
+    sqlx::query_scalar("SELECT id FROM users WHERE username = ?")
+        .bind(username)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("No account named {}", username))
+}
+
+fn describe_change_error(e: users::UserChangeError) -> String {
+    match e {
+        users::UserChangeError::NotFound => "Account not found".to_string(),
+        users::UserChangeError::LastAdmin => {
+            "Refusing to remove the last admin account".to_string()
+        }
+        users::UserChangeError::Database(e) => e,
+    }
+}
+
+/// Runs `command`, returning what to print on success. `password` must be given for
+/// commands where [`Command::needs_password`] is true.
+pub async fn run(
+    pool: &SqlitePool,
+    command: Command,
+    password: Option<String>,
+) -> Result<String, String> {
+    let password = || {
+        let password = password.clone().ok_or("A password is required")?;
+        check_password(&password)?;
+        hash_password(&password)
+    };
+
+    match command {
+        Command::Migrate => {
+            crate::db::migrate(pool).await.map_err(|e| e.to_string())?;
+            Ok("Migrations are up to date.".to_string())
+        }
+        Command::ListUsers => {
+            let users = users::list_users(pool).await.map_err(|e| e.to_string())?;
+            Ok(users
+                .iter()
+                .map(|u| format!("{}\t{}\t{}\t{}", u.username, u.role, u.created_at, u.id))
+                .collect::<Vec<_>>()
+                .join("\n"))
+        }
+        Command::CreateUser { username, role } => {
+            let username = username.trim();
+            if username.is_empty() || username.len() > 64 {
+                return Err("Username must be between 1 and 64 bytes".to_string());
+            }
+            let hash = password()?;
+            let id = users::create_user(pool, username, &hash, role)
+                .await
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("An account named {} already exists", username))?;
+            Ok(format!("Created {} account {} ({}).", role, username, id))
+        }
+        Command::DeleteUser { username } => {
+            let id = user_id(pool, &username).await?;
+            users::delete_user(pool, &id)
+                .await
+                .map_err(describe_change_error)?;
+            Ok(format!("Deleted account {}.", username))
+        }
+        Command::SetPassword { username } => {
+            let id = user_id(pool, &username).await?;
+            let hash = password()?;
+            sqlx::query("UPDATE users SET password_hash = ? WHERE id = ?")
+                .bind(&hash)
+                .bind(&id)
+                .execute(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+            let revoked = sessions::revoke_all_sessions(pool, &id)
+                .await
+                .map_err(|e| e.to_string())?;
+            // A reset is how a locked-out admin gets back in
+            throttle::clear_failures(pool, throttle::LockKind::Account, &username)
+                .await
+                .map_err(|e| e.to_string())?;
+            audit::record_event(
+                pool,
+                AuditAction::PasswordChange,
+                audit::NewAuditEvent {
+                    target: Some(&username),
+                    after: Some("reset with the admin CLI".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+            Ok(format!(
+                "Password reset for {}; {} sessions revoked.",
+                username, revoked
+            ))
+        }
+        Command::RevokeSessions { username } => {
+            let id = user_id(pool, &username).await?;
+            let revoked = sessions::revoke_all_sessions(pool, &id)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(format!("Revoked {} sessions for {}.", revoked, username))
+        }
+        Command::ExportContent {
+            path,
+            format,
+            include_media,
+        } => {
+            let file = std::fs::File::create(&path)
+                .map_err(|e| format!("Creating {}: {}", path.display(), e))?;
+            let media = include_media.then(frontend::api::storage::get_media_store);
+            let manifest = archive::write_archive(pool, media, format, file).await?;
+            let rows: usize = manifest.tables.values().sum();
+            Ok(format!(
+                "Exported {} rows and {} media files to {}.",
+                rows,
+                manifest.media.len(),
+                path.display()
+            ))
+        }
+        Command::ImportContent { path } => {
+            let file = std::fs::File::open(&path)
+                .map_err(|e| format!("Reading {}: {}", path.display(), e))?;
+            let store = frontend::api::storage::get_media_store();
+            let summary = archive::restore_archive(pool, store, file).await?;
+            Ok(summary.to_string())
+        }
+        Command::ImportArticles {
+            source,
+            path,
+            dry_run,
+        } => {
+            let articles = match source {
+                ImportSource::Json => importer::load_json(&path)?,
+                ImportSource::Wxr => {
+                    let xml = std::fs::read_to_string(&path)
+                        .map_err(|e| format!("Reading {}: {}", path.display(), e))?;
+                    importer::parse_wxr(&xml)?
+                }
+            };
+            let store = frontend::api::storage::get_media_store();
+            let report = importer::import_articles(pool, store, articles, dry_run).await?;
+            Ok(report.to_string())
+        }
+        Command::MediaVariants => {
+            let store = frontend::api::storage::get_media_store();
+            let count = images::backfill_variants(pool, store).await?;
+            Ok(format!("Processed {} media files.", count))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args(&args("migrate")), Ok(Command::Migrate));
+        assert_eq!(
+            parse_args(&args("user create jake --role editor")),
+            Ok(Command::CreateUser {
+                username: "jake".to_string(),
+                role: Role::Editor
+            })
+        );
+        assert_eq!(
+            parse_args(&args("content export site.zip --format markdown --media")),
+            Ok(Command::ExportContent {
+                path: PathBuf::from("site.zip"),
+                format: ArchiveFormat::Markdown,
+                include_media: true
+            })
+        );
+        assert!(parse_args(&args("content export")).is_err());
+        assert!(parse_args(&args("content export site.zip --format yaml")).is_err());
+        assert_eq!(
+            parse_args(&args("import wxr export.xml --dry-run")),
+            Ok(Command::ImportArticles {
+                source: ImportSource::Wxr,
+                path: PathBuf::from("export.xml"),
+                dry_run: true
+            })
+        );
+        assert_eq!(
+            parse_args(&args("media variants")),
+            Ok(Command::MediaVariants)
+        );
+        assert!(parse_args(&args("import wxr")).is_err());
+        assert!(parse_args(&args("user create jake --role owner")).is_err());
+        assert!(parse_args(&args("user")).is_err());
+        assert!(parse_args(&[]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_and_reset_password() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        run(&pool, Command::Migrate, None).await.unwrap();
+
+        let create = || Command::CreateUser {
+            username: "jake".to_string(),
+            role: Role::Admin,
+        };
+        assert!(run(&pool, create(), Some("short".to_string()))
+            .await
+            .is_err());
+        run(&pool, create(), Some("a-long-enough-password".to_string()))
+            .await
+            .unwrap();
+        assert!(
+            run(&pool, create(), Some("a-long-enough-password".to_string()))
+                .await
+                .is_err()
+        );
+
+        let listed = run(&pool, Command::ListUsers, None).await.unwrap();
+        assert!(listed.starts_with("jake\tadmin\t"));
+
+        run(
+            &pool,
+            Command::SetPassword {
+                username: "jake".to_string(),
+            },
+            Some("another-long-password".to_string()),
+        )
+        .await
+        .unwrap();
+        let hash: String = sqlx::query_scalar("SELECT password_hash FROM users")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert!(crate::api::admin::auth::verify_password(
+            "another-long-password",
+            &hash
+        ));
+
+        // The only admin can't be deleted
+        assert!(run(
+            &pool,
+            Command::DeleteUser {
+                username: "jake".to_string()
+            },
+            None
+        )
+        .await
+        .is_err());
+    }
+}
@@ -0,0 +1,28 @@
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::SqlitePool;
+use std::str::FromStr;
+
+/// Opens the SQLite database at `database_url`, creating the file if needed.
+pub async fn connect(database_url: &str, max_connections: u32) -> Result<SqlitePool, String> {
+    // Parse options and ensure database is created if it doesn't exist
+    let connect_options = SqliteConnectOptions::from_str(database_url)
+        .map_err(|e| format!("Invalid DATABASE_URL: {}", e))?
+        .create_if_missing(true)
+        .journal_mode(sqlx::sqlite::SqliteJournalMode::Wal)
+        .busy_timeout(std::time::Duration::from_secs(5));
+
+    // With WAL mode, SQLite allows concurrent readers, but all writers are still
+    // serialized with a single write lock. We explicitly set min_connections(1) to keep
+    // one connection warm to avoid cold-start latency.
+    SqlitePoolOptions::new()
+        .max_connections(max_connections)
+        .min_connections(1)
+        .connect_with(connect_options)
+        .await
+        .map_err(|e| format!("Failed to create database pool: {}", e))
+}
+
+/// Applies any migrations from `migrations/` that haven't run yet.
+pub async fn migrate(pool: &SqlitePool) -> Result<(), sqlx::migrate::MigrateError> {
+    sqlx::migrate!("../migrations").run(pool).await
+}
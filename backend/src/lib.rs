@@ -0,0 +1,9 @@
+//! Server code shared by the `backend` web server and the `admin` command-line tool.
+#![recursion_limit = "256"]
+
+pub mod api;
+pub mod cli;
+pub mod db;
+pub mod scheduler;
+pub mod server;
+pub mod state;
@@ -1,24 +1,20 @@
 #![recursion_limit = "256"]
 use axum::middleware;
 use axum::Router;
+use backend::api;
+use backend::scheduler;
+use backend::server::{file_and_error_handler, inject_doctype, redirect_old_slugs};
+use backend::state::AppState;
 use dotenvy::dotenv;
+use frontend::api::audit::ssr::ClientIp;
 use frontend::{App, Shell};
 use leptos::context::provide_context;
 use leptos::prelude::*;
 use leptos_axum::{generate_route_list, LeptosRoutes};
-use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
 use std::net::SocketAddr;
-use std::str::FromStr;
 use tower::ServiceBuilder;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-mod api;
-mod server;
-mod state;
-
-use crate::server::{file_and_error_handler, inject_doctype};
-use crate::state::AppState;
-
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     dotenv().ok();
@@ -32,39 +28,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Initialize JWT Secret early so it panics at startup if missing
     shared::auth::init_jwt_secret();
-    crate::api::admin::init_dummy_hash();
-    crate::api::init_trusted_proxies();
+    api::admin::init_dummy_hash();
+    api::init_trusted_proxies();
+    api::init_site_url();
+    frontend::api::storage::init_media_store();
 
     // Improved error handling for DATABASE_URL
     let database_url = std::env::var("DATABASE_URL")
         .map_err(|_| "DATABASE_URL environment variable must be set")?;
 
-    // Parse options and ensure database is created if it doesn't exist
-    let connect_options = SqliteConnectOptions::from_str(&database_url)
-        .map_err(|e| format!("Invalid DATABASE_URL: {}", e))?
-        .create_if_missing(true)
-        .journal_mode(sqlx::sqlite::SqliteJournalMode::Wal)
-        .busy_timeout(std::time::Duration::from_secs(5));
-
-    // With WAL mode, SQLite allows concurrent readers, but all writers are still
-    // serialized with a single write lock. Setting max_connections(5) helps with concurrent
-    // reads. We explicitly set min_connections(1) to keep one connection warm
-    // to avoid cold-start latency.
-    let pool = SqlitePoolOptions::new()
-        .max_connections(5)
-        .min_connections(1)
-        .connect_with(connect_options)
-        .await
-        .map_err(|e| format!("Failed to create database pool: {}", e))?;
+    // Several connections help with concurrent reads
+    let pool = backend::db::connect(&database_url, 5).await?;
 
     // Run migrations
-    sqlx::migrate!("../migrations")
-        .run(&pool)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to run migrations: {}", e);
-            e
-        })?;
+    backend::db::migrate(&pool).await.map_err(|e| {
+        tracing::error!("Failed to run migrations: {}", e);
+        e
+    })?;
+
+    frontend::api::github::spawn_sync_job(pool.clone());
+    scheduler::spawn_scheduler(pool.clone(), vec![std::sync::Arc::new(scheduler::LogHook)]);
 
     let user_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM users")
         .fetch_one(&pool)
@@ -73,7 +56,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     if user_count.0 == 0 {
         tracing::warn!("=====================================================================");
         tracing::warn!("WARNING: The 'users' table is empty. No admin user exists.");
-        tracing::warn!("Run './scripts/setup-dev.sh' or inject a seed migration to create one.");
+        tracing::warn!("Create one with 'cargo run --bin admin -- user create <username>'.");
         tracing::warn!("=====================================================================");
     }
 
@@ -156,13 +139,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             axum::routing::post({
                 let pool = app_state.pool.clone();
                 let options = app_state.leptos_options.clone();
-                move |req| {
+                move |req: axum::extract::Request| {
                     let pool = pool.clone();
                     let options = options.clone();
+                    let client_ip = ClientIp(api::proxy::request_client_ip(&req));
                     leptos_axum::handle_server_fns_with_context(
                         move || {
                             provide_context(pool.clone());
                             provide_context(options.clone());
+                            provide_context(client_ip.clone());
                         },
                         req,
                     )
@@ -171,13 +156,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             .get({
                 let pool = app_state.pool.clone();
                 let options = app_state.leptos_options.clone();
-                move |req| {
+                move |req: axum::extract::Request| {
                     let pool = pool.clone();
                     let options = options.clone();
+                    let client_ip = ClientIp(api::proxy::request_client_ip(&req));
                     leptos_axum::handle_server_fns_with_context(
                         move || {
                             provide_context(pool.clone());
                             provide_context(options.clone());
+                            provide_context(client_ip.clone());
                         },
                         req,
                     )
@@ -199,6 +186,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         )
         .fallback(file_and_error_handler)
         .layer(ServiceBuilder::new().layer(middleware::from_fn(inject_doctype)))
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            redirect_old_slugs,
+        ))
         .with_state(app_state);
 
     tracing::info!("listening on http://{}", &addr);
@@ -227,7 +218,47 @@ mod tests {
         assert!(!frontend::api::articles::ListMedia::PATH.is_empty());
         assert!(!frontend::api::articles::UploadMedia::PATH.is_empty());
         assert!(!frontend::api::articles::DeleteMedia::PATH.is_empty());
+        assert!(!frontend::api::media::UpdateMedia::PATH.is_empty());
+        assert!(!frontend::api::gallery::GetGallery::PATH.is_empty());
+        assert!(!frontend::api::gallery::ListAlbums::PATH.is_empty());
+        assert!(!frontend::api::gallery::GetAlbum::PATH.is_empty());
+        assert!(!frontend::api::gallery::GetAlbumContents::PATH.is_empty());
+        assert!(!frontend::api::gallery::SaveAlbum::PATH.is_empty());
+        assert!(!frontend::api::gallery::DeleteAlbum::PATH.is_empty());
+        assert!(!frontend::api::gallery::ReorderAlbums::PATH.is_empty());
+        assert!(!frontend::api::gallery::SetAlbumItems::PATH.is_empty());
+        assert!(!frontend::api::gallery::GetGalleryItem::PATH.is_empty());
+        assert!(!frontend::api::music::GetTracks::PATH.is_empty());
+        assert!(!frontend::api::music::GetTrack::PATH.is_empty());
+        assert!(!frontend::api::music::GetAllTracks::PATH.is_empty());
+        assert!(!frontend::api::music::SaveTrack::PATH.is_empty());
+        assert!(!frontend::api::music::DeleteTrack::PATH.is_empty());
+        assert!(!frontend::api::media::SetMetadataStripping::PATH.is_empty());
         assert!(!frontend::api::pages::GetPage::PATH.is_empty());
         assert!(!frontend::api::pages::SavePage::PATH.is_empty());
+        assert!(!frontend::api::blog::GetBlogPosts::PATH.is_empty());
+        assert!(!frontend::api::blog::GetBlogPost::PATH.is_empty());
+        assert!(!frontend::api::blog::GetBlogDraftsAndScheduled::PATH.is_empty());
+        assert!(!frontend::api::blog::SaveBlogPost::PATH.is_empty());
+        assert!(!frontend::api::blog::DeleteBlogPost::PATH.is_empty());
+        assert!(!frontend::api::creative::GetCreativeWorks::PATH.is_empty());
+        assert!(!frontend::api::creative::GetCreativeWork::PATH.is_empty());
+        assert!(!frontend::api::creative::GetCreativeDraftsAndScheduled::PATH.is_empty());
+        assert!(!frontend::api::creative::SaveCreativeWork::PATH.is_empty());
+        assert!(!frontend::api::creative::DeleteCreativeWork::PATH.is_empty());
+        assert!(!frontend::api::projects::GetProjects::PATH.is_empty());
+        assert!(!frontend::api::projects::SaveProject::PATH.is_empty());
+        assert!(!frontend::api::projects::DeleteProject::PATH.is_empty());
+        assert!(!frontend::api::projects::SyncGithubProjects::PATH.is_empty());
+        assert!(!frontend::api::search::SearchContent::PATH.is_empty());
+        assert!(!frontend::api::revisions::ListRevisions::PATH.is_empty());
+        assert!(!frontend::api::revisions::DiffRevisions::PATH.is_empty());
+        assert!(!frontend::api::revisions::RestoreRevision::PATH.is_empty());
+        assert!(!frontend::api::sessions::ListSessions::PATH.is_empty());
+        assert!(!frontend::api::sessions::RevokeSession::PATH.is_empty());
+        assert!(!frontend::api::audit::ListAuditEvents::PATH.is_empty());
+        assert!(!frontend::api::archive::ListBackups::PATH.is_empty());
+        assert!(!frontend::api::archive::CreateBackup::PATH.is_empty());
+        assert!(!frontend::api::archive::RestoreBackup::PATH.is_empty());
     }
 }
@@ -0,0 +1,176 @@
+use sqlx::{Row, SqlitePool};
+use std::sync::Arc;
+
+/// Default interval between scheduler passes (one minute).
+pub const DEFAULT_SCHEDULER_INTERVAL_SECS: u64 = 60;
+
+/// Tables with a `status` column that the scheduler publishes, as `(kind, table)`.
+const SCHEDULED_TABLES: &[(&str, &str)] = &[
+    ("article", "articles"),
+    ("blog", "blog_posts"),
+    ("creative", "creative_works"),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublishedPost {
+    pub kind: &'static str,
+    pub id: String,
+    pub slug: String,
+    pub scheduled_for: String,
+    /// Published by the startup pass because it came due while the server was down.
+    pub caught_up: bool,
+}
+
+/// Follow-up work for posts that just went live (feed regeneration, webhooks, cache
+/// purges). Hooks run after the status change is committed; slow work should be spawned.
+pub trait PublishHook: Send + Sync {
+    fn on_published(&self, post: &PublishedPost);
+}
+
+/// Default hook: just logs each publication.
+pub struct LogHook;
+
+impl PublishHook for LogHook {
+    fn on_published(&self, post: &PublishedPost) {
+        tracing::info!(
+            "Published scheduled {} '{}' (due {}{})",
+            post.kind,
+            post.slug,
+            post.scheduled_for,
+            if post.caught_up { ", caught up" } else { "" }
+        );
+    }
+}
+
+/// Flips every due `scheduled` row to `published` and records it in `publish_log`, in
+/// one transaction.
+pub async fn publish_due_posts(
+    pool: &SqlitePool,
+    caught_up: bool,
+) -> Result<Vec<PublishedPost>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let mut published = Vec::new();
+
+    for (kind, table) in SCHEDULED_TABLES {
+        let rows = sqlx::query(&format!(
+            "UPDATE {} SET status = 'published', updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') \
+             WHERE status = 'scheduled' AND published_at <= strftime('%Y-%m-%dT%H:%M:%fZ', 'now') \
+             RETURNING id, slug, published_at",
+            table
+        ))
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for row in rows {
+            let post = PublishedPost {
+                kind,
+                id: row.try_get("id")?,
+                slug: row.try_get("slug")?,
+                scheduled_for: row.try_get("published_at")?,
+                caught_up,
+            };
+            sqlx::query(
+                "INSERT INTO publish_log (kind, target_id, slug, scheduled_for, caught_up) VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind(post.kind)
+            .bind(&post.id)
+            .bind(&post.slug)
+            .bind(&post.scheduled_for)
+            .bind(post.caught_up)
+            .execute(&mut *tx)
+            .await?;
+            published.push(post);
+        }
+    }
+
+    tx.commit().await?;
+    Ok(published)
+}
+
+/// Starts the publishing task, which also drains the search queue on each pass. The first
+/// pass runs immediately to catch up on posts that came due while the server was down.
+/// The interval comes from `SCHEDULER_INTERVAL_SECS`; setting it to `0` disables the
+/// background job, and search then only indexes rows saved from the editors.
+pub fn spawn_scheduler(pool: SqlitePool, hooks: Vec<Arc<dyn PublishHook>>) {
+    let interval_secs = std::env::var("SCHEDULER_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_SCHEDULER_INTERVAL_SECS);
+    if interval_secs == 0 {
+        tracing::info!("Publishing scheduler disabled (SCHEDULER_INTERVAL_SECS=0)");
+        return;
+    }
+
+    tracing::info!("Starting publishing scheduler every {}s", interval_secs);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+        let mut caught_up = true;
+        loop {
+            interval.tick().await;
+            match publish_due_posts(&pool, caught_up).await {
+                Ok(posts) => {
+                    if caught_up && !posts.is_empty() {
+                        tracing::info!("Scheduler caught up on {} missed posts", posts.len());
+                    }
+                    for post in &posts {
+                        for hook in &hooks {
+                            hook.on_published(post);
+                        }
+                    }
+                    caught_up = false;
+                }
+                Err(e) => tracing::error!("Scheduler pass failed: {}", e),
+            }
+            // Picks up rows written outside the editors: imports, restores, the CLI
+            if let Err(e) = frontend::api::search::ssr::sync_index(&pool).await {
+                tracing::error!("Search indexing failed: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_publish_due_posts_flips_and_logs() {
+        let pool = frontend::api::test_pool().await;
+        for stmt in [
+            "INSERT INTO articles (id, slug, title, content, author, published_at, status) VALUES ('a1', 'due', 'Due', '', 'Jake Wray', '2020-01-01T00:00:00.000Z', 'scheduled')",
+            "INSERT INTO articles (id, slug, title, content, author, published_at, status) VALUES ('a2', 'future', 'Future', '', 'Jake Wray', '2999-01-01T00:00:00.000Z', 'scheduled')",
+            "INSERT INTO articles (id, slug, title, content, author, published_at, status) VALUES ('a3', 'draft', 'Draft', '', 'Jake Wray', '2020-01-01T00:00:00.000Z', 'draft')",
+        ] {
+            sqlx::query(stmt).execute(&pool).await.unwrap();
+        }
+
+        let published = publish_due_posts(&pool, true).await.unwrap();
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].slug, "due");
+        assert!(published[0].caught_up);
+
+        let statuses: Vec<(String, String)> = sqlx::query_as(
+            "SELECT slug, status FROM articles WHERE id IN ('a1', 'a2', 'a3') ORDER BY id",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            statuses,
+            vec![
+                ("due".to_string(), "published".to_string()),
+                ("future".to_string(), "scheduled".to_string()),
+                ("draft".to_string(), "draft".to_string()),
+            ]
+        );
+
+        let logged: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM publish_log WHERE target_id = 'a1' AND caught_up = 1",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(logged.0, 1);
+        assert!(publish_due_posts(&pool, false).await.unwrap().is_empty());
+    }
+}
@@ -11,7 +11,13 @@ pub async fn file_and_error_handler(
     req: axum::extract::Request,
 ) -> AxumResponse {
     let root = state.leptos_options.site_root.clone();
-    let res = get_static_file(uri, &root).await;
+    let mut res = get_static_file(uri.clone(), &root).await;
+
+    // Uploads written by the local media store live outside the site root
+    if res.status() != axum::http::StatusCode::OK && is_local_media_request(&uri) {
+        let media_root = frontend::api::storage::local_media_dir();
+        res = get_static_file(uri, &media_root.to_string_lossy()).await;
+    }
 
     if res.status() == axum::http::StatusCode::OK {
         res.into_response()
@@ -27,6 +33,16 @@ pub async fn file_and_error_handler(
     }
 }
 
+fn is_local_media_request(uri: &axum::http::Uri) -> bool {
+    if !frontend::api::storage::is_local_media_store() {
+        return false;
+    }
+    let prefix = frontend::api::storage::get_media_store().prefix();
+    uri.path()
+        .strip_prefix('/')
+        .is_some_and(|path| path.starts_with(prefix))
+}
+
 pub async fn get_static_file(uri: axum::http::Uri, root: &str) -> AxumResponse {
     let uri_str = uri.to_string();
     let req = axum::extract::Request::builder()
@@ -1,10 +1,13 @@
+use crate::api::public::feeds::urlencode;
 use axum::body::Body;
-use axum::http::Request;
+use axum::extract::State;
+use axum::http::{header, Method, Request, StatusCode};
 use axum::middleware::Next;
 use axum::response::{IntoResponse, Response as AxumResponse};
 use bytes::Bytes;
 use futures_util::stream;
 use futures_util::StreamExt;
+use sqlx::SqlitePool;
 
 pub async fn inject_doctype(
     req: Request<Body>,
@@ -28,3 +31,107 @@ pub async fn inject_doctype(
 
     Ok(res.into_response())
 }
+
+/// Article slug from a `/journalism/{slug}` path, percent-decoded.
+fn article_slug_from_path(path: &str) -> Option<String> {
+    let slug = path.strip_prefix("/journalism/")?.trim_end_matches('/');
+    if slug.is_empty() || slug.contains('/') {
+        return None;
+    }
+    percent_encoding::percent_decode_str(slug)
+        .decode_utf8()
+        .ok()
+        .map(|s| s.into_owned())
+}
+
+/// Permanently redirects renamed article slugs to the article's current URL. Old slugs
+/// are only followed while no live article has taken them over.
+pub async fn redirect_old_slugs(
+    State(pool): State<SqlitePool>,
+    req: Request<Body>,
+    next: Next,
+) -> AxumResponse {
+    if req.method() != Method::GET && req.method() != Method::HEAD {
+        return next.run(req).await;
+    }
+    let Some(slug) = article_slug_from_path(req.uri().path()) else {
+        return next.run(req).await;
+    };
+
+    let target: Result<Option<String>, sqlx::Error> = sqlx::query_scalar(
+        "SELECT a.slug FROM redirects r JOIN articles a ON a.id = r.target_id \
+         WHERE r.kind = 'article' AND r.old_slug = ?1 \
+         AND NOT EXISTS (SELECT 1 FROM articles WHERE slug = ?1)",
+    )
+    .bind(&slug)
+    .fetch_optional(&pool)
+    .await;
+
+    match target {
+        Ok(Some(new_slug)) => (
+            StatusCode::MOVED_PERMANENTLY,
+            [(
+                header::LOCATION,
+                format!("/journalism/{}", urlencode(&new_slug)),
+            )],
+        )
+            .into_response(),
+        Ok(None) => next.run(req).await,
+        Err(e) => {
+            tracing::error!("Failed to look up slug redirect: {}", e);
+            next.run(req).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_article_slug_from_path() {
+        assert_eq!(
+            article_slug_from_path("/journalism/old-story"),
+            Some("old-story".to_string())
+        );
+        assert_eq!(
+            article_slug_from_path("/journalism/caf%C3%A9/"),
+            Some("café".to_string())
+        );
+        assert_eq!(article_slug_from_path("/journalism"), None);
+        assert_eq!(article_slug_from_path("/journalism/a/b"), None);
+        assert_eq!(article_slug_from_path("/blog/old-story"), None);
+    }
+
+    #[tokio::test]
+    async fn test_redirect_old_slugs() {
+        use axum::routing::get;
+        use tower::ServiceExt;
+
+        let pool = frontend::api::test_pool().await;
+        for stmt in [
+            "INSERT INTO articles (id, slug, title, content, author) VALUES ('a1', 'new-story', 'Story', '', 'Jake Wray')",
+            "INSERT INTO redirects (kind, old_slug, target_id) VALUES ('article', 'old-story', 'a1')",
+        ] {
+            sqlx::query(stmt).execute(&pool).await.unwrap();
+        }
+        let app = axum::Router::new()
+            .route("/journalism/:slug", get(|| async { "article" }))
+            .layer(axum::middleware::from_fn_with_state(
+                pool.clone(),
+                redirect_old_slugs,
+            ));
+        let get = |path: &str| {
+            app.clone()
+                .oneshot(Request::get(path).body(Body::empty()).unwrap())
+        };
+
+        let res = get("/journalism/old-story").await.unwrap();
+        assert_eq!(res.status(), StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(res.headers()[header::LOCATION], "/journalism/new-story");
+        assert_eq!(
+            get("/journalism/new-story").await.unwrap().status(),
+            StatusCode::OK
+        );
+    }
+}
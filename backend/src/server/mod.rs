@@ -2,4 +2,4 @@ pub mod files;
 pub mod middleware;
 
 pub use files::file_and_error_handler;
-pub use middleware::inject_doctype;
+pub use middleware::{inject_doctype, redirect_old_slugs};
@@ -0,0 +1,865 @@
+//! Portable copies of the site's content, for backups and for moving content between
+//! deployments.
+//!
+//! A backup is a zip archive with a `manifest.json`, the content tables under `content/`
+//! (one JSON file per table, or one Markdown file with front matter per row) and,
+//! optionally, the media files under `media/`.
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Version of the archive layout, bumped when it changes incompatibly.
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// Content tables in an export, in the order they are restored.
+pub const CONTENT_TABLES: &[&str] = &[
+    "media_items",
+    "albums",
+    "album_items",
+    "articles",
+    "pages",
+    "blog_posts",
+    "creative_works",
+    "projects",
+    "music_tracks",
+];
+
+/// Every row of the content tables, as JSON objects keyed by column name.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ContentDump {
+    pub exported_at: String,
+    pub tables: BTreeMap<String, Vec<serde_json::Value>>,
+}
+
+/// How content rows are written in an archive.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveFormat {
+    /// `content/<table>.json` with every row of the table.
+    #[default]
+    Json,
+    /// `content/<table>/<id>.md`, with the `content` column as the body and the other
+    /// columns as front matter.
+    Markdown,
+}
+
+impl ArchiveFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ArchiveFormat::Json => "json",
+            ArchiveFormat::Markdown => "markdown",
+        }
+    }
+}
+
+impl std::str::FromStr for ArchiveFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(ArchiveFormat::Json),
+            "markdown" | "md" => Ok(ArchiveFormat::Markdown),
+            other => Err(format!("Unknown archive format: {}", other)),
+        }
+    }
+}
+
+/// `manifest.json` at the root of an archive.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ArchiveManifest {
+    pub archive_version: u32,
+    /// Latest migration applied to the database the archive was made from.
+    pub schema_version: i64,
+    pub exported_at: String,
+    pub format: ArchiveFormat,
+    /// Row count of each table.
+    pub tables: BTreeMap<String, usize>,
+    /// Object names of the media files included, if any.
+    #[serde(default)]
+    pub media: Vec<String>,
+}
+
+/// A backup archive kept on the server.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct BackupInfo {
+    pub name: String,
+    pub size: u64,
+    pub created_at: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct RestoreSummary {
+    /// Rows upserted per table.
+    pub tables: Vec<(String, usize)>,
+    pub media: usize,
+}
+
+impl std::fmt::Display for RestoreSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (table, rows) in &self.tables {
+            writeln!(f, "{}: {} rows", table, rows)?;
+        }
+        write!(f, "media files: {}", self.media)
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use super::{
+        ArchiveFormat, ArchiveManifest, BackupInfo, ContentDump, RestoreSummary, ARCHIVE_VERSION,
+        CONTENT_TABLES,
+    };
+    use crate::api::media::content_type_for;
+    use crate::api::storage::MediaStore;
+    use serde_json::{Map, Value};
+    use sqlx::SqlitePool;
+    use std::collections::BTreeMap;
+    use std::io::{Read, Seek, Write};
+    use std::path::PathBuf;
+
+    async fn table_columns(
+        executor: impl sqlx::SqliteExecutor<'_>,
+        table: &str,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar("SELECT name FROM pragma_table_info(?) ORDER BY cid")
+            .bind(table)
+            .fetch_all(executor)
+            .await
+    }
+
+    /// Whether `table` has a unique index on `slug` alone.
+    async fn has_unique_slug(
+        executor: impl sqlx::SqliteExecutor<'_>,
+        table: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let found: Option<i64> = sqlx::query_scalar(
+            "SELECT 1 FROM pragma_index_list(?1) AS il \
+             WHERE il.\"unique\" = 1 \
+             AND (SELECT group_concat(name) FROM pragma_index_info(il.name)) = 'slug'",
+        )
+        .bind(table)
+        .fetch_optional(executor)
+        .await?;
+        Ok(found.is_some())
+    }
+
+    fn quote_ident(name: &str) -> String {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+
+    /// Reads every content table. Column values keep their SQLite types.
+    pub async fn export_content(pool: &SqlitePool) -> Result<ContentDump, sqlx::Error> {
+        let mut dump = ContentDump {
+            exported_at: chrono::Utc::now()
+                .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+                .to_string(),
+            ..Default::default()
+        };
+        for table in CONTENT_TABLES {
+            let columns = table_columns(pool, table).await?;
+            let fields = columns
+                .iter()
+                .map(|c| format!("'{}', {}", c, quote_ident(c)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let rows: Vec<String> = sqlx::query_scalar(&format!(
+                "SELECT json_object({}) FROM {} ORDER BY rowid",
+                fields, table
+            ))
+            .fetch_all(pool)
+            .await?;
+            let rows = rows
+                .iter()
+                .map(|row| serde_json::from_str(row))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| sqlx::Error::Decode(e.into()))?;
+            dump.tables.insert(table.to_string(), rows);
+        }
+        Ok(dump)
+    }
+
+    /// Upserts the rows of `dump` in one transaction, returning how many rows each table
+    /// received. Rows are matched by id, or else by slug where slugs are unique (keeping
+    /// this database's id, e.g. for the seeded about page). Columns this database doesn't
+    /// have are ignored and tables outside [`CONTENT_TABLES`] are refused.
+    pub async fn import_content(
+        pool: &SqlitePool,
+        dump: &ContentDump,
+    ) -> Result<Vec<(String, usize)>, String> {
+        if let Some(unknown) = dump
+            .tables
+            .keys()
+            .find(|table| !CONTENT_TABLES.contains(&table.as_str()))
+        {
+            return Err(format!("Unknown table in import: {}", unknown));
+        }
+
+        let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+        let mut counts = Vec::new();
+        for table in CONTENT_TABLES {
+            let Some(rows) = dump.tables.get(*table).filter(|rows| !rows.is_empty()) else {
+                continue;
+            };
+            if rows
+                .iter()
+                .any(|row| row.get("id").and_then(|id| id.as_str()).is_none())
+            {
+                return Err(format!("Every row in {} needs a string id", table));
+            }
+            // Only set the columns the import mentions, so the rest keep their defaults
+            let columns: Vec<String> = table_columns(&mut *tx, table)
+                .await
+                .map_err(|e| e.to_string())?
+                .into_iter()
+                .filter(|c| rows.iter().any(|row| row.get(c).is_some()))
+                .collect();
+            let names = columns
+                .iter()
+                .map(|c| quote_ident(c))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let values = columns
+                .iter()
+                .map(|c| format!("value ->> '$.{}'", c))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let updates = columns
+                .iter()
+                .filter(|c| *c != "id")
+                .map(|c| format!("{0} = excluded.{0}", quote_ident(c)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let on_conflict = if updates.is_empty() {
+                "DO NOTHING".to_string()
+            } else {
+                format!("DO UPDATE SET {}", updates)
+            };
+            let mut conflict = format!("ON CONFLICT(id) {}", on_conflict);
+            if columns.iter().any(|c| c == "slug")
+                && has_unique_slug(&mut *tx, table)
+                    .await
+                    .map_err(|e| e.to_string())?
+            {
+                conflict.push_str(&format!(" ON CONFLICT(slug) {}", on_conflict));
+            }
+            let json = serde_json::to_string(rows).map_err(|e| e.to_string())?;
+            sqlx::query(&format!(
+                "INSERT INTO {} ({}) SELECT {} FROM json_each(?) WHERE true {}",
+                table, names, values, conflict
+            ))
+            .bind(json)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Importing {} failed: {}", table, e))?;
+            counts.push((table.to_string(), rows.len()));
+        }
+        tx.commit().await.map_err(|e| e.to_string())?;
+        Ok(counts)
+    }
+
+    /// Version of the latest migration applied to the database.
+    pub async fn schema_version(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("SELECT COALESCE(MAX(version), 0) FROM _sqlx_migrations WHERE success")
+            .fetch_one(pool)
+            .await
+    }
+
+    /// Column holding a row's main text, written as the body of its Markdown file.
+    const BODY_COLUMN: &str = "content";
+    const FRONT_MATTER: &str = "---\n";
+
+    /// A row as Markdown. Front matter values are JSON, which YAML readers accept too.
+    pub fn row_to_markdown(row: &Map<String, Value>) -> String {
+        let mut out = String::from(FRONT_MATTER);
+        for (column, value) in row {
+            if column == BODY_COLUMN && value.is_string() {
+                continue;
+            }
+            out.push_str(&format!("{}: {}\n", column, value));
+        }
+        out.push_str(FRONT_MATTER);
+        if let Some(body) = row.get(BODY_COLUMN).and_then(Value::as_str) {
+            out.push_str(body);
+        }
+        out
+    }
+
+    pub fn row_from_markdown(text: &str) -> Result<Map<String, Value>, String> {
+        let rest = text
+            .strip_prefix(FRONT_MATTER)
+            .ok_or("Missing front matter")?;
+        let (front_matter, body) = if let Some(body) = rest.strip_prefix(FRONT_MATTER) {
+            ("", body)
+        } else {
+            rest.split_once(&format!("\n{}", FRONT_MATTER))
+                .ok_or("Unterminated front matter")?
+        };
+        let mut row = Map::new();
+        for line in front_matter.lines() {
+            let (column, value) = line
+                .split_once(": ")
+                .ok_or_else(|| format!("Malformed front matter line: {}", line))?;
+            let value = serde_json::from_str(value)
+                .map_err(|e| format!("Bad value for {}: {}", column, e))?;
+            row.insert(column.to_string(), value);
+        }
+        if !row.contains_key(BODY_COLUMN) {
+            row.insert(BODY_COLUMN.to_string(), Value::String(body.to_string()));
+        }
+        Ok(row)
+    }
+
+    fn safe_file_stem(id: &str) -> String {
+        id.chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    }
+
+    fn zip_error(e: zip::result::ZipError) -> String {
+        format!("Archive error: {}", e)
+    }
+
+    /// Writes an archive of every content table to `writer`, including the files in
+    /// `media` when given.
+    pub async fn write_archive<W: Write + Seek + Send>(
+        pool: &SqlitePool,
+        media: Option<&dyn MediaStore>,
+        format: ArchiveFormat,
+        writer: W,
+    ) -> Result<ArchiveManifest, String> {
+        use zip::write::SimpleFileOptions;
+        use zip::CompressionMethod;
+
+        let dump = export_content(pool).await.map_err(|e| e.to_string())?;
+        let mut manifest = ArchiveManifest {
+            archive_version: ARCHIVE_VERSION,
+            schema_version: schema_version(pool).await.map_err(|e| e.to_string())?,
+            exported_at: dump.exported_at.clone(),
+            format,
+            tables: dump
+                .tables
+                .iter()
+                .map(|(table, rows)| (table.clone(), rows.len()))
+                .collect(),
+            media: Vec::new(),
+        };
+
+        let mut zip = zip::ZipWriter::new(writer);
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        for (table, rows) in &dump.tables {
+            match format {
+                ArchiveFormat::Json => {
+                    zip.start_file(format!("content/{}.json", table), options)
+                        .map_err(zip_error)?;
+                    serde_json::to_writer_pretty(&mut zip, rows).map_err(|e| e.to_string())?;
+                }
+                ArchiveFormat::Markdown => {
+                    for (i, row) in rows.iter().enumerate() {
+                        let Some(row) = row.as_object() else {
+                            continue;
+                        };
+                        let stem = row
+                            .get("id")
+                            .and_then(Value::as_str)
+                            .map(safe_file_stem)
+                            .unwrap_or_else(|| format!("row-{}", i + 1));
+                        zip.start_file(format!("content/{}/{}.md", table, stem), options)
+                            .map_err(zip_error)?;
+                        zip.write_all(row_to_markdown(row).as_bytes())
+                            .map_err(|e| e.to_string())?;
+                    }
+                }
+            }
+        }
+
+        if let Some(store) = media {
+            // Media is already compressed
+            let stored = options.compression_method(CompressionMethod::Stored);
+            for object in store.list().await? {
+                let data = store.get(&object.name).await?;
+                zip.start_file(format!("media/{}", object.name), stored)
+                    .map_err(zip_error)?;
+                zip.write_all(&data).map_err(|e| e.to_string())?;
+                manifest.media.push(object.name);
+            }
+        }
+
+        zip.start_file("manifest.json", options)
+            .map_err(zip_error)?;
+        serde_json::to_writer_pretty(&mut zip, &manifest).map_err(|e| e.to_string())?;
+        zip.finish().map_err(zip_error)?;
+        Ok(manifest)
+    }
+
+    /// Reads the manifest and content of an archive, refusing one this database can't
+    /// take: another archive version, or a newer schema than `current_schema`.
+    pub fn read_archive<R: Read + Seek>(
+        zip: &mut zip::ZipArchive<R>,
+        current_schema: i64,
+    ) -> Result<(ArchiveManifest, ContentDump), String> {
+        let manifest: ArchiveManifest = {
+            let file = zip
+                .by_name("manifest.json")
+                .map_err(|_| "Not a backup archive: manifest.json is missing".to_string())?;
+            serde_json::from_reader(file).map_err(|e| format!("Invalid manifest: {}", e))?
+        };
+        if manifest.archive_version != ARCHIVE_VERSION {
+            return Err(format!(
+                "Unsupported archive version {} (expected {})",
+                manifest.archive_version, ARCHIVE_VERSION
+            ));
+        }
+        if manifest.schema_version > current_schema {
+            return Err(format!(
+                "The archive is from a newer database schema ({}) than this one ({}); \
+                 update and run the migrations first",
+                manifest.schema_version, current_schema
+            ));
+        }
+
+        let mut dump = ContentDump {
+            exported_at: manifest.exported_at.clone(),
+            tables: BTreeMap::new(),
+        };
+        match manifest.format {
+            ArchiveFormat::Json => {
+                for table in manifest.tables.keys() {
+                    let name = format!("content/{}.json", table);
+                    let file = zip
+                        .by_name(&name)
+                        .map_err(|_| format!("The archive is missing {}", name))?;
+                    let rows = serde_json::from_reader(file)
+                        .map_err(|e| format!("Invalid {}: {}", name, e))?;
+                    dump.tables.insert(table.clone(), rows);
+                }
+            }
+            ArchiveFormat::Markdown => {
+                let names: Vec<String> = zip
+                    .file_names()
+                    .filter(|name| name.starts_with("content/") && name.ends_with(".md"))
+                    .map(String::from)
+                    .collect();
+                for name in names {
+                    let table = name["content/".len()..]
+                        .split('/')
+                        .next()
+                        .unwrap_or_default()
+                        .to_string();
+                    let mut text = String::new();
+                    zip.by_name(&name)
+                        .map_err(zip_error)?
+                        .read_to_string(&mut text)
+                        .map_err(|e| format!("Reading {}: {}", name, e))?;
+                    let row = row_from_markdown(&text).map_err(|e| format!("{}: {}", name, e))?;
+                    dump.tables
+                        .entry(table)
+                        .or_default()
+                        .push(Value::Object(row));
+                }
+            }
+        }
+
+        for (table, expected) in &manifest.tables {
+            let found = dump.tables.get(table).map_or(0, Vec::len);
+            if found != *expected {
+                return Err(format!(
+                    "The archive is incomplete: {} has {} of {} rows",
+                    table, found, expected
+                ));
+            }
+        }
+        Ok((manifest, dump))
+    }
+
+    /// Restores an archive: upserts its content by id and writes its media files back
+    /// into `store`.
+    pub async fn restore_archive<R: Read + Seek + Send>(
+        pool: &SqlitePool,
+        store: &dyn MediaStore,
+        reader: R,
+    ) -> Result<RestoreSummary, String> {
+        let mut zip =
+            zip::ZipArchive::new(reader).map_err(|e| format!("Not a backup archive: {}", e))?;
+        let current_schema = schema_version(pool).await.map_err(|e| e.to_string())?;
+        let (manifest, dump) = read_archive(&mut zip, current_schema)?;
+        let tables = import_content(pool, &dump).await?;
+
+        for name in &manifest.media {
+            let mut data = Vec::new();
+            zip.by_name(&format!("media/{}", name))
+                .map_err(|_| format!("The archive is missing media/{}", name))?
+                .read_to_end(&mut data)
+                .map_err(|e| format!("Reading media/{}: {}", name, e))?;
+            store.put(name, content_type_for(name), data).await?;
+        }
+        Ok(RestoreSummary {
+            tables,
+            media: manifest.media.len(),
+        })
+    }
+
+    /// Where backups made from the admin page are kept (`BACKUP_DIR`).
+    pub fn backup_dir() -> PathBuf {
+        std::env::var("BACKUP_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from("data/backups"))
+    }
+
+    /// Path of the backup called `name`, refusing anything but a plain archive file name.
+    pub fn backup_path(name: &str) -> Result<PathBuf, String> {
+        let valid = name.ends_with(".zip")
+            && !name.starts_with('.')
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if !valid {
+            return Err("Invalid backup name".to_string());
+        }
+        Ok(backup_dir().join(name))
+    }
+
+    fn backup_info(path: &std::path::Path) -> Result<BackupInfo, String> {
+        let metadata = std::fs::metadata(path).map_err(|e| e.to_string())?;
+        let created_at: chrono::DateTime<chrono::Utc> =
+            metadata.modified().map_err(|e| e.to_string())?.into();
+        Ok(BackupInfo {
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            size: metadata.len(),
+            created_at: created_at.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        })
+    }
+
+    /// Backups in [`backup_dir`], newest first.
+    pub fn list_backup_files() -> Result<Vec<BackupInfo>, String> {
+        let dir = backup_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut backups = std::fs::read_dir(&dir)
+            .map_err(|e| format!("Reading {}: {}", dir.display(), e))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "zip"))
+            .map(|path| backup_info(&path))
+            .collect::<Result<Vec<_>, _>>()?;
+        backups.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.name.cmp(&a.name)));
+        Ok(backups)
+    }
+
+    /// Writes a new archive into [`backup_dir`].
+    pub async fn create_backup_file(
+        pool: &SqlitePool,
+        media: Option<&dyn MediaStore>,
+        format: ArchiveFormat,
+    ) -> Result<BackupInfo, String> {
+        let dir = backup_dir();
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Creating {}: {}", dir.display(), e))?;
+        let name = format!(
+            "backup-{}-{}.zip",
+            chrono::Utc::now().format("%Y%m%d-%H%M%S"),
+            format.as_str()
+        );
+        let path = dir.join(name);
+        let file = std::fs::File::create(&path)
+            .map_err(|e| format!("Creating {}: {}", path.display(), e))?;
+        if let Err(e) = write_archive(pool, media, format, file).await {
+            let _ = std::fs::remove_file(&path);
+            return Err(e);
+        }
+        backup_info(&path)
+    }
+}
+
+/// Backups kept on the server, for the admin backups page.
+#[server(ListBackups, "/api")]
+pub async fn list_backups() -> Result<Vec<BackupInfo>, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Admin).await?;
+
+        ssr::list_backup_files().map_err(ServerFnError::new)
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    Ok(Vec::new())
+}
+
+/// Writes a new backup archive on the server, with the media files if `include_media`.
+#[server(CreateBackup, "/api")]
+pub async fn create_backup(
+    format: ArchiveFormat,
+    include_media: bool,
+) -> Result<BackupInfo, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        let claims = require_role(Role::Admin).await?;
+
+        use sqlx::SqlitePool;
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        let media = include_media.then(crate::api::storage::get_media_store);
+        let backup = ssr::create_backup_file(&pool, media, format)
+            .await
+            .map_err(ServerFnError::new)?;
+
+        use crate::api::audit::{ssr as audit, AuditAction};
+        let client_ip = audit::request_client_ip();
+        audit::record_event(
+            &pool,
+            AuditAction::BackupCreate,
+            audit::NewAuditEvent {
+                actor: Some(&claims.sub),
+                ip: client_ip.as_deref(),
+                target: Some(&backup.name),
+                after: Some(format!(
+                    "{} archive{}, {} bytes",
+                    format.as_str(),
+                    if include_media { " with media" } else { "" },
+                    backup.size
+                )),
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to record audit event: {}", e)))?;
+        Ok(backup)
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = (format, include_media);
+        Err(ServerFnError::new(
+            "Backups are only available on the server",
+        ))
+    }
+}
+
+/// Restores one of the server's backups over the current content.
+#[server(RestoreBackup, "/api")]
+pub async fn restore_backup(name: String) -> Result<RestoreSummary, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        let claims = require_role(Role::Admin).await?;
+
+        use sqlx::SqlitePool;
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        let path = ssr::backup_path(&name).map_err(ServerFnError::new)?;
+        let file = std::fs::File::open(&path)
+            .map_err(|_| ServerFnError::new(format!("No backup named {}", name)))?;
+        let store = crate::api::storage::get_media_store();
+        let summary = ssr::restore_archive(&pool, store, file)
+            .await
+            .map_err(ServerFnError::new)?;
+
+        use crate::api::audit::{ssr as audit, AuditAction};
+        let client_ip = audit::request_client_ip();
+        let rows: usize = summary.tables.iter().map(|(_, rows)| rows).sum();
+        audit::record_event(
+            &pool,
+            AuditAction::BackupRestore,
+            audit::NewAuditEvent {
+                actor: Some(&claims.sub),
+                ip: client_ip.as_deref(),
+                target: Some(&name),
+                after: Some(format!(
+                    "{} rows and {} media files restored",
+                    rows, summary.media
+                )),
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to record audit event: {}", e)))?;
+        Ok(summary)
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = name;
+        Err(ServerFnError::new(
+            "Backups are only available on the server",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "ssr")]
+    #[tokio::test]
+    async fn test_export_import_roundtrip() {
+        use super::ssr::*;
+        use super::ContentDump;
+
+        let source = crate::api::test_pool().await;
+        sqlx::query(
+            "INSERT INTO articles (id, slug, title, content, author, published_at, origin, wp_id) \
+             VALUES ('a1', 'council-vote', 'Council vote', '<p>Yes</p>', 'Jake Wray', '2026-01-02T00:00:00.000Z', 'imported', 42)",
+        )
+        .execute(&source)
+        .await
+        .unwrap();
+        let source_about: String = sqlx::query_scalar(
+            "UPDATE pages SET title = 'About' WHERE slug = 'about' RETURNING id",
+        )
+        .fetch_one(&source)
+        .await
+        .unwrap();
+
+        let dump = export_content(&source).await.unwrap();
+        assert_eq!(dump.tables["articles"].len(), 1);
+        assert_eq!(dump.tables["articles"][0]["wp_id"], 42);
+
+        // Through the file format and into an empty database, twice to check it upserts
+        let dump: ContentDump =
+            serde_json::from_str(&serde_json::to_string(&dump).unwrap()).unwrap();
+        let target = crate::api::test_pool().await;
+        import_content(&target, &dump).await.unwrap();
+        let counts = import_content(&target, &dump).await.unwrap();
+        assert!(counts.contains(&("pages".to_string(), 1)));
+
+        let (title, wp_id): (String, i64) =
+            sqlx::query_as("SELECT title, wp_id FROM articles WHERE id = 'a1'")
+                .fetch_one(&target)
+                .await
+                .unwrap();
+        assert_eq!((title.as_str(), wp_id), ("Council vote", 42));
+
+        // The target's seeded about page has its own id; it is updated rather than duplicated
+        let about: (String, String) =
+            sqlx::query_as("SELECT id, title FROM pages WHERE slug = 'about'")
+                .fetch_one(&target)
+                .await
+                .unwrap();
+        assert_ne!(about.0, source_about);
+        assert_eq!(about.1, "About");
+
+        let mut bad = dump.clone();
+        bad.tables.insert("users".to_string(), Vec::new());
+        assert!(import_content(&target, &bad).await.is_err());
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn test_markdown_rows() {
+        use super::ssr::{row_from_markdown, row_to_markdown};
+
+        let row = serde_json::json!({
+            "id": "a1",
+            "title": "Council: the vote",
+            "content": "<p>Yes</p>\n---\n<p>No</p>",
+            "wp_id": 42,
+            "cover_image_url": null,
+        });
+        let row = row.as_object().unwrap();
+        let text = row_to_markdown(row);
+        assert!(text.starts_with("---\n"));
+        assert!(text.ends_with("---\n<p>Yes</p>\n---\n<p>No</p>"));
+        assert_eq!(&row_from_markdown(&text).unwrap(), row);
+
+        assert!(row_from_markdown("no front matter").is_err());
+    }
+
+    #[cfg(feature = "ssr")]
+    #[tokio::test]
+    async fn test_archive_roundtrip() {
+        use super::ssr::*;
+        use super::ArchiveFormat;
+        use crate::api::storage::{LocalMediaStore, MediaStore};
+        use std::io::Cursor;
+
+        let store_at = |name: &str| {
+            LocalMediaStore::new(
+                std::env::temp_dir().join(format!("{}-{}", name, uuid::Uuid::new_v4())),
+                "media/",
+            )
+        };
+        let source = crate::api::test_pool().await;
+        sqlx::query(
+            "INSERT INTO articles (id, slug, title, content, author, published_at, origin) \
+             VALUES ('a1', 'council-vote', 'Council vote', '<p>Yes</p>', 'Jake Wray', '2026-01-02T00:00:00.000Z', 'local')",
+        )
+        .execute(&source)
+        .await
+        .unwrap();
+        let source_store = store_at("archive-source");
+        source_store
+            .put("media/photo.jpg", "image/jpeg", vec![1, 2, 3])
+            .await
+            .unwrap();
+
+        for format in [ArchiveFormat::Json, ArchiveFormat::Markdown] {
+            let mut buffer = Cursor::new(Vec::new());
+            let manifest = write_archive(&source, Some(&source_store), format, &mut buffer)
+                .await
+                .unwrap();
+            assert_eq!(manifest.format, format);
+            assert_eq!(manifest.tables["articles"], 1);
+            assert_eq!(manifest.media, vec!["media/photo.jpg".to_string()]);
+            assert_eq!(
+                manifest.schema_version,
+                schema_version(&source).await.unwrap()
+            );
+
+            let target = crate::api::test_pool().await;
+            let target_store = store_at("archive-target");
+            buffer.set_position(0);
+            let summary = restore_archive(&target, &target_store, buffer)
+                .await
+                .unwrap();
+            assert!(summary.tables.contains(&("articles".to_string(), 1)));
+            assert_eq!(summary.media, 1);
+
+            let (title, content): (String, String) =
+                sqlx::query_as("SELECT title, content FROM articles WHERE id = 'a1'")
+                    .fetch_one(&target)
+                    .await
+                    .unwrap();
+            assert_eq!(
+                (title.as_str(), content.as_str()),
+                ("Council vote", "<p>Yes</p>")
+            );
+            assert_eq!(
+                target_store.get("media/photo.jpg").await.unwrap(),
+                vec![1, 2, 3]
+            );
+            let _ = std::fs::remove_dir_all(target_store.root());
+        }
+        let _ = std::fs::remove_dir_all(source_store.root());
+
+        // Archives from a newer schema are refused before anything is written
+        let mut buffer = Cursor::new(Vec::new());
+        write_archive(&source, None, ArchiveFormat::Json, &mut buffer)
+            .await
+            .unwrap();
+        buffer.set_position(0);
+        let mut zip = zip::ZipArchive::new(buffer).unwrap();
+        let current = schema_version(&source).await.unwrap();
+        assert!(read_archive(&mut zip, current).is_ok());
+        assert!(read_archive(&mut zip, current - 1)
+            .unwrap_err()
+            .contains("newer database schema"));
+
+        assert!(backup_path("backup-20260101-000000-json.zip").is_ok());
+        assert!(backup_path("../secrets.zip").is_err());
+        assert!(backup_path(".hidden.zip").is_err());
+        assert!(backup_path("backup.tar").is_err());
+    }
+}
@@ -1,19 +1,26 @@
 use leptos::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 // Re-exports for backward compatibility
 #[cfg(feature = "ssr")]
 pub use crate::api::auth::ssr_utils;
 pub use crate::api::media::{
-    delete_media, list_media, upload_media, DeleteMedia, ListMedia, MediaItem, UploadMedia,
+    delete_media, list_media, upload_media, DeleteMedia, ImageVariant, ListMedia, MediaItem,
+    UploadMedia,
 };
 pub use crate::utils::html::extract_figcaption;
 pub use crate::utils::slug::{parse_article_date, sanitize_slug};
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Article {
+    /// Database id; `None` for articles that haven't been saved yet.
+    #[serde(default)]
+    pub id: Option<String>,
     pub slug: String,
     pub title: String,
+    /// `YYYY-MM-DD`; older article files call it `date`.
+    #[serde(alias = "date")]
     pub iso_date: String,
     pub display_date: String,
     pub source_url: String,
@@ -26,6 +33,9 @@ pub struct Article {
     pub byline: Option<String>,
     #[serde(default)]
     pub status: Option<String>,
+    /// Resized copies of the article's images, keyed by image URL.
+    #[serde(default)]
+    pub image_variants: BTreeMap<String, Vec<ImageVariant>>,
 }
 
 #[server(GetArticles, "/api")]
@@ -37,7 +47,7 @@ pub async fn get_articles() -> Result<Vec<Article>, ServerFnError> {
             .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
 
         let rows = sqlx::query(
-            "SELECT slug, title, excerpt, content, cover_image_url, cover_image_caption, author, published_at, status FROM articles WHERE (status IS NULL OR status = 'published' OR (status = 'scheduled' AND published_at <= strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))) AND status != 'draft' ORDER BY published_at DESC, title ASC, slug ASC"
+            "SELECT id, slug, title, excerpt, content, cover_image_url, cover_image_caption, author, published_at, status FROM articles WHERE (status IS NULL OR status = 'published' OR (status = 'scheduled' AND published_at <= strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))) AND status != 'draft' ORDER BY published_at DESC, title ASC, slug ASC"
         )
         .fetch_all(&pool)
         .await
@@ -46,6 +56,7 @@ pub async fn get_articles() -> Result<Vec<Article>, ServerFnError> {
         let mut articles = Vec::new();
         for row in rows {
             use sqlx::Row;
+            let id: String = row.get("id");
             let slug: String = row.get("slug");
             let title: String = row.get("title");
             let content_html: String = row.get("content");
@@ -87,6 +98,7 @@ pub async fn get_articles() -> Result<Vec<Article>, ServerFnError> {
             });
 
             articles.push(Article {
+                id: Some(id),
                 slug,
                 title,
                 iso_date,
@@ -102,9 +114,28 @@ pub async fn get_articles() -> Result<Vec<Article>, ServerFnError> {
                     Some(author)
                 },
                 status,
+                image_variants: BTreeMap::new(),
             });
         }
 
+        // Cards only show the cover
+        let covers: Vec<String> = articles
+            .iter()
+            .filter_map(|a| a.images.first().cloned())
+            .collect();
+        let variants = crate::api::images::variants_for_urls(&pool, &covers)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))?;
+        for article in &mut articles {
+            if let Some(cover) = article.images.first() {
+                if let Some(cover_variants) = variants.get(cover) {
+                    article
+                        .image_variants
+                        .insert(cover.clone(), cover_variants.clone());
+                }
+            }
+        }
+
         Ok(articles)
     }
 
@@ -123,7 +154,7 @@ pub async fn get_article(slug: String) -> Result<Option<Article>, ServerFnError>
         let clean_slug = sanitize_slug(&slug);
 
         let row = sqlx::query(
-            "SELECT slug, title, excerpt, content, cover_image_url, cover_image_caption, author, published_at, status FROM articles WHERE slug = ?"
+            "SELECT id, slug, title, excerpt, content, cover_image_url, cover_image_caption, author, published_at, status FROM articles WHERE slug = ?"
         )
         .bind(&clean_slug)
         .fetch_optional(&pool)
@@ -132,6 +163,7 @@ pub async fn get_article(slug: String) -> Result<Option<Article>, ServerFnError>
 
         if let Some(row) = row {
             use sqlx::Row;
+            let id: String = row.get("id");
             let slug: String = row.get("slug");
             let title: String = row.get("title");
             let content_html: String = row.get("content");
@@ -171,7 +203,14 @@ pub async fn get_article(slug: String) -> Result<Option<Article>, ServerFnError>
                 crate::utils::html::extract_body_preview(&content_html).unwrap_or_default()
             });
 
+            let mut urls = images.clone();
+            urls.extend(crate::utils::html::image_sources(&content_html));
+            let image_variants = crate::api::images::variants_for_urls(&pool, &urls)
+                .await
+                .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))?;
+
             Ok(Some(Article {
+                id: Some(id),
                 slug,
                 title,
                 iso_date,
@@ -187,6 +226,7 @@ pub async fn get_article(slug: String) -> Result<Option<Article>, ServerFnError>
                     Some(author)
                 },
                 status,
+                image_variants,
             }))
         } else {
             Ok(None)
@@ -198,18 +238,19 @@ pub async fn get_article(slug: String) -> Result<Option<Article>, ServerFnError>
 }
 
 #[server(GetDraftsAndScheduled, "/api")]
-pub async fn get_drafts_and_scheduled(token: String) -> Result<Vec<Article>, ServerFnError> {
+pub async fn get_drafts_and_scheduled() -> Result<Vec<Article>, ServerFnError> {
     #[cfg(feature = "ssr")]
     {
-        use crate::api::auth::ssr_utils::verify_token;
-        verify_token(&token)?;
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Contributor).await?;
 
         use sqlx::SqlitePool;
         let pool = use_context::<SqlitePool>()
             .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
 
         let rows = sqlx::query(
-            "SELECT slug, title, excerpt, content, cover_image_url, cover_image_caption, author, published_at, status FROM articles WHERE status = 'draft' OR (status = 'scheduled' AND published_at > strftime('%Y-%m-%dT%H:%M:%fZ', 'now')) ORDER BY published_at DESC, title ASC, slug ASC"
+            "SELECT id, slug, title, excerpt, content, cover_image_url, cover_image_caption, author, published_at, status FROM articles WHERE status = 'draft' OR (status = 'scheduled' AND published_at > strftime('%Y-%m-%dT%H:%M:%fZ', 'now')) ORDER BY published_at DESC, title ASC, slug ASC"
         )
         .fetch_all(&pool)
         .await
@@ -218,6 +259,7 @@ pub async fn get_drafts_and_scheduled(token: String) -> Result<Vec<Article>, Ser
         let mut articles = Vec::new();
         for row in rows {
             use sqlx::Row;
+            let id: String = row.get("id");
             let slug: String = row.get("slug");
             let title: String = row.get("title");
             let content_html: String = row.get("content");
@@ -258,6 +300,7 @@ pub async fn get_drafts_and_scheduled(token: String) -> Result<Vec<Article>, Ser
             });
 
             articles.push(Article {
+                id: Some(id),
                 slug,
                 title,
                 iso_date,
@@ -273,6 +316,7 @@ pub async fn get_drafts_and_scheduled(token: String) -> Result<Vec<Article>, Ser
                     Some(author)
                 },
                 status,
+                image_variants: BTreeMap::new(),
             });
         }
 
@@ -283,12 +327,81 @@ pub async fn get_drafts_and_scheduled(token: String) -> Result<Vec<Article>, Ser
     Ok(Vec::new())
 }
 
+/// Points `old_slug` at the article with `id` and drops any redirect that would now
+/// shadow `new_slug`.
+#[cfg(feature = "ssr")]
+pub(crate) async fn record_slug_redirect(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    old_slug: &str,
+    new_slug: &str,
+    id: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO redirects (kind, old_slug, target_id) VALUES ('article', ?, ?) \
+         ON CONFLICT(kind, old_slug) DO UPDATE SET target_id = excluded.target_id, \
+            created_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+    )
+    .bind(old_slug)
+    .bind(id)
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query("DELETE FROM redirects WHERE kind = 'article' AND old_slug = ?")
+        .bind(new_slug)
+        .execute(&mut **tx)
+        .await?;
+
+    Ok(())
+}
+
+/// The saved article a save applies to, as `(id, slug, title, status)`. Saved articles
+/// are matched by id so their slug can change; new ones (e.g. from the composer) fall
+/// back to matching on slug. A slug that another article uses is refused.
+#[cfg(feature = "ssr")]
+pub(crate) async fn find_saved_article(
+    pool: &sqlx::SqlitePool,
+    id: Option<&str>,
+    slug: &str,
+) -> Result<Option<(String, String, String, String)>, ServerFnError> {
+    let db_error = |e: sqlx::Error| ServerFnError::new(format!("Database query failed: {}", e));
+    let by_id: Option<(String, String, String, String)> = match id.filter(|id| !id.is_empty()) {
+        Some(id) => sqlx::query_as("SELECT id, slug, title, status FROM articles WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .map_err(db_error)?,
+        None => None,
+    };
+    let by_slug: Option<(String, String, String, String)> =
+        sqlx::query_as("SELECT id, slug, title, status FROM articles WHERE slug = ?")
+            .bind(slug)
+            .fetch_optional(pool)
+            .await
+            .map_err(db_error)?;
+
+    match (by_id, by_slug) {
+        (Some(by_id), Some(by_slug)) if by_id.0 != by_slug.0 => Err(ServerFnError::new(format!(
+            "Another article already uses the slug '{}'",
+            slug
+        ))),
+        (Some(row), _) | (None, Some(row)) => Ok(Some(row)),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Audit log description of an article's state.
+#[cfg(feature = "ssr")]
+pub(crate) fn summarize_article(title: &str, slug: &str, status: &str) -> String {
+    format!("\"{}\" at /journalism/{} ({})", title, slug, status)
+}
+
 #[server(SaveArticle, "/api")]
-pub async fn save_article(token: String, article: Article) -> Result<(), ServerFnError> {
+pub async fn save_article(article: Article) -> Result<(), ServerFnError> {
     #[cfg(feature = "ssr")]
     {
-        use crate::api::auth::ssr_utils::verify_token;
-        verify_token(&token)?;
+        use crate::api::auth::ssr_utils::{check_can_save, require_role};
+        use shared::auth::Role;
+        let claims = require_role(Role::Contributor).await?;
 
         use sqlx::SqlitePool;
         let pool = use_context::<SqlitePool>()
@@ -305,22 +418,31 @@ pub async fn save_article(token: String, article: Article) -> Result<(), ServerF
         let author = article.byline.unwrap_or_else(|| "Jake Wray".to_string());
         let status = article.status.unwrap_or_else(|| "published".to_string());
 
-        let mut id = uuid::Uuid::new_v4().to_string();
-        let existing = sqlx::query("SELECT id FROM articles WHERE slug = ?")
-            .bind(&clean_slug)
-            .fetch_optional(&pool)
+        let existing = find_saved_article(&pool, article.id.as_deref(), &clean_slug).await?;
+        let (id, old_slug) = match &existing {
+            Some((id, slug, _, _)) => (id.clone(), Some(slug.clone())),
+            None => (uuid::Uuid::new_v4().to_string(), None),
+        };
+        check_can_save(
+            claims.role,
+            &status,
+            existing.as_ref().map(|(_, _, _, status)| status.as_str()),
+        )?;
+        let before = existing
+            .as_ref()
+            .map(|(_, slug, title, status)| summarize_article(title, slug, status));
+
+        use crate::api::revisions::{ssr::record_revision, RevisionKind};
+        let mut tx = pool
+            .begin()
             .await
-            .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))?;
-
-        if let Some(row) = existing {
-            use sqlx::Row;
-            id = row.get("id");
-        }
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
 
         sqlx::query(
             "INSERT INTO articles (id, slug, title, excerpt, content, cover_image_url, cover_image_caption, author, published_at, origin, status, updated_at) \
              VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 'local', ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now')) \
-             ON CONFLICT(slug) DO UPDATE SET \
+             ON CONFLICT(id) DO UPDATE SET \
+                slug = excluded.slug, \
                 title = excluded.title, \
                 excerpt = excluded.excerpt, \
                 content = excluded.content, \
@@ -341,10 +463,48 @@ pub async fn save_article(token: String, article: Article) -> Result<(), ServerF
         .bind(&author)
         .bind(&published_at)
         .bind(&status)
-        .execute(&pool)
+        .execute(&mut *tx)
         .await
         .map_err(|e| ServerFnError::new(format!("Database insert/update failed: {}", e)))?;
 
+        if let Some(old_slug) = old_slug.filter(|old| *old != clean_slug) {
+            record_slug_redirect(&mut tx, &old_slug, &clean_slug, &id)
+                .await
+                .map_err(|e| ServerFnError::new(format!("Failed to record redirect: {}", e)))?;
+        }
+
+        record_revision(
+            &mut *tx,
+            RevisionKind::Article,
+            &id,
+            &claims.sub,
+            &article.title,
+            &article.content_html,
+        )
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to record revision: {}", e)))?;
+
+        use crate::api::audit::{ssr as audit, AuditAction};
+        let client_ip = audit::request_client_ip();
+        audit::record_event(
+            &mut *tx,
+            AuditAction::ArticleSave,
+            audit::NewAuditEvent {
+                actor: Some(&claims.sub),
+                ip: client_ip.as_deref(),
+                target: Some(&clean_slug),
+                before,
+                after: Some(summarize_article(&article.title, &clean_slug, &status)),
+            },
+        )
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to record audit event: {}", e)))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+        crate::api::search::ssr::refresh_index(&pool).await;
+
         Ok(())
     }
 
@@ -353,11 +513,12 @@ pub async fn save_article(token: String, article: Article) -> Result<(), ServerF
 }
 
 #[server(DeleteArticle, "/api")]
-pub async fn delete_article(token: String, slug: String) -> Result<(), ServerFnError> {
+pub async fn delete_article(slug: String) -> Result<(), ServerFnError> {
     #[cfg(feature = "ssr")]
     {
-        use crate::api::auth::ssr_utils::verify_token;
-        verify_token(&token)?;
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        let claims = require_role(Role::Editor).await?;
 
         use sqlx::SqlitePool;
         let pool = use_context::<SqlitePool>()
@@ -365,11 +526,47 @@ pub async fn delete_article(token: String, slug: String) -> Result<(), ServerFnE
 
         let clean_slug = sanitize_slug(&slug);
 
-        sqlx::query("DELETE FROM articles WHERE slug = ?")
-            .bind(&clean_slug)
-            .execute(&pool)
+        let mut tx = pool
+            .begin()
             .await
-            .map_err(|e| ServerFnError::new(format!("Database delete failed: {}", e)))?;
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+        sqlx::query(
+            "DELETE FROM redirects WHERE kind = 'article' AND target_id IN (SELECT id FROM articles WHERE slug = ?)",
+        )
+        .bind(&clean_slug)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database delete failed: {}", e)))?;
+
+        let deleted: Option<(String, String)> =
+            sqlx::query_as("DELETE FROM articles WHERE slug = ? RETURNING title, status")
+                .bind(&clean_slug)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(|e| ServerFnError::new(format!("Database delete failed: {}", e)))?;
+
+        if let Some((title, status)) = deleted {
+            use crate::api::audit::{ssr as audit, AuditAction};
+            let client_ip = audit::request_client_ip();
+            audit::record_event(
+                &mut *tx,
+                AuditAction::ArticleDelete,
+                audit::NewAuditEvent {
+                    actor: Some(&claims.sub),
+                    ip: client_ip.as_deref(),
+                    target: Some(&clean_slug),
+                    before: Some(summarize_article(&title, &clean_slug, &status)),
+                    after: None,
+                },
+            )
+            .await
+            .map_err(|e| ServerFnError::new(format!("Failed to record audit event: {}", e)))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
 
         Ok(())
     }
@@ -415,4 +612,76 @@ mod tests {
         let article_scheduled: Article = serde_json::from_str(json_with_status).unwrap();
         assert_eq!(article_scheduled.status, Some("scheduled".to_string()));
     }
+
+    #[cfg(feature = "ssr")]
+    #[tokio::test]
+    async fn test_slug_renames_keep_redirects_pointing_at_the_article() {
+        let pool = crate::api::test_pool().await;
+        sqlx::query(
+            "INSERT INTO articles (id, slug, title, content, author) VALUES \
+             ('a1', 'first', 'Story', '', 'Jake Wray'), ('x1', 'taken', 'Other', '', 'Jake Wray')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // Renames as `save_article` does them: the row, then the redirect
+        let rename = |from: &'static str, to: &'static str| {
+            let pool = pool.clone();
+            async move {
+                let mut tx = pool.begin().await.unwrap();
+                sqlx::query("UPDATE articles SET slug = ? WHERE id = 'a1'")
+                    .bind(to)
+                    .execute(&mut *tx)
+                    .await
+                    .unwrap();
+                record_slug_redirect(&mut tx, from, to, "a1").await.unwrap();
+                tx.commit().await.unwrap();
+            }
+        };
+        let redirects = || async {
+            sqlx::query_as::<_, (String, String)>(
+                "SELECT r.old_slug, a.slug FROM redirects r JOIN articles a ON a.id = r.target_id \
+                 WHERE r.kind = 'article' ORDER BY r.old_slug",
+            )
+            .fetch_all(&pool)
+            .await
+            .unwrap()
+        };
+        let pair = |old: &str, new: &str| (old.to_string(), new.to_string());
+
+        rename("first", "second").await;
+        assert_eq!(redirects().await, vec![pair("first", "second")]);
+
+        // A chain points every old slug at the current one
+        rename("second", "third").await;
+        assert_eq!(
+            redirects().await,
+            vec![pair("first", "third"), pair("second", "third")]
+        );
+
+        // Going back to an old slug drops its redirect rather than looping
+        rename("third", "first").await;
+        assert_eq!(
+            redirects().await,
+            vec![pair("second", "first"), pair("third", "first")]
+        );
+
+        // Saving finds the article by id, and won't take another article's slug
+        let found = find_saved_article(&pool, Some("a1"), "fourth")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!((found.0.as_str(), found.1.as_str()), ("a1", "first"));
+        assert!(find_saved_article(&pool, Some("a1"), "taken")
+            .await
+            .is_err());
+        assert_eq!(
+            find_saved_article(&pool, None, "taken")
+                .await
+                .unwrap()
+                .map(|row| row.0),
+            Some("x1".to_string())
+        );
+    }
 }
@@ -0,0 +1,354 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Security-relevant admin actions recorded in `audit_events`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Login,
+    LoginFailed,
+    PasswordChange,
+    ArticleSave,
+    ArticleDelete,
+    PageSave,
+    MediaUpload,
+    MediaUpdate,
+    MediaDelete,
+    BackupCreate,
+    BackupRestore,
+}
+
+impl AuditAction {
+    pub const ALL: [AuditAction; 11] = [
+        AuditAction::Login,
+        AuditAction::LoginFailed,
+        AuditAction::PasswordChange,
+        AuditAction::ArticleSave,
+        AuditAction::ArticleDelete,
+        AuditAction::PageSave,
+        AuditAction::MediaUpload,
+        AuditAction::MediaUpdate,
+        AuditAction::MediaDelete,
+        AuditAction::BackupCreate,
+        AuditAction::BackupRestore,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Login => "login",
+            AuditAction::LoginFailed => "login_failed",
+            AuditAction::PasswordChange => "password_change",
+            AuditAction::ArticleSave => "article_save",
+            AuditAction::ArticleDelete => "article_delete",
+            AuditAction::PageSave => "page_save",
+            AuditAction::MediaUpload => "media_upload",
+            AuditAction::MediaUpdate => "media_update",
+            AuditAction::MediaDelete => "media_delete",
+            AuditAction::BackupCreate => "backup_create",
+            AuditAction::BackupRestore => "backup_restore",
+        }
+    }
+}
+
+impl std::fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for AuditAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AuditAction::ALL
+            .into_iter()
+            .find(|action| action.as_str() == s)
+            .ok_or_else(|| format!("Unknown audit action: {}", s))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AuditEvent {
+    pub id: i64,
+    pub created_at: String,
+    pub action: String,
+    /// User id of whoever acted; unset for failed logins.
+    pub actor: Option<String>,
+    /// The actor's username, while their account still exists.
+    pub actor_name: Option<String>,
+    pub ip: Option<String>,
+    pub target: Option<String>,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// Narrows the audit log. Empty fields match everything; dates are `YYYY-MM-DD` and
+/// inclusive.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct AuditFilter {
+    pub action: Option<String>,
+    /// Username or user id.
+    pub actor: Option<String>,
+    /// Matches anywhere in the target.
+    pub target: Option<String>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+}
+
+impl AuditFilter {
+    /// Query string for the JSON export endpoint.
+    pub fn to_query(&self) -> String {
+        [
+            ("action", &self.action),
+            ("actor", &self.actor),
+            ("target", &self.target),
+            ("since", &self.since),
+            ("until", &self.until),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| {
+            let value = value.as_deref().map(str::trim).filter(|v| !v.is_empty())?;
+            Some(format!("{}={}", name, urlencoding::encode(value)))
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use super::{AuditAction, AuditEvent, AuditFilter};
+    use sqlx::{Row, SqlitePool};
+
+    /// Client IP of the request a server function is handling, provided as context by
+    /// the backend from `extract_client_ip`.
+    #[derive(Clone, Debug, Default)]
+    pub struct ClientIp(pub Option<String>);
+
+    pub fn request_client_ip() -> Option<String> {
+        leptos::prelude::use_context::<ClientIp>().and_then(|ip| ip.0)
+    }
+
+    #[derive(Debug, Default)]
+    pub struct NewAuditEvent<'a> {
+        pub actor: Option<&'a str>,
+        pub ip: Option<&'a str>,
+        pub target: Option<&'a str>,
+        /// Short description of the target before the change.
+        pub before: Option<String>,
+        /// ...and after it.
+        pub after: Option<String>,
+    }
+
+    /// Appends an event to the audit log. Where the change runs in a transaction, pass
+    /// that so the event is only kept if the change is.
+    pub async fn record_event<'e, E>(
+        executor: E,
+        action: AuditAction,
+        event: NewAuditEvent<'_>,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: sqlx::SqliteExecutor<'e>,
+    {
+        sqlx::query(
+            "INSERT INTO audit_events (action, actor, ip, target, before_summary, after_summary) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(action.as_str())
+        .bind(event.actor)
+        .bind(event.ip)
+        .bind(event.target)
+        .bind(event.before)
+        .bind(event.after)
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    fn non_empty(value: &Option<String>) -> Option<&str> {
+        value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+    }
+
+    /// Events matching `filter`, newest first.
+    pub async fn list_events(
+        pool: &SqlitePool,
+        filter: &AuditFilter,
+        limit: i64,
+    ) -> Result<Vec<AuditEvent>, sqlx::Error> {
+        let mut query = sqlx::QueryBuilder::new(
+            "SELECT e.id, e.created_at, e.action, e.actor, u.username AS actor_name, e.ip, \
+                e.target, e.before_summary, e.after_summary \
+             FROM audit_events e LEFT JOIN users u ON u.id = e.actor WHERE 1 = 1",
+        );
+        if let Some(action) = non_empty(&filter.action) {
+            query.push(" AND e.action = ").push_bind(action.to_string());
+        }
+        if let Some(actor) = non_empty(&filter.actor) {
+            query
+                .push(" AND (e.actor = ")
+                .push_bind(actor.to_string())
+                .push(" OR u.username = ")
+                .push_bind(actor.to_string())
+                .push(")");
+        }
+        if let Some(target) = non_empty(&filter.target) {
+            query
+                .push(" AND instr(lower(e.target), lower(")
+                .push_bind(target.to_string())
+                .push(")) > 0");
+        }
+        // Timestamps are ISO 8601 text, so dates compare as prefixes
+        if let Some(since) = non_empty(&filter.since) {
+            query
+                .push(" AND e.created_at >= ")
+                .push_bind(since.to_string());
+        }
+        if let Some(until) = non_empty(&filter.until) {
+            query
+                .push(" AND e.created_at < ")
+                .push_bind(format!("{}T99", until));
+        }
+        query
+            .push(" ORDER BY e.id DESC LIMIT ")
+            .push_bind(limit.max(1));
+
+        let rows = query.build().fetch_all(pool).await?;
+        Ok(rows
+            .iter()
+            .map(|row| AuditEvent {
+                id: row.get("id"),
+                created_at: row.get("created_at"),
+                action: row.get("action"),
+                actor: row.get("actor"),
+                actor_name: row.get("actor_name"),
+                ip: row.get("ip"),
+                target: row.get("target"),
+                before: row.get("before_summary"),
+                after: row.get("after_summary"),
+            })
+            .collect())
+    }
+}
+
+/// The most recent audit events matching `filter`, for the admin audit page.
+#[server(ListAuditEvents, "/api")]
+pub async fn list_audit_events(filter: AuditFilter) -> Result<Vec<AuditEvent>, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Admin).await?;
+
+        use sqlx::SqlitePool;
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        ssr::list_events(&pool, &filter, 200)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = filter;
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_action_roundtrip() {
+        for action in AuditAction::ALL {
+            assert_eq!(action.to_string().parse::<AuditAction>(), Ok(action));
+        }
+        assert!("publish".parse::<AuditAction>().is_err());
+    }
+
+    #[test]
+    fn test_filter_query_string() {
+        let filter = AuditFilter {
+            action: Some("article_delete".to_string()),
+            target: Some("city council".to_string()),
+            since: Some(" ".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            filter.to_query(),
+            "action=article_delete&target=city%20council"
+        );
+        assert_eq!(AuditFilter::default().to_query(), "");
+    }
+
+    #[cfg(feature = "ssr")]
+    #[tokio::test]
+    async fn test_record_and_filter_events() {
+        use super::ssr::*;
+
+        let pool = crate::api::test_pool().await;
+        let admin_id: String = sqlx::query_scalar(
+            "INSERT INTO users (username, password_hash) VALUES ('admin', 'x') RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        record_event(
+            &pool,
+            AuditAction::Login,
+            NewAuditEvent {
+                actor: Some(&admin_id),
+                ip: Some("203.0.113.7"),
+                target: Some("admin"),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        record_event(
+            &pool,
+            AuditAction::ArticleDelete,
+            NewAuditEvent {
+                actor: Some(&admin_id),
+                target: Some("city-council-votes"),
+                before: Some("City council votes (published)".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let all = list_events(&pool, &AuditFilter::default(), 100)
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].action, "article_delete");
+        assert_eq!(all[0].actor_name.as_deref(), Some("admin"));
+
+        let by_target = AuditFilter {
+            target: Some("Council".to_string()),
+            actor: Some("admin".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(list_events(&pool, &by_target, 100).await.unwrap().len(), 1);
+        let today = AuditFilter {
+            since: Some(all[0].created_at[..10].to_string()),
+            until: Some(all[0].created_at[..10].to_string()),
+            ..Default::default()
+        };
+        assert_eq!(list_events(&pool, &today, 100).await.unwrap().len(), 2);
+
+        // The log is append-only
+        assert!(sqlx::query("UPDATE audit_events SET actor = NULL")
+            .execute(&pool)
+            .await
+            .is_err());
+        assert!(sqlx::query("DELETE FROM audit_events")
+            .execute(&pool)
+            .await
+            .is_err());
+    }
+}
@@ -1,23 +1,52 @@
 #[cfg(feature = "ssr")]
 pub mod ssr_utils {
     use leptos::prelude::ServerFnError;
+    use serde::{Deserialize, Serialize};
+    use shared::auth::Role;
     use std::path::PathBuf;
 
     pub fn get_articles_dir() -> PathBuf {
         PathBuf::from("data/articles")
     }
 
-    // Simple JWT verification helper
-    pub fn verify_token(token: &str) -> Result<String, ServerFnError> {
-        use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
-        use serde::Deserialize;
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct Claims {
+        pub sub: String,
+        pub exp: usize,
+        /// Session id; the token is only honoured while its `sessions` row is active.
+        pub jti: String,
+        #[serde(default)]
+        pub role: Role,
+    }
 
-        #[derive(Deserialize)]
-        struct Claims {
-            sub: String,
-            #[allow(dead_code)]
-            exp: usize,
-        }
+    /// Value of cookie `name`, looking through every `Cookie` header.
+    pub fn cookie_value<'a>(headers: &'a http::HeaderMap, name: &str) -> Option<&'a str> {
+        headers
+            .get_all(http::header::COOKIE)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(';'))
+            .filter_map(|pair| pair.trim().split_once('='))
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Access token from the HttpOnly cookie, or an `Authorization: Bearer` header for
+    /// non-browser clients.
+    pub fn token_from_headers(headers: &http::HeaderMap) -> Option<&str> {
+        cookie_value(headers, shared::auth::ACCESS_COOKIE)
+            .filter(|t| !t.is_empty())
+            .or_else(|| {
+                headers
+                    .get(http::header::AUTHORIZATION)
+                    .and_then(|h| h.to_str().ok())
+                    .and_then(|s| s.strip_prefix("Bearer "))
+            })
+    }
+
+    /// Checks the JWT signature and expiry without consulting the session store.
+    pub fn decode_token(token: &str) -> Result<Claims, ServerFnError> {
+        use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
 
         let token_data = decode::<Claims>(
             token,
@@ -26,6 +55,103 @@ pub mod ssr_utils {
         )
         .map_err(|e| ServerFnError::new(format!("Invalid token: {}", e)))?;
 
-        Ok(token_data.claims.sub)
+        Ok(token_data.claims)
+    }
+
+    /// Claims of the request's access token, which must belong to an active session.
+    pub async fn current_claims() -> Result<Claims, ServerFnError> {
+        use leptos::prelude::use_context;
+
+        let parts = use_context::<http::request::Parts>()
+            .ok_or_else(|| ServerFnError::new("Request parts not found in Leptos context"))?;
+        let token = token_from_headers(&parts.headers)
+            .ok_or_else(|| ServerFnError::new("Invalid token: not signed in"))?;
+        let claims = decode_token(token)?;
+
+        let pool = use_context::<sqlx::SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+        let active = crate::api::sessions::ssr::touch_session(&pool, &claims.jti, &claims.sub)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))?;
+        if !active {
+            return Err(ServerFnError::new("Invalid token: session revoked"));
+        }
+
+        Ok(claims)
+    }
+
+    /// Guard for server functions: the signed-in user's claims, provided their role is at
+    /// least `min`.
+    pub async fn require_role(min: Role) -> Result<Claims, ServerFnError> {
+        let claims = current_claims().await?;
+        if claims.role < min {
+            return Err(forbidden(min));
+        }
+        Ok(claims)
+    }
+
+    /// Contributors may only write drafts: saving with any other status, or editing something
+    /// that is already scheduled or published, needs an editor.
+    pub fn check_can_save(
+        role: Role,
+        status: &str,
+        existing_status: Option<&str>,
+    ) -> Result<(), ServerFnError> {
+        let touches_live = status != "draft" || existing_status.is_some_and(|s| s != "draft");
+        if touches_live && !role.can_publish() {
+            return Err(forbidden(Role::Editor));
+        }
+        Ok(())
+    }
+
+    /// Showing a media item in the public galleries, or taking it out, is publishing it, so
+    /// only an editor may change it.
+    pub fn check_can_show_in_gallery(
+        role: Role,
+        current: bool,
+        requested: bool,
+    ) -> Result<(), ServerFnError> {
+        if current != requested && !role.can_publish() {
+            return Err(forbidden(Role::Editor));
+        }
+        Ok(())
+    }
+
+    /// Error for a signed-in user whose role is below `needed`.
+    pub fn forbidden(needed: Role) -> ServerFnError {
+        ServerFnError::new(format!("Forbidden: requires the {} role", needed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn test_contributors_only_save_drafts() {
+        use super::ssr_utils::check_can_save;
+        use shared::auth::Role;
+
+        assert!(check_can_save(Role::Contributor, "draft", None).is_ok());
+        assert!(check_can_save(Role::Contributor, "draft", Some("draft")).is_ok());
+        assert!(check_can_save(Role::Contributor, "published", None).is_err());
+        assert!(check_can_save(Role::Contributor, "scheduled", Some("draft")).is_err());
+        // Turning a live post back into a draft is unpublishing it
+        assert!(check_can_save(Role::Contributor, "draft", Some("published")).is_err());
+        assert!(check_can_save(Role::Editor, "published", Some("draft")).is_ok());
+        assert!(check_can_save(Role::Admin, "draft", Some("scheduled")).is_ok());
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn test_contributors_cannot_change_gallery_visibility() {
+        use super::ssr_utils::check_can_show_in_gallery;
+        use shared::auth::Role;
+
+        assert!(check_can_show_in_gallery(Role::Contributor, false, true).is_err());
+        assert!(check_can_show_in_gallery(Role::Contributor, true, false).is_err());
+        // Editing the other details leaves the flag as it was
+        assert!(check_can_show_in_gallery(Role::Contributor, true, true).is_ok());
+        assert!(check_can_show_in_gallery(Role::Contributor, false, false).is_ok());
+        assert!(check_can_show_in_gallery(Role::Editor, false, true).is_ok());
     }
 }
@@ -0,0 +1,342 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::slug::sanitize_slug;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct BlogPost {
+    pub slug: String,
+    pub title: String,
+    pub iso_date: String,
+    pub display_date: String,
+    pub content_html: String,
+    pub excerpt: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
+/// Splits a comma-separated tag string into trimmed, de-duplicated tags.
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input.split(',') {
+        let tag = tag.trim();
+        if !tag.is_empty() && !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr_mappers {
+    use super::BlogPost;
+    use sqlx::Row;
+
+    pub const BLOG_POST_COLUMNS: &str = "slug, title, content, published_at, tags, status";
+
+    pub fn map_blog_post(row: &sqlx::sqlite::SqliteRow) -> BlogPost {
+        let slug: String = row.get("slug");
+        let title: String = row.get("title");
+        let content_html: String = row.get("content");
+        let published_at: String = row.get("published_at");
+        let tags_json: Option<String> = row.get("tags");
+        let status: Option<String> = row.get("status");
+
+        let iso_date = published_at
+            .split('T')
+            .next()
+            .unwrap_or(&published_at)
+            .to_string();
+        let display_date = if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(&published_at) {
+            dt.format("%B %d, %Y").to_string()
+        } else {
+            iso_date.clone()
+        };
+
+        let tags = tags_json
+            .and_then(|t| serde_json::from_str::<Vec<String>>(&t).ok())
+            .unwrap_or_default();
+
+        let excerpt = crate::utils::html::extract_body_preview(&content_html).unwrap_or_default();
+
+        BlogPost {
+            slug,
+            title,
+            iso_date,
+            display_date,
+            content_html,
+            excerpt,
+            tags,
+            status,
+        }
+    }
+}
+
+#[server(GetBlogPosts, "/api")]
+pub async fn get_blog_posts() -> Result<Vec<BlogPost>, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use sqlx::SqlitePool;
+        use ssr_mappers::{map_blog_post, BLOG_POST_COLUMNS};
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM blog_posts WHERE (status IS NULL OR status = 'published' OR (status = 'scheduled' AND published_at <= strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))) AND status != 'draft' ORDER BY published_at DESC, title ASC, slug ASC",
+            BLOG_POST_COLUMNS
+        ))
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))?;
+
+        Ok(rows.iter().map(map_blog_post).collect())
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    Ok(Vec::new())
+}
+
+/// The post at `slug`; with `live_only`, only once it's published.
+#[cfg(feature = "ssr")]
+pub(crate) async fn load_blog_post(
+    pool: &sqlx::SqlitePool,
+    slug: &str,
+    live_only: bool,
+) -> Result<Option<BlogPost>, sqlx::Error> {
+    let row = sqlx::query(&format!(
+        "SELECT {} FROM blog_posts WHERE slug = ?1 AND (NOT ?2 OR ((status IS NULL OR status = 'published' OR (status = 'scheduled' AND published_at <= strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))) AND status != 'draft'))",
+        ssr_mappers::BLOG_POST_COLUMNS
+    ))
+    .bind(sanitize_slug(slug))
+    .bind(live_only)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.as_ref().map(ssr_mappers::map_blog_post))
+}
+
+/// A published post, for the public `/blog/:slug` page.
+#[server(GetBlogPost, "/api")]
+pub async fn get_blog_post(slug: String) -> Result<Option<BlogPost>, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use sqlx::SqlitePool;
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        load_blog_post(&pool, &slug, true)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    Ok(None)
+}
+
+/// Any post, drafts included, for the composer.
+#[server(GetBlogPostForEdit, "/api")]
+pub async fn get_blog_post_for_edit(slug: String) -> Result<Option<BlogPost>, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Contributor).await?;
+
+        use sqlx::SqlitePool;
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        load_blog_post(&pool, &slug, false)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    Ok(None)
+}
+
+#[server(GetBlogDraftsAndScheduled, "/api")]
+pub async fn get_blog_drafts_and_scheduled() -> Result<Vec<BlogPost>, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Contributor).await?;
+
+        use sqlx::SqlitePool;
+        use ssr_mappers::{map_blog_post, BLOG_POST_COLUMNS};
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM blog_posts WHERE status = 'draft' OR (status = 'scheduled' AND published_at > strftime('%Y-%m-%dT%H:%M:%fZ', 'now')) ORDER BY published_at DESC, title ASC, slug ASC",
+            BLOG_POST_COLUMNS
+        ))
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))?;
+
+        Ok(rows.iter().map(map_blog_post).collect())
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    Ok(Vec::new())
+}
+
+#[server(SaveBlogPost, "/api")]
+pub async fn save_blog_post(post: BlogPost) -> Result<(), ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::{check_can_save, require_role};
+        use shared::auth::Role;
+        let claims = require_role(Role::Contributor).await?;
+
+        use sqlx::SqlitePool;
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        let clean_slug = sanitize_slug(&post.slug);
+        if clean_slug.is_empty() {
+            return Err(ServerFnError::new("Blog post slug cannot be empty"));
+        }
+        if post.title.trim().is_empty() {
+            return Err(ServerFnError::new("Blog post title cannot be empty"));
+        }
+
+        let (published_at, _iso_date, _display_date) =
+            crate::utils::slug::parse_article_date(&post.display_date);
+        let status = post.status.unwrap_or_else(|| "published".to_string());
+        let tags = if post.tags.is_empty() {
+            None
+        } else {
+            Some(
+                serde_json::to_string(&post.tags)
+                    .map_err(|e| ServerFnError::new(format!("Invalid tags: {}", e)))?,
+            )
+        };
+
+        let mut id = uuid::Uuid::new_v4().to_string();
+        let existing = sqlx::query(
+            "SELECT id, COALESCE(status, 'published') AS status FROM blog_posts WHERE slug = ?",
+        )
+        .bind(&clean_slug)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))?;
+
+        let mut existing_status: Option<String> = None;
+        if let Some(row) = existing {
+            use sqlx::Row;
+            id = row.get("id");
+            existing_status = row.get("status");
+        }
+        check_can_save(claims.role, &status, existing_status.as_deref())?;
+
+        sqlx::query(
+            "INSERT INTO blog_posts (id, slug, title, content, published_at, tags, status, updated_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now')) \
+             ON CONFLICT(slug) DO UPDATE SET \
+                title = excluded.title, \
+                content = excluded.content, \
+                published_at = excluded.published_at, \
+                tags = excluded.tags, \
+                status = excluded.status, \
+                updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+        )
+        .bind(&id)
+        .bind(&clean_slug)
+        .bind(&post.title)
+        .bind(&post.content_html)
+        .bind(&published_at)
+        .bind(&tags)
+        .bind(&status)
+        .execute(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database insert/update failed: {}", e)))?;
+        crate::api::search::ssr::refresh_index(&pool).await;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    Ok(())
+}
+
+#[server(DeleteBlogPost, "/api")]
+pub async fn delete_blog_post(slug: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Editor).await?;
+
+        use sqlx::SqlitePool;
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        let clean_slug = sanitize_slug(&slug);
+
+        sqlx::query("DELETE FROM blog_posts WHERE slug = ?")
+            .bind(&clean_slug)
+            .execute(&pool)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database delete failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tags() {
+        assert_eq!(parse_tags(""), Vec::<String>::new());
+        assert_eq!(
+            parse_tags(" rust, life ,, Rust, travel "),
+            vec!["rust".to_string(), "life".to_string(), "travel".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_blog_post_defaults() {
+        let json = r#"{
+            "slug": "hello",
+            "title": "Hello",
+            "iso_date": "2026-09-01",
+            "display_date": "September 1, 2026",
+            "content_html": "<p>Hi</p>",
+            "excerpt": "Hi"
+        }"#;
+
+        let post: BlogPost = serde_json::from_str(json).unwrap();
+        assert!(post.tags.is_empty());
+        assert_eq!(post.status, None);
+    }
+
+    #[cfg(feature = "ssr")]
+    #[tokio::test]
+    async fn test_drafts_are_only_loaded_for_editing() {
+        let pool = crate::api::test_pool().await;
+        sqlx::query(
+            "INSERT INTO blog_posts (slug, title, content, status, published_at) VALUES \
+             ('live', 'Live', '', 'published', '2020-01-01T00:00:00.000Z'), \
+             ('secret', 'Secret', '', 'draft', '2020-01-01T00:00:00.000Z'), \
+             ('later', 'Later', '', 'scheduled', '2999-01-01T00:00:00.000Z')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        assert!(load_blog_post(&pool, "live", true).await.unwrap().is_some());
+        for slug in ["secret", "later"] {
+            assert!(load_blog_post(&pool, slug, true).await.unwrap().is_none());
+            assert!(load_blog_post(&pool, slug, false).await.unwrap().is_some());
+        }
+    }
+}
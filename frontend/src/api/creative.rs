@@ -0,0 +1,320 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+pub use shared::CreativeType;
+
+use crate::utils::slug::sanitize_slug;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct Chapter {
+    #[serde(default)]
+    pub title: String,
+    pub content_html: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CreativeWork {
+    pub slug: String,
+    pub title: String,
+    pub work_type: CreativeType,
+    #[serde(default)]
+    pub synopsis: String,
+    #[serde(default)]
+    pub chapters: Vec<Chapter>,
+    pub iso_date: String,
+    pub display_date: String,
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
+/// Encodes chapters for the `creative_works.content` column. Novels are stored as a
+/// JSON array of chapters; stories and poems keep their HTML as-is.
+pub fn encode_chapters(work_type: &CreativeType, chapters: &[Chapter]) -> String {
+    match work_type {
+        CreativeType::Novel => serde_json::to_string(chapters).unwrap_or_default(),
+        _ => chapters
+            .iter()
+            .map(|c| c.content_html.as_str())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+/// Inverse of [`encode_chapters`]. Content that isn't a chapter array is treated as a
+/// single untitled chapter, so works written before chapters existed still render.
+pub fn decode_chapters(content: &str) -> Vec<Chapter> {
+    if content.trim().is_empty() {
+        return Vec::new();
+    }
+    if content.trim_start().starts_with('[') {
+        if let Ok(chapters) = serde_json::from_str::<Vec<Chapter>>(content) {
+            return chapters;
+        }
+    }
+    vec![Chapter {
+        title: String::new(),
+        content_html: content.to_string(),
+    }]
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr_mappers {
+    use super::{decode_chapters, CreativeType, CreativeWork};
+    use sqlx::Row;
+
+    pub const CREATIVE_WORK_COLUMNS: &str =
+        "slug, title, work_type, synopsis, content, status, published_at";
+
+    pub fn map_creative_work(row: &sqlx::sqlite::SqliteRow) -> CreativeWork {
+        let slug: String = row.get("slug");
+        let title: String = row.get("title");
+        let work_type: String = row.get("work_type");
+        let synopsis: Option<String> = row.get("synopsis");
+        let content: Option<String> = row.get("content");
+        let status: Option<String> = row.get("status");
+        let published_at: Option<String> = row.get("published_at");
+        let published_at = published_at.unwrap_or_default();
+
+        let iso_date = published_at
+            .split('T')
+            .next()
+            .unwrap_or(&published_at)
+            .to_string();
+        let display_date = if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(&published_at) {
+            dt.format("%B %d, %Y").to_string()
+        } else {
+            iso_date.clone()
+        };
+
+        CreativeWork {
+            slug,
+            title,
+            work_type: work_type.parse().unwrap_or(CreativeType::Story),
+            synopsis: synopsis.unwrap_or_default(),
+            chapters: decode_chapters(&content.unwrap_or_default()),
+            iso_date,
+            display_date,
+            status,
+        }
+    }
+}
+
+#[server(GetCreativeWorks, "/api")]
+pub async fn get_creative_works(
+    work_type: Option<CreativeType>,
+) -> Result<Vec<CreativeWork>, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use sqlx::SqlitePool;
+        use ssr_mappers::{map_creative_work, CREATIVE_WORK_COLUMNS};
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM creative_works WHERE (status IS NULL OR status = 'published' OR (status = 'scheduled' AND published_at <= strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))) AND status != 'draft' AND (?1 IS NULL OR work_type = ?1) ORDER BY published_at DESC, title ASC, slug ASC",
+            CREATIVE_WORK_COLUMNS
+        ))
+        .bind(work_type.map(|t| t.to_string()))
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))?;
+
+        Ok(rows.iter().map(map_creative_work).collect())
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = work_type;
+        Ok(Vec::new())
+    }
+}
+
+#[server(GetCreativeWork, "/api")]
+pub async fn get_creative_work(slug: String) -> Result<Option<CreativeWork>, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use sqlx::SqlitePool;
+        use ssr_mappers::{map_creative_work, CREATIVE_WORK_COLUMNS};
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        let clean_slug = sanitize_slug(&slug);
+
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM creative_works WHERE slug = ?",
+            CREATIVE_WORK_COLUMNS
+        ))
+        .bind(&clean_slug)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))?;
+
+        Ok(row.as_ref().map(map_creative_work))
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    Ok(None)
+}
+
+#[server(GetCreativeDraftsAndScheduled, "/api")]
+pub async fn get_creative_drafts_and_scheduled() -> Result<Vec<CreativeWork>, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Contributor).await?;
+
+        use sqlx::SqlitePool;
+        use ssr_mappers::{map_creative_work, CREATIVE_WORK_COLUMNS};
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM creative_works WHERE status = 'draft' OR (status = 'scheduled' AND published_at > strftime('%Y-%m-%dT%H:%M:%fZ', 'now')) ORDER BY published_at DESC, title ASC, slug ASC",
+            CREATIVE_WORK_COLUMNS
+        ))
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))?;
+
+        Ok(rows.iter().map(map_creative_work).collect())
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    Ok(Vec::new())
+}
+
+#[server(SaveCreativeWork, "/api")]
+pub async fn save_creative_work(work: CreativeWork) -> Result<(), ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::{check_can_save, require_role};
+        use shared::auth::Role;
+        let claims = require_role(Role::Contributor).await?;
+
+        use sqlx::SqlitePool;
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        let clean_slug = sanitize_slug(&work.slug);
+        if clean_slug.is_empty() {
+            return Err(ServerFnError::new("Creative work slug cannot be empty"));
+        }
+        if work.title.trim().is_empty() {
+            return Err(ServerFnError::new("Creative work title cannot be empty"));
+        }
+
+        let (published_at, _iso_date, _display_date) =
+            crate::utils::slug::parse_article_date(&work.display_date);
+        let status = work.status.unwrap_or_else(|| "published".to_string());
+        let content = encode_chapters(&work.work_type, &work.chapters);
+        let synopsis = Some(work.synopsis.trim()).filter(|s| !s.is_empty());
+
+        let mut id = uuid::Uuid::new_v4().to_string();
+        let existing = sqlx::query(
+            "SELECT id, COALESCE(status, 'published') AS status FROM creative_works WHERE slug = ?",
+        )
+        .bind(&clean_slug)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))?;
+
+        let mut existing_status: Option<String> = None;
+        if let Some(row) = existing {
+            use sqlx::Row;
+            id = row.get("id");
+            existing_status = row.get("status");
+        }
+        check_can_save(claims.role, &status, existing_status.as_deref())?;
+
+        sqlx::query(
+            "INSERT INTO creative_works (id, slug, title, work_type, synopsis, content, status, published_at, updated_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now')) \
+             ON CONFLICT(slug) DO UPDATE SET \
+                title = excluded.title, \
+                work_type = excluded.work_type, \
+                synopsis = excluded.synopsis, \
+                content = excluded.content, \
+                status = excluded.status, \
+                published_at = excluded.published_at, \
+                updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+        )
+        .bind(&id)
+        .bind(&clean_slug)
+        .bind(&work.title)
+        .bind(work.work_type.to_string())
+        .bind(synopsis)
+        .bind(&content)
+        .bind(&status)
+        .bind(&published_at)
+        .execute(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database insert/update failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    Ok(())
+}
+
+#[server(DeleteCreativeWork, "/api")]
+pub async fn delete_creative_work(slug: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Editor).await?;
+
+        use sqlx::SqlitePool;
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        let clean_slug = sanitize_slug(&slug);
+
+        sqlx::query("DELETE FROM creative_works WHERE slug = ?")
+            .bind(&clean_slug)
+            .execute(&pool)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database delete failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_novel_chapters_roundtrip() {
+        let chapters = vec![
+            Chapter {
+                title: "One".to_string(),
+                content_html: "<p>It began.</p>".to_string(),
+            },
+            Chapter {
+                title: "Two".to_string(),
+                content_html: "<p>It ended.</p>".to_string(),
+            },
+        ];
+
+        let encoded = encode_chapters(&CreativeType::Novel, &chapters);
+        assert_eq!(decode_chapters(&encoded), chapters);
+    }
+
+    #[test]
+    fn test_plain_content_is_single_chapter() {
+        let chapters = vec![Chapter {
+            title: String::new(),
+            content_html: "<p>A short poem.</p>".to_string(),
+        }];
+
+        let encoded = encode_chapters(&CreativeType::Poetry, &chapters);
+        assert_eq!(encoded, "<p>A short poem.</p>");
+        assert_eq!(decode_chapters(&encoded), chapters);
+        assert!(decode_chapters("  ").is_empty());
+    }
+}
@@ -0,0 +1,398 @@
+//! Camera metadata from uploaded photos, and removal of the parts of it that shouldn't be
+//! public: where the photo was taken and the serial numbers of the gear that took it.
+//! Both can appear twice, in the EXIF block and again in an XMP packet.
+
+use exif::{Context, Field, In, Tag, Value};
+
+/// `CameraSerialNumber` from the DNG spec, which kamadak-exif doesn't name.
+const CAMERA_SERIAL_NUMBER: Tag = Tag(Context::Tiff, 0xc62f);
+
+/// Starts the APP1 segment that holds a JPEG's XMP packet.
+const JPEG_XMP_PREFIX: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+/// Keyword of the `iTXt` chunk that holds a PNG's XMP packet.
+const PNG_XMP_KEYWORD: &[u8] = b"XML:com.adobe.xmp";
+/// Bit in a WebP `VP8X` header saying the file has an `XMP ` chunk.
+const WEBP_XMP_FLAG: u8 = 0b0000_0100;
+
+/// What an image's EXIF block says about how it was taken.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PhotoMetadata {
+    /// UTC (`...Z`) when the camera recorded its time zone, the camera's local time
+    /// otherwise.
+    pub taken_at: Option<String>,
+    pub camera: Option<String>,
+    pub lens: Option<String>,
+    /// Whether there are GPS or serial-number tags to strip.
+    pub has_private_tags: bool,
+}
+
+/// The raw EXIF (TIFF) block of a JPEG, PNG or WebP file, if it has one.
+pub fn extract_exif(data: &[u8]) -> Option<Vec<u8>> {
+    use img_parts::{Bytes, DynImage, ImageEXIF};
+    let image = DynImage::from_bytes(Bytes::copy_from_slice(data)).ok()??;
+    image.exif().map(|exif| exif.to_vec())
+}
+
+/// Replaces the EXIF block of `data` with `exif`, or removes it when `exif` is `None`.
+pub fn replace_exif(data: Vec<u8>, exif: Option<Vec<u8>>) -> Result<Vec<u8>, String> {
+    use img_parts::{Bytes, DynImage, ImageEXIF};
+    let mut image = DynImage::from_bytes(Bytes::from(data))
+        .map_err(|e| format!("Unreadable image: {}", e))?
+        .ok_or("Metadata can only be edited in JPEG, PNG and WebP files")?;
+    image.set_exif(exif.map(Bytes::from));
+    Ok(image.encoder().bytes().to_vec())
+}
+
+/// The text of an uncompressed XMP `iTXt` chunk.
+fn png_xmp(contents: &[u8]) -> Option<&[u8]> {
+    let rest = contents
+        .strip_prefix(PNG_XMP_KEYWORD)?
+        .strip_prefix(b"\0")?;
+    // Compression flag and method, then the (empty) language tag and translated keyword
+    let (&[0, _], rest) = rest.split_at_checked(2)? else {
+        return None;
+    };
+    let mut parts = rest.splitn(3, |&b| b == 0);
+    let (_, _, text) = (parts.next()?, parts.next()?, parts.next()?);
+    Some(text)
+}
+
+fn is_png_xmp(contents: &[u8]) -> bool {
+    contents
+        .strip_prefix(PNG_XMP_KEYWORD)
+        .is_some_and(|rest| rest.starts_with(b"\0"))
+}
+
+/// The XMP packet of a JPEG, PNG or WebP file, if it has one.
+pub fn extract_xmp(data: &[u8]) -> Option<Vec<u8>> {
+    use img_parts::jpeg::markers::APP1;
+    use img_parts::webp::CHUNK_XMP;
+    use img_parts::{Bytes, DynImage};
+    let xmp = match DynImage::from_bytes(Bytes::copy_from_slice(data)).ok()?? {
+        DynImage::Jpeg(jpeg) => jpeg
+            .segments_by_marker(APP1)
+            .find_map(|segment| segment.contents().strip_prefix(JPEG_XMP_PREFIX))?
+            .to_vec(),
+        DynImage::Png(png) => png
+            .chunks_by_type(*b"iTXt")
+            .find_map(|chunk| png_xmp(chunk.contents()))?
+            .to_vec(),
+        DynImage::WebP(webp) => webp.chunk_by_id(CHUNK_XMP)?.content().data()?.to_vec(),
+    };
+    Some(xmp)
+}
+
+/// Replaces the XMP packet of `data` with `xmp`, or removes it when `xmp` is `None`.
+pub fn replace_xmp(data: Vec<u8>, xmp: Option<Vec<u8>>) -> Result<Vec<u8>, String> {
+    use img_parts::jpeg::markers::{APP0, APP1};
+    use img_parts::jpeg::JpegSegment;
+    use img_parts::png::PngChunk;
+    use img_parts::riff::{RiffChunk, RiffContent};
+    use img_parts::webp::{CHUNK_VP8X, CHUNK_XMP};
+    use img_parts::{Bytes, DynImage};
+
+    let image = DynImage::from_bytes(Bytes::from(data))
+        .map_err(|e| format!("Unreadable image: {}", e))?
+        .ok_or("Metadata can only be edited in JPEG, PNG and WebP files")?;
+    let bytes = match image {
+        DynImage::Jpeg(mut jpeg) => {
+            let segments = jpeg.segments_mut();
+            segments.retain(|segment| {
+                !(segment.marker() == APP1 && segment.contents().starts_with(JPEG_XMP_PREFIX))
+            });
+            if let Some(xmp) = xmp {
+                // After JFIF and EXIF, as cameras write it
+                let at = segments
+                    .iter()
+                    .position(|segment| !matches!(segment.marker(), APP0 | APP1))
+                    .unwrap_or(segments.len());
+                let contents = [JPEG_XMP_PREFIX, &xmp].concat();
+                segments.insert(at, JpegSegment::new_with_contents(APP1, contents.into()));
+            }
+            jpeg.encoder().bytes()
+        }
+        DynImage::Png(mut png) => {
+            let chunks = png.chunks_mut();
+            chunks.retain(|chunk| !(chunk.kind() == *b"iTXt" && is_png_xmp(chunk.contents())));
+            if let Some(xmp) = xmp {
+                let at = chunks
+                    .iter()
+                    .position(|chunk| chunk.kind() == *b"IDAT")
+                    .unwrap_or(chunks.len().saturating_sub(1));
+                let contents = [PNG_XMP_KEYWORD, b"\0\0\0\0\0", &xmp].concat();
+                chunks.insert(at, PngChunk::new(*b"iTXt", contents.into()));
+            }
+            png.encoder().bytes()
+        }
+        DynImage::WebP(mut webp) => {
+            webp.remove_chunks_by_id(CHUNK_XMP);
+            // Only extended (VP8X) files can carry metadata chunks
+            if xmp.is_some() && !webp.has_chunk(CHUNK_VP8X) {
+                let (width, height) = webp.dimensions().ok_or("Unreadable WebP dimensions")?;
+                let mut header = vec![0; 4];
+                header.extend_from_slice(&(width - 1).to_le_bytes()[..3]);
+                header.extend_from_slice(&(height - 1).to_le_bytes()[..3]);
+                let chunk = RiffChunk::new(CHUNK_VP8X, RiffContent::Data(header.into()));
+                webp.chunks_mut().insert(0, chunk);
+            }
+            if let Some(vp8x) = webp
+                .chunks_mut()
+                .iter_mut()
+                .find(|chunk| chunk.id() == CHUNK_VP8X)
+            {
+                if let RiffContent::Data(header) = vp8x.content_mut() {
+                    let mut flags = header.to_vec();
+                    if let Some(first) = flags.first_mut() {
+                        if xmp.is_some() {
+                            *first |= WEBP_XMP_FLAG;
+                        } else {
+                            *first &= !WEBP_XMP_FLAG;
+                        }
+                    }
+                    *header = flags.into();
+                }
+            }
+            if let Some(xmp) = xmp {
+                let chunk = RiffChunk::new(CHUNK_XMP, RiffContent::Data(xmp.into()));
+                webp.chunks_mut().push(chunk);
+            }
+            webp.encoder().bytes()
+        }
+    };
+    Ok(bytes.to_vec())
+}
+
+/// Whether an XMP packet has GPS or serial-number properties, e.g. `exif:GPSLatitude`
+/// or `aux:SerialNumber`.
+pub fn is_private_xmp(xmp: &[u8]) -> bool {
+    let text = String::from_utf8_lossy(xmp);
+    text.contains(":GPS") || text.contains("SerialNumber")
+}
+
+fn is_private(field: &Field) -> bool {
+    matches!(field.tag, Tag(Context::Gps, _))
+        || [
+            Tag::BodySerialNumber,
+            Tag::LensSerialNumber,
+            CAMERA_SERIAL_NUMBER,
+            // Maker notes are opaque and usually hold the body's serial number too
+            Tag::MakerNote,
+        ]
+        .contains(&field.tag)
+}
+
+fn ascii(exif: &exif::Exif, tag: Tag) -> Option<String> {
+    match &exif.get_field(tag, In::PRIMARY)?.value {
+        Value::Ascii(values) => {
+            let text = String::from_utf8_lossy(values.first()?);
+            let text = text.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+            (!text.is_empty()).then(|| text.to_string())
+        }
+        _ => None,
+    }
+}
+
+fn taken_at(exif: &exif::Exif) -> Option<String> {
+    let (time_tag, offset_tag) = if exif.get_field(Tag::DateTimeOriginal, In::PRIMARY).is_some() {
+        (Tag::DateTimeOriginal, Tag::OffsetTimeOriginal)
+    } else {
+        (Tag::DateTime, Tag::OffsetTime)
+    };
+    let Value::Ascii(values) = &exif.get_field(time_tag, In::PRIMARY)?.value else {
+        return None;
+    };
+    let mut time = exif::DateTime::from_ascii(values.first()?).ok()?;
+    if let Some(Value::Ascii(offset)) = exif
+        .get_field(offset_tag, In::PRIMARY)
+        .map(|field| &field.value)
+    {
+        if let Some(offset) = offset.first() {
+            let _ = time.parse_offset(offset);
+        }
+    }
+
+    let local =
+        chrono::NaiveDate::from_ymd_opt(time.year.into(), time.month.into(), time.day.into())?
+            .and_hms_opt(time.hour.into(), time.minute.into(), time.second.into())?;
+    Some(match time.offset {
+        Some(minutes) => (local - chrono::Duration::minutes(minutes.into()))
+            .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+            .to_string(),
+        None => local.format("%Y-%m-%dT%H:%M:%S").to_string(),
+    })
+}
+
+/// Reads the capture date, camera and lens from a raw EXIF block.
+pub fn read_metadata(raw_exif: &[u8]) -> PhotoMetadata {
+    let Ok(exif) = exif::Reader::new().read_raw(raw_exif.to_vec()) else {
+        return PhotoMetadata::default();
+    };
+    // "Canon" and "Canon EOS R5" is just "Canon EOS R5"
+    let join = |make: Option<String>, model: Option<String>| match (make, model) {
+        (Some(make), Some(model)) if model.to_lowercase().starts_with(&make.to_lowercase()) => {
+            Some(model)
+        }
+        (Some(make), Some(model)) => Some(format!("{} {}", make, model)),
+        (make, model) => model.or(make),
+    };
+    let has_private_tags = exif.fields().any(is_private);
+    PhotoMetadata {
+        taken_at: taken_at(&exif),
+        camera: join(ascii(&exif, Tag::Make), ascii(&exif, Tag::Model)),
+        lens: join(ascii(&exif, Tag::LensMake), ascii(&exif, Tag::LensModel)),
+        has_private_tags,
+    }
+}
+
+/// A raw EXIF block without GPS and serial-number tags. Only the main image's tags are
+/// kept (the embedded thumbnail goes too). `None` when nothing is left.
+pub fn strip_private_tags(raw_exif: &[u8]) -> Result<Option<Vec<u8>>, String> {
+    let exif = exif::Reader::new()
+        .read_raw(raw_exif.to_vec())
+        .map_err(|e| format!("Unreadable EXIF: {}", e))?;
+    let mut writer = exif::experimental::Writer::new();
+    let mut kept = 0;
+    for field in exif.fields() {
+        if field.ifd_num == In::PRIMARY && !is_private(field) {
+            writer.push_field(field);
+            kept += 1;
+        }
+    }
+    if kept == 0 {
+        return Ok(None);
+    }
+    let mut out = std::io::Cursor::new(Vec::new());
+    writer
+        .write(&mut out, exif.little_endian())
+        .map_err(|e| format!("Writing EXIF failed: {}", e))?;
+    Ok(Some(out.into_inner()))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A raw EXIF block as a phone would write it.
+    pub(crate) fn sample_exif() -> Vec<u8> {
+        let ascii = |tag, text: &str| Field {
+            tag,
+            ifd_num: In::PRIMARY,
+            value: Value::Ascii(vec![text.as_bytes().to_vec()]),
+        };
+        let fields = [
+            ascii(Tag::Make, "Canon"),
+            ascii(Tag::Model, "Canon EOS R5"),
+            ascii(Tag::LensModel, "RF24-70mm F2.8 L IS USM"),
+            ascii(Tag::DateTimeOriginal, "2024:05:01 12:34:56"),
+            ascii(Tag::OffsetTimeOriginal, "-07:00"),
+            ascii(Tag::BodySerialNumber, "012345678901"),
+            ascii(Tag::GPSLatitudeRef, "N"),
+            Field {
+                tag: Tag::GPSLatitude,
+                ifd_num: In::PRIMARY,
+                value: Value::Rational(vec![(54, 1).into(), (31, 1).into(), (0, 1).into()]),
+            },
+            Field {
+                tag: Tag::Orientation,
+                ifd_num: In::PRIMARY,
+                value: Value::Short(vec![6]),
+            },
+        ];
+        let mut writer = exif::experimental::Writer::new();
+        for field in &fields {
+            writer.push_field(field);
+        }
+        let mut out = std::io::Cursor::new(Vec::new());
+        writer.write(&mut out, false).unwrap();
+        out.into_inner()
+    }
+
+    /// An XMP packet repeating the location and body serial number.
+    pub(crate) fn sample_xmp() -> Vec<u8> {
+        concat!(
+            r#"<?xpacket begin="" id="W5M0MpCehiHzreSzNTczkc9d"?>"#,
+            r#"<x:xmpmeta xmlns:x="adobe:ns:meta/"><rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">"#,
+            r#"<rdf:Description xmlns:exif="http://ns.adobe.com/exif/1.0/" xmlns:aux="http://ns.adobe.com/exif/1.0/aux/""#,
+            r#" exif:GPSLatitude="54,31.0N" exif:GPSLongitude="128,35.0W" aux:SerialNumber="012345678901"/>"#,
+            r#"</rdf:RDF></x:xmpmeta><?xpacket end="w"?>"#,
+        )
+        .as_bytes()
+        .to_vec()
+    }
+
+    #[test]
+    fn test_read_metadata() {
+        let metadata = read_metadata(&sample_exif());
+        assert_eq!(
+            metadata,
+            PhotoMetadata {
+                taken_at: Some("2024-05-01T19:34:56.000Z".to_string()),
+                camera: Some("Canon EOS R5".to_string()),
+                lens: Some("RF24-70mm F2.8 L IS USM".to_string()),
+                has_private_tags: true,
+            }
+        );
+        assert_eq!(read_metadata(b"junk"), PhotoMetadata::default());
+    }
+
+    #[test]
+    fn test_strip_private_tags() {
+        let stripped = strip_private_tags(&sample_exif()).unwrap().unwrap();
+        let metadata = read_metadata(&stripped);
+        assert!(!metadata.has_private_tags);
+        assert_eq!(metadata.camera.as_deref(), Some("Canon EOS R5"));
+        let exif = exif::Reader::new().read_raw(stripped).unwrap();
+        assert!(exif.get_field(Tag::Orientation, In::PRIMARY).is_some());
+        assert!(exif.get_field(Tag::GPSLatitude, In::PRIMARY).is_none());
+    }
+
+    #[test]
+    fn test_replace_exif() {
+        let mut jpeg = std::io::Cursor::new(Vec::new());
+        image::RgbImage::new(8, 8)
+            .write_to(&mut jpeg, image::ImageFormat::Jpeg)
+            .unwrap();
+        let jpeg = jpeg.into_inner();
+        assert_eq!(extract_exif(&jpeg), None);
+
+        let with_exif = replace_exif(jpeg, Some(sample_exif())).unwrap();
+        assert_eq!(extract_exif(&with_exif), Some(sample_exif()));
+        let without = replace_exif(with_exif, None).unwrap();
+        assert_eq!(extract_exif(&without), None);
+        assert!(image::load_from_memory(&without).is_ok());
+    }
+
+    #[test]
+    fn test_replace_xmp() {
+        let pixels = image::RgbImage::from_pixel(8, 8, image::Rgb([90, 120, 150]));
+        let encode = |format| {
+            let mut out = std::io::Cursor::new(Vec::new());
+            pixels.write_to(&mut out, format).unwrap();
+            out.into_inner()
+        };
+        let webp = webp::Encoder::from_rgb(&pixels, 8, 8)
+            .encode_simple(false, 80.0)
+            .unwrap()
+            .to_vec();
+
+        assert!(is_private_xmp(&sample_xmp()));
+        assert!(!is_private_xmp(
+            b"<x:xmpmeta><dc:title>Harbour</dc:title></x:xmpmeta>"
+        ));
+        for file in [
+            encode(image::ImageFormat::Jpeg),
+            encode(image::ImageFormat::Png),
+            webp,
+        ] {
+            assert_eq!(extract_xmp(&file), None);
+            let with_xmp = replace_xmp(file, Some(sample_xmp())).unwrap();
+            assert_eq!(extract_xmp(&with_xmp), Some(sample_xmp()));
+            assert!(image::load_from_memory(&with_xmp).is_ok());
+
+            let without = replace_xmp(with_xmp, None).unwrap();
+            assert_eq!(extract_xmp(&without), None);
+            assert!(!String::from_utf8_lossy(&without).contains("GPSLatitude"));
+            assert!(image::load_from_memory(&without).is_ok());
+        }
+    }
+}
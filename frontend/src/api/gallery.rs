@@ -0,0 +1,793 @@
+//! Public photography and visual art galleries, and the albums they're arranged into.
+//!
+//! Galleries list the `media_items` marked `show_in_gallery`, newest capture first.
+//! Albums are named, admin-ordered selections of those items.
+
+use crate::api::media::ImageVariant;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use shared::{MediaCategory, MediaContext};
+
+/// Gallery items per page.
+pub const GALLERY_PAGE_SIZE: u32 = 24;
+
+/// An image as shown in a gallery.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GalleryItem {
+    pub id: String,
+    pub url: String,
+    pub thumbnail_url: Option<String>,
+    #[serde(default)]
+    pub variants: Vec<ImageVariant>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub alt_text: Option<String>,
+    pub context: MediaContext,
+    pub taken_at: Option<String>,
+    /// `taken_at` as "May 1, 2024".
+    #[serde(default)]
+    pub capture_date: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+impl GalleryItem {
+    pub fn alt(&self) -> String {
+        self.alt_text
+            .clone()
+            .or_else(|| self.title.clone())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GalleryPage {
+    pub items: Vec<GalleryItem>,
+    /// Items across all pages.
+    pub total: u32,
+    /// Zero-based.
+    pub page: u32,
+    pub per_page: u32,
+}
+
+impl GalleryPage {
+    pub fn page_count(&self) -> u32 {
+        self.total.div_ceil(self.per_page.max(1)).max(1)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Album {
+    /// Empty for albums that haven't been saved yet.
+    #[serde(default)]
+    pub id: String,
+    /// Made from the title when left empty.
+    #[serde(default)]
+    pub slug: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub category: MediaCategory,
+    pub context: MediaContext,
+    /// Public items in the album.
+    #[serde(default)]
+    pub item_count: u32,
+    /// The album's first public item.
+    #[serde(default)]
+    pub cover: Option<GalleryItem>,
+}
+
+/// An album with one page of its items, in album order.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AlbumPage {
+    pub album: Album,
+    pub items: GalleryPage,
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use super::{Album, AlbumPage, GalleryItem, GalleryPage};
+    use shared::{MediaCategory, MediaContext};
+    use sqlx::sqlite::SqliteRow;
+    use sqlx::{Row, SqlitePool};
+
+    const ITEM_COLUMNS: &str = "m.id, m.url, m.thumbnail_url, m.variants, m.title, m.description, \
+        m.alt_text, m.context, m.taken_at, m.width, m.height";
+    const ALBUM_COLUMNS: &str = "id, slug, title, description, category, context";
+
+    /// "May 1, 2024" from a `taken_at` timestamp, whether or not it's in UTC.
+    pub fn format_capture_date(taken_at: &str) -> Option<String> {
+        let date = chrono::NaiveDate::parse_from_str(taken_at.get(..10)?, "%Y-%m-%d").ok()?;
+        Some(date.format("%B %-d, %Y").to_string())
+    }
+
+    fn item_from_row(row: &SqliteRow) -> GalleryItem {
+        let variants: String = row.get("variants");
+        let taken_at: Option<String> = row.get("taken_at");
+        GalleryItem {
+            id: row.get("id"),
+            url: row.get("url"),
+            thumbnail_url: row.get("thumbnail_url"),
+            variants: serde_json::from_str(&variants).unwrap_or_default(),
+            title: row.get("title"),
+            description: row.get("description"),
+            alt_text: row.get("alt_text"),
+            context: row
+                .get::<String, _>("context")
+                .parse()
+                .unwrap_or(MediaContext::Personal),
+            capture_date: taken_at.as_deref().and_then(format_capture_date),
+            taken_at,
+            width: row.get("width"),
+            height: row.get("height"),
+        }
+    }
+
+    fn album_from_row(row: &SqliteRow) -> Album {
+        Album {
+            id: row.get("id"),
+            slug: row.get("slug"),
+            title: row.get("title"),
+            description: row.get("description"),
+            category: row
+                .get::<String, _>("category")
+                .parse()
+                .unwrap_or(MediaCategory::Photography),
+            context: row
+                .get::<String, _>("context")
+                .parse()
+                .unwrap_or(MediaContext::Personal),
+            item_count: 0,
+            cover: None,
+        }
+    }
+
+    /// One page of the public items in `category`, newest capture first.
+    pub async fn list_gallery(
+        pool: &SqlitePool,
+        category: MediaCategory,
+        context: Option<MediaContext>,
+        page: u32,
+        per_page: u32,
+    ) -> Result<GalleryPage, sqlx::Error> {
+        let per_page = per_page.max(1);
+        let context = context.map(|c| c.to_string());
+        let filter = "FROM media_items m WHERE m.show_in_gallery AND m.category = ? \
+             AND (? IS NULL OR m.context = ?)";
+        let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) {}", filter))
+            .bind(category.to_string())
+            .bind(&context)
+            .bind(&context)
+            .fetch_one(pool)
+            .await?;
+        let rows = sqlx::query(&format!(
+            "SELECT {} {} ORDER BY coalesce(m.taken_at, m.created_at) DESC, m.rowid DESC \
+             LIMIT ? OFFSET ?",
+            ITEM_COLUMNS, filter
+        ))
+        .bind(category.to_string())
+        .bind(&context)
+        .bind(&context)
+        .bind(i64::from(per_page))
+        .bind(i64::from(page) * i64::from(per_page))
+        .fetch_all(pool)
+        .await?;
+        Ok(GalleryPage {
+            items: rows.iter().map(item_from_row).collect(),
+            total: total as u32,
+            page,
+            per_page,
+        })
+    }
+
+    /// A single public item of `category`.
+    pub async fn get_gallery_item(
+        pool: &SqlitePool,
+        category: MediaCategory,
+        id: &str,
+    ) -> Result<Option<GalleryItem>, sqlx::Error> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM media_items m WHERE m.id = ? AND m.category = ? AND m.show_in_gallery",
+            ITEM_COLUMNS
+        ))
+        .bind(id)
+        .bind(category.to_string())
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.as_ref().map(item_from_row))
+    }
+
+    /// Items of an album in album order; only the public ones unless `include_hidden`.
+    async fn album_items(
+        pool: &SqlitePool,
+        album_id: &str,
+        include_hidden: bool,
+        page: u32,
+        per_page: u32,
+    ) -> Result<GalleryPage, sqlx::Error> {
+        let per_page = per_page.max(1);
+        let filter = "FROM album_items ai JOIN media_items m ON m.id = ai.media_id \
+             WHERE ai.album_id = ? AND (? OR m.show_in_gallery)";
+        let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) {}", filter))
+            .bind(album_id)
+            .bind(include_hidden)
+            .fetch_one(pool)
+            .await?;
+        let rows = sqlx::query(&format!(
+            "SELECT {} {} ORDER BY ai.position, m.rowid LIMIT ? OFFSET ?",
+            ITEM_COLUMNS, filter
+        ))
+        .bind(album_id)
+        .bind(include_hidden)
+        .bind(i64::from(per_page))
+        .bind(i64::from(page) * i64::from(per_page))
+        .fetch_all(pool)
+        .await?;
+        Ok(GalleryPage {
+            items: rows.iter().map(item_from_row).collect(),
+            total: total as u32,
+            page,
+            per_page,
+        })
+    }
+
+    async fn with_cover(pool: &SqlitePool, mut album: Album) -> Result<Album, sqlx::Error> {
+        let first = album_items(pool, &album.id, false, 0, 1).await?;
+        album.item_count = first.total;
+        album.cover = first.items.into_iter().next();
+        Ok(album)
+    }
+
+    /// Albums in admin order, all of them or those in `category`.
+    pub async fn list_albums(
+        pool: &SqlitePool,
+        category: Option<MediaCategory>,
+    ) -> Result<Vec<Album>, sqlx::Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM albums WHERE (? IS NULL OR category = ?) ORDER BY position, created_at",
+            ALBUM_COLUMNS
+        ))
+        .bind(category.map(|c| c.to_string()))
+        .bind(category.map(|c| c.to_string()))
+        .fetch_all(pool)
+        .await?;
+        let mut albums = Vec::new();
+        for row in &rows {
+            albums.push(with_cover(pool, album_from_row(row)).await?);
+        }
+        Ok(albums)
+    }
+
+    pub async fn get_album(
+        pool: &SqlitePool,
+        slug: &str,
+        page: u32,
+        per_page: u32,
+    ) -> Result<Option<AlbumPage>, sqlx::Error> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM albums WHERE slug = ?",
+            ALBUM_COLUMNS
+        ))
+        .bind(slug)
+        .fetch_optional(pool)
+        .await?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let album = with_cover(pool, album_from_row(&row)).await?;
+        let items = album_items(pool, &album.id, false, page, per_page).await?;
+        Ok(Some(AlbumPage { album, items }))
+    }
+
+    /// Every item of an album, hidden ones included, for the album editor.
+    pub async fn album_contents(
+        pool: &SqlitePool,
+        album_id: &str,
+    ) -> Result<Vec<GalleryItem>, sqlx::Error> {
+        Ok(album_items(pool, album_id, true, 0, u32::MAX).await?.items)
+    }
+
+    /// Creates or updates an album; new albums go last. Returns the album as saved.
+    pub async fn save_album(pool: &SqlitePool, album: &Album) -> Result<Album, String> {
+        let title = album.title.trim();
+        if title.is_empty() {
+            return Err("Album title cannot be empty".to_string());
+        }
+        let slug_source = if album.slug.trim().is_empty() {
+            title
+        } else {
+            album.slug.as_str()
+        };
+        let slug = crate::utils::slug::sanitize_page_slug(slug_source)
+            .trim_matches('-')
+            .to_string();
+        if slug.is_empty() {
+            return Err("Album slug cannot be empty".to_string());
+        }
+        let id = if album.id.is_empty() {
+            uuid::Uuid::new_v4().to_string()
+        } else {
+            album.id.clone()
+        };
+        let description = album
+            .description
+            .as_deref()
+            .map(str::trim)
+            .filter(|d| !d.is_empty());
+
+        sqlx::query(
+            "INSERT INTO albums (id, slug, title, description, category, context, position) \
+             VALUES (?, ?, ?, ?, ?, ?, (SELECT COALESCE(MAX(position) + 1, 0) FROM albums)) \
+             ON CONFLICT(id) DO UPDATE SET \
+                slug = excluded.slug, \
+                title = excluded.title, \
+                description = excluded.description, \
+                category = excluded.category, \
+                context = excluded.context, \
+                updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+        )
+        .bind(&id)
+        .bind(&slug)
+        .bind(title)
+        .bind(description)
+        .bind(album.category.to_string())
+        .bind(album.context.to_string())
+        .execute(pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                format!("Another album already uses the slug \"{}\"", slug)
+            }
+            e => format!("Database insert/update failed: {}", e),
+        })?;
+
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM albums WHERE id = ?",
+            ALBUM_COLUMNS
+        ))
+        .bind(&id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| format!("Database query failed: {}", e))?;
+        with_cover(pool, album_from_row(&row))
+            .await
+            .map_err(|e| format!("Database query failed: {}", e))
+    }
+
+    pub async fn delete_album(pool: &SqlitePool, id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM albums WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Puts albums in the order of `ids`. Albums not listed keep their position.
+    pub async fn reorder_albums(pool: &SqlitePool, ids: &[String]) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        for (position, id) in ids.iter().enumerate() {
+            sqlx::query("UPDATE albums SET position = ? WHERE id = ?")
+                .bind(position as i64)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await
+    }
+
+    /// Replaces an album's items with `media_ids`, in that order.
+    pub async fn set_album_items(
+        pool: &SqlitePool,
+        album_id: &str,
+        media_ids: &[String],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query("DELETE FROM album_items WHERE album_id = ?")
+            .bind(album_id)
+            .execute(&mut *tx)
+            .await?;
+        for (position, media_id) in media_ids.iter().enumerate() {
+            sqlx::query(
+                "INSERT INTO album_items (album_id, media_id, position) VALUES (?, ?, ?) \
+                 ON CONFLICT(album_id, media_id) DO NOTHING",
+            )
+            .bind(album_id)
+            .bind(media_id)
+            .bind(position as i64)
+            .execute(&mut *tx)
+            .await?;
+        }
+        sqlx::query(
+            "UPDATE albums SET updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+        )
+        .bind(album_id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await
+    }
+}
+
+/// One page of the public gallery for `category`, optionally narrowed to a context.
+#[server(GetGallery, "/api")]
+pub async fn get_gallery(
+    category: MediaCategory,
+    context: Option<MediaContext>,
+    page: u32,
+) -> Result<GalleryPage, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        let pool = use_context::<sqlx::SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+        ssr::list_gallery(&pool, category, context, page, GALLERY_PAGE_SIZE)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = (category, context, page);
+        Ok(GalleryPage::default())
+    }
+}
+
+/// A public item of `category`, e.g. for a video's own page.
+#[server(GetGalleryItem, "/api")]
+pub async fn get_gallery_item(
+    category: MediaCategory,
+    id: String,
+) -> Result<Option<GalleryItem>, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        let pool = use_context::<sqlx::SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+        ssr::get_gallery_item(&pool, category, &id)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = (category, id);
+        Ok(None)
+    }
+}
+
+/// Albums in admin order; all of them when `category` is unset.
+#[server(ListAlbums, "/api")]
+pub async fn list_albums(category: Option<MediaCategory>) -> Result<Vec<Album>, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        let pool = use_context::<sqlx::SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+        ssr::list_albums(&pool, category)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = category;
+        Ok(Vec::new())
+    }
+}
+
+#[server(GetAlbum, "/api")]
+pub async fn get_album(slug: String, page: u32) -> Result<Option<AlbumPage>, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        let pool = use_context::<sqlx::SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+        ssr::get_album(&pool, &slug, page, GALLERY_PAGE_SIZE)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = (slug, page);
+        Ok(None)
+    }
+}
+
+/// Every item of an album, including ones not shown in the galleries.
+#[server(GetAlbumContents, "/api")]
+pub async fn get_album_contents(album_id: String) -> Result<Vec<GalleryItem>, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Editor).await?;
+
+        let pool = use_context::<sqlx::SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+        ssr::album_contents(&pool, &album_id)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = album_id;
+        Ok(Vec::new())
+    }
+}
+
+#[server(SaveAlbum, "/api")]
+pub async fn save_album(album: Album) -> Result<Album, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Editor).await?;
+
+        let pool = use_context::<sqlx::SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+        ssr::save_album(&pool, &album)
+            .await
+            .map_err(ServerFnError::new)
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = album;
+        Err(ServerFnError::new("Albums can only be saved on the server"))
+    }
+}
+
+#[server(DeleteAlbum, "/api")]
+pub async fn delete_album(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Editor).await?;
+
+        let pool = use_context::<sqlx::SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+        ssr::delete_album(&pool, &id)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database delete failed: {}", e)))
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = id;
+        Ok(())
+    }
+}
+
+/// Puts the albums in the order of `ids`.
+#[server(ReorderAlbums, "/api")]
+pub async fn reorder_albums(ids: Vec<String>) -> Result<(), ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Editor).await?;
+
+        let pool = use_context::<sqlx::SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+        ssr::reorder_albums(&pool, &ids)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database update failed: {}", e)))
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = ids;
+        Ok(())
+    }
+}
+
+/// Replaces an album's items with `media_ids`, in that order.
+#[server(SetAlbumItems, "/api")]
+pub async fn set_album_items(
+    album_id: String,
+    media_ids: Vec<String>,
+) -> Result<(), ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Editor).await?;
+
+        let pool = use_context::<sqlx::SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+        ssr::set_album_items(&pool, &album_id, &media_ids)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database update failed: {}", e)))
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = (album_id, media_ids);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn test_format_capture_date() {
+        use super::ssr::format_capture_date;
+        assert_eq!(
+            format_capture_date("2024-05-01T19:34:56.000Z").as_deref(),
+            Some("May 1, 2024")
+        );
+        assert_eq!(
+            format_capture_date("2023-12-24T08:00:00").as_deref(),
+            Some("December 24, 2023")
+        );
+        assert_eq!(format_capture_date("sometime"), None);
+    }
+
+    #[cfg(feature = "ssr")]
+    #[tokio::test]
+    async fn test_galleries_and_albums() {
+        use super::ssr::*;
+
+        let pool = crate::api::test_pool().await;
+        let mut ids = Vec::new();
+        for (name, category, context, shown, taken_at) in [
+            (
+                "dawn",
+                "photography",
+                "personal",
+                true,
+                "2024-05-01T06:00:00.000Z",
+            ),
+            (
+                "rally",
+                "photography",
+                "professional",
+                true,
+                "2024-06-01T12:00:00.000Z",
+            ),
+            (
+                "receipt",
+                "photography",
+                "personal",
+                false,
+                "2024-07-01T12:00:00.000Z",
+            ),
+            (
+                "sketch",
+                "visual_art",
+                "personal",
+                true,
+                "2024-01-01T12:00:00.000Z",
+            ),
+        ] {
+            let id: String = sqlx::query_scalar(
+                "INSERT INTO media_items (title, url, object_name, category, context, show_in_gallery, taken_at) \
+                 VALUES (?, ?, ?, ?, ?, ?, ?) RETURNING id",
+            )
+            .bind(name)
+            .bind(format!("/media/uploads/{}.jpg", name))
+            .bind(format!("media/uploads/{}.jpg", name))
+            .bind(category)
+            .bind(context)
+            .bind(shown)
+            .bind(taken_at)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            ids.push(id);
+        }
+
+        // Hidden items stay out; newest capture first
+        let photos = list_gallery(&pool, MediaCategory::Photography, None, 0, 24)
+            .await
+            .unwrap();
+        let titles: Vec<_> = photos
+            .items
+            .iter()
+            .map(|i| i.title.clone().unwrap())
+            .collect();
+        assert_eq!(titles, vec!["rally", "dawn"]);
+        assert_eq!(photos.items[1].capture_date.as_deref(), Some("May 1, 2024"));
+        let personal = list_gallery(
+            &pool,
+            MediaCategory::Photography,
+            Some(MediaContext::Personal),
+            0,
+            24,
+        )
+        .await
+        .unwrap();
+        assert_eq!(personal.total, 1);
+        let second_page = list_gallery(&pool, MediaCategory::Photography, None, 1, 1)
+            .await
+            .unwrap();
+        assert_eq!(second_page.items[0].title.as_deref(), Some("dawn"));
+        assert_eq!(second_page.page_count(), 2);
+        let dawn = get_gallery_item(&pool, MediaCategory::Photography, &ids[0])
+            .await
+            .unwrap();
+        assert_eq!(dawn.unwrap().title.as_deref(), Some("dawn"));
+        for (category, id) in [
+            (MediaCategory::VisualArt, &ids[0]),
+            (MediaCategory::Photography, &ids[2]),
+        ] {
+            assert!(get_gallery_item(&pool, category, id)
+                .await
+                .unwrap()
+                .is_none());
+        }
+
+        let new_album = |title: &str, category| Album {
+            id: String::new(),
+            slug: String::new(),
+            title: title.to_string(),
+            description: None,
+            category,
+            context: MediaContext::Personal,
+            item_count: 0,
+            cover: None,
+        };
+        let trip = save_album(
+            &pool,
+            &new_album("Skeena Trip!", MediaCategory::Photography),
+        )
+        .await
+        .unwrap();
+        assert_eq!(trip.slug, "skeena-trip");
+        let city = save_album(&pool, &new_album("City", MediaCategory::Photography))
+            .await
+            .unwrap();
+        save_album(&pool, &new_album("Sketchbook", MediaCategory::VisualArt))
+            .await
+            .unwrap();
+        assert!(
+            save_album(&pool, &new_album("Skeena trip", MediaCategory::Photography))
+                .await
+                .is_err()
+        );
+
+        // Album order, then item order, are the admin's
+        set_album_items(
+            &pool,
+            &trip.id,
+            &[ids[0].clone(), ids[2].clone(), ids[1].clone()],
+        )
+        .await
+        .unwrap();
+        reorder_albums(&pool, &[city.id.clone(), trip.id.clone()])
+            .await
+            .unwrap();
+        let albums = list_albums(&pool, Some(MediaCategory::Photography))
+            .await
+            .unwrap();
+        let slugs: Vec<_> = albums.iter().map(|a| a.slug.as_str()).collect();
+        assert_eq!(slugs, vec!["city", "skeena-trip"]);
+        assert_eq!(albums[1].item_count, 2);
+        assert_eq!(albums[1].cover.as_ref().unwrap().id, ids[0]);
+        assert_eq!(list_albums(&pool, None).await.unwrap().len(), 3);
+
+        let page = get_album(&pool, "skeena-trip", 0, 24)
+            .await
+            .unwrap()
+            .unwrap();
+        let order: Vec<_> = page.items.items.iter().map(|i| i.id.clone()).collect();
+        assert_eq!(order, vec![ids[0].clone(), ids[1].clone()]);
+        assert_eq!(album_contents(&pool, &trip.id).await.unwrap().len(), 3);
+        assert!(get_album(&pool, "nowhere", 0, 24).await.unwrap().is_none());
+
+        // Deleting a media item drops it from its albums
+        sqlx::query("DELETE FROM media_items WHERE id = ?")
+            .bind(&ids[0])
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(album_contents(&pool, &trip.id).await.unwrap().len(), 2);
+        delete_album(&pool, &trip.id).await.unwrap();
+        let left: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM album_items")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(left, 0);
+    }
+}
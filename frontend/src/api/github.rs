@@ -0,0 +1,254 @@
+//! Minimal GitHub REST client used to keep the `projects` table in sync with the
+//! repositories it links to.
+//!
+//! The API base URL comes from `GITHUB_API_BASE_URL` (default `https://api.github.com`)
+//! so tests and GitHub Enterprise installs can point it elsewhere. `GITHUB_TOKEN` is
+//! optional and only raises the rate limit.
+
+use serde::Deserialize;
+use sqlx::{Row, SqlitePool};
+use std::sync::OnceLock;
+
+pub const DEFAULT_GITHUB_API_BASE_URL: &str = "https://api.github.com";
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct RepoMetadata {
+    pub description: Option<String>,
+    pub stargazers_count: i64,
+    pub html_url: String,
+}
+
+#[derive(Clone)]
+pub struct GithubClient {
+    base_url: String,
+    token: Option<String>,
+    http: reqwest::Client,
+}
+
+impl GithubClient {
+    pub fn new(base_url: impl Into<String>, token: Option<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            token: token.filter(|t| !t.trim().is_empty()),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub async fn fetch_repo(&self, owner: &str, repo: &str) -> Result<RepoMetadata, String> {
+        let url = format!("{}/repos/{}/{}", self.base_url, owner, repo);
+        let mut request = self
+            .http
+            .get(&url)
+            .header(reqwest::header::USER_AGENT, "jakewray.dev")
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json");
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("GitHub request failed: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!(
+                "GitHub returned {} for {}/{}",
+                response.status(),
+                owner,
+                repo
+            ));
+        }
+
+        response
+            .json::<RepoMetadata>()
+            .await
+            .map_err(|e| format!("Invalid GitHub response: {}", e))
+    }
+}
+
+static GITHUB_CLIENT: OnceLock<GithubClient> = OnceLock::new();
+
+pub fn get_github_client() -> &'static GithubClient {
+    GITHUB_CLIENT.get_or_init(|| {
+        let base_url = std::env::var("GITHUB_API_BASE_URL")
+            .ok()
+            .filter(|u| !u.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_GITHUB_API_BASE_URL.to_string());
+        GithubClient::new(base_url, std::env::var("GITHUB_TOKEN").ok())
+    })
+}
+
+/// Default interval between background syncs (six hours).
+pub const DEFAULT_SYNC_INTERVAL_SECS: u64 = 6 * 60 * 60;
+
+/// Starts the periodic sync task. The interval comes from `GITHUB_SYNC_INTERVAL_SECS`;
+/// setting it to `0` disables the background job (the admin can still sync manually).
+pub fn spawn_sync_job(pool: SqlitePool) {
+    let interval_secs = std::env::var("GITHUB_SYNC_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_SYNC_INTERVAL_SECS);
+    if interval_secs == 0 {
+        tracing::info!("GitHub project sync disabled (GITHUB_SYNC_INTERVAL_SECS=0)");
+        return;
+    }
+
+    let client = get_github_client();
+    tracing::info!(
+        "Starting GitHub project sync every {}s against {}",
+        interval_secs,
+        client.base_url()
+    );
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+        loop {
+            interval.tick().await;
+            match sync_projects(&pool, client).await {
+                Ok(n) => tracing::debug!("GitHub sync refreshed {} projects", n),
+                Err(e) => tracing::error!("GitHub sync failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Extracts `(owner, repo)` from a `https://github.com/owner/repo` style URL.
+pub fn parse_github_repo(url: &str) -> Option<(String, String)> {
+    let rest = url
+        .trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("www.")
+        .strip_prefix("github.com/")?;
+    let mut parts = rest.split('/').filter(|p| !p.is_empty());
+    let owner = parts.next()?;
+    let repo = parts.next()?.trim_end_matches(".git");
+    if repo.is_empty() {
+        return None;
+    }
+    Some((owner.to_string(), repo.to_string()))
+}
+
+/// Refreshes stars and descriptions for every project with a GitHub URL.
+/// Returns how many projects were updated; failures for individual repos are logged
+/// and skipped so one missing repo doesn't stop the rest.
+pub async fn sync_projects(pool: &SqlitePool, client: &GithubClient) -> Result<usize, String> {
+    let rows = sqlx::query("SELECT id, github_url FROM projects WHERE github_url IS NOT NULL")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Database query failed: {}", e))?;
+
+    let mut updated = 0;
+    for row in rows {
+        let id: String = row.get("id");
+        let github_url: String = row.get("github_url");
+        let Some((owner, repo)) = parse_github_repo(&github_url) else {
+            continue;
+        };
+
+        let metadata = match client.fetch_repo(&owner, &repo).await {
+            Ok(m) => m,
+            Err(e) => {
+                tracing::warn!("Skipping GitHub sync for {}/{}: {}", owner, repo, e);
+                continue;
+            }
+        };
+
+        sqlx::query(
+            "UPDATE projects SET stars = ?, description = COALESCE(NULLIF(?, ''), description) WHERE id = ?",
+        )
+        .bind(metadata.stargazers_count)
+        .bind(metadata.description.unwrap_or_default())
+        .bind(&id)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Database update failed: {}", e))?;
+        updated += 1;
+    }
+
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Serves `body` as a JSON 200 response to every request on a random local port.
+    async fn mock_github(body: &'static str) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 4096];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let response = if request.starts_with("GET /repos/jake/site ") {
+                    format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                } else {
+                    "HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                        .to_string()
+                };
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    #[test]
+    fn test_parse_github_repo() {
+        assert_eq!(
+            parse_github_repo("https://github.com/jake/site"),
+            Some(("jake".to_string(), "site".to_string()))
+        );
+        assert_eq!(
+            parse_github_repo("https://www.github.com/jake/site.git/"),
+            Some(("jake".to_string(), "site".to_string()))
+        );
+        assert_eq!(parse_github_repo("https://gitlab.com/jake/site"), None);
+        assert_eq!(parse_github_repo("https://github.com/jake"), None);
+    }
+
+    #[tokio::test]
+    async fn test_sync_projects_against_mock_server() {
+        let base_url = mock_github(
+            r#"{"description":"Personal site","stargazers_count":42,"html_url":"https://github.com/jake/site"}"#,
+        )
+        .await;
+        let client = GithubClient::new(base_url, None);
+
+        let pool = crate::api::test_pool().await;
+        sqlx::query(
+            "INSERT INTO projects (id, name, description, github_url) VALUES \
+             ('a', 'Site', NULL, 'https://github.com/jake/site'), \
+             ('b', 'Gone', 'Kept', 'https://github.com/jake/missing'), \
+             ('c', 'Local', 'No repo', NULL)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let updated = sync_projects(&pool, &client).await.unwrap();
+        assert_eq!(updated, 1);
+
+        let rows: Vec<(String, Option<String>, i64)> =
+            sqlx::query_as("SELECT id, description, stars FROM projects ORDER BY id")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                ("a".to_string(), Some("Personal site".to_string()), 42),
+                ("b".to_string(), Some("Kept".to_string()), 0),
+                ("c".to_string(), Some("No repo".to_string()), 0),
+            ]
+        );
+    }
+}
@@ -0,0 +1,632 @@
+//! Resized WebP and JPEG copies of uploaded images, so pages can offer a `srcset` instead
+//! of the full-size photo.
+//!
+//! Variants are stored next to the original under `variants/` and listed, with the
+//! original's size, in the image's `media_items` row.
+
+use crate::api::exif::{self, PhotoMetadata};
+use crate::api::media::{content_type_for, ImageVariant, VariantFormat};
+use crate::api::storage::MediaStore;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageReader, RgbImage};
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+
+/// Widths variants are made at. Images are never enlarged: one narrower than the widest
+/// gets a variant at its own width instead of the larger ones.
+pub const VARIANT_WIDTHS: [u32; 4] = [320, 640, 1024, 1600];
+/// Width of the variant used as `media_items.thumbnail_url`.
+pub const THUMBNAIL_WIDTH: u32 = 320;
+const JPEG_QUALITY: u8 = 82;
+const WEBP_QUALITY: f32 = 80.0;
+
+pub struct EncodedVariant {
+    pub width: u32,
+    pub format: VariantFormat,
+    pub data: Vec<u8>,
+}
+
+pub struct ProcessedImage {
+    pub width: u32,
+    pub height: u32,
+    pub variants: Vec<EncodedVariant>,
+}
+
+/// Whether variants are made for `name`. GIFs are left alone so animations survive.
+pub fn has_variants(name: &str) -> bool {
+    matches!(
+        content_type_for(name),
+        "image/jpeg" | "image/png" | "image/webp"
+    )
+}
+
+/// Whether `object_name` is itself a variant of another image.
+pub fn is_variant(object_name: &str) -> bool {
+    object_name.starts_with("variants/") || object_name.contains("/variants/")
+}
+
+/// `uploads/photo.jpg` at 640px as WebP is `uploads/variants/photo-640.webp`.
+pub fn variant_object_name(object_name: &str, width: u32, format: VariantFormat) -> String {
+    let (dir, file) = match object_name.rsplit_once('/') {
+        Some((dir, file)) => (format!("{}/", dir), file),
+        None => (String::new(), object_name),
+    };
+    let stem = file.rsplit_once('.').map_or(file, |(stem, _)| stem);
+    format!("{}variants/{}-{}.{}", dir, stem, width, format.extension())
+}
+
+fn decode(data: &[u8]) -> Result<DynamicImage, String> {
+    let unreadable = |e: image::ImageError| format!("Unreadable image: {}", e);
+    let mut decoder = ImageReader::new(std::io::Cursor::new(data))
+        .with_guessed_format()
+        .map_err(|e| format!("Unreadable image: {}", e))?
+        .into_decoder()
+        .map_err(unreadable)?;
+    let orientation = decoder.orientation().map_err(unreadable)?;
+    let mut image = DynamicImage::from_decoder(decoder).map_err(unreadable)?;
+    // Cameras store photos as shot and leave turning them upright to the EXIF tag
+    image.apply_orientation(orientation);
+    Ok(image)
+}
+
+/// Composites transparent pixels onto white, since JPEG has no alpha channel.
+fn flatten(image: &DynamicImage) -> RgbImage {
+    if !image.color().has_alpha() {
+        return image.to_rgb8();
+    }
+    let rgba = image.to_rgba8();
+    RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let [r, g, b, a] = rgba.get_pixel(x, y).0;
+        let over_white = |c: u8| ((c as u32 * a as u32 + 255 * (255 - a as u32)) / 255) as u8;
+        image::Rgb([over_white(r), over_white(g), over_white(b)])
+    })
+}
+
+fn encode_webp(image: &DynamicImage) -> Result<Vec<u8>, String> {
+    let (width, height) = (image.width(), image.height());
+    let encoded = if image.color().has_alpha() {
+        let pixels = image.to_rgba8();
+        webp::Encoder::from_rgba(&pixels, width, height).encode_simple(false, WEBP_QUALITY)
+    } else {
+        let pixels = image.to_rgb8();
+        webp::Encoder::from_rgb(&pixels, width, height).encode_simple(false, WEBP_QUALITY)
+    };
+    encoded
+        .map(|memory| memory.to_vec())
+        .map_err(|e| format!("WebP encoding failed: {:?}", e))
+}
+
+fn encode_jpeg(image: &DynamicImage) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut out, JPEG_QUALITY)
+        .encode_image(&flatten(image))
+        .map_err(|e| format!("JPEG encoding failed: {}", e))?;
+    Ok(out)
+}
+
+/// Decodes an image and encodes its variants. CPU-bound; run it off the async runtime.
+pub fn render_variants(data: &[u8]) -> Result<ProcessedImage, String> {
+    let image = decode(data)?;
+    let (width, height) = (image.width(), image.height());
+    let mut widths: Vec<u32> = VARIANT_WIDTHS.into_iter().filter(|w| *w < width).collect();
+    if VARIANT_WIDTHS.last().is_some_and(|widest| width <= *widest) {
+        widths.push(width);
+    }
+
+    let mut variants = Vec::new();
+    for variant_width in widths {
+        let resized = if variant_width == width {
+            image.clone()
+        } else {
+            image.resize(variant_width, u32::MAX, FilterType::Lanczos3)
+        };
+        variants.push(EncodedVariant {
+            width: variant_width,
+            format: VariantFormat::Webp,
+            data: encode_webp(&resized)?,
+        });
+        variants.push(EncodedVariant {
+            width: variant_width,
+            format: VariantFormat::Jpeg,
+            data: encode_jpeg(&resized)?,
+        });
+    }
+    Ok(ProcessedImage {
+        width,
+        height,
+        variants,
+    })
+}
+
+/// An image ready to store: the bytes to publish, its variants and what its EXIF said.
+#[derive(Default)]
+struct PreparedImage {
+    data: Vec<u8>,
+    processed: Option<ProcessedImage>,
+    metadata: PhotoMetadata,
+    original_exif: Option<Vec<u8>>,
+    /// The uploaded XMP packet, kept only when it has GPS or serial-number properties.
+    private_xmp: Option<Vec<u8>>,
+    /// Whether GPS and serial-number tags were removed from `data`.
+    stripped: bool,
+}
+
+/// Reads the EXIF of `data`, strips its private tags when `strip` and renders the
+/// variants. CPU-bound, so it runs off the async runtime.
+async fn prepare_image(
+    object_name: &str,
+    data: Vec<u8>,
+    strip: bool,
+) -> Result<PreparedImage, String> {
+    if !has_variants(object_name) {
+        return Ok(PreparedImage {
+            data,
+            ..Default::default()
+        });
+    }
+    tokio::task::spawn_blocking(move || {
+        let original_exif = exif::extract_exif(&data);
+        let private_xmp = exif::extract_xmp(&data).filter(|xmp| exif::is_private_xmp(xmp));
+        let mut metadata = original_exif
+            .as_deref()
+            .map(exif::read_metadata)
+            .unwrap_or_default();
+        metadata.has_private_tags |= private_xmp.is_some();
+        let stripped = strip && metadata.has_private_tags;
+        let mut data = match &original_exif {
+            Some(original) if stripped => {
+                exif::replace_exif(data, exif::strip_private_tags(original)?)?
+            }
+            _ => data,
+        };
+        // XMP repeats the EXIF properties; the whole packet goes rather than a rewrite of it
+        if stripped && private_xmp.is_some() {
+            data = exif::replace_xmp(data, None)?;
+        }
+        let processed = render_variants(&data)?;
+        Ok(PreparedImage {
+            data,
+            processed: Some(processed),
+            metadata,
+            original_exif,
+            private_xmp,
+            stripped,
+        })
+    })
+    .await
+    .map_err(|e| format!("Image processing failed: {}", e))?
+}
+
+/// Writes the variants of an already stored image and records it in `media_items`.
+async fn save_media_item(
+    pool: &SqlitePool,
+    store: &dyn MediaStore,
+    object_name: &str,
+    url: &str,
+    image: PreparedImage,
+) -> Result<(), String> {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+
+    let mut variants = Vec::new();
+    let (mut width, mut height) = (None, None);
+    if let Some(processed) = image.processed {
+        width = Some(processed.width);
+        height = Some(processed.height);
+        for variant in processed.variants {
+            let name = variant_object_name(object_name, variant.width, variant.format);
+            let url = store
+                .put(&name, variant.format.content_type(), variant.data)
+                .await?;
+            variants.push(ImageVariant {
+                width: variant.width,
+                format: variant.format,
+                url,
+            });
+        }
+    }
+    let thumbnail_url = variants
+        .iter()
+        .filter(|v| v.format == VariantFormat::Jpeg && v.width <= THUMBNAIL_WIDTH)
+        .max_by_key(|v| v.width)
+        .or_else(|| {
+            variants
+                .iter()
+                .filter(|v| v.format == VariantFormat::Jpeg)
+                .min_by_key(|v| v.width)
+        })
+        .map(|v| v.url.clone());
+    let category = if content_type_for(object_name).starts_with("video/") {
+        "video"
+    } else {
+        "photography"
+    };
+    let title = object_name.rsplit('/').next().unwrap_or(object_name);
+    let metadata = image.metadata;
+    // Nothing private to strip counts as stripped
+    let strip_metadata = image.stripped || !metadata.has_private_tags;
+
+    sqlx::query(
+        "INSERT INTO media_items (title, url, object_name, thumbnail_url, width, height, variants, category, \
+         taken_at, camera, lens, original_exif, original_xmp, has_private_metadata, strip_metadata) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) \
+         ON CONFLICT(object_name) DO UPDATE SET url = excluded.url, thumbnail_url = excluded.thumbnail_url, \
+         width = excluded.width, height = excluded.height, variants = excluded.variants, \
+         taken_at = excluded.taken_at, camera = excluded.camera, lens = excluded.lens, \
+         original_exif = excluded.original_exif, original_xmp = excluded.original_xmp, \
+         has_private_metadata = excluded.has_private_metadata, \
+         strip_metadata = excluded.strip_metadata",
+    )
+    .bind(title)
+    .bind(url)
+    .bind(object_name)
+    .bind(thumbnail_url)
+    .bind(width)
+    .bind(height)
+    .bind(serde_json::to_string(&variants).map_err(|e| e.to_string())?)
+    .bind(category)
+    .bind(metadata.taken_at)
+    .bind(metadata.camera)
+    .bind(metadata.lens)
+    .bind(image.original_exif.map(|exif| STANDARD.encode(exif)))
+    .bind(image.private_xmp.map(|xmp| STANDARD.encode(xmp)))
+    .bind(metadata.has_private_tags)
+    .bind(strip_metadata)
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Database query failed: {}", e))?;
+    Ok(())
+}
+
+/// Stores `data` as `object_name` together with its variants and records it in
+/// `media_items`, returning the original's URL. GPS and serial-number tags are stripped
+/// from the stored file, along with an XMP packet that repeats them (see
+/// [`set_metadata_stripping`] to publish them after all).
+/// Images that can't be decoded are refused before anything is written.
+pub async fn store_image(
+    pool: &SqlitePool,
+    store: &dyn MediaStore,
+    object_name: &str,
+    data: Vec<u8>,
+) -> Result<String, String> {
+    let mut image = prepare_image(object_name, data, true).await?;
+    let url = store
+        .put(
+            object_name,
+            content_type_for(object_name),
+            std::mem::take(&mut image.data),
+        )
+        .await?;
+    save_media_item(pool, store, object_name, &url, image).await?;
+    Ok(url)
+}
+
+/// Strips the GPS and serial-number tags from a stored image, or puts them back from the
+/// EXIF block and XMP packet saved at upload.
+pub async fn set_metadata_stripping(
+    pool: &SqlitePool,
+    store: &dyn MediaStore,
+    object_name: &str,
+    strip: bool,
+) -> Result<(), String> {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+
+    let original: Option<(Option<String>, Option<String>)> = sqlx::query_as(
+        "SELECT original_exif, original_xmp FROM media_items \
+         WHERE object_name = ? AND has_private_metadata",
+    )
+    .bind(object_name)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Database query failed: {}", e))?;
+    let (original_exif, original_xmp) = original
+        .filter(|(exif, xmp)| exif.is_some() || xmp.is_some())
+        .ok_or("This image has no location or serial-number metadata")?;
+    let decode = |saved: Option<String>| {
+        saved
+            .map(|saved| STANDARD.decode(saved))
+            .transpose()
+            .map_err(|e| format!("Saved metadata is corrupt: {}", e))
+    };
+    let (original_exif, original_xmp) = (decode(original_exif)?, decode(original_xmp)?);
+
+    let mut data = store.get(object_name).await?;
+    if let Some(original) = original_exif {
+        let exif = if strip {
+            exif::strip_private_tags(&original)?
+        } else {
+            Some(original)
+        };
+        data = exif::replace_exif(data, exif)?;
+    }
+    if let Some(original) = original_xmp {
+        data = exif::replace_xmp(data, (!strip).then_some(original))?;
+    }
+    store
+        .put(object_name, content_type_for(object_name), data)
+        .await?;
+    sqlx::query("UPDATE media_items SET strip_metadata = ? WHERE object_name = ?")
+        .bind(strip)
+        .bind(object_name)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Database query failed: {}", e))?;
+    Ok(())
+}
+
+/// Deletes an image, its variants and its `media_items` row.
+pub async fn delete_image(
+    pool: &SqlitePool,
+    store: &dyn MediaStore,
+    object_name: &str,
+) -> Result<(), String> {
+    let variants: Option<String> =
+        sqlx::query_scalar("SELECT variants FROM media_items WHERE object_name = ?")
+            .bind(object_name)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| format!("Database query failed: {}", e))?;
+    let variants: Vec<ImageVariant> = variants
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    for variant in variants {
+        if let Some(name) = store.object_name_from_url(&variant.url) {
+            store.delete(&name).await?;
+        }
+    }
+    store.delete(object_name).await?;
+    sqlx::query("DELETE FROM media_items WHERE object_name = ?")
+        .bind(object_name)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Database query failed: {}", e))?;
+    Ok(())
+}
+
+/// Records stored files missing from `media_items`, e.g. ones copied into the store by
+/// hand, and makes variants for images that don't have any yet, e.g. ones uploaded before
+/// variants existed. Returns how many files were processed.
+pub async fn backfill_variants(pool: &SqlitePool, store: &dyn MediaStore) -> Result<usize, String> {
+    let recorded: std::collections::HashMap<String, bool> = sqlx::query_as(
+        "SELECT object_name, variants != '[]' FROM media_items WHERE object_name IS NOT NULL",
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Database query failed: {}", e))?
+    .into_iter()
+    .collect();
+    let mut processed_count = 0;
+    for object in store.list().await? {
+        // Skip variants and directory placeholders
+        if is_variant(&object.name) || object.name.ends_with('/') {
+            continue;
+        }
+        let done = recorded
+            .get(&object.name)
+            .is_some_and(|has_variants_already| {
+                *has_variants_already || !has_variants(&object.name)
+            });
+        if done {
+            continue;
+        }
+        // Files already public are left as they are, private tags included
+        let data = store.get(&object.name).await?;
+        let image = prepare_image(&object.name, data, false)
+            .await
+            .map_err(|e| format!("{}: {}", object.name, e))?;
+        save_media_item(pool, store, &object.name, &object.url, image).await?;
+        processed_count += 1;
+    }
+    Ok(processed_count)
+}
+
+/// Variants of each of `urls` that has any, keyed by URL.
+pub async fn variants_for_urls(
+    pool: &SqlitePool,
+    urls: &[String],
+) -> Result<BTreeMap<String, Vec<ImageVariant>>, sqlx::Error> {
+    if urls.is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    let urls_json = serde_json::to_string(urls).unwrap_or_else(|_| "[]".to_string());
+    let rows: Vec<(String, String)> = sqlx::query_as(
+        "SELECT url, variants FROM media_items \
+         WHERE url IN (SELECT value FROM json_each(?)) AND variants != '[]'",
+    )
+    .bind(urls_json)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|(url, variants)| Some((url, serde_json::from_str(&variants).ok()?)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::storage::LocalMediaStore;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let image = image::RgbaImage::from_fn(width, height, |x, y| {
+            image::Rgba([(x % 256) as u8, (y % 256) as u8, 128, 200])
+        });
+        let mut out = std::io::Cursor::new(Vec::new());
+        image.write_to(&mut out, image::ImageFormat::Png).unwrap();
+        out.into_inner()
+    }
+
+    #[test]
+    fn test_render_variants() {
+        let processed = render_variants(&png(2000, 1000)).unwrap();
+        assert_eq!((processed.width, processed.height), (2000, 1000));
+        assert_eq!(processed.variants.len(), VARIANT_WIDTHS.len() * 2);
+        for variant in &processed.variants {
+            let decoded = image::load_from_memory(&variant.data).unwrap();
+            assert_eq!(decoded.width(), variant.width);
+            assert_eq!(decoded.height(), variant.width / 2);
+        }
+
+        // Small images get one size, their own
+        let processed = render_variants(&png(500, 400)).unwrap();
+        let widths: Vec<u32> = processed.variants.iter().map(|v| v.width).collect();
+        assert_eq!(widths, vec![320, 320, 500, 500]);
+
+        assert!(render_variants(b"not an image").is_err());
+    }
+
+    #[test]
+    fn test_variant_object_name() {
+        assert_eq!(
+            variant_object_name("media/uploads/1_photo.jpg", 640, VariantFormat::Webp),
+            "media/uploads/variants/1_photo-640.webp"
+        );
+        assert_eq!(
+            variant_object_name("photo.png", 320, VariantFormat::Jpeg),
+            "variants/photo-320.jpg"
+        );
+        assert!(is_variant("media/uploads/variants/1_photo-640.webp"));
+        assert!(!is_variant("media/uploads/1_photo.jpg"));
+    }
+
+    #[tokio::test]
+    async fn test_store_and_delete_image() {
+        let pool = crate::api::test_pool().await;
+        let store = LocalMediaStore::new(
+            std::env::temp_dir().join(format!("images-{}", uuid::Uuid::new_v4())),
+            "media/",
+        );
+
+        let url = store_image(&pool, &store, "media/uploads/a.png", png(700, 350))
+            .await
+            .unwrap();
+        let variants = variants_for_urls(&pool, std::slice::from_ref(&url))
+            .await
+            .unwrap();
+        let widths: Vec<(u32, VariantFormat)> =
+            variants[&url].iter().map(|v| (v.width, v.format)).collect();
+        assert_eq!(
+            widths,
+            vec![
+                (320, VariantFormat::Webp),
+                (320, VariantFormat::Jpeg),
+                (640, VariantFormat::Webp),
+                (640, VariantFormat::Jpeg),
+                (700, VariantFormat::Webp),
+                (700, VariantFormat::Jpeg),
+            ]
+        );
+        let (thumbnail, width): (Option<String>, Option<i64>) = sqlx::query_as(
+            "SELECT thumbnail_url, width FROM media_items WHERE object_name = 'media/uploads/a.png'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            thumbnail.as_deref(),
+            Some("/media/uploads/variants/a-320.jpg")
+        );
+        assert_eq!(width, Some(700));
+        assert_eq!(store.list().await.unwrap().len(), 7);
+
+        // Nothing left to backfill
+        assert_eq!(backfill_variants(&pool, &store).await.unwrap(), 0);
+        store
+            .put("media/uploads/b.png", "image/png", png(100, 100))
+            .await
+            .unwrap();
+        store
+            .put(
+                "media/uploads/c.mp4",
+                "video/mp4",
+                b"not really a video".to_vec(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(backfill_variants(&pool, &store).await.unwrap(), 2);
+        assert_eq!(backfill_variants(&pool, &store).await.unwrap(), 0);
+        let category: String = sqlx::query_scalar(
+            "SELECT category FROM media_items WHERE object_name = 'media/uploads/c.mp4'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(category, "video");
+
+        delete_image(&pool, &store, "media/uploads/a.png")
+            .await
+            .unwrap();
+        assert_eq!(store.list().await.unwrap().len(), 4);
+        assert!(variants_for_urls(&pool, &[url]).await.unwrap().is_empty());
+
+        let _ = std::fs::remove_dir_all(store.root());
+    }
+
+    #[tokio::test]
+    async fn test_exif_stripping() {
+        use crate::api::exif::tests::{sample_exif, sample_xmp};
+        use crate::api::exif::{
+            extract_exif, extract_xmp, read_metadata, replace_exif, replace_xmp,
+        };
+
+        let pool = crate::api::test_pool().await;
+        let store = LocalMediaStore::new(
+            std::env::temp_dir().join(format!("images-{}", uuid::Uuid::new_v4())),
+            "media/",
+        );
+        let mut jpeg = std::io::Cursor::new(Vec::new());
+        image::RgbImage::from_pixel(40, 20, image::Rgb([90, 120, 150]))
+            .write_to(&mut jpeg, image::ImageFormat::Jpeg)
+            .unwrap();
+        let jpeg = replace_exif(jpeg.into_inner(), Some(sample_exif())).unwrap();
+        let jpeg = replace_xmp(jpeg, Some(sample_xmp())).unwrap();
+
+        let name = "media/uploads/shot.jpg";
+        store_image(&pool, &store, name, jpeg).await.unwrap();
+        let public = read_metadata(&extract_exif(&store.get(name).await.unwrap()).unwrap());
+        assert!(!public.has_private_tags);
+        assert_eq!(public.camera.as_deref(), Some("Canon EOS R5"));
+        assert_eq!(extract_xmp(&store.get(name).await.unwrap()), None);
+
+        // Orientation 6 turns the 40x20 photo upright
+        let row: (Option<String>, Option<String>, Option<i64>, bool, bool) = sqlx::query_as(
+            "SELECT taken_at, camera, width, has_private_metadata, strip_metadata \
+             FROM media_items WHERE object_name = ?",
+        )
+        .bind(name)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            row,
+            (
+                Some("2024-05-01T19:34:56.000Z".to_string()),
+                Some("Canon EOS R5".to_string()),
+                Some(20),
+                true,
+                true
+            )
+        );
+
+        set_metadata_stripping(&pool, &store, name, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            extract_exif(&store.get(name).await.unwrap()),
+            Some(sample_exif())
+        );
+        assert_eq!(
+            extract_xmp(&store.get(name).await.unwrap()),
+            Some(sample_xmp())
+        );
+        set_metadata_stripping(&pool, &store, name, true)
+            .await
+            .unwrap();
+        let public = read_metadata(&extract_exif(&store.get(name).await.unwrap()).unwrap());
+        assert!(!public.has_private_tags);
+        assert_eq!(extract_xmp(&store.get(name).await.unwrap()), None);
+
+        let _ = std::fs::remove_dir_all(store.root());
+    }
+}
@@ -0,0 +1,1126 @@
+//! Imports journalism into `articles` from the JSON files in `data/articles` and from
+//! WordPress WXR exports.
+//!
+//! Both sources are read into the same [`Article`] the site serves. Imported rows get
+//! `origin = 'imported'` and are matched on `wp_id`, falling back to slug, so importing
+//! the same source again only applies what changed. Images hosted elsewhere are copied
+//! into the configured media store and the article is pointed at the copies.
+
+use crate::api::articles::{record_slug_redirect, summarize_article, Article};
+use crate::api::storage::MediaStore;
+use crate::utils::html::{
+    extract_body_preview, image_sources, strip_image_attributes, strip_tags, tag_attribute,
+};
+use crate::utils::slug::{parse_article_date, sanitize_page_slug, sanitize_slug};
+use serde::Deserialize;
+use sqlx::{Row, SqlitePool};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+const DEFAULT_BYLINE: &str = "Jake Wray";
+
+/// An article read from an import source.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportedArticle {
+    /// Post id on the WordPress site the article came from, when known.
+    pub wp_id: Option<i64>,
+    /// `published_at` as stored, e.g. `2021-01-08T17:30:00.000Z`.
+    pub published_at: String,
+    pub article: Article,
+}
+
+/// A column an import would change on an existing article.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImportAction {
+    Create,
+    Update(Vec<FieldChange>),
+    Unchanged,
+    /// Left alone, for the given reason.
+    Conflict(String),
+}
+
+/// What an import did, or for a dry run would do, to each article by slug.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub entries: Vec<(String, ImportAction)>,
+    /// Object names of the images copied into the media store.
+    pub images: Vec<String>,
+}
+
+impl ImportReport {
+    fn count(&self, matches: fn(&ImportAction) -> bool) -> usize {
+        self.entries
+            .iter()
+            .filter(|(_, action)| matches(action))
+            .count()
+    }
+}
+
+/// One-line excerpts of `before` and `after` from just before where they first differ.
+fn excerpt_difference(before: &str, after: &str) -> (String, String) {
+    const CONTEXT: usize = 20;
+    const WIDTH: usize = 60;
+
+    let differs_at = before
+        .char_indices()
+        .zip(after.chars())
+        .find(|((_, a), b)| a != b)
+        .map_or(before.len().min(after.len()), |((i, _), _)| i);
+    let start = before[..differs_at]
+        .char_indices()
+        .rev()
+        .nth(CONTEXT - 1)
+        .map_or(0, |(i, _)| i);
+    let excerpt = |value: &str| {
+        let rest = value[start..].replace(['\n', '\r'], " ");
+        let mut shown: String = rest.chars().take(WIDTH).collect();
+        if shown.len() < rest.len() {
+            shown.push('…');
+        }
+        if start > 0 {
+            shown.insert(0, '…');
+        }
+        format!("\"{}\"", shown)
+    };
+    (excerpt(before), excerpt(after))
+}
+
+impl std::fmt::Display for ImportReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (slug, action) in &self.entries {
+            match action {
+                ImportAction::Create => writeln!(f, "create    {}", slug)?,
+                ImportAction::Update(changes) => {
+                    writeln!(f, "update    {}", slug)?;
+                    for change in changes {
+                        let (before, after) = excerpt_difference(&change.before, &change.after);
+                        writeln!(f, "            {}: {} -> {}", change.field, before, after)?;
+                    }
+                }
+                ImportAction::Unchanged => {}
+                ImportAction::Conflict(reason) => writeln!(f, "conflict  {}: {}", slug, reason)?,
+            }
+        }
+        let created = self.count(|a| matches!(a, ImportAction::Create));
+        let updated = self.count(|a| matches!(a, ImportAction::Update(_)));
+        let unchanged = self.count(|a| matches!(a, ImportAction::Unchanged));
+        let conflicts = self.count(|a| matches!(a, ImportAction::Conflict(_)));
+        if self.dry_run {
+            write!(
+                f,
+                "Dry run: {} to create, {} to update, {} unchanged, {} conflicting; {} images to copy.",
+                created,
+                updated,
+                unchanged,
+                conflicts,
+                self.images.len()
+            )
+        } else {
+            write!(
+                f,
+                "{} created, {} updated, {} unchanged, {} conflicting; {} images copied.",
+                created,
+                updated,
+                unchanged,
+                conflicts,
+                self.images.len()
+            )
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ArticleFile {
+    #[serde(default)]
+    wp_id: Option<i64>,
+    #[serde(flatten)]
+    article: Article,
+}
+
+/// Parses an article file: one article object, or a list of them as in the old
+/// `journalism.json`.
+pub fn parse_json(json: &str) -> Result<Vec<ImportedArticle>, String> {
+    let items = match serde_json::from_str(json).map_err(|e| e.to_string())? {
+        serde_json::Value::Array(items) => items,
+        item => vec![item],
+    };
+    items
+        .into_iter()
+        .map(|item| {
+            let file: ArticleFile = serde_json::from_value(item).map_err(|e| e.to_string())?;
+            let mut article = file.article;
+            let date = if article.iso_date.trim().is_empty() {
+                &article.display_date
+            } else {
+                &article.iso_date
+            };
+            let (published_at, iso_date, display_date) = parse_article_date(date);
+            article.id = None;
+            article.slug = sanitize_slug(&article.slug);
+            article.iso_date = iso_date;
+            if article.display_date.trim().is_empty() {
+                article.display_date = display_date;
+            }
+            Ok(ImportedArticle {
+                wp_id: file.wp_id,
+                published_at,
+                article,
+            })
+        })
+        .collect()
+}
+
+/// Reads an article file, or every `.json` file in a directory such as `data/articles`.
+pub fn load_json(path: &Path) -> Result<Vec<ImportedArticle>, String> {
+    if !path.is_dir() {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| format!("Reading {}: {}", path.display(), e))?;
+        return parse_json(&json).map_err(|e| format!("{}: {}", path.display(), e));
+    }
+    let mut files: Vec<PathBuf> = std::fs::read_dir(path)
+        .map_err(|e| format!("Reading {}: {}", path.display(), e))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|file| file.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+    let mut articles = Vec::new();
+    for file in files {
+        articles.extend(load_json(&file)?);
+    }
+    Ok(articles)
+}
+
+const WP_NAMESPACE: &str = "http://wordpress.org/export/";
+const CONTENT_NAMESPACE: &str = "http://purl.org/rss/1.0/modules/content/";
+const DC_NAMESPACE: &str = "http://purl.org/dc/elements/1.1/";
+
+// WXR versions differ only in the version at the end of the namespace
+fn is_wp(ns: Option<&str>) -> bool {
+    ns.is_some_and(|ns| ns.starts_with(WP_NAMESPACE) && !ns.ends_with("/excerpt/"))
+}
+
+fn is_excerpt(ns: Option<&str>) -> bool {
+    ns.is_some_and(|ns| ns.starts_with(WP_NAMESPACE) && ns.ends_with("/excerpt/"))
+}
+
+/// Trimmed, non-empty text of the first child of `node` called `name` in a namespace
+/// accepted by `ns`.
+fn child_text<'a>(
+    node: roxmltree::Node<'a, '_>,
+    ns: fn(Option<&str>) -> bool,
+    name: &str,
+) -> Option<&'a str> {
+    node.children()
+        .find(|child| child.tag_name().name() == name && ns(child.tag_name().namespace()))
+        .and_then(|child| child.text())
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+}
+
+fn wp<'a>(node: roxmltree::Node<'a, '_>, name: &str) -> Option<&'a str> {
+    child_text(node, is_wp, name)
+}
+
+/// Parses the published posts, drafts and scheduled posts in a WordPress export.
+pub fn parse_wxr(xml: &str) -> Result<Vec<ImportedArticle>, String> {
+    let doc =
+        roxmltree::Document::parse(xml).map_err(|e| format!("Not a WordPress export: {}", e))?;
+    let channel = doc
+        .root_element()
+        .children()
+        .find(|node| node.has_tag_name("channel"))
+        .ok_or("Not a WordPress export: no channel")?;
+
+    let authors: HashMap<&str, &str> = channel
+        .children()
+        .filter(|node| node.tag_name().name() == "author" && is_wp(node.tag_name().namespace()))
+        .filter_map(|author| {
+            Some((
+                wp(author, "author_login")?,
+                wp(author, "author_display_name")?,
+            ))
+        })
+        .collect();
+    let items: Vec<_> = channel
+        .children()
+        .filter(|node| node.has_tag_name("item"))
+        .collect();
+    // Attachment post id -> (URL, caption)
+    let attachments: HashMap<&str, (&str, Option<&str>)> = items
+        .iter()
+        .filter(|item| wp(**item, "post_type") == Some("attachment"))
+        .filter_map(|item| {
+            Some((
+                wp(*item, "post_id")?,
+                (
+                    wp(*item, "attachment_url")?,
+                    child_text(*item, is_excerpt, "encoded"),
+                ),
+            ))
+        })
+        .collect();
+
+    let mut articles = Vec::new();
+    for item in items
+        .into_iter()
+        .filter(|item| wp(*item, "post_type") == Some("post"))
+    {
+        let status = match wp(item, "status") {
+            Some("publish") => "published",
+            Some("future") => "scheduled",
+            Some("draft" | "pending") => "draft",
+            // Private, trashed and auto-saved posts
+            _ => continue,
+        };
+        let wp_id = wp(item, "post_id").and_then(|id| id.parse().ok());
+        let title = child_text(item, |ns| ns.is_none(), "title")
+            .unwrap_or_default()
+            .to_string();
+        let slug = match wp(item, "post_name") {
+            Some(name) => urlencoding::decode(name)
+                .map(|name| name.into_owned())
+                .unwrap_or_else(|_| name.to_string()),
+            None => sanitize_page_slug(&title).trim_matches('-').to_string(),
+        };
+        let slug = sanitize_slug(&slug);
+        if slug.is_empty() {
+            // An untitled draft has nothing worth importing
+            continue;
+        }
+
+        // Drafts have no GMT date yet
+        let date = ["post_date_gmt", "post_date"]
+            .into_iter()
+            .filter_map(|field| wp(item, field))
+            .find_map(|date| chrono::NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M:%S").ok())
+            .ok_or_else(|| format!("Post {} has no valid date", slug))?;
+        let (published_at, iso_date, display_date) =
+            parse_article_date(&date.format("%Y-%m-%dT%H:%M:%SZ").to_string());
+
+        let content = wordpress_html(
+            child_text(item, |ns| ns == Some(CONTENT_NAMESPACE), "encoded").unwrap_or_default(),
+        );
+        let thumbnail = item
+            .children()
+            .filter(|node| {
+                node.tag_name().name() == "postmeta" && is_wp(node.tag_name().namespace())
+            })
+            .find(|meta| wp(*meta, "meta_key") == Some("_thumbnail_id"))
+            .and_then(|meta| wp(meta, "meta_value"))
+            .and_then(|id| attachments.get(id));
+        let mut images: Vec<String> = thumbnail
+            .map(|(url, _)| url.to_string())
+            .into_iter()
+            .collect();
+        for src in image_sources(&content) {
+            if !images.contains(&src) {
+                images.push(src);
+            }
+        }
+        let captions = thumbnail
+            .and_then(|(_, caption)| *caption)
+            .map(strip_tags)
+            .into_iter()
+            .collect();
+        let excerpt = child_text(item, is_excerpt, "encoded")
+            .map(strip_tags)
+            .filter(|excerpt| !excerpt.is_empty())
+            .or_else(|| extract_body_preview(&content))
+            .unwrap_or_default();
+        let byline = child_text(item, |ns| ns == Some(DC_NAMESPACE), "creator")
+            .map(|login| authors.get(login).copied().unwrap_or(login).to_string());
+
+        articles.push(ImportedArticle {
+            wp_id,
+            published_at,
+            article: Article {
+                id: None,
+                slug,
+                title,
+                iso_date,
+                display_date,
+                source_url: child_text(item, |ns| ns.is_none(), "link")
+                    .unwrap_or_default()
+                    .to_string(),
+                content_html: content,
+                images,
+                captions,
+                excerpt,
+                byline,
+                status: Some(status.to_string()),
+                image_variants: Default::default(),
+            },
+        });
+    }
+    Ok(articles)
+}
+
+/// Turns post content as WordPress stores it into the HTML it would render: `[caption]`
+/// shortcodes become figures, bare paragraphs get `<p>` tags, and responsive image
+/// attributes pointing at the old site are dropped.
+fn wordpress_html(content: &str) -> String {
+    let content = expand_captions(&content.replace("\r\n", "\n"));
+    let content = if content.contains("<p>") || content.contains("<p ") {
+        content
+    } else {
+        autop(&content)
+    };
+    strip_image_attributes(&content, &["srcset", "sizes"])
+}
+
+fn expand_captions(content: &str) -> String {
+    const CLOSE: &str = "[/caption]";
+
+    let mut out = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find("[caption") {
+        let Some(open_end) = rest[start..].find(']').map(|i| start + i + 1) else {
+            break;
+        };
+        let Some(close) = rest[open_end..].find(CLOSE).map(|i| open_end + i) else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+
+        // The image, possibly wrapped in a link, comes first and the caption after it.
+        // Newer exports put the caption in an attribute instead.
+        let inner = rest[open_end..close].trim();
+        let media_end = if inner.starts_with("<a") {
+            inner.find("</a>").map(|i| i + 4)
+        } else {
+            inner.find('>').map(|i| i + 1)
+        }
+        .unwrap_or(inner.len());
+        let (media, caption) = inner.split_at(media_end);
+        let caption = Some(caption.trim())
+            .filter(|c| !c.is_empty())
+            .or_else(|| tag_attribute(&rest[start..open_end], "caption"))
+            .unwrap_or_default();
+        if caption.is_empty() {
+            out.push_str(&format!("<figure class=\"wp-caption\">{}</figure>", media));
+        } else {
+            out.push_str(&format!(
+                "<figure class=\"wp-caption\">{}<figcaption>{}</figcaption></figure>",
+                media, caption
+            ));
+        }
+        rest = &rest[close + CLOSE.len()..];
+    }
+    out.push_str(rest);
+    out
+}
+
+const BLOCK_TAGS: &[&str] = &[
+    "<h",
+    "<ul",
+    "<ol",
+    "<li",
+    "<blockquote",
+    "<figure",
+    "<div",
+    "<table",
+    "<pre",
+    "<iframe",
+    "<!--",
+];
+
+/// Wraps blank-line separated text in paragraphs, like WordPress's `wpautop`.
+fn autop(content: &str) -> String {
+    content
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|block| !block.is_empty())
+        .map(|block| {
+            if BLOCK_TAGS.iter().any(|tag| block.starts_with(tag)) {
+                block.to_string()
+            } else {
+                format!("<p>{}</p>", block.replace('\n', "<br>\n"))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// An image to copy into the media store.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImageCopy {
+    pub url: String,
+    pub object_name: String,
+}
+
+fn safe_file_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric() || matches!(c, '.' | '-' | '_'))
+        .collect()
+}
+
+/// Points `article` at copies of its externally hosted images in `store`, returning the
+/// copies to make. Object names come from the slug, a short hash of the source URL and the
+/// file name, so importing the same article again maps to the same objects while two
+/// images that share a file name still get their own.
+pub fn plan_images(store: &dyn MediaStore, article: &mut Article) -> Vec<ImageCopy> {
+    use sha2::{Digest, Sha256};
+
+    let folder = Some(safe_file_name(&article.slug))
+        .filter(|folder| !folder.is_empty() && !folder.starts_with('.'))
+        .unwrap_or_else(|| "article".to_string());
+    let mut urls = article.images.clone();
+    for src in image_sources(&article.content_html) {
+        if !urls.contains(&src) {
+            urls.push(src);
+        }
+    }
+    // Longest first, so rewriting one URL can't clobber another that it's a prefix of
+    urls.sort_by_key(|url| std::cmp::Reverse(url.len()));
+
+    let mut copies: Vec<ImageCopy> = Vec::new();
+    for url in urls {
+        let external = url.starts_with("http://") || url.starts_with("https://");
+        if !external || store.object_name_from_url(&url).is_some() {
+            continue;
+        }
+        let source = url.replace("&amp;", "&");
+        let hash = format!("{:x}", Sha256::digest(source.as_bytes()));
+        let path = source.split(['?', '#']).next().unwrap_or_default();
+        let file = safe_file_name(path.rsplit('/').next().unwrap_or_default());
+        let file = if file.is_empty() || file.starts_with('.') {
+            "image".to_string()
+        } else {
+            file
+        };
+        let object_name = format!(
+            "{}imports/{}/{}-{}",
+            store.prefix(),
+            folder,
+            &hash[..8],
+            file
+        );
+        let public_url = store.public_url(&object_name);
+
+        article.content_html = article.content_html.replace(&url, &public_url);
+        for image in article.images.iter_mut().filter(|image| **image == url) {
+            *image = public_url.clone();
+        }
+        if !copies.iter().any(|copy| copy.url == source) {
+            copies.push(ImageCopy {
+                url: source,
+                object_name,
+            });
+        }
+    }
+    copies
+}
+
+const EXISTING_COLUMNS: &str = "id, wp_id, slug, title, excerpt, content, cover_image_url, \
+     cover_image_caption, author, published_at, status, origin";
+
+struct Existing {
+    id: String,
+    wp_id: Option<i64>,
+    slug: String,
+    title: String,
+    excerpt: Option<String>,
+    content: String,
+    cover_image_url: Option<String>,
+    cover_image_caption: Option<String>,
+    author: String,
+    published_at: String,
+    status: String,
+    origin: String,
+}
+
+enum Key<'a> {
+    WpId(i64),
+    Slug(&'a str),
+}
+
+async fn find_existing(pool: &SqlitePool, key: Key<'_>) -> Result<Option<Existing>, sqlx::Error> {
+    let column = match key {
+        Key::WpId(_) => "wp_id",
+        Key::Slug(_) => "slug",
+    };
+    let sql = format!(
+        "SELECT {} FROM articles WHERE {} = ?",
+        EXISTING_COLUMNS, column
+    );
+    let query = sqlx::query(&sql);
+    let query = match key {
+        Key::WpId(wp_id) => query.bind(wp_id),
+        Key::Slug(slug) => query.bind(slug),
+    };
+    let row = query.fetch_optional(pool).await?;
+    Ok(row.map(|row| Existing {
+        id: row.get("id"),
+        wp_id: row.get("wp_id"),
+        slug: row.get("slug"),
+        title: row.get("title"),
+        excerpt: row.get("excerpt"),
+        content: row.get("content"),
+        cover_image_url: row.get("cover_image_url"),
+        cover_image_caption: row.get("cover_image_caption"),
+        author: row.get("author"),
+        published_at: row.get("published_at"),
+        status: row.get("status"),
+        origin: row.get("origin"),
+    }))
+}
+
+/// Column values an imported article is stored with.
+struct Record<'a> {
+    wp_id: Option<i64>,
+    slug: &'a str,
+    title: &'a str,
+    excerpt: &'a str,
+    content: &'a str,
+    cover_image_url: Option<&'a str>,
+    cover_image_caption: Option<&'a str>,
+    author: &'a str,
+    published_at: &'a str,
+    status: &'a str,
+}
+
+impl<'a> Record<'a> {
+    fn new(imported: &'a ImportedArticle) -> Self {
+        let article = &imported.article;
+        Record {
+            wp_id: imported.wp_id,
+            slug: &article.slug,
+            title: &article.title,
+            excerpt: &article.excerpt,
+            content: &article.content_html,
+            cover_image_url: article.images.first().map(String::as_str),
+            cover_image_caption: article.captions.first().map(String::as_str),
+            author: article.byline.as_deref().unwrap_or(DEFAULT_BYLINE),
+            published_at: &imported.published_at,
+            status: article.status.as_deref().unwrap_or("published"),
+        }
+    }
+
+    fn changes_from(&self, existing: &Existing) -> Vec<FieldChange> {
+        let mut changes = Vec::new();
+        let mut compare = |field, before: &str, after: &str| {
+            if before != after {
+                changes.push(FieldChange {
+                    field,
+                    before: before.to_string(),
+                    after: after.to_string(),
+                });
+            }
+        };
+        if let Some(wp_id) = self.wp_id {
+            let before = existing.wp_id.map(|id| id.to_string()).unwrap_or_default();
+            compare("wp_id", &before, &wp_id.to_string());
+        }
+        compare("slug", &existing.slug, self.slug);
+        compare("title", &existing.title, self.title);
+        compare(
+            "excerpt",
+            existing.excerpt.as_deref().unwrap_or_default(),
+            self.excerpt,
+        );
+        compare("content", &existing.content, self.content);
+        compare(
+            "cover_image_url",
+            existing.cover_image_url.as_deref().unwrap_or_default(),
+            self.cover_image_url.unwrap_or_default(),
+        );
+        // Captions written on the site are kept
+        let caption = existing.cover_image_caption.as_deref().unwrap_or_default();
+        if caption.is_empty() {
+            compare(
+                "cover_image_caption",
+                caption,
+                self.cover_image_caption.unwrap_or_default(),
+            );
+        }
+        compare("author", &existing.author, self.author);
+        compare("published_at", &existing.published_at, self.published_at);
+        compare("status", &existing.status, self.status);
+        compare("origin", &existing.origin, "imported");
+        changes
+    }
+}
+
+async fn plan_article(
+    pool: &SqlitePool,
+    imported: &ImportedArticle,
+) -> Result<(Option<Existing>, ImportAction), sqlx::Error> {
+    let record = Record::new(imported);
+    let by_wp_id = match record.wp_id {
+        Some(wp_id) => find_existing(pool, Key::WpId(wp_id)).await?,
+        None => None,
+    };
+    let by_slug = find_existing(pool, Key::Slug(record.slug)).await?;
+
+    let conflict = |reason: String| Ok((None, ImportAction::Conflict(reason)));
+    let existing = match (by_wp_id, by_slug) {
+        (Some(by_wp_id), Some(by_slug)) if by_wp_id.id != by_slug.id => {
+            return conflict(format!(
+                "the slug is taken by another article ({})",
+                by_slug.title
+            ));
+        }
+        (None, Some(by_slug)) if by_slug.origin == "local" => {
+            return conflict("an article written on the site already uses the slug".to_string());
+        }
+        (None, Some(by_slug))
+            if record.wp_id.is_some()
+                && by_slug.wp_id.is_some_and(|id| Some(id) != record.wp_id) =>
+        {
+            return conflict(format!(
+                "the slug belongs to WordPress post {}",
+                by_slug.wp_id.unwrap_or_default()
+            ));
+        }
+        (Some(existing), _) | (None, Some(existing)) => existing,
+        (None, None) => return Ok((None, ImportAction::Create)),
+    };
+    let changes = record.changes_from(&existing);
+    let action = if changes.is_empty() {
+        ImportAction::Unchanged
+    } else {
+        ImportAction::Update(changes)
+    };
+    Ok((Some(existing), action))
+}
+
+async fn write_article(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    imported: &ImportedArticle,
+    existing: Option<&Existing>,
+) -> Result<(), sqlx::Error> {
+    let record = Record::new(imported);
+    let id = existing.map_or_else(|| uuid::Uuid::new_v4().to_string(), |e| e.id.clone());
+
+    sqlx::query(
+        "INSERT INTO articles (id, wp_id, slug, title, excerpt, content, cover_image_url, cover_image_caption, author, published_at, origin, status, updated_at) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 'imported', ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now')) \
+         ON CONFLICT(id) DO UPDATE SET \
+            wp_id = COALESCE(excluded.wp_id, articles.wp_id), \
+            slug = excluded.slug, \
+            title = excluded.title, \
+            excerpt = excluded.excerpt, \
+            content = excluded.content, \
+            cover_image_url = excluded.cover_image_url, \
+            cover_image_caption = COALESCE(NULLIF(articles.cover_image_caption, ''), excluded.cover_image_caption), \
+            author = excluded.author, \
+            published_at = excluded.published_at, \
+            origin = 'imported', \
+            status = excluded.status, \
+            updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+    )
+    .bind(&id)
+    .bind(record.wp_id)
+    .bind(record.slug)
+    .bind(record.title)
+    .bind(record.excerpt)
+    .bind(record.content)
+    .bind(record.cover_image_url)
+    .bind(record.cover_image_caption)
+    .bind(record.author)
+    .bind(record.published_at)
+    .bind(record.status)
+    .execute(&mut **tx)
+    .await?;
+
+    if let Some(old_slug) = existing.map(|e| &e.slug).filter(|old| *old != record.slug) {
+        record_slug_redirect(tx, old_slug, record.slug, &id).await?;
+    }
+
+    use crate::api::revisions::{ssr::record_revision, RevisionKind};
+    record_revision(
+        &mut **tx,
+        RevisionKind::Article,
+        &id,
+        "import",
+        record.title,
+        record.content,
+    )
+    .await?;
+
+    use crate::api::audit::{ssr as audit, AuditAction};
+    audit::record_event(
+        &mut **tx,
+        AuditAction::ArticleSave,
+        audit::NewAuditEvent {
+            target: Some(record.slug),
+            before: existing.map(|e| summarize_article(&e.title, &e.slug, &e.status)),
+            after: Some(format!(
+                "{}, imported",
+                summarize_article(record.title, record.slug, record.status)
+            )),
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+async fn download(client: &reqwest::Client, url: &str) -> Result<Vec<u8>, String> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("Downloading {} failed: {}", url, e))?;
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Downloading {} failed: {}", url, e))?;
+    Ok(bytes.to_vec())
+}
+
+/// Works out what importing `articles` changes and, unless `dry_run`, copies their
+/// images into `store` and then writes them in one transaction.
+pub async fn import_articles(
+    pool: &SqlitePool,
+    store: &dyn MediaStore,
+    articles: Vec<ImportedArticle>,
+    dry_run: bool,
+) -> Result<ImportReport, String> {
+    let mut seen_slugs = HashSet::new();
+    let mut seen_wp_ids = HashSet::new();
+    let mut planned = Vec::new();
+    for mut imported in articles {
+        let copies = plan_images(store, &mut imported.article);
+        let new_slug = seen_slugs.insert(imported.article.slug.clone());
+        let new_wp_id = imported.wp_id.is_none_or(|id| seen_wp_ids.insert(id));
+        let (existing, action) = if new_slug && new_wp_id {
+            plan_article(pool, &imported)
+                .await
+                .map_err(|e| format!("Database query failed: {}", e))?
+        } else {
+            let reason = "appears more than once in the import".to_string();
+            (None, ImportAction::Conflict(reason))
+        };
+        planned.push((imported, copies, existing, action));
+    }
+
+    // Images of unchanged articles are copied too, in case the media store is new
+    let mut copies: Vec<&ImageCopy> = Vec::new();
+    for (_, article_copies, _, action) in &planned {
+        if matches!(action, ImportAction::Conflict(_)) {
+            continue;
+        }
+        for copy in article_copies {
+            if !copies.iter().any(|c| c.object_name == copy.object_name) {
+                copies.push(copy);
+            }
+        }
+    }
+    if !copies.is_empty() {
+        let stored: HashSet<String> = store
+            .list()
+            .await?
+            .into_iter()
+            .map(|object| object.name)
+            .collect();
+        copies.retain(|copy| !stored.contains(&copy.object_name));
+    }
+
+    let report = ImportReport {
+        dry_run,
+        entries: planned
+            .iter()
+            .map(|(imported, _, _, action)| (imported.article.slug.clone(), action.clone()))
+            .collect(),
+        images: copies.iter().map(|copy| copy.object_name.clone()).collect(),
+    };
+    if dry_run {
+        return Ok(report);
+    }
+
+    if !copies.is_empty() {
+        let client = reqwest::Client::builder()
+            .user_agent("jakewray.dev importer")
+            .build()
+            .map_err(|e| e.to_string())?;
+        for copy in &copies {
+            let data = download(&client, &copy.url).await?;
+            crate::api::images::store_image(pool, store, &copy.object_name, data)
+                .await
+                .map_err(|e| format!("{}: {}", copy.url, e))?;
+        }
+    }
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    for (imported, _, existing, action) in &planned {
+        if matches!(action, ImportAction::Create | ImportAction::Update(_)) {
+            write_article(&mut tx, imported, existing.as_ref())
+                .await
+                .map_err(|e| format!("Importing {} failed: {}", imported.article.slug, e))?;
+        }
+    }
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::storage::LocalMediaStore;
+
+    const WXR: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0"
+    xmlns:excerpt="http://wordpress.org/export/1.2/excerpt/"
+    xmlns:content="http://purl.org/rss/1.0/modules/content/"
+    xmlns:dc="http://purl.org/dc/elements/1.1/"
+    xmlns:wp="http://wordpress.org/export/1.2/">
+<channel>
+    <wp:author><wp:author_login><![CDATA[jcray]]></wp:author_login><wp:author_display_name><![CDATA[Jake Wray]]></wp:author_display_name></wp:author>
+    <item>
+        <title>Council votes on budget</title>
+        <link>https://example.wordpress.com/2021/01/08/council-votes/</link>
+        <dc:creator><![CDATA[jcray]]></dc:creator>
+        <content:encoded><![CDATA[[caption id="attachment_7" width="640"]<img src="https://example.files.wordpress.com/2021/01/hall.jpg?w=640" srcset="https://example.files.wordpress.com/2021/01/hall.jpg?w=300 300w" sizes="(max-width: 640px) 100vw"> City hall on Tuesday.[/caption]
+
+Council met on Tuesday.
+It voted 5-2.
+
+<h3>Next steps</h3>]]></content:encoded>
+        <excerpt:encoded><![CDATA[]]></excerpt:encoded>
+        <wp:post_id>42</wp:post_id>
+        <wp:post_date><![CDATA[2021-01-08 09:30:00]]></wp:post_date>
+        <wp:post_date_gmt><![CDATA[2021-01-08 17:30:00]]></wp:post_date_gmt>
+        <wp:post_name><![CDATA[council-votes-on-budget]]></wp:post_name>
+        <wp:status><![CDATA[publish]]></wp:status>
+        <wp:post_type><![CDATA[post]]></wp:post_type>
+        <wp:postmeta><wp:meta_key><![CDATA[_thumbnail_id]]></wp:meta_key><wp:meta_value><![CDATA[7]]></wp:meta_value></wp:postmeta>
+    </item>
+    <item>
+        <title>hall.jpg</title>
+        <excerpt:encoded><![CDATA[City hall on Tuesday.]]></excerpt:encoded>
+        <wp:post_id>7</wp:post_id>
+        <wp:post_type><![CDATA[attachment]]></wp:post_type>
+        <wp:attachment_url><![CDATA[https://example.files.wordpress.com/2021/01/hall.jpg]]></wp:attachment_url>
+    </item>
+    <item>
+        <title>Half-finished idea</title>
+        <wp:post_id>43</wp:post_id>
+        <wp:post_date><![CDATA[2021-02-01 10:00:00]]></wp:post_date>
+        <wp:post_date_gmt><![CDATA[0000-00-00 00:00:00]]></wp:post_date_gmt>
+        <wp:status><![CDATA[draft]]></wp:status>
+        <wp:post_type><![CDATA[post]]></wp:post_type>
+    </item>
+    <item>
+        <title>Deleted</title>
+        <wp:post_id>44</wp:post_id>
+        <wp:status><![CDATA[trash]]></wp:status>
+        <wp:post_type><![CDATA[post]]></wp:post_type>
+    </item>
+</channel>
+</rss>"#;
+
+    #[test]
+    fn test_parse_wxr() {
+        let articles = parse_wxr(WXR).unwrap();
+        assert_eq!(articles.len(), 2);
+
+        let post = &articles[0];
+        assert_eq!(post.wp_id, Some(42));
+        assert_eq!(post.published_at, "2021-01-08T17:30:00.000Z");
+        assert_eq!(post.article.slug, "council-votes-on-budget");
+        assert_eq!(post.article.byline.as_deref(), Some("Jake Wray"));
+        assert_eq!(post.article.status.as_deref(), Some("published"));
+        assert_eq!(
+            post.article.images,
+            vec![
+                "https://example.files.wordpress.com/2021/01/hall.jpg",
+                "https://example.files.wordpress.com/2021/01/hall.jpg?w=640",
+            ]
+        );
+        assert_eq!(post.article.captions, vec!["City hall on Tuesday."]);
+        assert_eq!(
+            post.article.content_html,
+            "<figure class=\"wp-caption\"><img src=\"https://example.files.wordpress.com/2021/01/hall.jpg?w=640\"><figcaption>City hall on Tuesday.</figcaption></figure>\n\
+             <p>Council met on Tuesday.<br>\nIt voted 5-2.</p>\n\
+             <h3>Next steps</h3>"
+        );
+        assert_eq!(
+            post.article.excerpt,
+            "Council met on Tuesday.\nIt voted 5-2."
+        );
+
+        let draft = &articles[1];
+        assert_eq!(draft.article.slug, "half-finished-idea");
+        assert_eq!(draft.article.status.as_deref(), Some("draft"));
+        assert_eq!(draft.published_at, "2021-02-01T10:00:00.000Z");
+
+        assert!(parse_wxr("<html></html>").is_err());
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let one = r#"{"slug": "terrace-fair", "title": "Terrace fair", "date": "2025-04-02",
+            "display_date": "April 02, 2025", "source_url": "", "excerpt": "Rides",
+            "content_html": "<p>Rides</p>", "images": [], "tags": ["archive"]}"#;
+        let articles = parse_json(one).unwrap();
+        assert_eq!(articles[0].article.iso_date, "2025-04-02");
+        assert_eq!(articles[0].published_at, "2025-04-02T00:00:00.000Z");
+        assert_eq!(articles[0].wp_id, None);
+
+        let many = format!("[{}, {}]", one, one.replace("{", "{\"wp_id\": 9, "));
+        let articles = parse_json(&many).unwrap();
+        assert_eq!(articles.len(), 2);
+        assert_eq!(articles[1].wp_id, Some(9));
+
+        assert!(parse_json(r#"{"slug": "no-title"}"#).is_err());
+    }
+
+    #[test]
+    fn test_plan_images() {
+        let store = LocalMediaStore::new("unused", "media/journalism/");
+        let mut article = parse_json(
+            r#"{"slug": "fair", "title": "Fair", "iso_date": "2025-04-02", "display_date": "",
+                "source_url": "", "excerpt": "",
+                "content_html": "<img src=\"https://old.example/a.jpg?w=1&amp;h=2\"><img src=\"/media/journalism/b.jpg\">",
+                "images": ["https://old.example/a.jpg?w=1&amp;h=2"]}"#,
+        )
+        .unwrap()
+        .remove(0)
+        .article;
+
+        let copies = plan_images(&store, &mut article);
+        assert_eq!(
+            copies,
+            vec![ImageCopy {
+                url: "https://old.example/a.jpg?w=1&h=2".to_string(),
+                object_name: "media/journalism/imports/fair/099c1994-a.jpg".to_string(),
+            }]
+        );
+        assert_eq!(
+            article.images,
+            vec!["/media/journalism/imports/fair/099c1994-a.jpg"]
+        );
+        assert_eq!(
+            article.content_html,
+            "<img src=\"/media/journalism/imports/fair/099c1994-a.jpg\"><img src=\"/media/journalism/b.jpg\">"
+        );
+        assert!(plan_images(&store, &mut article).is_empty());
+    }
+
+    #[test]
+    fn test_plan_images_with_shared_file_names() {
+        let store = LocalMediaStore::new("unused", "media/journalism/");
+        let mut article = parse_json(
+            r#"{"slug": "fair", "title": "Fair", "iso_date": "2025-04-02", "display_date": "",
+                "source_url": "", "excerpt": "", "images": [],
+                "content_html": "<img src=\"https://old.example/uploads/2019/01/photo.jpg\"><img src=\"https://old.example/uploads/2020/05/photo.jpg\"><img src=\"https://old.example/uploads/2019/01/photo.jpg\">"}"#,
+        )
+        .unwrap()
+        .remove(0)
+        .article;
+
+        let copies = plan_images(&store, &mut article);
+        assert_eq!(
+            copies,
+            vec![
+                ImageCopy {
+                    url: "https://old.example/uploads/2019/01/photo.jpg".to_string(),
+                    object_name: "media/journalism/imports/fair/8f556f9b-photo.jpg".to_string(),
+                },
+                ImageCopy {
+                    url: "https://old.example/uploads/2020/05/photo.jpg".to_string(),
+                    object_name: "media/journalism/imports/fair/4f2edd5d-photo.jpg".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            article.content_html,
+            "<img src=\"/media/journalism/imports/fair/8f556f9b-photo.jpg\">\
+             <img src=\"/media/journalism/imports/fair/4f2edd5d-photo.jpg\">\
+             <img src=\"/media/journalism/imports/fair/8f556f9b-photo.jpg\">"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_is_idempotent() {
+        let pool = crate::api::test_pool().await;
+        sqlx::query(
+            "INSERT INTO articles (slug, title, content, author, origin) \
+             VALUES ('half-finished-idea', 'Mine', '<p>Mine</p>', 'Jake Wray', 'local')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let store = LocalMediaStore::new("unused", "media/journalism/");
+        // Keep the test offline: the images are already in the store
+        let articles: Vec<ImportedArticle> = parse_wxr(WXR)
+            .unwrap()
+            .into_iter()
+            .map(|mut imported| {
+                imported.article.content_html = imported
+                    .article
+                    .content_html
+                    .replace("https://example.files.wordpress.com/", "/media/journalism/");
+                imported.article.images.clear();
+                imported
+            })
+            .collect();
+
+        let report = import_articles(&pool, &store, articles.clone(), true)
+            .await
+            .unwrap();
+        assert_eq!(report.entries[0].1, ImportAction::Create);
+        assert!(matches!(report.entries[1].1, ImportAction::Conflict(_)));
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM articles")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+
+        import_articles(&pool, &store, articles.clone(), false)
+            .await
+            .unwrap();
+        let (origin, wp_id): (String, i64) = sqlx::query_as(
+            "SELECT origin, wp_id FROM articles WHERE slug = 'council-votes-on-budget'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!((origin.as_str(), wp_id), ("imported", 42));
+
+        let again = import_articles(&pool, &store, articles.clone(), false)
+            .await
+            .unwrap();
+        assert_eq!(again.entries[0].1, ImportAction::Unchanged);
+
+        // A renamed post is matched on its WordPress id and keeps a redirect
+        let mut renamed = articles.clone();
+        renamed[0].article.slug = "budget-passes".to_string();
+        let report = import_articles(&pool, &store, renamed, false)
+            .await
+            .unwrap();
+        let ImportAction::Update(changes) = &report.entries[0].1 else {
+            panic!("expected an update, got {:?}", report.entries[0].1);
+        };
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, "slug");
+        let redirects: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM redirects WHERE old_slug = 'council-votes-on-budget'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(redirects, 1);
+        assert!(report
+            .to_string()
+            .contains("slug: \"council-votes-on-budget\" -> \"budget-passes\""));
+    }
+
+    #[test]
+    fn test_excerpt_difference() {
+        let before = format!("{}<p>old ending</p>", "x".repeat(100));
+        let after = format!("{}<p>new ending</p>", "x".repeat(100));
+        let (before, after) = excerpt_difference(&before, &after);
+        assert_eq!(before, format!("\"…{}<p>old ending</p>\"", "x".repeat(17)));
+        assert_eq!(after, format!("\"…{}<p>new ending</p>\"", "x".repeat(17)));
+    }
+}
@@ -1,76 +1,480 @@
 use leptos::prelude::*;
 use serde::{Deserialize, Serialize};
+use shared::{MediaCategory, MediaContext};
 
+/// A file in the media library, as recorded in `media_items`.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct MediaItem {
+    pub id: String,
     pub url: String,
+    /// File name, shown where there's no title.
     pub name: String,
+    #[serde(default)]
+    pub object_name: String,
+    /// Small variant for grids and pickers, for images that have them.
+    #[serde(default)]
+    pub thumbnail_url: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub alt_text: Option<String>,
+    pub category: MediaCategory,
+    pub context: MediaContext,
+    /// Whether the item is listed on the public gallery for its category.
+    #[serde(default)]
+    pub show_in_gallery: bool,
+    pub created_at: String,
+    /// Capture details from the image's EXIF, when it had any.
+    #[serde(default)]
+    pub taken_at: Option<String>,
+    #[serde(default)]
+    pub camera: Option<String>,
+    #[serde(default)]
+    pub lens: Option<String>,
+    /// For images uploaded with GPS or serial-number tags: whether those are stripped from
+    /// the public file.
+    #[serde(default)]
+    pub strip_metadata: Option<bool>,
 }
 
+impl MediaItem {
+    pub fn display_name(&self) -> &str {
+        self.title.as_deref().unwrap_or(&self.name)
+    }
+
+    pub fn is_video(&self) -> bool {
+        content_type_for(&self.url).starts_with("video/")
+    }
+}
+
+/// The editable part of a [`MediaItem`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MediaDetails {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub alt_text: Option<String>,
+    pub category: MediaCategory,
+    pub context: MediaContext,
+    #[serde(default)]
+    pub show_in_gallery: bool,
+}
+
+impl From<&MediaItem> for MediaDetails {
+    fn from(item: &MediaItem) -> Self {
+        MediaDetails {
+            title: item.title.clone(),
+            description: item.description.clone(),
+            alt_text: item.alt_text.clone(),
+            category: item.category,
+            context: item.context,
+            show_in_gallery: item.show_in_gallery,
+        }
+    }
+}
+
+impl std::fmt::Display for MediaDetails {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({}, {}){}{}",
+            self.title.as_deref().unwrap_or("untitled"),
+            self.category,
+            self.context,
+            if self.alt_text.is_some() {
+                ", with alt text"
+            } else {
+                ""
+            },
+            if self.show_in_gallery {
+                ", in galleries"
+            } else {
+                ""
+            }
+        )
+    }
+}
+
+/// Media library items per page.
+pub const MEDIA_PAGE_SIZE: u32 = 40;
+
+/// Narrows the media library. Empty fields match everything.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct MediaQuery {
+    /// Matches anywhere in the title, description, alt text or file name.
+    pub search: Option<String>,
+    pub category: Option<MediaCategory>,
+    pub context: Option<MediaContext>,
+    /// Only items that are, or aren't, shown in the galleries.
+    pub in_gallery: Option<bool>,
+    /// Zero-based.
+    pub page: u32,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MediaPage {
+    pub items: Vec<MediaItem>,
+    /// Items matching the query across all pages.
+    pub total: u32,
+    pub page: u32,
+    pub per_page: u32,
+}
+
+impl MediaPage {
+    pub fn page_count(&self) -> u32 {
+        self.total.div_ceil(self.per_page.max(1)).max(1)
+    }
+}
+
+/// Encoding of a resized copy of an uploaded image.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum VariantFormat {
+    Webp,
+    Jpeg,
+}
+
+impl VariantFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            VariantFormat::Webp => "webp",
+            VariantFormat::Jpeg => "jpg",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            VariantFormat::Webp => "image/webp",
+            VariantFormat::Jpeg => "image/jpeg",
+        }
+    }
+}
+
+/// A resized copy of an image, as stored in `media_items.variants`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ImageVariant {
+    pub width: u32,
+    pub format: VariantFormat,
+    pub url: String,
+}
+
+/// `srcset` value listing the variants in `format`, narrowest first.
+pub fn variant_srcset(variants: &[ImageVariant], format: VariantFormat) -> String {
+    let mut matching: Vec<&ImageVariant> = variants.iter().filter(|v| v.format == format).collect();
+    matching.sort_by_key(|v| v.width);
+    matching
+        .iter()
+        .map(|v| format!("{} {}w", v.url, v.width))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(feature = "ssr")]
+use crate::api::audit::AuditAction;
+
+/// Adds an upload or deletion to the audit log. The object has already changed by then, so
+/// a failure here is reported but can't be rolled back.
+#[cfg(feature = "ssr")]
+async fn record_media_event(
+    action: AuditAction,
+    actor: &str,
+    object_name: &str,
+    before: Option<String>,
+    after: Option<String>,
+) -> Result<(), ServerFnError> {
+    use crate::api::audit::ssr as audit;
+    let pool = use_context::<sqlx::SqlitePool>()
+        .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+    let client_ip = audit::request_client_ip();
+    audit::record_event(
+        &pool,
+        action,
+        audit::NewAuditEvent {
+            actor: Some(actor),
+            ip: client_ip.as_deref(),
+            target: Some(object_name),
+            before,
+            after,
+        },
+    )
+    .await
+    .map_err(|e| ServerFnError::new(format!("Failed to record audit event: {}", e)))
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use super::{MediaDetails, MediaItem, MediaPage, MediaQuery};
+    use shared::{MediaCategory, MediaContext};
+    use sqlx::sqlite::SqliteRow;
+    use sqlx::{QueryBuilder, Row, Sqlite, SqlitePool};
+
+    const COLUMNS: &str = "id, url, object_name, thumbnail_url, title, description, alt_text, \
+        category, context, show_in_gallery, created_at, taken_at, camera, lens, has_private_metadata, \
+        strip_metadata";
+
+    fn item_from_row(row: &SqliteRow) -> MediaItem {
+        let url: String = row.get("url");
+        let object_name: String = row
+            .get::<Option<String>, _>("object_name")
+            .unwrap_or_default();
+        let name = [object_name.as_str(), url.as_str()]
+            .into_iter()
+            .filter_map(|path| path.rsplit('/').next())
+            .find(|name| !name.is_empty())
+            .unwrap_or_default()
+            .to_string();
+        let has_private: bool = row.get("has_private_metadata");
+        MediaItem {
+            id: row.get("id"),
+            name,
+            object_name,
+            thumbnail_url: row.get("thumbnail_url"),
+            title: row.get("title"),
+            description: row.get("description"),
+            alt_text: row.get("alt_text"),
+            category: row
+                .get::<String, _>("category")
+                .parse()
+                .unwrap_or(MediaCategory::Photography),
+            context: row
+                .get::<String, _>("context")
+                .parse()
+                .unwrap_or(MediaContext::Personal),
+            show_in_gallery: row.get("show_in_gallery"),
+            created_at: row.get("created_at"),
+            taken_at: row.get("taken_at"),
+            camera: row.get("camera"),
+            lens: row.get("lens"),
+            strip_metadata: has_private.then(|| row.get("strip_metadata")),
+            url,
+        }
+    }
+
+    fn non_empty(value: &Option<String>) -> Option<String> {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    }
+
+    fn push_filters(query: &mut QueryBuilder<'_, Sqlite>, filter: &MediaQuery) {
+        query.push(" WHERE 1 = 1");
+        if let Some(search) = non_empty(&filter.search) {
+            query
+                .push(
+                    " AND instr(lower(coalesce(title, '') || ' ' || coalesce(description, '') \
+                     || ' ' || coalesce(alt_text, '') || ' ' || coalesce(object_name, url)), lower(",
+                )
+                .push_bind(search)
+                .push(")) > 0");
+        }
+        if let Some(category) = filter.category {
+            query
+                .push(" AND category = ")
+                .push_bind(category.to_string());
+        }
+        if let Some(context) = filter.context {
+            query.push(" AND context = ").push_bind(context.to_string());
+        }
+        if let Some(in_gallery) = filter.in_gallery {
+            query.push(" AND show_in_gallery = ").push_bind(in_gallery);
+        }
+    }
+
+    /// One page of the items matching `filter`, newest first.
+    pub async fn list_items(
+        pool: &SqlitePool,
+        filter: &MediaQuery,
+        per_page: u32,
+    ) -> Result<MediaPage, sqlx::Error> {
+        let per_page = per_page.max(1);
+        let mut count = QueryBuilder::new("SELECT COUNT(*) FROM media_items");
+        push_filters(&mut count, filter);
+        let total: i64 = count.build_query_scalar().fetch_one(pool).await?;
+
+        let mut query = QueryBuilder::new(format!("SELECT {} FROM media_items", COLUMNS));
+        push_filters(&mut query, filter);
+        query
+            .push(" ORDER BY created_at DESC, rowid DESC LIMIT ")
+            .push_bind(i64::from(per_page))
+            .push(" OFFSET ")
+            .push_bind(i64::from(filter.page) * i64::from(per_page));
+        let rows = query.build().fetch_all(pool).await?;
+
+        Ok(MediaPage {
+            items: rows.iter().map(item_from_row).collect(),
+            total: total as u32,
+            page: filter.page,
+            per_page,
+        })
+    }
+
+    pub async fn get_item(
+        pool: &SqlitePool,
+        object_name: &str,
+    ) -> Result<Option<MediaItem>, sqlx::Error> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM media_items WHERE object_name = ?",
+            COLUMNS
+        ))
+        .bind(object_name)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.as_ref().map(item_from_row))
+    }
+
+    /// Saves an item's details, blank text as unset. Returns whether the item exists.
+    pub async fn update_details(
+        pool: &SqlitePool,
+        object_name: &str,
+        details: &MediaDetails,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE media_items SET title = ?, description = ?, alt_text = ?, category = ?, \
+             context = ?, show_in_gallery = ? WHERE object_name = ?",
+        )
+        .bind(non_empty(&details.title))
+        .bind(non_empty(&details.description))
+        .bind(non_empty(&details.alt_text))
+        .bind(details.category.to_string())
+        .bind(details.context.to_string())
+        .bind(details.show_in_gallery)
+        .bind(object_name)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Titles of the articles whose cover or body uses the file at `url` or one of its
+    /// variants.
+    pub async fn articles_using(
+        pool: &SqlitePool,
+        object_name: &str,
+        url: &str,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let row: Option<(String, String)> =
+            sqlx::query_as("SELECT url, variants FROM media_items WHERE object_name = ?")
+                .bind(object_name)
+                .fetch_optional(pool)
+                .await?;
+        let mut urls = vec![url.to_string()];
+        if let Some((recorded_url, variants)) = row {
+            urls.push(recorded_url);
+            let variants: Vec<super::ImageVariant> =
+                serde_json::from_str(&variants).unwrap_or_default();
+            urls.extend(variants.into_iter().map(|v| v.url));
+        }
+        let urls_json = serde_json::to_string(&urls).unwrap_or_else(|_| "[]".to_string());
+        sqlx::query_scalar(
+            "SELECT title FROM articles WHERE EXISTS (\
+                SELECT 1 FROM json_each(?) \
+                WHERE articles.cover_image_url = value OR instr(articles.content, value) > 0\
+             ) ORDER BY title",
+        )
+        .bind(urls_json)
+        .fetch_all(pool)
+        .await
+    }
+}
+
+/// One page of the media library.
 #[server(ListMedia, "/api")]
-pub async fn list_media(token: String) -> Result<Vec<MediaItem>, ServerFnError> {
+pub async fn list_media(query: MediaQuery) -> Result<MediaPage, ServerFnError> {
     #[cfg(feature = "ssr")]
     {
-        use crate::api::auth::ssr_utils::verify_token;
-        verify_token(&token)?;
-        use google_cloud_storage::client::{Client, ClientConfig};
-        use google_cloud_storage::http::objects::list::ListObjectsRequest;
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Contributor).await?;
 
-        let config = ClientConfig::default()
-            .with_auth()
+        let pool = use_context::<sqlx::SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+        ssr::list_items(&pool, &query, MEDIA_PAGE_SIZE)
             .await
-            .map_err(|e| ServerFnError::new(format!("Failed to load GCS auth config: {}", e)))?;
-        let client = Client::new(config);
+            .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))
+    }
 
-        let request = ListObjectsRequest {
-            bucket: "jakewray-portfolio".to_string(),
-            prefix: Some("media/journalism/".to_string()),
-            ..Default::default()
-        };
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = query;
+        Ok(MediaPage::default())
+    }
+}
 
-        let response = client
-            .list_objects(&request)
+/// Saves the title, description, alt text, category and context of a media item, and
+/// whether it's shown in the galleries, which only an editor may change.
+#[server(UpdateMedia, "/api")]
+pub async fn update_media(
+    object_name: String,
+    details: MediaDetails,
+) -> Result<MediaItem, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::{check_can_show_in_gallery, require_role};
+        use shared::auth::Role;
+        let claims = require_role(Role::Contributor).await?;
+
+        let pool = use_context::<sqlx::SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+        let db_error = |e: sqlx::Error| ServerFnError::new(format!("Database query failed: {}", e));
+        let before = ssr::get_item(&pool, &object_name)
             .await
-            .map_err(|e| ServerFnError::new(format!("GCS list objects failed: {}", e)))?;
-
-        let mut items = Vec::new();
-        let base_url = "https://storage.googleapis.com/jakewray-portfolio";
-
-        if let Some(objects) = response.items {
-            for object in objects {
-                let name = object
-                    .name
-                    .split('/')
-                    .next_back()
-                    .unwrap_or(&object.name)
-                    .to_string();
-                if name.is_empty() {
-                    continue; // Skip directory placeholders
-                }
-                items.push(MediaItem {
-                    url: format!("{}/{}", base_url, object.name),
-                    name,
-                });
-            }
-        }
+            .map_err(db_error)?
+            .ok_or_else(|| ServerFnError::new("Media item not found"))?;
+        check_can_show_in_gallery(claims.role, before.show_in_gallery, details.show_in_gallery)?;
+        ssr::update_details(&pool, &object_name, &details)
+            .await
+            .map_err(db_error)?;
+        let after = ssr::get_item(&pool, &object_name)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| ServerFnError::new("Media item not found"))?;
 
-        Ok(items)
+        record_media_event(
+            AuditAction::MediaUpdate,
+            &claims.sub,
+            &object_name,
+            Some(MediaDetails::from(&before).to_string()),
+            Some(MediaDetails::from(&after).to_string()),
+        )
+        .await?;
+        Ok(after)
     }
 
     #[cfg(not(feature = "ssr"))]
-    Ok(Vec::new())
+    {
+        let _ = (object_name, details);
+        Err(ServerFnError::new(
+            "The media library is only available on the server",
+        ))
+    }
+}
+
+/// Content type to store a media file with, from its extension.
+pub fn content_type_for(filename: &str) -> &'static str {
+    let ext = filename.split('.').next_back().unwrap_or("").to_lowercase();
+    match ext.as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "mp3" => "audio/mpeg",
+        "m4a" => "audio/mp4",
+        "ogg" => "audio/ogg",
+        "wav" => "audio/wav",
+        _ => "application/octet-stream",
+    }
 }
 
 #[server(UploadMedia, "/api")]
-pub async fn upload_media(
-    token: String,
-    filename: String,
-    data: Vec<u8>,
-) -> Result<String, ServerFnError> {
+pub async fn upload_media(filename: String, data: Vec<u8>) -> Result<String, ServerFnError> {
     #[cfg(feature = "ssr")]
     {
-        use crate::api::auth::ssr_utils::verify_token;
-        verify_token(&token)?;
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        let claims = require_role(Role::Contributor).await?;
 
         // We'll upload to a 'uploads' folder for manual picking or sorting later
         let filtered_name: String = filename
@@ -84,51 +488,31 @@ pub async fn upload_media(
 
         let timestamp = chrono::Utc::now().timestamp();
         let safe_name = format!("{}_{}", timestamp, filtered_name);
-        use google_cloud_storage::client::{Client, ClientConfig};
-        use google_cloud_storage::http::objects::upload::{Media, UploadObjectRequest, UploadType};
 
-        let config = ClientConfig::default()
-            .with_auth()
-            .await
-            .map_err(|e| ServerFnError::new(format!("Failed to load GCS auth config: {}", e)))?;
-        let client = Client::new(config);
-
-        let ext = filtered_name
-            .split('.')
-            .next_back()
-            .unwrap_or("")
-            .to_lowercase();
-        let content_type = match ext.as_str() {
-            "jpg" | "jpeg" => "image/jpeg",
-            "png" => "image/png",
-            "gif" => "image/gif",
-            "webp" => "image/webp",
-            "svg" => "image/svg+xml",
-            "mp4" => "video/mp4",
-            _ => "application/octet-stream",
-        };
+        let content_type = content_type_for(&filtered_name);
 
-        let upload_type = UploadType::Simple(Media {
-            name: format!("media/journalism/uploads/{}", safe_name).into(),
-            content_length: Some(data.len() as u64),
-            content_type: content_type.to_string().into(),
-        });
+        let store = crate::api::storage::get_media_store();
+        let object_name = format!("{}uploads/{}", store.prefix(), safe_name);
 
-        let request = UploadObjectRequest {
-            bucket: "jakewray-portfolio".to_string(),
-            ..Default::default()
-        };
-
-        // GCS upload_object takes &UploadObjectRequest, Body (Vec<u8>), and &UploadType
-        client
-            .upload_object(&request, data, &upload_type)
+        let pool = use_context::<sqlx::SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+        let size = data.len();
+        let url = crate::api::images::store_image(&pool, store, &object_name, data)
             .await
-            .map_err(|e| ServerFnError::new(format!("GCS upload failed: {}", e)))?;
+            .map_err(ServerFnError::new)?;
 
-        Ok(format!(
-            "https://storage.googleapis.com/jakewray-portfolio/media/journalism/uploads/{}",
-            safe_name
-        ))
+        record_media_event(
+            AuditAction::MediaUpload,
+            &claims.sub,
+            &object_name,
+            None,
+            Some(format!(
+                "{}, {} bytes, served at {}",
+                content_type, size, url
+            )),
+        )
+        .await?;
+        Ok(url)
     }
 
     #[cfg(not(feature = "ssr"))]
@@ -136,39 +520,235 @@ pub async fn upload_media(
 }
 
 #[server(DeleteMedia, "/api")]
-pub async fn delete_media(token: String, object_name: String) -> Result<(), ServerFnError> {
+pub async fn delete_media(object_name: String) -> Result<(), ServerFnError> {
     #[cfg(feature = "ssr")]
     {
-        use crate::api::auth::ssr_utils::verify_token;
-        verify_token(&token)?;
-        use google_cloud_storage::client::{Client, ClientConfig};
-        use google_cloud_storage::http::objects::delete::DeleteObjectRequest;
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        let claims = require_role(Role::Admin).await?;
 
-        let config = ClientConfig::default()
-            .with_auth()
-            .await
-            .map_err(|e| ServerFnError::new(format!("Failed to load GCS auth config: {}", e)))?;
-        let client = Client::new(config);
+        let store = crate::api::storage::get_media_store();
+
+        // Accept either a bare object name or a public URL from this store
+        let object_name = store
+            .object_name_from_url(&object_name)
+            .unwrap_or(object_name);
 
         // Safety check to prevent deleting things outside of the public uploads directory
-        if !object_name.starts_with("media/journalism/") {
-            return Err(ServerFnError::new("Unauthorized directory access"));
+        store
+            .check_object_name(&object_name)
+            .map_err(ServerFnError::new)?;
+
+        let pool = use_context::<sqlx::SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+        let in_use = ssr::articles_using(&pool, &object_name, &store.public_url(&object_name))
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))?;
+        if !in_use.is_empty() {
+            return Err(ServerFnError::new(format!(
+                "{} is still used by: {}",
+                object_name.rsplit('/').next().unwrap_or(&object_name),
+                in_use.join(", ")
+            )));
         }
+        crate::api::images::delete_image(&pool, store, &object_name)
+            .await
+            .map_err(ServerFnError::new)?;
 
-        let request = DeleteObjectRequest {
-            bucket: "jakewray-portfolio".to_string(),
-            object: object_name,
-            ..Default::default()
-        };
+        record_media_event(
+            AuditAction::MediaDelete,
+            &claims.sub,
+            &object_name,
+            Some(format!("served at {}", store.public_url(&object_name))),
+            None,
+        )
+        .await
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    Ok(())
+}
 
-        client
-            .delete_object(&request)
+/// Strips an image's GPS and serial-number tags from its public file, or publishes them.
+#[server(SetMetadataStripping, "/api")]
+pub async fn set_metadata_stripping(object_name: String, strip: bool) -> Result<(), ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        let claims = require_role(Role::Editor).await?;
+
+        let pool = use_context::<sqlx::SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+        let store = crate::api::storage::get_media_store();
+        store
+            .check_object_name(&object_name)
+            .map_err(ServerFnError::new)?;
+        crate::api::images::set_metadata_stripping(&pool, store, &object_name, strip)
             .await
-            .map_err(|e| ServerFnError::new(format!("GCS delete failed: {}", e)))?;
+            .map_err(ServerFnError::new)?;
 
-        Ok(())
+        let describe = |stripped: bool| {
+            if stripped {
+                "location and serial numbers stripped"
+            } else {
+                "location and serial numbers public"
+            }
+            .to_string()
+        };
+        record_media_event(
+            AuditAction::MediaUpdate,
+            &claims.sub,
+            &object_name,
+            Some(describe(!strip)),
+            Some(describe(strip)),
+        )
+        .await
     }
 
     #[cfg(not(feature = "ssr"))]
-    Ok(())
+    {
+        let _ = (object_name, strip);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_count() {
+        let page = |total| MediaPage {
+            total,
+            per_page: 40,
+            ..Default::default()
+        };
+        assert_eq!(page(0).page_count(), 1);
+        assert_eq!(page(40).page_count(), 1);
+        assert_eq!(page(41).page_count(), 2);
+    }
+
+    #[cfg(feature = "ssr")]
+    #[tokio::test]
+    async fn test_list_update_and_references() {
+        use super::ssr::*;
+
+        let pool = crate::api::test_pool().await;
+        for (name, category) in [
+            ("harbour.jpg", "photography"),
+            ("council.jpg", "photography"),
+            ("interview.mp4", "video"),
+        ] {
+            sqlx::query(
+                "INSERT INTO media_items (title, url, object_name, category) VALUES (?, ?, ?, ?)",
+            )
+            .bind(name)
+            .bind(format!("/media/uploads/{}", name))
+            .bind(format!("media/uploads/{}", name))
+            .bind(category)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let first = list_items(&pool, &MediaQuery::default(), 2).await.unwrap();
+        assert_eq!((first.total, first.items.len()), (3, 2));
+        assert_eq!(first.page_count(), 2);
+        // Newest first
+        assert_eq!(first.items[0].name, "interview.mp4");
+        assert!(first.items[0].is_video());
+        let second = list_items(
+            &pool,
+            &MediaQuery {
+                page: 1,
+                ..Default::default()
+            },
+            2,
+        )
+        .await
+        .unwrap();
+        assert_eq!(second.items.len(), 1);
+
+        let updated = update_details(
+            &pool,
+            "media/uploads/council.jpg",
+            &MediaDetails {
+                title: Some("Council chambers".to_string()),
+                description: Some("  ".to_string()),
+                alt_text: Some("Councillors seated around a horseshoe table".to_string()),
+                category: MediaCategory::Photography,
+                context: MediaContext::Professional,
+                show_in_gallery: true,
+            },
+        )
+        .await
+        .unwrap();
+        assert!(updated);
+        let item = get_item(&pool, "media/uploads/council.jpg")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(item.display_name(), "Council chambers");
+        assert_eq!(item.description, None);
+        assert_eq!(item.context, MediaContext::Professional);
+        assert!(item.show_in_gallery);
+        assert!(!update_details(
+            &pool,
+            "media/uploads/missing.jpg",
+            &MediaDetails::from(&item)
+        )
+        .await
+        .unwrap());
+
+        // Search covers alt text; filters combine
+        let search = |search: &str, category| MediaQuery {
+            search: Some(search.to_string()),
+            category,
+            ..Default::default()
+        };
+        let found = list_items(&pool, &search("HORSESHOE", None), 40)
+            .await
+            .unwrap();
+        assert_eq!(found.items.len(), 1);
+        assert_eq!(found.items[0].object_name, "media/uploads/council.jpg");
+        let found = list_items(&pool, &search(".jpg", Some(MediaCategory::Video)), 40)
+            .await
+            .unwrap();
+        assert_eq!(found.total, 0);
+        let professional = MediaQuery {
+            context: Some(MediaContext::Professional),
+            ..Default::default()
+        };
+        assert_eq!(list_items(&pool, &professional, 40).await.unwrap().total, 1);
+        let hidden = MediaQuery {
+            in_gallery: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(list_items(&pool, &hidden, 40).await.unwrap().total, 2);
+
+        sqlx::query(
+            "INSERT INTO articles (slug, title, content, author, origin) \
+             VALUES ('harbour', 'Harbour at dawn', '<img src=\"/media/uploads/harbour.jpg\">', 'Jake Wray', 'local')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let using = articles_using(
+            &pool,
+            "media/uploads/harbour.jpg",
+            "/media/uploads/harbour.jpg",
+        )
+        .await
+        .unwrap();
+        assert_eq!(using, vec!["Harbour at dawn".to_string()]);
+        assert!(articles_using(
+            &pool,
+            "media/uploads/council.jpg",
+            "/media/uploads/council.jpg"
+        )
+        .await
+        .unwrap()
+        .is_empty());
+    }
 }
@@ -1,8 +1,64 @@
+pub mod archive;
 pub mod articles;
+pub mod audit;
 pub mod auth;
+pub mod blog;
+pub mod creative;
+#[cfg(feature = "ssr")]
+pub mod exif;
+pub mod gallery;
+#[cfg(feature = "ssr")]
+pub mod github;
+#[cfg(feature = "ssr")]
+pub mod images;
+#[cfg(feature = "ssr")]
+pub mod importer;
 pub mod media;
+pub mod music;
 pub mod pages;
+pub mod projects;
+pub mod revisions;
+pub mod search;
+pub mod sessions;
+#[cfg(feature = "ssr")]
+pub mod storage;
+
+/// A fresh in-memory database with every migration applied. One connection, so the
+/// whole test sees the same database. The backend's tests reach it through the
+/// `test-support` feature.
+#[cfg(all(feature = "ssr", any(test, feature = "test-support")))]
+pub async fn test_pool() -> sqlx::SqlitePool {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    sqlx::migrate!("../migrations").run(&pool).await.unwrap();
+    pool
+}
 
 pub use articles::*;
+pub use audit::{list_audit_events, AuditAction, AuditEvent, AuditFilter, ListAuditEvents};
+pub use blog::{
+    delete_blog_post, get_blog_drafts_and_scheduled, get_blog_post, get_blog_post_for_edit,
+    get_blog_posts, parse_tags, save_blog_post, BlogPost, DeleteBlogPost,
+    GetBlogDraftsAndScheduled, GetBlogPost, GetBlogPostForEdit, GetBlogPosts, SaveBlogPost,
+};
+pub use creative::{
+    decode_chapters, delete_creative_work, encode_chapters, get_creative_drafts_and_scheduled,
+    get_creative_work, get_creative_works, save_creative_work, Chapter, CreativeType, CreativeWork,
+    DeleteCreativeWork, GetCreativeDraftsAndScheduled, GetCreativeWork, GetCreativeWorks,
+    SaveCreativeWork,
+};
 pub use media::*;
 pub use pages::*;
+pub use projects::{
+    delete_project, get_projects, save_project, sort_projects, sync_github_projects, DeleteProject,
+    GetProjects, Project, SaveProject, SyncGithubProjects,
+};
+pub use revisions::{
+    diff_revisions, list_revisions, restore_revision, DiffRevisions, ListRevisions,
+    RestoreRevision, RevisionKind, RevisionSummary,
+};
+pub use search::{build_match_query, render_snippet, search_content, SearchContent, SearchResult};
+pub use sessions::{list_sessions, revoke_session, ListSessions, RevokeSession, SessionInfo};
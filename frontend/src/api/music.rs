@@ -0,0 +1,384 @@
+//! Music tracks, played from a self-hosted audio file, a third-party embed, or both.
+
+use crate::utils::embed::{parse_embed, Embed};
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MusicTrack {
+    /// Empty for tracks that haven't been saved yet.
+    #[serde(default)]
+    pub id: String,
+    /// Made from the title when left empty.
+    #[serde(default)]
+    pub slug: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// A file on this site or an https URL, played with the browser's audio player.
+    #[serde(default)]
+    pub audio_url: Option<String>,
+    /// Embed code or share link from an allowed provider; saved as the rebuilt player URL.
+    #[serde(default)]
+    pub embed_code: Option<String>,
+    /// Unset for drafts. Saving an empty value publishes the track now.
+    #[serde(default)]
+    pub published_at: Option<String>,
+    /// `published_at` as "May 1, 2024".
+    #[serde(default)]
+    pub published_date: Option<String>,
+}
+
+impl MusicTrack {
+    pub fn embed(&self) -> Option<Embed> {
+        self.embed_code.as_deref().and_then(parse_embed)
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use super::MusicTrack;
+    use crate::utils::embed::{is_media_url, parse_embed, supported_providers};
+    use sqlx::sqlite::SqliteRow;
+    use sqlx::{Row, SqlitePool};
+
+    const COLUMNS: &str = "id, slug, title, description, audio_url, embed_code, published_at";
+    const PUBLISHED: &str =
+        "published_at IS NOT NULL AND published_at <= strftime('%Y-%m-%dT%H:%M:%fZ', 'now')";
+
+    fn track_from_row(row: &SqliteRow) -> MusicTrack {
+        let published_at: Option<String> = row.get("published_at");
+        MusicTrack {
+            id: row.get("id"),
+            slug: row.get("slug"),
+            title: row.get("title"),
+            description: row.get("description"),
+            audio_url: row.get("audio_url"),
+            embed_code: row.get("embed_code"),
+            published_date: published_at
+                .as_deref()
+                .and_then(crate::api::gallery::ssr::format_capture_date),
+            published_at,
+        }
+    }
+
+    /// Newest first; drafts and tracks dated in the future only with `include_drafts`.
+    pub async fn list_tracks(
+        pool: &SqlitePool,
+        include_drafts: bool,
+    ) -> Result<Vec<MusicTrack>, sqlx::Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM music_tracks WHERE ? OR ({}) \
+             ORDER BY published_at IS NULL DESC, published_at DESC, title",
+            COLUMNS, PUBLISHED
+        ))
+        .bind(include_drafts)
+        .fetch_all(pool)
+        .await?;
+        Ok(rows.iter().map(track_from_row).collect())
+    }
+
+    pub async fn get_track(
+        pool: &SqlitePool,
+        slug: &str,
+        include_drafts: bool,
+    ) -> Result<Option<MusicTrack>, sqlx::Error> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM music_tracks WHERE slug = ? AND (? OR ({}))",
+            COLUMNS, PUBLISHED
+        ))
+        .bind(slug)
+        .bind(include_drafts)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.as_ref().map(track_from_row))
+    }
+
+    /// Creates or updates a track after checking its audio URL and embed code.
+    pub async fn save_track(pool: &SqlitePool, track: &MusicTrack) -> Result<MusicTrack, String> {
+        let title = track.title.trim();
+        if title.is_empty() {
+            return Err("Track title cannot be empty".to_string());
+        }
+        let slug_source = if track.slug.trim().is_empty() {
+            title
+        } else {
+            track.slug.as_str()
+        };
+        let slug = crate::utils::slug::sanitize_page_slug(slug_source)
+            .trim_matches('-')
+            .to_string();
+        if slug.is_empty() {
+            return Err("Track slug cannot be empty".to_string());
+        }
+        let non_empty = |v: &Option<String>| {
+            v.as_deref()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+        let audio_url = non_empty(&track.audio_url);
+        if audio_url.as_deref().is_some_and(|url| !is_media_url(url)) {
+            return Err("Audio URL must be a path on this site or an https:// URL".to_string());
+        }
+        let embed_src = match non_empty(&track.embed_code) {
+            Some(code) => Some(
+                parse_embed(&code)
+                    .ok_or_else(|| {
+                        format!(
+                            "Embed code isn't from a supported provider ({})",
+                            supported_providers()
+                        )
+                    })?
+                    .src,
+            ),
+            None => None,
+        };
+        let id = if track.id.is_empty() {
+            uuid::Uuid::new_v4().to_string()
+        } else {
+            track.id.clone()
+        };
+
+        sqlx::query(
+            "INSERT INTO music_tracks (id, slug, title, description, audio_url, embed_code, published_at) \
+             VALUES (?, ?, ?, ?, ?, ?, CASE WHEN ? THEN COALESCE(?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now')) END) \
+             ON CONFLICT(id) DO UPDATE SET \
+                slug = excluded.slug, \
+                title = excluded.title, \
+                description = excluded.description, \
+                audio_url = excluded.audio_url, \
+                embed_code = excluded.embed_code, \
+                published_at = excluded.published_at",
+        )
+        .bind(&id)
+        .bind(&slug)
+        .bind(title)
+        .bind(non_empty(&track.description))
+        .bind(&audio_url)
+        .bind(&embed_src)
+        .bind(track.published_at.is_some())
+        .bind(non_empty(&track.published_at))
+        .execute(pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                format!("Another track already uses the slug \"{}\"", slug)
+            }
+            e => format!("Database insert/update failed: {}", e),
+        })?;
+
+        get_track(pool, &slug, true)
+            .await
+            .map_err(|e| format!("Database query failed: {}", e))?
+            .ok_or_else(|| "Saved track not found".to_string())
+    }
+
+    pub async fn delete_track(pool: &SqlitePool, id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM music_tracks WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Published tracks, newest first.
+#[server(GetTracks, "/api")]
+pub async fn get_tracks() -> Result<Vec<MusicTrack>, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        let pool = use_context::<sqlx::SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+        ssr::list_tracks(&pool, false)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    Ok(Vec::new())
+}
+
+#[server(GetTrack, "/api")]
+pub async fn get_track(slug: String) -> Result<Option<MusicTrack>, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        let pool = use_context::<sqlx::SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+        ssr::get_track(&pool, &slug, false)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = slug;
+        Ok(None)
+    }
+}
+
+/// Every track, drafts first, for the track editor.
+#[server(GetAllTracks, "/api")]
+pub async fn get_all_tracks() -> Result<Vec<MusicTrack>, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Editor).await?;
+
+        let pool = use_context::<sqlx::SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+        ssr::list_tracks(&pool, true)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    Ok(Vec::new())
+}
+
+#[server(SaveTrack, "/api")]
+pub async fn save_track(track: MusicTrack) -> Result<MusicTrack, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Editor).await?;
+
+        let pool = use_context::<sqlx::SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+        ssr::save_track(&pool, &track)
+            .await
+            .map_err(ServerFnError::new)
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = track;
+        Err(ServerFnError::new("Tracks can only be saved on the server"))
+    }
+}
+
+#[server(DeleteTrack, "/api")]
+pub async fn delete_track(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Editor).await?;
+
+        let pool = use_context::<sqlx::SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+        ssr::delete_track(&pool, &id)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database delete failed: {}", e)))
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = id;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "ssr")]
+    #[tokio::test]
+    async fn test_tracks() {
+        use super::ssr::*;
+        use super::MusicTrack;
+        use crate::utils::embed::EmbedProvider;
+
+        let pool = crate::api::test_pool().await;
+
+        let track = |title: &str, published_at: Option<&str>| MusicTrack {
+            title: title.to_string(),
+            published_at: published_at.map(str::to_string),
+            ..Default::default()
+        };
+
+        let first = save_track(
+            &pool,
+            &MusicTrack {
+                audio_url: Some(" /media/uploads/first-light.mp3 ".to_string()),
+                embed_code: Some(
+                    r#"<iframe src="https://w.soundcloud.com/player/?url=https%3A//api.soundcloud.com/tracks/293&amp;color=%23ff5500" onload="alert(1)"></iframe>"#
+                        .to_string(),
+                ),
+                ..track("First Light", Some("2024-05-01T12:00:00.000Z"))
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(first.slug, "first-light");
+        assert_eq!(
+            first.audio_url.as_deref(),
+            Some("/media/uploads/first-light.mp3")
+        );
+        // Only the rebuilt player URL is kept
+        assert_eq!(
+            first.embed_code.as_deref(),
+            Some("https://w.soundcloud.com/player/?url=https%3A//api.soundcloud.com/tracks/293")
+        );
+        assert_eq!(first.embed().unwrap().provider, EmbedProvider::SoundCloud);
+        assert_eq!(first.published_date.as_deref(), Some("May 1, 2024"));
+
+        let now = save_track(&pool, &track("Right Now", Some("")))
+            .await
+            .unwrap();
+        assert!(now.published_at.is_some());
+        save_track(&pool, &track("Demo", None)).await.unwrap();
+        save_track(&pool, &track("Someday", Some("2999-01-01T00:00:00.000Z")))
+            .await
+            .unwrap();
+
+        // Drafts and future tracks stay off the public list
+        let public: Vec<_> = list_tracks(&pool, false)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|t| t.slug)
+            .collect();
+        assert_eq!(public, vec!["right-now", "first-light"]);
+        let all = list_tracks(&pool, true).await.unwrap();
+        assert_eq!(all.len(), 4);
+        assert_eq!(all[0].slug, "demo");
+        assert!(get_track(&pool, "demo", false).await.unwrap().is_none());
+        assert!(get_track(&pool, "demo", true).await.unwrap().is_some());
+
+        let refused = [
+            MusicTrack {
+                embed_code: Some("<script>alert(1)</script>".to_string()),
+                ..track("Bad Embed", None)
+            },
+            MusicTrack {
+                audio_url: Some("javascript:alert(1)".to_string()),
+                ..track("Bad Audio", None)
+            },
+            track("first light", None),
+            track("   ", None),
+        ];
+        for bad in &refused {
+            assert!(save_track(&pool, bad).await.is_err(), "{}", bad.title);
+        }
+
+        // Updating unpublishes when the date is cleared
+        let draft = save_track(
+            &pool,
+            &MusicTrack {
+                published_at: None,
+                ..first.clone()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(draft.id, first.id);
+        assert!(draft.published_at.is_none());
+        delete_track(&pool, &first.id).await.unwrap();
+        assert!(get_track(&pool, "first-light", true)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
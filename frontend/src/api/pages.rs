@@ -62,12 +62,19 @@ pub async fn get_page(slug: String) -> Result<Option<PageContent>, ServerFnError
     Ok(None)
 }
 
+/// Audit log description of a page's state.
+#[cfg(feature = "ssr")]
+fn summarize_page(title: &str, content: &str) -> String {
+    format!("\"{}\" ({} characters)", title, content.chars().count())
+}
+
 #[server(SavePage, "/api")]
-pub async fn save_page(token: String, page: PageContent) -> Result<(), ServerFnError> {
+pub async fn save_page(page: PageContent) -> Result<(), ServerFnError> {
     #[cfg(feature = "ssr")]
     {
-        use crate::api::auth::ssr_utils::verify_token;
-        verify_token(&token)?; // Admin guard
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        let author_sub = require_role(Role::Editor).await?.sub;
 
         use sqlx::SqlitePool;
         let pool = use_context::<SqlitePool>()
@@ -82,8 +89,9 @@ pub async fn save_page(token: String, page: PageContent) -> Result<(), ServerFnE
         }
 
         let mut id = uuid::Uuid::new_v4().to_string();
+        let mut before = None;
 
-        let existing = sqlx::query("SELECT id FROM pages WHERE slug = ?")
+        let existing = sqlx::query("SELECT id, title, content FROM pages WHERE slug = ?")
             .bind(&clean_slug)
             .fetch_optional(&pool)
             .await
@@ -92,8 +100,18 @@ pub async fn save_page(token: String, page: PageContent) -> Result<(), ServerFnE
         if let Some(row) = existing {
             use sqlx::Row;
             id = row.get("id");
+            before = Some(summarize_page(
+                &row.get::<String, _>("title"),
+                &row.get::<String, _>("content"),
+            ));
         }
 
+        use crate::api::revisions::{ssr::record_revision, RevisionKind};
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
         sqlx::query(
             "INSERT INTO pages (id, slug, title, content, updated_at) \
              VALUES (?, ?, ?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now')) \
@@ -106,10 +124,42 @@ pub async fn save_page(token: String, page: PageContent) -> Result<(), ServerFnE
         .bind(&clean_slug)
         .bind(&page.title)
         .bind(&page.content_html)
-        .execute(&pool)
+        .execute(&mut *tx)
         .await
         .map_err(|e| ServerFnError::new(format!("Database insert/update failed: {}", e)))?;
 
+        record_revision(
+            &mut *tx,
+            RevisionKind::Page,
+            &id,
+            &author_sub,
+            &page.title,
+            &page.content_html,
+        )
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to record revision: {}", e)))?;
+
+        use crate::api::audit::{ssr as audit, AuditAction};
+        let client_ip = audit::request_client_ip();
+        audit::record_event(
+            &mut *tx,
+            AuditAction::PageSave,
+            audit::NewAuditEvent {
+                actor: Some(&author_sub),
+                ip: client_ip.as_deref(),
+                target: Some(&clean_slug),
+                before,
+                after: Some(summarize_page(&page.title, &page.content_html)),
+            },
+        )
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to record audit event: {}", e)))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+        crate::api::search::ssr::refresh_index(&pool).await;
+
         Ok(())
     }
 
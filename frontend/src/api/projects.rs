@@ -0,0 +1,214 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct Project {
+    /// Empty for projects that haven't been saved yet.
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub github_url: Option<String>,
+    #[serde(default)]
+    pub demo_url: Option<String>,
+    #[serde(default)]
+    pub technologies: Vec<String>,
+    #[serde(default)]
+    pub stars: i64,
+    #[serde(default)]
+    pub is_featured: bool,
+}
+
+/// Featured projects first, then by stars, then alphabetically.
+pub fn sort_projects(projects: &mut [Project]) {
+    projects.sort_by(|a, b| {
+        b.is_featured
+            .cmp(&a.is_featured)
+            .then(b.stars.cmp(&a.stars))
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr_mappers {
+    use super::Project;
+    use sqlx::Row;
+
+    pub const PROJECT_COLUMNS: &str =
+        "id, name, description, github_url, demo_url, technologies, stars, is_featured";
+
+    pub fn map_project(row: &sqlx::sqlite::SqliteRow) -> Project {
+        let technologies: Option<String> = row.get("technologies");
+        let description: Option<String> = row.get("description");
+        let stars: Option<i64> = row.get("stars");
+        let is_featured: Option<bool> = row.get("is_featured");
+
+        Project {
+            id: row.get("id"),
+            name: row.get("name"),
+            description: description.unwrap_or_default(),
+            github_url: row.get("github_url"),
+            demo_url: row.get("demo_url"),
+            technologies: technologies
+                .and_then(|t| serde_json::from_str::<Vec<String>>(&t).ok())
+                .unwrap_or_default(),
+            stars: stars.unwrap_or(0),
+            is_featured: is_featured.unwrap_or(false),
+        }
+    }
+}
+
+#[server(GetProjects, "/api")]
+pub async fn get_projects() -> Result<Vec<Project>, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use sqlx::SqlitePool;
+        use ssr_mappers::{map_project, PROJECT_COLUMNS};
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        let rows = sqlx::query(&format!("SELECT {} FROM projects", PROJECT_COLUMNS))
+            .fetch_all(&pool)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))?;
+
+        let mut projects: Vec<Project> = rows.iter().map(map_project).collect();
+        sort_projects(&mut projects);
+        Ok(projects)
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    Ok(Vec::new())
+}
+
+#[server(SaveProject, "/api")]
+pub async fn save_project(project: Project) -> Result<(), ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Editor).await?;
+
+        use sqlx::SqlitePool;
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        if project.name.trim().is_empty() {
+            return Err(ServerFnError::new("Project name cannot be empty"));
+        }
+
+        let id = if project.id.is_empty() {
+            uuid::Uuid::new_v4().to_string()
+        } else {
+            project.id.clone()
+        };
+        let technologies = serde_json::to_string(&project.technologies)
+            .map_err(|e| ServerFnError::new(format!("Invalid technologies: {}", e)))?;
+        let non_empty = |v: Option<String>| v.filter(|s| !s.trim().is_empty());
+
+        // Stars are left to the GitHub sync, so saving can't overwrite what it fetched
+        sqlx::query(
+            "INSERT INTO projects (id, name, description, github_url, demo_url, technologies, is_featured, updated_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now')) \
+             ON CONFLICT(id) DO UPDATE SET \
+                name = excluded.name, \
+                description = excluded.description, \
+                github_url = excluded.github_url, \
+                demo_url = excluded.demo_url, \
+                technologies = excluded.technologies, \
+                is_featured = excluded.is_featured, \
+                updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+        )
+        .bind(&id)
+        .bind(project.name.trim())
+        .bind(&project.description)
+        .bind(non_empty(project.github_url))
+        .bind(non_empty(project.demo_url))
+        .bind(&technologies)
+        .bind(project.is_featured)
+        .execute(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database insert/update failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    Ok(())
+}
+
+#[server(DeleteProject, "/api")]
+pub async fn delete_project(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Editor).await?;
+
+        use sqlx::SqlitePool;
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        sqlx::query("DELETE FROM projects WHERE id = ?")
+            .bind(&id)
+            .execute(&pool)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database delete failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    Ok(())
+}
+
+/// Runs the GitHub sync immediately and returns the number of projects refreshed.
+#[server(SyncGithubProjects, "/api")]
+pub async fn sync_github_projects() -> Result<usize, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Editor).await?;
+
+        use sqlx::SqlitePool;
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        crate::api::github::sync_projects(&pool, crate::api::github::get_github_client())
+            .await
+            .map_err(ServerFnError::new)
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    Ok(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(name: &str, stars: i64, is_featured: bool) -> Project {
+        Project {
+            name: name.to_string(),
+            stars,
+            is_featured,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_sort_projects_featured_first() {
+        let mut projects = vec![
+            project("beta", 50, false),
+            project("alpha", 1, true),
+            project("gamma", 50, false),
+            project("delta", 300, false),
+        ];
+        sort_projects(&mut projects);
+        let names: Vec<&str> = projects.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["alpha", "delta", "beta", "gamma"]);
+    }
+}
@@ -0,0 +1,345 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Content types that keep a revision history.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RevisionKind {
+    Article,
+    Page,
+}
+
+impl std::fmt::Display for RevisionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RevisionKind::Article => write!(f, "article"),
+            RevisionKind::Page => write!(f, "page"),
+        }
+    }
+}
+
+impl std::str::FromStr for RevisionKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "article" => Ok(RevisionKind::Article),
+            "page" => Ok(RevisionKind::Page),
+            _ => Err(format!("Unknown revision kind: {}", s)),
+        }
+    }
+}
+
+impl RevisionKind {
+    /// Public URL of the revised item.
+    pub fn public_url(&self, slug: &str) -> String {
+        match self {
+            RevisionKind::Article => format!("/journalism/{}", slug),
+            RevisionKind::Page => format!("/{}", slug),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RevisionSummary {
+    pub id: String,
+    pub author: String,
+    pub created_at: String,
+    pub title: String,
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use super::RevisionKind;
+    use sqlx::Row;
+
+    impl RevisionKind {
+        /// (revisions table, foreign key column, revised table)
+        pub(crate) fn tables(&self) -> (&'static str, &'static str, &'static str) {
+            match self {
+                RevisionKind::Article => ("article_revisions", "article_id", "articles"),
+                RevisionKind::Page => ("page_revisions", "page_id", "pages"),
+            }
+        }
+    }
+
+    /// Appends a snapshot of `title`/`content` to the item's history. Call it with the
+    /// same transaction as the write it records.
+    pub async fn record_revision<'e, E>(
+        executor: E,
+        kind: RevisionKind,
+        target_id: &str,
+        author: &str,
+        title: &str,
+        content: &str,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: sqlx::SqliteExecutor<'e>,
+    {
+        let (table, fk, _) = kind.tables();
+        sqlx::query(&format!(
+            "INSERT INTO {} ({}, author_sub, title, content) VALUES (?, ?, ?, ?)",
+            table, fk
+        ))
+        .bind(target_id)
+        .bind(author)
+        .bind(title)
+        .bind(content)
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    pub(crate) struct StoredRevision {
+        pub target_id: String,
+        pub title: String,
+        pub content: String,
+    }
+
+    pub(crate) async fn load_revision(
+        pool: &sqlx::SqlitePool,
+        kind: RevisionKind,
+        id: &str,
+    ) -> Result<Option<StoredRevision>, sqlx::Error> {
+        let (table, fk, _) = kind.tables();
+        let row = sqlx::query(&format!(
+            "SELECT {} AS target_id, title, content FROM {} WHERE id = ?",
+            fk, table
+        ))
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row.map(|row| StoredRevision {
+            target_id: row.get("target_id"),
+            title: row.get("title"),
+            content: row.get("content"),
+        }))
+    }
+}
+
+#[server(ListRevisions, "/api")]
+pub async fn list_revisions(
+    kind: RevisionKind,
+    slug: String,
+) -> Result<Vec<RevisionSummary>, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Contributor).await?;
+
+        use sqlx::{Row, SqlitePool};
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        let (table, fk, parent) = kind.tables();
+        let rows = sqlx::query(&format!(
+            "SELECT r.id, r.author_sub, r.created_at, r.title FROM {} r JOIN {} p ON p.id = r.{} WHERE p.slug = ? ORDER BY r.created_at DESC, r.rowid DESC",
+            table, parent, fk
+        ))
+        .bind(&slug)
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| RevisionSummary {
+                id: row.get("id"),
+                author: row.get("author_sub"),
+                created_at: row.get("created_at"),
+                title: row.get("title"),
+            })
+            .collect())
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = (kind, slug);
+        Ok(Vec::new())
+    }
+}
+
+/// Returns the `to` revision's HTML with changes since `from` marked with `<ins>`/`<del>`.
+#[server(DiffRevisions, "/api")]
+pub async fn diff_revisions(
+    kind: RevisionKind,
+    from_id: String,
+    to_id: String,
+) -> Result<String, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        require_role(Role::Contributor).await?;
+
+        use sqlx::SqlitePool;
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        let load = |id: String| {
+            let pool = pool.clone();
+            async move {
+                ssr::load_revision(&pool, kind, &id)
+                    .await
+                    .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))?
+                    .ok_or_else(|| ServerFnError::new("Revision not found"))
+            }
+        };
+        let from = load(from_id).await?;
+        let to = load(to_id).await?;
+        if from.target_id != to.target_id {
+            return Err(ServerFnError::new(
+                "Revisions belong to different documents",
+            ));
+        }
+
+        let mut html = String::new();
+        if from.title != to.title {
+            html.push_str(&format!(
+                "<h1>{}</h1>",
+                crate::utils::diff::diff_html(&from.title, &to.title)
+            ));
+        }
+        html.push_str(&crate::utils::diff::diff_html(&from.content, &to.content));
+        Ok(html)
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = (kind, from_id, to_id);
+        Ok(String::new())
+    }
+}
+
+/// Copies a revision's title and content back onto its article or page. The restore is
+/// itself recorded as a new revision so it can be undone the same way.
+#[server(RestoreRevision, "/api")]
+pub async fn restore_revision(
+    kind: RevisionKind,
+    revision_id: String,
+) -> Result<String, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        let author_sub = require_role(Role::Editor).await?.sub;
+
+        use sqlx::{Row, SqlitePool};
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        let revision = ssr::load_revision(&pool, kind, &revision_id)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))?
+            .ok_or_else(|| ServerFnError::new("Revision not found"))?;
+
+        let (_, _, parent) = kind.tables();
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+        let row = sqlx::query(&format!(
+            "UPDATE {} SET title = ?, content = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ? RETURNING slug",
+            parent
+        ))
+        .bind(&revision.title)
+        .bind(&revision.content)
+        .bind(&revision.target_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database update failed: {}", e)))?
+        .ok_or_else(|| ServerFnError::new("The revised item no longer exists"))?;
+
+        ssr::record_revision(
+            &mut *tx,
+            kind,
+            &revision.target_id,
+            &author_sub,
+            &revision.title,
+            &revision.content,
+        )
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to record revision: {}", e)))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database error: {}", e)))?;
+
+        Ok(row.get("slug"))
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = (kind, revision_id);
+        Ok(String::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_revision_kind_roundtrip() {
+        for kind in [RevisionKind::Article, RevisionKind::Page] {
+            assert_eq!(kind.to_string().parse::<RevisionKind>(), Ok(kind));
+        }
+        assert!("blog".parse::<RevisionKind>().is_err());
+        assert_eq!(RevisionKind::Page.public_url("about"), "/about");
+    }
+
+    #[cfg(feature = "ssr")]
+    #[tokio::test]
+    async fn test_record_and_load_revision() {
+        let pool = crate::api::test_pool().await;
+        sqlx::query("INSERT INTO pages (id, slug, title, content) VALUES ('p1', 'colophon', 'Colophon', '')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        ssr::record_revision(
+            &pool,
+            RevisionKind::Page,
+            "p1",
+            "admin",
+            "About",
+            "<p>v1</p>",
+        )
+        .await
+        .unwrap();
+        let id: String = sqlx::query_scalar("SELECT id FROM page_revisions WHERE page_id = 'p1'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+        let stored = ssr::load_revision(&pool, RevisionKind::Page, &id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.target_id, "p1");
+        assert_eq!(stored.title, "About");
+        assert_eq!(stored.content, "<p>v1</p>");
+        assert!(ssr::load_revision(&pool, RevisionKind::Page, "missing")
+            .await
+            .unwrap()
+            .is_none());
+
+        // Revisions belong to a page and go with it
+        assert!(
+            ssr::record_revision(&pool, RevisionKind::Page, "missing", "admin", "Gone", "")
+                .await
+                .is_err()
+        );
+        sqlx::query("DELETE FROM pages WHERE id = 'p1'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(ssr::load_revision(&pool, RevisionKind::Page, &id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
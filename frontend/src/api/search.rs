@@ -0,0 +1,317 @@
+use leptos::prelude::*;
+pub use shared::SearchResult;
+
+/// Default and maximum number of results returned by a search.
+pub const DEFAULT_SEARCH_LIMIT: u32 = 20;
+pub const MAX_SEARCH_LIMIT: u32 = 50;
+
+/// Longest query we accept, in terms; anything beyond this is dropped.
+const MAX_QUERY_TERMS: usize = 16;
+
+/// Turns free-form user input into an FTS5 MATCH expression. Every word becomes a
+/// quoted prefix term (`"terr"*`), so FTS5 operators and punctuation in the input
+/// can't produce syntax errors. Returns `None` when there is nothing to search for.
+pub fn build_match_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .take(MAX_QUERY_TERMS)
+        .map(|t| format!("\"{}\"*", t))
+        .collect();
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+/// Escapes an FTS5 snippet and converts its match markers (`\x02`/`\x03`) into `<mark>` tags.
+pub fn render_snippet(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len() + 16);
+    for c in raw.chars() {
+        match c {
+            '\u{2}' => out.push_str("<mark>"),
+            '\u{3}' => out.push_str("</mark>"),
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// The searchable text of an HTML body: tags become spaces (an unclosed `<` is kept as
+/// text), the entities editors produce are decoded and whitespace is collapsed.
+pub fn index_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(open) = rest.find('<') {
+        let Some(close) = rest[open..].find('>') else {
+            break;
+        };
+        text.push_str(&rest[..open]);
+        text.push(' ');
+        rest = &rest[open + close + 1..];
+    }
+    text.push_str(rest);
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&#8217;", "'")
+        .replace("&quot;", "\"")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Public URL for a search hit.
+pub fn result_url(kind: &str, slug: &str) -> String {
+    match kind {
+        "article" => format!("/journalism/{}", slug),
+        "blog" => format!("/blog/{}", slug),
+        _ => format!("/{}", slug),
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use super::{
+        build_match_query, index_text, render_snippet, result_url, SearchResult, MAX_SEARCH_LIMIT,
+    };
+    use sqlx::{Row, SqlitePool};
+
+    /// Indexes the rows that triggers on `articles`, `blog_posts` and `pages` queued in
+    /// `search_queue` when they were written. Runs after each save and on every scheduler
+    /// pass, which picks up imports and restores; searches only read the index.
+    pub async fn sync_index(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+        let pending: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM search_queue)")
+            .fetch_one(pool)
+            .await?;
+        if !pending {
+            return Ok(());
+        }
+
+        let mut tx = pool.begin().await?;
+        // Claiming the queue takes the write lock first, so a save and a scheduler pass
+        // running together don't index the same rows
+        let queued: Vec<(String, String)> =
+            sqlx::query_as("DELETE FROM search_queue RETURNING kind, slug")
+                .fetch_all(&mut *tx)
+                .await?;
+        for (kind, slug) in queued {
+            let sql = match kind.as_str() {
+                "article" => {
+                    "SELECT title, COALESCE(excerpt, '') AS excerpt, content FROM articles WHERE slug = ?"
+                }
+                "blog" => "SELECT title, '' AS excerpt, content FROM blog_posts WHERE slug = ?",
+                "page" => "SELECT title, '' AS excerpt, content FROM pages WHERE slug = ?",
+                _ => continue,
+            };
+            // Deleted since it was queued
+            let Some(row) = sqlx::query(sql)
+                .bind(&slug)
+                .fetch_optional(&mut *tx)
+                .await?
+            else {
+                continue;
+            };
+            sqlx::query("DELETE FROM search_index WHERE kind = ? AND slug = ?")
+                .bind(&kind)
+                .bind(&slug)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query(
+                "INSERT INTO search_index (kind, slug, title, excerpt, body) VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind(&kind)
+            .bind(&slug)
+            .bind(row.get::<String, _>("title"))
+            .bind(row.get::<String, _>("excerpt"))
+            .bind(index_text(row.get("content")))
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    /// [`sync_index`] for the save paths: a failure is only logged, since the write has
+    /// been committed and the scheduler's next pass retries it.
+    pub async fn refresh_index(pool: &SqlitePool) {
+        if let Err(e) = sync_index(pool).await {
+            tracing::warn!("Search indexing failed: {}", e);
+        }
+    }
+
+    /// Runs a ranked search against `search_index`, optionally restricted to one kind
+    /// (`article`, `blog` or `page`). Drafts and not-yet-published scheduled posts are
+    /// filtered out here rather than in the index so they appear once their time comes.
+    pub async fn run_search(
+        pool: &SqlitePool,
+        query: &str,
+        kind: Option<&str>,
+        limit: u32,
+    ) -> Result<Vec<SearchResult>, sqlx::Error> {
+        let Some(match_query) = build_match_query(query) else {
+            return Ok(Vec::new());
+        };
+
+        // bm25 weights: kind, slug (unindexed), title, excerpt, body
+        let rows = sqlx::query(
+            "SELECT kind, slug, title, \
+                snippet(search_index, -1, char(2), char(3), '…', 12) AS snippet, \
+                bm25(search_index, 0.0, 0.0, 10.0, 4.0, 1.0) AS score \
+             FROM search_index \
+             WHERE search_index MATCH ?1 \
+               AND (?2 IS NULL OR kind = ?2) \
+               AND ( \
+                 (kind = 'article' AND EXISTS (SELECT 1 FROM articles a WHERE a.slug = search_index.slug AND (a.status IS NULL OR a.status = 'published' OR (a.status = 'scheduled' AND a.published_at <= strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))) AND a.status != 'draft')) \
+                 OR (kind = 'blog' AND EXISTS (SELECT 1 FROM blog_posts b WHERE b.slug = search_index.slug AND (b.status IS NULL OR b.status = 'published' OR (b.status = 'scheduled' AND b.published_at <= strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))) AND b.status != 'draft')) \
+                 OR kind = 'page' \
+               ) \
+             ORDER BY score ASC \
+             LIMIT ?3",
+        )
+        .bind(&match_query)
+        .bind(kind)
+        .bind(limit.clamp(1, MAX_SEARCH_LIMIT))
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let kind: String = row.get("kind");
+                let slug: String = row.get("slug");
+                let snippet: String = row.get("snippet");
+                let score: f64 = row.get("score");
+                SearchResult {
+                    url: result_url(&kind, &slug),
+                    kind,
+                    slug,
+                    title: row.get("title"),
+                    snippet: render_snippet(&snippet),
+                    // bm25 is negative with lower = better; flip it so higher ranks first
+                    rank: -score,
+                }
+            })
+            .collect())
+    }
+}
+
+#[server(SearchContent, "/api")]
+pub async fn search_content(
+    query: String,
+    kind: Option<String>,
+) -> Result<Vec<SearchResult>, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use sqlx::SqlitePool;
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        ssr::run_search(&pool, &query, kind.as_deref(), DEFAULT_SEARCH_LIMIT)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Search failed: {}", e)))
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = (query, kind);
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_match_query_quotes_terms() {
+        assert_eq!(
+            build_match_query("Terrace council"),
+            Some("\"Terrace\"* \"council\"*".to_string())
+        );
+        assert_eq!(
+            build_match_query("\"NEAR(a b)\" OR -x*"),
+            Some("\"NEAR\"* \"a\"* \"b\"* \"OR\"* \"x\"*".to_string())
+        );
+        assert_eq!(build_match_query("  !!  "), None);
+    }
+
+    #[test]
+    fn test_render_snippet_escapes_and_marks() {
+        assert_eq!(
+            render_snippet("the \u{2}council\u{3} <script> & co"),
+            "the <mark>council</mark> &lt;script&gt; &amp; co"
+        );
+    }
+
+    #[test]
+    fn test_index_text_strips_tags() {
+        assert_eq!(
+            index_text(
+                "<p>One</p><p>two&nbsp;&amp; <em>three</em></p>\n<img src=\"a.jpg\">four <unclosed"
+            ),
+            "One two & three four <unclosed"
+        );
+    }
+
+    #[cfg(feature = "ssr")]
+    #[tokio::test]
+    async fn test_run_search_ranks_and_hides_drafts() {
+        let pool = crate::api::test_pool().await;
+        for stmt in [
+            "INSERT INTO articles (slug, title, content, author, status) VALUES \
+                ('in-body', 'Road work', '<p>The <strong>council</strong>&nbsp;approved paving</p>', 'Jake Wray', 'published'), \
+                ('in-title', 'Council votes', '<p>A meeting was held</p>', 'Jake Wray', 'published'), \
+                ('draft', 'Council secrets', '<p>Unpublished</p>', 'Jake Wray', 'draft')",
+            "INSERT INTO blog_posts (slug, title, content) VALUES ('notes', 'Notes', '<p>Gravel roads</p>')",
+            "INSERT INTO pages (slug, title, content) VALUES ('reporter', 'Reporter', '<p>Former <b>council</b> reporter</p>')",
+        ] {
+            sqlx::query(stmt).execute(&pool).await.unwrap();
+        }
+        // Searching never writes, so nothing is found until the queue is indexed
+        assert!(ssr::run_search(&pool, "council", None, 10)
+            .await
+            .unwrap()
+            .is_empty());
+        ssr::sync_index(&pool).await.unwrap();
+
+        let results = ssr::run_search(&pool, "counc", Some("article"), 10)
+            .await
+            .unwrap();
+        let slugs: Vec<&str> = results.iter().map(|r| r.slug.as_str()).collect();
+        assert_eq!(slugs, vec!["in-title", "in-body"]);
+        assert_eq!(results[0].url, "/journalism/in-title");
+        assert!(results[1]
+            .snippet
+            .contains("The <mark>council</mark> approved"));
+
+        let all = ssr::run_search(&pool, "council", None, 10).await.unwrap();
+        assert_eq!(all.len(), 3);
+        assert!(ssr::run_search(&pool, "***", None, 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Edits and deletes reach the index through the triggers
+        for stmt in [
+            "UPDATE articles SET content = '<p>Gravel</p>' WHERE slug = 'in-body'",
+            "UPDATE blog_posts SET slug = 'road-notes' WHERE slug = 'notes'",
+            "DELETE FROM pages WHERE slug = 'reporter'",
+        ] {
+            sqlx::query(stmt).execute(&pool).await.unwrap();
+        }
+        ssr::sync_index(&pool).await.unwrap();
+        let council = ssr::run_search(&pool, "council", None, 10).await.unwrap();
+        assert_eq!(council.len(), 1);
+        assert_eq!(council[0].slug, "in-title");
+        let gravel = ssr::run_search(&pool, "gravel", None, 10).await.unwrap();
+        let slugs: Vec<&str> = gravel.iter().map(|r| r.slug.as_str()).collect();
+        assert_eq!(slugs.len(), 2);
+        assert!(slugs.contains(&"in-body") && slugs.contains(&"road-notes"));
+    }
+}
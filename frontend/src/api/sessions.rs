@@ -0,0 +1,200 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SessionInfo {
+    pub jti: String,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: String,
+    pub last_seen_at: String,
+    pub expires_at: String,
+    /// The session the request was made with.
+    pub current: bool,
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use sqlx::SqlitePool;
+
+    pub async fn create_session(
+        pool: &SqlitePool,
+        jti: &str,
+        user_id: &str,
+        ip: Option<&str>,
+        user_agent: Option<&str>,
+        expires_at: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO sessions (jti, user_id, ip, user_agent, expires_at) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(jti)
+        .bind(user_id)
+        .bind(ip)
+        .bind(user_agent)
+        .bind(expires_at)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns whether the session is active, bumping `last_seen_at` if so.
+    pub async fn touch_session(
+        pool: &SqlitePool,
+        jti: &str,
+        user_id: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE sessions SET last_seen_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') \
+             WHERE jti = ? AND user_id = ? AND revoked_at IS NULL \
+             AND expires_at > strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+        )
+        .bind(jti)
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Revokes one of `user_id`'s sessions. Returns false if there was nothing to revoke.
+    pub async fn revoke_session(
+        pool: &SqlitePool,
+        user_id: &str,
+        jti: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE sessions SET revoked_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') \
+             WHERE jti = ? AND user_id = ? AND revoked_at IS NULL",
+        )
+        .bind(jti)
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Revokes every active session of `user_id`, returning how many were revoked.
+    pub async fn revoke_all_sessions(pool: &SqlitePool, user_id: &str) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE sessions SET revoked_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') \
+             WHERE user_id = ? AND revoked_at IS NULL",
+        )
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+/// Active (unexpired, unrevoked) sessions of the signed-in user, most recently used first.
+#[server(ListSessions, "/api")]
+pub async fn list_sessions() -> Result<Vec<SessionInfo>, ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::current_claims;
+        let claims = current_claims().await?;
+        let (user_id, current_jti) = (claims.sub, claims.jti);
+
+        use sqlx::{Row, SqlitePool};
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        let rows = sqlx::query(
+            "SELECT jti, ip, user_agent, created_at, last_seen_at, expires_at FROM sessions \
+             WHERE user_id = ? AND revoked_at IS NULL AND expires_at > strftime('%Y-%m-%dT%H:%M:%fZ', 'now') \
+             ORDER BY last_seen_at DESC",
+        )
+        .bind(&user_id)
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Database query failed: {}", e)))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let jti: String = row.get("jti");
+                SessionInfo {
+                    current: jti == current_jti,
+                    jti,
+                    ip: row.get("ip"),
+                    user_agent: row.get("user_agent"),
+                    created_at: row.get("created_at"),
+                    last_seen_at: row.get("last_seen_at"),
+                    expires_at: row.get("expires_at"),
+                }
+            })
+            .collect())
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    Ok(Vec::new())
+}
+
+#[server(RevokeSession, "/api")]
+pub async fn revoke_session(jti: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "ssr")]
+    {
+        use crate::api::auth::ssr_utils::require_role;
+        use shared::auth::Role;
+        let user_id = require_role(Role::Contributor).await?.sub;
+
+        use sqlx::SqlitePool;
+        let pool = use_context::<SqlitePool>()
+            .ok_or_else(|| ServerFnError::new("SqlitePool not found in Leptos context"))?;
+
+        let revoked = ssr::revoke_session(&pool, &user_id, &jti)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Database update failed: {}", e)))?;
+        if !revoked {
+            return Err(ServerFnError::new("Session not found"));
+        }
+        Ok(())
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = jti;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "ssr")]
+    #[tokio::test]
+    async fn test_session_revocation() {
+        use super::ssr::*;
+
+        let pool = crate::api::test_pool().await;
+        sqlx::query("INSERT INTO users (id, username, password_hash) VALUES ('u1', 'tester', 'x')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        for jti in ["a", "b"] {
+            create_session(
+                &pool,
+                jti,
+                "u1",
+                Some("203.0.113.7"),
+                None,
+                "2999-01-01T00:00:00.000Z",
+            )
+            .await
+            .unwrap();
+        }
+        create_session(&pool, "old", "u1", None, None, "2000-01-01T00:00:00.000Z")
+            .await
+            .unwrap();
+
+        assert!(touch_session(&pool, "a", "u1").await.unwrap());
+        assert!(!touch_session(&pool, "a", "u2").await.unwrap());
+        assert!(!touch_session(&pool, "old", "u1").await.unwrap());
+
+        assert!(revoke_session(&pool, "u1", "a").await.unwrap());
+        assert!(!touch_session(&pool, "a", "u1").await.unwrap());
+        assert!(touch_session(&pool, "b", "u1").await.unwrap());
+
+        revoke_all_sessions(&pool, "u1").await.unwrap();
+        assert!(!touch_session(&pool, "b", "u1").await.unwrap());
+    }
+}
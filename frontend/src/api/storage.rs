@@ -0,0 +1,392 @@
+//! Pluggable media storage used by the media server functions.
+//!
+//! The backend is picked once at startup from `MEDIA_STORE` (`gcs` or `local`).
+//! Object names are identical across backends (e.g. `media/journalism/uploads/x.jpg`),
+//! so switching stores only changes where the bytes live and the public URL prefix.
+
+use async_trait::async_trait;
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+
+pub const DEFAULT_GCS_BUCKET: &str = "jakewray-portfolio";
+pub const DEFAULT_MEDIA_PREFIX: &str = "media/journalism/";
+pub const DEFAULT_LOCAL_DIR: &str = "data/media";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoredObject {
+    pub name: String,
+    pub url: String,
+}
+
+#[async_trait]
+pub trait MediaStore: Send + Sync {
+    /// Lists every object under the store's prefix.
+    async fn list(&self) -> Result<Vec<StoredObject>, String>;
+
+    /// Writes `data` under `name` and returns the public URL.
+    async fn put(&self, name: &str, content_type: &str, data: Vec<u8>) -> Result<String, String>;
+
+    /// Reads the bytes of `name`, e.g. for a backup.
+    async fn get(&self, name: &str) -> Result<Vec<u8>, String>;
+
+    async fn delete(&self, name: &str) -> Result<(), String>;
+
+    /// Prefix every object managed by this store lives under.
+    fn prefix(&self) -> &str;
+
+    fn public_url(&self, name: &str) -> String;
+
+    /// Maps a public URL back to its object name, if it belongs to this store.
+    fn object_name_from_url(&self, url: &str) -> Option<String> {
+        let base = self.public_url("");
+        url.strip_prefix(&base).map(|s| s.to_string())
+    }
+
+    /// Guards against touching objects outside the managed prefix.
+    fn check_object_name(&self, name: &str) -> Result<(), String> {
+        if !name.starts_with(self.prefix()) {
+            return Err("Unauthorized directory access".to_string());
+        }
+        if Path::new(name)
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)))
+        {
+            return Err("Invalid object name".to_string());
+        }
+        Ok(())
+    }
+}
+
+pub struct GcsMediaStore {
+    bucket: String,
+    prefix: String,
+}
+
+impl GcsMediaStore {
+    pub fn new(bucket: impl Into<String>, prefix: impl Into<String>) -> Self {
+        Self {
+            bucket: bucket.into(),
+            prefix: prefix.into(),
+        }
+    }
+
+    async fn client(&self) -> Result<google_cloud_storage::client::Client, String> {
+        use google_cloud_storage::client::{Client, ClientConfig};
+
+        let config = ClientConfig::default()
+            .with_auth()
+            .await
+            .map_err(|e| format!("Failed to load GCS auth config: {}", e))?;
+        Ok(Client::new(config))
+    }
+}
+
+#[async_trait]
+impl MediaStore for GcsMediaStore {
+    async fn list(&self) -> Result<Vec<StoredObject>, String> {
+        use google_cloud_storage::http::objects::list::ListObjectsRequest;
+
+        let client = self.client().await?;
+        let request = ListObjectsRequest {
+            bucket: self.bucket.clone(),
+            prefix: Some(self.prefix.clone()),
+            ..Default::default()
+        };
+
+        let response = client
+            .list_objects(&request)
+            .await
+            .map_err(|e| format!("GCS list objects failed: {}", e))?;
+
+        Ok(response
+            .items
+            .unwrap_or_default()
+            .into_iter()
+            .map(|object| StoredObject {
+                url: self.public_url(&object.name),
+                name: object.name,
+            })
+            .collect())
+    }
+
+    async fn put(&self, name: &str, content_type: &str, data: Vec<u8>) -> Result<String, String> {
+        use google_cloud_storage::http::objects::upload::{Media, UploadObjectRequest, UploadType};
+
+        self.check_object_name(name)?;
+        let client = self.client().await?;
+
+        let upload_type = UploadType::Simple(Media {
+            name: name.to_string().into(),
+            content_length: Some(data.len() as u64),
+            content_type: content_type.to_string().into(),
+        });
+
+        let request = UploadObjectRequest {
+            bucket: self.bucket.clone(),
+            ..Default::default()
+        };
+
+        // GCS upload_object takes &UploadObjectRequest, Body (Vec<u8>), and &UploadType
+        client
+            .upload_object(&request, data, &upload_type)
+            .await
+            .map_err(|e| format!("GCS upload failed: {}", e))?;
+
+        Ok(self.public_url(name))
+    }
+
+    async fn get(&self, name: &str) -> Result<Vec<u8>, String> {
+        use google_cloud_storage::http::objects::download::Range;
+        use google_cloud_storage::http::objects::get::GetObjectRequest;
+
+        self.check_object_name(name)?;
+        let client = self.client().await?;
+
+        let request = GetObjectRequest {
+            bucket: self.bucket.clone(),
+            object: name.to_string(),
+            ..Default::default()
+        };
+
+        client
+            .download_object(&request, &Range::default())
+            .await
+            .map_err(|e| format!("GCS download failed: {}", e))
+    }
+
+    async fn delete(&self, name: &str) -> Result<(), String> {
+        use google_cloud_storage::http::objects::delete::DeleteObjectRequest;
+
+        self.check_object_name(name)?;
+        let client = self.client().await?;
+
+        let request = DeleteObjectRequest {
+            bucket: self.bucket.clone(),
+            object: name.to_string(),
+            ..Default::default()
+        };
+
+        client
+            .delete_object(&request)
+            .await
+            .map_err(|e| format!("GCS delete failed: {}", e))
+    }
+
+    fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    fn public_url(&self, name: &str) -> String {
+        format!("https://storage.googleapis.com/{}/{}", self.bucket, name)
+    }
+}
+
+/// Stores media on the local filesystem. Files are written to `root/<object name>`
+/// and served by the backend's static file handler under `/<object name>`.
+pub struct LocalMediaStore {
+    root: PathBuf,
+    prefix: String,
+}
+
+impl LocalMediaStore {
+    pub fn new(root: impl Into<PathBuf>, prefix: impl Into<String>) -> Self {
+        Self {
+            root: root.into(),
+            prefix: prefix.into(),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+#[async_trait]
+impl MediaStore for LocalMediaStore {
+    async fn list(&self) -> Result<Vec<StoredObject>, String> {
+        let root = self.root.clone();
+        let start = root.join(&self.prefix);
+        if !start.exists() {
+            return Ok(Vec::new());
+        }
+
+        let names = tokio::task::spawn_blocking(move || {
+            walkdir::WalkDir::new(&start)
+                .sort_by_file_name()
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file())
+                .filter_map(|e| {
+                    let rel = e.path().strip_prefix(&root).ok()?;
+                    let parts: Vec<String> = rel
+                        .components()
+                        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                        .collect();
+                    Some(parts.join("/"))
+                })
+                .collect::<Vec<_>>()
+        })
+        .await
+        .map_err(|e| format!("Local media listing failed: {}", e))?;
+
+        Ok(names
+            .into_iter()
+            .map(|name| StoredObject {
+                url: self.public_url(&name),
+                name,
+            })
+            .collect())
+    }
+
+    async fn put(&self, name: &str, _content_type: &str, data: Vec<u8>) -> Result<String, String> {
+        self.check_object_name(name)?;
+        let path = self.root.join(name);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| format!("Failed to create media directory: {}", e))?;
+        }
+        tokio::fs::write(&path, data)
+            .await
+            .map_err(|e| format!("Local media write failed: {}", e))?;
+        Ok(self.public_url(name))
+    }
+
+    async fn get(&self, name: &str) -> Result<Vec<u8>, String> {
+        self.check_object_name(name)?;
+        tokio::fs::read(self.root.join(name))
+            .await
+            .map_err(|e| format!("Local media read failed: {}", e))
+    }
+
+    async fn delete(&self, name: &str) -> Result<(), String> {
+        self.check_object_name(name)?;
+        tokio::fs::remove_file(self.root.join(name))
+            .await
+            .map_err(|e| format!("Local media delete failed: {}", e))
+    }
+
+    fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    fn public_url(&self, name: &str) -> String {
+        format!("/{}", name)
+    }
+}
+
+static MEDIA_STORE: OnceLock<Box<dyn MediaStore>> = OnceLock::new();
+
+/// Call early to log the selected backend at startup; `get_media_store` also
+/// initializes lazily.
+pub fn init_media_store() {
+    let store = get_media_store();
+    tracing::info!(
+        "Initialized media store with prefix '{}' at {}",
+        store.prefix(),
+        store.public_url("")
+    );
+}
+
+pub fn get_media_store() -> &'static dyn MediaStore {
+    MEDIA_STORE
+        .get_or_init(|| {
+            let prefix = std::env::var("MEDIA_PREFIX")
+                .ok()
+                .filter(|p| !p.trim().is_empty())
+                .map(|p| format!("{}/", p.trim().trim_matches('/')))
+                .unwrap_or_else(|| DEFAULT_MEDIA_PREFIX.to_string());
+
+            match std::env::var("MEDIA_STORE").as_deref() {
+                Ok("local") => Box::new(LocalMediaStore::new(local_media_dir(), prefix)),
+                Ok("gcs") | Err(_) => {
+                    let bucket = std::env::var("MEDIA_GCS_BUCKET")
+                        .unwrap_or_else(|_| DEFAULT_GCS_BUCKET.to_string());
+                    Box::new(GcsMediaStore::new(bucket, prefix))
+                }
+                Ok(other) => {
+                    panic!("Invalid MEDIA_STORE '{}': expected 'gcs' or 'local'", other)
+                }
+            }
+        })
+        .as_ref()
+}
+
+/// Directory the local store writes into. Also used by the backend to serve the files.
+pub fn local_media_dir() -> PathBuf {
+    std::env::var("MEDIA_LOCAL_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(DEFAULT_LOCAL_DIR))
+}
+
+pub fn is_local_media_store() -> bool {
+    std::env::var("MEDIA_STORE").as_deref() == Ok("local")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store() -> LocalMediaStore {
+        let dir = std::env::temp_dir().join(format!("media-store-{}", uuid::Uuid::new_v4()));
+        LocalMediaStore::new(dir, DEFAULT_MEDIA_PREFIX)
+    }
+
+    #[tokio::test]
+    async fn test_local_store_roundtrip() {
+        let store = temp_store();
+        assert!(store.list().await.unwrap().is_empty());
+
+        let name = "media/journalism/uploads/1_photo.jpg";
+        let url = store.put(name, "image/jpeg", vec![1, 2, 3]).await.unwrap();
+        assert_eq!(url, "/media/journalism/uploads/1_photo.jpg");
+        assert_eq!(
+            std::fs::read(store.root().join(name)).unwrap(),
+            vec![1, 2, 3]
+        );
+
+        let listed = store.list().await.unwrap();
+        assert_eq!(
+            listed,
+            vec![StoredObject {
+                name: name.to_string(),
+                url: url.clone(),
+            }]
+        );
+        assert_eq!(store.object_name_from_url(&url), Some(name.to_string()));
+        assert_eq!(store.get(name).await.unwrap(), vec![1, 2, 3]);
+
+        store.delete(name).await.unwrap();
+        assert!(store.list().await.unwrap().is_empty());
+
+        let _ = std::fs::remove_dir_all(store.root());
+    }
+
+    #[tokio::test]
+    async fn test_local_store_rejects_outside_prefix() {
+        let store = temp_store();
+        assert!(store
+            .put("other/file.jpg", "image/jpeg", vec![])
+            .await
+            .is_err());
+        assert!(store
+            .put("media/journalism/../../etc/passwd", "text/plain", vec![])
+            .await
+            .is_err());
+        assert!(store.delete("media/journalism/../secret").await.is_err());
+    }
+
+    #[test]
+    fn test_gcs_public_url() {
+        let store = GcsMediaStore::new("bucket", DEFAULT_MEDIA_PREFIX);
+        let url = store.public_url("media/journalism/uploads/a.jpg");
+        assert_eq!(
+            url,
+            "https://storage.googleapis.com/bucket/media/journalism/uploads/a.jpg"
+        );
+        assert_eq!(
+            store.object_name_from_url(&url).as_deref(),
+            Some("media/journalism/uploads/a.jpg")
+        );
+    }
+}
@@ -1,15 +1,32 @@
 use crate::components::footer::Footer;
 use crate::components::navbar::Navbar;
 use crate::pages::about::AboutPage;
+use crate::pages::admin::albums::AdminAlbums;
+use crate::pages::admin::audit::AdminAudit;
+use crate::pages::admin::backups::AdminBackups;
 use crate::pages::admin::composer::AdminComposer;
+use crate::pages::admin::creative::AdminCreativeEditor;
 use crate::pages::admin::dashboard::AdminDashboard;
+use crate::pages::admin::lockouts::AdminLockouts;
 use crate::pages::admin::login::AdminLoginPage;
 use crate::pages::admin::media::AdminMedia;
+use crate::pages::admin::music::AdminMusic;
 use crate::pages::admin::password_change::AdminPasswordChange;
-use crate::pages::blog::PersonalBlogPage;
+use crate::pages::admin::projects::AdminProjects;
+use crate::pages::admin::revisions::AdminRevisions;
+use crate::pages::admin::security::AdminSecurity;
+use crate::pages::admin::sessions::AdminSessions;
+use crate::pages::admin::users::AdminUsers;
+use crate::pages::blog::{BlogPostPage, PersonalBlogPage};
+use crate::pages::creative::{CreativeWorkPage, CreativeWritingPage, PersonalPage};
+use crate::pages::gallery::{
+    PhotographyAlbumPage, PhotographyPage, VisualArtAlbumPage, VisualArtPage,
+};
 use crate::pages::home::{AdminRedirect, HomePage, NotFound};
 use crate::pages::journalism::{JournalismArticlePage, JournalismPage};
+use crate::pages::music::{MusicPage, TrackPage};
 use crate::pages::programming::ProgrammingPage;
+use crate::pages::video::{VideoDetailPage, VideoPage};
 
 use leptos::prelude::*;
 use leptos_meta::*;
@@ -39,6 +56,8 @@ pub fn Shell() -> impl IntoView {
                 <Meta name="description" content="Journalist, Programmer, Photographer."/>
                 <Title text="Jake Wray"/>
                 <Stylesheet id="leptos" href="/pkg/jakewray_ca.css"/>
+                <Link rel="alternate" type_="application/rss+xml" title="Jake Wray — Journalism" href="/feed.xml"/>
+                <Link rel="alternate" type_="application/rss+xml" title="Jake Wray — Blog" href="/blog/feed.xml"/>
                 <MetaTags/>
             </head>
             <body><App/><HydrationScripts options=options/></body>
@@ -75,6 +94,18 @@ fn MainLayout() -> impl IntoView {
                     <Route path=path!("/journalism") view=JournalismPage/>
                     <Route path=path!("/journalism/:slug") view=JournalismArticlePage/>
                     <Route path=path!("/blog") view=PersonalBlogPage/>
+                    <Route path=path!("/blog/:slug") view=BlogPostPage/>
+                    <Route path=path!("/writing") view=CreativeWritingPage/>
+                    <Route path=path!("/writing/:slug") view=CreativeWorkPage/>
+                    <Route path=path!("/personal") view=PersonalPage/>
+                    <Route path=path!("/photography") view=PhotographyPage/>
+                    <Route path=path!("/photography/albums/:slug") view=PhotographyAlbumPage/>
+                    <Route path=path!("/visual-art") view=VisualArtPage/>
+                    <Route path=path!("/visual-art/albums/:slug") view=VisualArtAlbumPage/>
+                    <Route path=path!("/video") view=VideoPage/>
+                    <Route path=path!("/video/:id") view=VideoDetailPage/>
+                    <Route path=path!("/music") view=MusicPage/>
+                    <Route path=path!("/music/:slug") view=TrackPage/>
                     <Route path=path!("/about") view=AboutPage/>
 
                     // Admin Routes
@@ -82,8 +113,19 @@ fn MainLayout() -> impl IntoView {
                     <Route path=path!("/admin/dashboard") view=AdminDashboard/>
                     <Route path=path!("/admin/login") view=AdminLoginPage/>
                     <Route path=path!("/admin/compose") view=AdminComposer/>
+                    <Route path=path!("/admin/writing") view=AdminCreativeEditor/>
                     <Route path=path!("/admin/password-change") view=AdminPasswordChange/>
                     <Route path=path!("/admin/media") view=AdminMedia/>
+                    <Route path=path!("/admin/projects") view=AdminProjects/>
+                    <Route path=path!("/admin/albums") view=AdminAlbums/>
+                    <Route path=path!("/admin/music") view=AdminMusic/>
+                    <Route path=path!("/admin/revisions") view=AdminRevisions/>
+                    <Route path=path!("/admin/sessions") view=AdminSessions/>
+                    <Route path=path!("/admin/security") view=AdminSecurity/>
+                    <Route path=path!("/admin/users") view=AdminUsers/>
+                    <Route path=path!("/admin/lockouts") view=AdminLockouts/>
+                    <Route path=path!("/admin/audit") view=AdminAudit/>
+                    <Route path=path!("/admin/backups") view=AdminBackups/>
                 </Routes>
             </main>
             <Footer/>
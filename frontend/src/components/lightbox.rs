@@ -0,0 +1,100 @@
+use crate::api::gallery::GalleryItem;
+use crate::components::responsive_image::ResponsiveImage;
+use leptos::ev;
+use leptos::prelude::*;
+
+/// Full-screen viewer for a page of gallery items. `current` is the open item's index;
+/// `None` closes it. Escape closes, the arrow keys step through the items.
+#[component]
+pub fn Lightbox(items: Vec<GalleryItem>, current: RwSignal<Option<usize>>) -> impl IntoView {
+    let count = items.len();
+    let step = move |forward: bool| {
+        current.update(|c| {
+            if let Some(i) = c {
+                *i = if forward {
+                    (*i + 1) % count
+                } else {
+                    (*i + count - 1) % count
+                };
+            }
+        })
+    };
+
+    let handle = window_event_listener(ev::keydown, move |ev| {
+        if current.get_untracked().is_none() {
+            return;
+        }
+        match ev.key().as_str() {
+            "Escape" => current.set(None),
+            "ArrowLeft" => step(false),
+            "ArrowRight" => step(true),
+            _ => return,
+        }
+        ev.prevent_default();
+    });
+    on_cleanup(move || handle.remove());
+
+    let items = StoredValue::new(items);
+    view! {
+        {move || current.get().and_then(|i| items.with_value(|items| items.get(i).cloned())).map(|item| {
+            let index = current.get_untracked().unwrap_or_default();
+            let title = item.title.clone();
+            let description = item.description.clone();
+            let capture_date = item.capture_date.clone();
+            view! {
+                <div
+                    class="lightbox"
+                    role="dialog"
+                    aria-modal="true"
+                    aria-label=item.alt()
+                    on:click=move |_| current.set(None)
+                >
+                    <button type="button" class="lightbox-close" aria-label="Close" on:click=move |_| current.set(None)>
+                        "×"
+                    </button>
+                    {(count > 1).then(|| view! {
+                        <button
+                            type="button"
+                            class="lightbox-nav lightbox-prev"
+                            aria-label="Previous"
+                            on:click=move |ev| {
+                                ev.stop_propagation();
+                                step(false);
+                            }
+                        >
+                            "‹"
+                        </button>
+                        <button
+                            type="button"
+                            class="lightbox-nav lightbox-next"
+                            aria-label="Next"
+                            on:click=move |ev| {
+                                ev.stop_propagation();
+                                step(true);
+                            }
+                        >
+                            "›"
+                        </button>
+                    })}
+                    <figure class="lightbox-figure" on:click=move |ev| ev.stop_propagation()>
+                        <ResponsiveImage
+                            src=item.url.clone()
+                            alt=item.alt()
+                            variants=item.variants.clone()
+                            sizes="100vw"
+                            class="lightbox-image"
+                        />
+                        <figcaption class="lightbox-caption">
+                            {title.map(|t| view! { <p class="font-bold">{t}</p> })}
+                            {description.map(|d| view! { <p>{d}</p> })}
+                            <p class="lightbox-meta">
+                                {capture_date.map(|d| view! { <span>{d}</span> })}
+                                <span>{format!("{} / {}", index + 1, count)}</span>
+                            </p>
+                        </figcaption>
+                    </figure>
+                </div>
+            }
+        })}
+    }
+}
@@ -1,4 +1,5 @@
-use crate::api::media::{list_media, upload_media, MediaItem};
+use crate::api::media::{list_media, upload_media, MediaItem, MediaQuery};
+use crate::utils::session::{clear_session, ensure_session, is_auth_error};
 use leptos::ev;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
@@ -6,11 +7,7 @@ use wasm_bindgen_futures::JsFuture;
 use web_sys::{FileList, HtmlInputElement};
 
 #[component]
-pub fn MediaPicker<F>(
-    token: Signal<String>,
-    on_select: F,
-    current_image: Option<String>,
-) -> impl IntoView
+pub fn MediaPicker<F>(on_select: F, current_image: Option<String>) -> impl IntoView
 where
     F: Fn(String) + 'static + Send + Sync + Clone,
 {
@@ -18,37 +15,33 @@ where
     let (loading, set_loading) = signal(true);
     let (uploading, set_uploading) = signal(false);
     let (error_msg, set_error_msg) = signal(String::new());
+    let (search, set_search) = signal(String::new());
+    let (page, set_page) = signal(0u32);
+    let (page_count, set_page_count) = signal(1u32);
 
     let fetch_media = move || {
         set_loading.set(true);
-        let t = token.get();
-        if shared::auth::is_token_expired(&t) {
-            #[cfg(target_arch = "wasm32")]
-            if let Some(window) = web_sys::window() {
-                if let Ok(Some(storage)) = window.local_storage() {
-                    let _ = storage.remove_item("admin_token");
-                }
-            }
-            set_error_msg.set(
-                "Session expired (Expired or missing token). Please log in again to upload media."
-                    .to_string(),
-            );
-            set_loading.set(false);
-            return;
-        }
+        let query = MediaQuery {
+            search: Some(search.get_untracked()),
+            page: page.get_untracked(),
+            ..Default::default()
+        };
 
         spawn_local(async move {
-            match list_media(t).await {
-                Ok(res) => set_items.set(res),
+            if !ensure_session().await {
+                set_error_msg
+                    .set("Session expired. Please log in again to upload media.".to_string());
+                set_loading.set(false);
+                return;
+            }
+            match list_media(query).await {
+                Ok(res) => {
+                    set_page_count.set(res.page_count());
+                    set_items.set(res.items);
+                }
                 Err(e) => {
-                    let err_str = e.to_string();
-                    if err_str.contains("Invalid token") || err_str.contains("ExpiredSignature") {
-                        #[cfg(target_arch = "wasm32")]
-                        if let Some(window) = web_sys::window() {
-                            if let Ok(Some(storage)) = window.local_storage() {
-                                let _ = storage.remove_item("admin_token");
-                            }
-                        }
+                    if is_auth_error(&e.to_string()) {
+                        clear_session();
                         set_error_msg.set(
                             "Session expired. Please log in again to manage media.".to_string(),
                         );
@@ -61,8 +54,10 @@ where
         });
     };
 
-    // Initial fetch
+    // Initial fetch, and again as the search or page changes
     Effect::new(move || {
+        search.track();
+        page.track();
         fetch_media();
     });
 
@@ -71,24 +66,19 @@ where
         let files: Option<FileList> = input.files();
         if let Some(files) = files {
             if let Some(file) = files.get(0) {
-                let t = token.get();
-                if shared::auth::is_token_expired(&t) {
-                    #[cfg(target_arch = "wasm32")]
-                    if let Some(window) = web_sys::window() {
-                        if let Ok(Some(storage)) = window.local_storage() {
-                            let _ = storage.remove_item("admin_token");
-                        }
-                    }
-                    set_error_msg
-                        .set("Upload failed: Session expired. Please log in again.".to_string());
-                    return;
-                }
                 let f_clone = fetch_media;
                 let filename = file.name();
                 let file_clone = file.clone(); // web_sys::File is Clone (JsValue wrapper)
                 set_uploading.set(true);
 
                 spawn_local(async move {
+                    if !ensure_session().await {
+                        set_error_msg.set(
+                            "Upload failed: Session expired. Please log in again.".to_string(),
+                        );
+                        set_uploading.set(false);
+                        return;
+                    }
                     // Read file as bytes via web_sys
                     let array_buffer_promise = file_clone.array_buffer();
                     match JsFuture::from(array_buffer_promise).await {
@@ -96,21 +86,18 @@ where
                             let uint8_array = js_sys::Uint8Array::new(&array_buffer);
                             let bytes = uint8_array.to_vec();
 
-                            match upload_media(t, filename, bytes).await {
+                            match upload_media(filename, bytes).await {
                                 Ok(_url) => {
-                                    f_clone(); // Refresh list
+                                    // Newest first, so the upload is at the top of page one
+                                    if page.get_untracked() == 0 {
+                                        f_clone();
+                                    } else {
+                                        set_page.set(0);
+                                    }
                                 }
                                 Err(e) => {
-                                    let err_str = e.to_string();
-                                    if err_str.contains("Invalid token")
-                                        || err_str.contains("ExpiredSignature")
-                                    {
-                                        #[cfg(target_arch = "wasm32")]
-                                        if let Some(window) = web_sys::window() {
-                                            if let Ok(Some(storage)) = window.local_storage() {
-                                                let _ = storage.remove_item("admin_token");
-                                            }
-                                        }
+                                    if is_auth_error(&e.to_string()) {
+                                        clear_session();
                                         set_error_msg.set(
                                             "Upload failed: Session expired. Please log in again."
                                                 .to_string(),
@@ -136,6 +123,16 @@ where
                 <button class="btn btn-sm btn-secondary" on:click=move |_| fetch_media()>"Refresh"</button>
             </div>
 
+            <input
+                type="text"
+                placeholder="Search media..."
+                class="w-full text-sm py-2 px-3 mb-4 rounded-md border border-gray-300"
+                on:input=move |ev| {
+                    set_page.set(0);
+                    set_search.set(event_target_value(&ev));
+                }
+            />
+
             {move || if !error_msg.get().is_empty() {
                 let msg = error_msg.get();
                 let is_expired = msg.contains("Session expired") || msg.contains("Invalid token");
@@ -194,7 +191,12 @@ where
                                 )
                                 on:click=move |_| os(u.clone())
                             >
-                                <img src=url.clone() alt=item.name.clone() class="w-full h-full object-cover" />
+                                <img
+                                    src=item.thumbnail_url.clone().unwrap_or_else(|| url.clone())
+                                    alt=item.alt_text.clone().unwrap_or_else(|| item.display_name().to_string())
+                                    title=item.display_name().to_string()
+                                    class="w-full h-full object-cover"
+                                />
                                 {if is_selected {
                                     Some(view! {
                                         <div class="absolute top-1 right-1 bg-blue-600 text-white rounded-full p-1">
@@ -209,6 +211,26 @@ where
                     }).collect_view().into_any()
                 }}
             </div>
+
+            {move || (page_count.get() > 1).then(|| view! {
+                <div class="flex items-center justify-between mt-3 text-xs text-gray-600">
+                    <button
+                        class="btn btn-sm btn-secondary"
+                        disabled=move || page.get() == 0
+                        on:click=move |_| set_page.update(|p| *p = p.saturating_sub(1))
+                    >
+                        "Previous"
+                    </button>
+                    <span>{move || format!("Page {} of {}", page.get() + 1, page_count.get())}</span>
+                    <button
+                        class="btn btn-sm btn-secondary"
+                        disabled=move || page.get() + 1 >= page_count.get()
+                        on:click=move |_| set_page.update(|p| *p += 1)
+                    >
+                        "Next"
+                    </button>
+                </div>
+            })}
         </div>
     }
 }
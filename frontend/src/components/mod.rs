@@ -1,4 +1,7 @@
 pub mod footer;
+pub mod lightbox;
 pub mod media_picker;
 pub mod navbar;
+pub mod player;
+pub mod responsive_image;
 pub mod rich_editor;
@@ -20,7 +20,7 @@ pub fn Navbar() -> impl IntoView {
         <header class="site-header">
             <div class="container nav-container">
                 <A href="/" attr:class="site-brand">"Jake Wray"</A>
-                <nav class="nav-links"><A href="/code" attr:class=is_active("/code")>"Code"</A><A href="/blog" attr:class=is_active("/blog")>"Blog"</A><A href="/journalism" attr:class=is_active("/journalism")>"Journalism"</A><A href="/about" attr:class=is_active("/about")>"About Me"</A></nav>
+                <nav class="nav-links"><A href="/code" attr:class=is_active("/code")>"Code"</A><A href="/blog" attr:class=is_active("/blog")>"Blog"</A><A href="/writing" attr:class=is_active("/writing")>"Writing"</A><A href="/journalism" attr:class=is_active("/journalism")>"Journalism"</A><A href="/photography" attr:class=is_active("/photography")>"Photography"</A><A href="/about" attr:class=is_active("/about")>"About Me"</A></nav>
             </div>
         </header>
     }
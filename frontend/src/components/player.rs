@@ -0,0 +1,50 @@
+use crate::utils::embed::{Embed, EMBED_ALLOW, EMBED_SANDBOX};
+use leptos::prelude::*;
+
+/// A third-party player in a sandboxed frame. Only the rebuilt player URL from
+/// [`parse_embed`](crate::utils::embed::parse_embed) reaches the page.
+#[component]
+pub fn EmbedPlayer(embed: Embed, #[prop(into)] title: String) -> impl IntoView {
+    let height = embed.height();
+    let class = if height.is_none() {
+        "embed-player embed-video"
+    } else {
+        "embed-player"
+    };
+
+    view! {
+        <div class=class>
+            <iframe
+                src=embed.src
+                title=title
+                height=height.map(|h| h.to_string())
+                sandbox=EMBED_SANDBOX
+                allow=EMBED_ALLOW
+                allowfullscreen=true
+                referrerpolicy="strict-origin-when-cross-origin"
+            ></iframe>
+        </div>
+    }
+}
+
+/// The browser's own player for self-hosted audio.
+#[component]
+pub fn AudioPlayer(#[prop(into)] src: String) -> impl IntoView {
+    let download = src.clone();
+    view! {
+        <audio class="audio-player" controls preload="metadata" src=src>
+            <a href=download>"Download the audio"</a>
+        </audio>
+    }
+}
+
+/// The browser's own player for self-hosted video.
+#[component]
+pub fn VideoPlayer(#[prop(into)] src: String, poster: Option<String>) -> impl IntoView {
+    let download = src.clone();
+    view! {
+        <video class="video-player" controls preload="metadata" playsinline=true src=src poster=poster>
+            <a href=download>"Download the video"</a>
+        </video>
+    }
+}
@@ -0,0 +1,27 @@
+use crate::api::media::{variant_srcset, ImageVariant, VariantFormat};
+use leptos::prelude::*;
+
+/// An `<img>` that offers the image's resized variants, WebP first, when it has any.
+#[component]
+pub fn ResponsiveImage(
+    #[prop(into)] src: String,
+    #[prop(into)] alt: String,
+    /// Variants of `src`; empty for images without any.
+    #[prop(optional)]
+    variants: Vec<ImageVariant>,
+    /// The `sizes` hint, i.e. how wide the image is laid out.
+    sizes: &'static str,
+    #[prop(optional, into)] class: String,
+) -> impl IntoView {
+    let non_empty = |srcset: String| (!srcset.is_empty()).then_some(srcset);
+    let webp = non_empty(variant_srcset(&variants, VariantFormat::Webp));
+    let jpeg = non_empty(variant_srcset(&variants, VariantFormat::Jpeg));
+    let img_sizes = jpeg.is_some().then_some(sizes);
+
+    view! {
+        <picture>
+            {webp.map(|srcset| view! { <source type="image/webp" srcset=srcset sizes=sizes/> })}
+            <img src=src srcset=jpeg sizes=img_sizes class=class alt=alt/>
+        </picture>
+    }
+}
@@ -1,7 +1,6 @@
 pub mod api;
 mod app;
 pub mod components;
-pub mod data;
 pub mod pages;
 pub mod utils;
 pub use app::*;
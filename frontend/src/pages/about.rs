@@ -1,5 +1,6 @@
 use crate::api::pages::{get_page, save_page, PageContent};
 use crate::components::rich_editor::RichTextEditor;
+use crate::utils::session::{clear_session, ensure_session, has_session, is_auth_error};
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 
@@ -9,26 +10,9 @@ pub fn AboutPage() -> impl IntoView {
 
     // Auth State
     let (is_admin, set_is_admin) = signal(false);
-    let (token, set_token) = signal(String::new());
 
     Effect::new(move || {
-        #[cfg(target_arch = "wasm32")]
-        {
-            if let Ok(Some(storage)) = web_sys::window().unwrap().local_storage() {
-                if let Ok(Some(t)) = storage.get_item("admin_token") {
-                    if !t.is_empty() {
-                        if shared::auth::is_token_expired(&t) {
-                            let _ = storage.remove_item("admin_token");
-                            set_is_admin.set(false);
-                            set_token.set(String::new());
-                        } else {
-                            set_token.set(t);
-                            set_is_admin.set(true);
-                        }
-                    }
-                }
-            }
-        }
+        set_is_admin.set(has_session());
     });
 
     // Edit State
@@ -46,7 +30,6 @@ pub fn AboutPage() -> impl IntoView {
     };
 
     let on_save = move || {
-        let t = token.get();
         let new_title = edit_title.get();
         let new_content = edit_content.get();
 
@@ -66,7 +49,8 @@ pub fn AboutPage() -> impl IntoView {
         };
 
         spawn_local(async move {
-            match save_page(t, new_page).await {
+            ensure_session().await;
+            match save_page(new_page).await {
                 Ok(_) => {
                     set_save_status.set("Saved successfully!".to_string());
                     set_is_saving.set(false);
@@ -75,16 +59,9 @@ pub fn AboutPage() -> impl IntoView {
                 }
                 Err(e) => {
                     set_is_saving.set(false);
-                    let err_str = e.to_string();
-                    if err_str.contains("Invalid token") || err_str.contains("ExpiredSignature") {
-                        #[cfg(target_arch = "wasm32")]
-                        if let Some(window) = web_sys::window() {
-                            if let Ok(Some(storage)) = window.local_storage() {
-                                let _ = storage.remove_item("admin_token");
-                            }
-                        }
+                    if is_auth_error(&e.to_string()) {
+                        clear_session();
                         set_is_admin.set(false);
-                        set_token.set(String::new());
                         set_save_status
                             .set("Save failed: Session expired. Please log in again.".to_string());
                     } else {
@@ -191,20 +168,23 @@ pub fn AboutPage() -> impl IntoView {
                                                     </span>
                                                     <span class="text-xs text-gray-500 font-medium">"You can edit the content on this page"</span>
                                                 </div>
-                                                <button
-                                                    type="button"
-                                                    class="btn btn-sm btn-primary flex items-center gap-1.5"
-                                                    on:click=move |_| {
-                                                        if let Some(ref p) = page_data {
-                                                            turn_on_edit(p);
+                                                <div class="flex items-center gap-2">
+                                                    <button
+                                                        type="button"
+                                                        class="btn btn-sm btn-primary flex items-center gap-1.5"
+                                                        on:click=move |_| {
+                                                            if let Some(ref p) = page_data {
+                                                                turn_on_edit(p);
+                                                            }
                                                         }
-                                                    }
-                                                >
-                                                    <svg xmlns="http://www.w3.org/2000/svg" class="h-4 w-4" fill="none" viewBox="0 0 24 24" stroke="currentColor">
-                                                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M11 5H6a2 2 0 00-2 2v11a2 2 0 002 2h11a2 2 0 002-2v-5m-1.414-9.414a2 2 0 112.828 2.828L11.828 15H9v-2.828l8.586-8.586z" />
-                                                    </svg>
-                                                    "Edit About Me"
-                                                </button>
+                                                    >
+                                                        <svg xmlns="http://www.w3.org/2000/svg" class="h-4 w-4" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                                            <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M11 5H6a2 2 0 00-2 2v11a2 2 0 002 2h11a2 2 0 002-2v-5m-1.414-9.414a2 2 0 112.828 2.828L11.828 15H9v-2.828l8.586-8.586z" />
+                                                        </svg>
+                                                        "Edit About Me"
+                                                    </button>
+                                                    <a href="/admin/revisions?kind=page&slug=about" class="btn btn-sm btn-secondary">"History"</a>
+                                                </div>
                                             </div>
                                         }
                                     })
@@ -0,0 +1,425 @@
+use crate::api::gallery::{
+    delete_album, get_album_contents, list_albums, reorder_albums, save_album, set_album_items,
+    Album, GalleryItem,
+};
+use crate::api::media::{list_media, MediaItem, MediaQuery};
+use crate::utils::session::{current_role, ensure_session, has_session};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use shared::auth::Role;
+use shared::{MediaCategory, MediaContext};
+
+/// An item in the album being arranged.
+#[derive(Clone, Debug, PartialEq)]
+struct AlbumEntry {
+    id: String,
+    thumbnail_url: String,
+    label: String,
+}
+
+impl From<GalleryItem> for AlbumEntry {
+    fn from(item: GalleryItem) -> Self {
+        AlbumEntry {
+            label: item.title.clone().unwrap_or_else(|| item.url.clone()),
+            thumbnail_url: item.thumbnail_url.unwrap_or(item.url),
+            id: item.id,
+        }
+    }
+}
+
+impl From<&MediaItem> for AlbumEntry {
+    fn from(item: &MediaItem) -> Self {
+        AlbumEntry {
+            id: item.id.clone(),
+            thumbnail_url: item
+                .thumbnail_url
+                .clone()
+                .unwrap_or_else(|| item.url.clone()),
+            label: item.display_name().to_string(),
+        }
+    }
+}
+
+/// Swaps `ids[index]` with its neighbour; `false` when it's already at that end.
+fn move_in<T>(items: &mut [T], index: usize, up: bool) -> bool {
+    let other = if up {
+        index.checked_sub(1)
+    } else {
+        Some(index + 1)
+    };
+    match other.filter(|&o| o < items.len()) {
+        Some(o) => {
+            items.swap(index, o);
+            true
+        }
+        None => false,
+    }
+}
+
+#[component]
+pub fn AdminAlbums() -> impl IntoView {
+    let (signed_in, set_signed_in) = signal(false);
+    let (refresh_counter, set_refresh_counter) = signal(0);
+    let (status, set_status) = signal(String::new());
+
+    // Form state; `editing_id` is empty while creating a new album
+    let (editing_id, set_editing_id) = signal(String::new());
+    let (title, set_title) = signal(String::new());
+    let (slug, set_slug) = signal(String::new());
+    let (description, set_description) = signal(String::new());
+    let (category, set_category) = signal(MediaCategory::Photography);
+    let (context, set_context) = signal(MediaContext::Personal);
+
+    // The album whose items are being arranged
+    let (arranging, set_arranging) = signal(None::<Album>);
+    let entries = RwSignal::new(Vec::<AlbumEntry>::new());
+    let (search, set_search) = signal(String::new());
+    let (candidate_page, set_candidate_page) = signal(0u32);
+
+    Effect::new(move || {
+        if has_session() && current_role().is_some_and(Role::can_publish) {
+            set_signed_in.set(true);
+        } else {
+            #[cfg(target_arch = "wasm32")]
+            leptos_router::hooks::use_navigate()("/admin/dashboard", Default::default());
+        }
+    });
+
+    let albums_resource = Resource::new(
+        move || (signed_in.get(), refresh_counter.get()),
+        |(signed_in, _)| async move {
+            if !signed_in {
+                return Ok(Vec::new());
+            }
+            ensure_session().await;
+            list_albums(None).await
+        },
+    );
+
+    // Gallery items of the album's category that can be added to it
+    let candidates_resource = Resource::new(
+        move || {
+            (
+                arranging.get().map(|a| a.category),
+                search.get(),
+                candidate_page.get(),
+            )
+        },
+        |(category, search, page)| async move {
+            let Some(category) = category else {
+                return Ok(Default::default());
+            };
+            ensure_session().await;
+            list_media(MediaQuery {
+                search: Some(search).filter(|s| !s.trim().is_empty()),
+                category: Some(category),
+                in_gallery: Some(true),
+                page,
+                ..Default::default()
+            })
+            .await
+        },
+    );
+
+    let reset_form = move || {
+        set_editing_id.set(String::new());
+        set_title.set(String::new());
+        set_slug.set(String::new());
+        set_description.set(String::new());
+        set_category.set(MediaCategory::Photography);
+        set_context.set(MediaContext::Personal);
+    };
+
+    let edit = move |album: Album| {
+        set_editing_id.set(album.id);
+        set_title.set(album.title);
+        set_slug.set(album.slug);
+        set_description.set(album.description.unwrap_or_default());
+        set_category.set(album.category);
+        set_context.set(album.context);
+    };
+
+    let on_save = move |_| {
+        let album = Album {
+            id: editing_id.get(),
+            slug: slug.get(),
+            title: title.get(),
+            description: Some(description.get()),
+            category: category.get(),
+            context: context.get(),
+            item_count: 0,
+            cover: None,
+        };
+        set_status.set("Saving...".to_string());
+        spawn_local(async move {
+            ensure_session().await;
+            match save_album(album).await {
+                Ok(album) => {
+                    set_status.set(format!("Album saved as /{}.", album.slug));
+                    reset_form();
+                    set_refresh_counter.update(|c| *c += 1);
+                }
+                Err(e) => set_status.set(format!("Error saving album: {}", e)),
+            }
+        });
+    };
+
+    let on_delete = move |id: String| {
+        #[cfg(target_arch = "wasm32")]
+        {
+            if let Some(win) = web_sys::window() {
+                if !win
+                    .confirm_with_message("Delete this album? Its images stay in the library.")
+                    .unwrap_or(false)
+                {
+                    return;
+                }
+            }
+        }
+        spawn_local(async move {
+            ensure_session().await;
+            match delete_album(id.clone()).await {
+                Ok(_) => {
+                    set_status.set("Album deleted.".to_string());
+                    if arranging.get_untracked().is_some_and(|a| a.id == id) {
+                        set_arranging.set(None);
+                    }
+                    set_refresh_counter.update(|c| *c += 1);
+                }
+                Err(e) => set_status.set(format!("Error deleting album: {}", e)),
+            }
+        });
+    };
+
+    let on_reorder = move |mut ids: Vec<String>, index: usize, up: bool| {
+        if !move_in(&mut ids, index, up) {
+            return;
+        }
+        spawn_local(async move {
+            ensure_session().await;
+            match reorder_albums(ids).await {
+                Ok(_) => set_refresh_counter.update(|c| *c += 1),
+                Err(e) => set_status.set(format!("Error reordering albums: {}", e)),
+            }
+        });
+    };
+
+    let on_arrange = move |album: Album| {
+        let album_id = album.id.clone();
+        set_arranging.set(Some(album));
+        set_search.set(String::new());
+        set_candidate_page.set(0);
+        entries.set(Vec::new());
+        spawn_local(async move {
+            ensure_session().await;
+            match get_album_contents(album_id).await {
+                Ok(items) => entries.set(items.into_iter().map(AlbumEntry::from).collect()),
+                Err(e) => set_status.set(format!("Error loading album items: {}", e)),
+            }
+        });
+    };
+
+    let on_save_items = move |_| {
+        let Some(album) = arranging.get_untracked() else {
+            return;
+        };
+        let media_ids = entries.with_untracked(|e| e.iter().map(|e| e.id.clone()).collect());
+        set_status.set("Saving album items...".to_string());
+        spawn_local(async move {
+            ensure_session().await;
+            match set_album_items(album.id, media_ids).await {
+                Ok(_) => {
+                    set_status.set(format!("Saved the items of \"{}\".", album.title));
+                    set_refresh_counter.update(|c| *c += 1);
+                }
+                Err(e) => set_status.set(format!("Error saving album items: {}", e)),
+            }
+        });
+    };
+
+    view! {
+        <div class="container py-12 max-w-4xl">
+            <div class="flex justify-between items-center mb-8">
+                <h1 class="text-3xl font-bold">"Albums"</h1>
+                <a href="/admin/dashboard" class="btn btn-secondary">"Back to Dashboard"</a>
+            </div>
+
+            <p class="text-sm text-sky-700 mb-4">{move || status.get()}</p>
+
+            <div class="bg-white border rounded-xl p-6 shadow-sm mb-8">
+                <h2 class="text-xl font-bold mb-4">
+                    {move || if editing_id.get().is_empty() { "New Album" } else { "Edit Album" }}
+                </h2>
+                <div class="grid gap-4 md:grid-cols-2">
+                    <input type="text" class="p-3 border rounded-lg" placeholder="Title"
+                        prop:value=move || title.get()
+                        on:input=move |ev| set_title.set(event_target_value(&ev))
+                    />
+                    <input type="text" class="p-3 border rounded-lg" placeholder="Slug (made from the title if empty)"
+                        prop:value=move || slug.get()
+                        on:input=move |ev| set_slug.set(event_target_value(&ev))
+                    />
+                    <select class="p-3 border rounded-lg bg-white"
+                        on:change=move |ev| {
+                            if let Ok(c) = event_target_value(&ev).parse() {
+                                set_category.set(c);
+                            }
+                        }
+                    >
+                        {[MediaCategory::Photography, MediaCategory::VisualArt].into_iter().map(|c| view! {
+                            <option value=c.to_string() selected=move || category.get() == c>{c.label()}</option>
+                        }).collect_view()}
+                    </select>
+                    <select class="p-3 border rounded-lg bg-white"
+                        on:change=move |ev| {
+                            if let Ok(c) = event_target_value(&ev).parse() {
+                                set_context.set(c);
+                            }
+                        }
+                    >
+                        {MediaContext::ALL.into_iter().map(|c| view! {
+                            <option value=c.to_string() selected=move || context.get() == c>{c.label()}</option>
+                        }).collect_view()}
+                    </select>
+                </div>
+                <textarea class="w-full p-3 border rounded-lg mt-4" rows="3" placeholder="Description"
+                    prop:value=move || description.get()
+                    on:input=move |ev| set_description.set(event_target_value(&ev))
+                ></textarea>
+                <div class="flex gap-2 mt-4">
+                    <button class="btn btn-primary" on:click=on_save>"Save Album"</button>
+                    <button class="btn btn-secondary" on:click=move |_| reset_form()>"Clear"</button>
+                </div>
+            </div>
+
+            <Suspense fallback=move || view! { <p>"Loading albums..."</p> }>
+                {move || {
+                    albums_resource.get().map(|res| match res {
+                        Ok(albums) if albums.is_empty() => {
+                            view! { <p class="text-gray-500">"No albums yet."</p> }.into_any()
+                        }
+                        Ok(albums) => {
+                            let ids: Vec<String> = albums.iter().map(|a| a.id.clone()).collect();
+                            let last = albums.len() - 1;
+                            view! {
+                                <div class="divide-y border rounded-lg overflow-hidden bg-white mb-8">
+                                    {albums.into_iter().enumerate().map(|(i, album)| {
+                                        let id = album.id.clone();
+                                        let (up_ids, down_ids) = (ids.clone(), ids.clone());
+                                        let (edit_album, arrange_album) = (album.clone(), album.clone());
+                                        let label = format!(
+                                            "{} · {} · {} · {} items",
+                                            album.title,
+                                            album.category.label(),
+                                            album.context.label(),
+                                            album.item_count
+                                        );
+                                        view! {
+                                            <div class="p-4 flex items-center justify-between gap-4">
+                                                <span class="font-semibold">{label}</span>
+                                                <div class="flex gap-2">
+                                                    <button class="btn btn-sm btn-secondary" disabled=i == 0
+                                                        on:click=move |_| on_reorder(up_ids.clone(), i, true)>"↑"</button>
+                                                    <button class="btn btn-sm btn-secondary" disabled=i == last
+                                                        on:click=move |_| on_reorder(down_ids.clone(), i, false)>"↓"</button>
+                                                    <button class="btn btn-sm btn-secondary" on:click=move |_| edit(edit_album.clone())>"Edit"</button>
+                                                    <button class="btn btn-sm btn-secondary" on:click=move |_| on_arrange(arrange_album.clone())>"Items"</button>
+                                                    <button class="btn btn-sm btn-secondary text-red-600" on:click=move |_| on_delete(id.clone())>"Delete"</button>
+                                                </div>
+                                            </div>
+                                        }
+                                    }).collect_view()}
+                                </div>
+                            }.into_any()
+                        }
+                        Err(e) => view! { <p class="text-red-500">"Error loading albums: " {e.to_string()}</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+
+            {move || arranging.get().map(|album| view! {
+                <div class="bg-white border rounded-xl p-6 shadow-sm">
+                    <div class="flex justify-between items-center mb-4">
+                        <h2 class="text-xl font-bold">{format!("Items of \"{}\"", album.title)}</h2>
+                        <div class="flex gap-2">
+                            <button class="btn btn-primary" on:click=on_save_items>"Save Items"</button>
+                            <button class="btn btn-secondary" on:click=move |_| set_arranging.set(None)>"Close"</button>
+                        </div>
+                    </div>
+
+                    {move || {
+                        let list = entries.get();
+                        if list.is_empty() {
+                            return view! { <p class="text-gray-500 mb-6">"No items yet. Add some below."</p> }.into_any();
+                        }
+                        let last = list.len() - 1;
+                        view! {
+                            <ol class="divide-y border rounded-lg mb-6">
+                                {list.into_iter().enumerate().map(|(i, entry)| view! {
+                                    <li class="p-2 flex items-center gap-3">
+                                        <img src=entry.thumbnail_url class="w-12 h-12 object-cover rounded" alt=""/>
+                                        <span class="flex-1 truncate">{entry.label}</span>
+                                        <button class="btn btn-sm btn-secondary" disabled=i == 0
+                                            on:click=move |_| entries.update(|e| { move_in(e, i, true); })>"↑"</button>
+                                        <button class="btn btn-sm btn-secondary" disabled=i == last
+                                            on:click=move |_| entries.update(|e| { move_in(e, i, false); })>"↓"</button>
+                                        <button class="btn btn-sm btn-secondary text-red-600"
+                                            on:click=move |_| entries.update(|e| { e.remove(i); })>"Remove"</button>
+                                    </li>
+                                }).collect_view()}
+                            </ol>
+                        }.into_any()
+                    }}
+
+                    <h3 class="font-bold mb-1">{format!("Add from the {} gallery", album.category.label().to_lowercase())}</h3>
+                    <p class="text-xs text-gray-500 mb-2">"Only library items marked for the galleries are listed."</p>
+                    <input type="search" class="w-full p-2 border rounded-lg mb-3" placeholder="Search"
+                        prop:value=move || search.get()
+                        on:input=move |ev| {
+                            set_search.set(event_target_value(&ev));
+                            set_candidate_page.set(0);
+                        }
+                    />
+                    <Suspense fallback=move || view! { <p>"Loading..."</p> }>
+                        {move || {
+                            candidates_resource.get().map(|res| match res {
+                                Ok(page) => {
+                                    let page_count = page.page_count();
+                                    let current = page.page;
+                                    view! {
+                                        <div class="grid grid-cols-4 md:grid-cols-6 gap-2">
+                                            {page.items.iter().map(AlbumEntry::from).map(|entry| {
+                                                let id = entry.id.clone();
+                                                let in_album = move || entries.with(|e| e.iter().any(|x| x.id == id));
+                                                let title = entry.label.clone();
+                                                view! {
+                                                    <button type="button" class="relative aspect-square" title=title
+                                                        disabled=in_album.clone()
+                                                        class:opacity-40=in_album
+                                                        on:click=move |_| entries.update(|e| e.push(entry.clone()))
+                                                    >
+                                                        <img src=entry.thumbnail_url.clone() class="w-full h-full object-cover rounded" alt=""/>
+                                                    </button>
+                                                }
+                                            }).collect_view()}
+                                        </div>
+                                        {(page_count > 1).then(|| view! {
+                                            <div class="flex justify-between items-center mt-3 text-sm">
+                                                <button class="btn btn-sm btn-secondary" disabled=current == 0
+                                                    on:click=move |_| set_candidate_page.update(|p| *p = p.saturating_sub(1))>"Previous"</button>
+                                                <span>{format!("Page {} of {}", current + 1, page_count)}</span>
+                                                <button class="btn btn-sm btn-secondary" disabled=current + 1 >= page_count
+                                                    on:click=move |_| set_candidate_page.update(|p| *p += 1)>"Next"</button>
+                                            </div>
+                                        })}
+                                    }.into_any()
+                                }
+                                Err(e) => view! { <p class="text-red-500">"Error loading media: " {e.to_string()}</p> }.into_any(),
+                            })
+                        }}
+                    </Suspense>
+                </div>
+            })}
+        </div>
+    }
+}
@@ -0,0 +1,157 @@
+use crate::api::audit::{list_audit_events, AuditAction, AuditEvent, AuditFilter};
+use crate::utils::session::{current_role, ensure_session, has_session};
+use leptos::prelude::*;
+use shared::auth::Role;
+
+#[component]
+pub fn AdminAudit() -> impl IntoView {
+    let (signed_in, set_signed_in) = signal(false);
+    let (filter, set_filter) = signal(AuditFilter::default());
+
+    let (action, set_action) = signal(String::new());
+    let (actor, set_actor) = signal(String::new());
+    let (target, set_target) = signal(String::new());
+    let (since, set_since) = signal(String::new());
+    let (until, set_until) = signal(String::new());
+
+    Effect::new(move || {
+        if has_session() && current_role().is_some_and(Role::can_manage_users) {
+            set_signed_in.set(true);
+        } else {
+            #[cfg(target_arch = "wasm32")]
+            leptos_router::hooks::use_navigate()("/admin/dashboard", Default::default());
+        }
+    });
+
+    let events_resource = Resource::new(
+        move || (signed_in.get(), filter.get()),
+        |(signed_in, filter)| async move {
+            if !signed_in {
+                Ok(Vec::new())
+            } else {
+                ensure_session().await;
+                list_audit_events(filter).await
+            }
+        },
+    );
+
+    let field = |value: ReadSignal<String>| {
+        let value = value.get();
+        (!value.trim().is_empty()).then_some(value)
+    };
+    let apply = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        set_filter.set(AuditFilter {
+            action: field(action),
+            actor: field(actor),
+            target: field(target),
+            since: field(since),
+            until: field(until),
+        });
+    };
+
+    let export_href = move || {
+        let query = filter.get().to_query();
+        if query.is_empty() {
+            "/admin/audit/export".to_string()
+        } else {
+            format!("/admin/audit/export?{}", query)
+        }
+    };
+
+    let render_event = |event: AuditEvent| {
+        let who = event
+            .actor_name
+            .or(event.actor)
+            .unwrap_or_else(|| "anonymous".to_string());
+        let change = match (event.before, event.after) {
+            (Some(before), Some(after)) => format!("{} → {}", before, after),
+            (Some(before), None) => format!("was {}", before),
+            (None, Some(after)) => after,
+            (None, None) => String::new(),
+        };
+        view! {
+            <div class="p-3">
+                <p class="font-semibold">
+                    <span class="text-xs uppercase text-gray-500 mr-2">{event.action}</span>
+                    {event.target.unwrap_or_default()}
+                </p>
+                <p class="text-xs text-gray-500">
+                    {format!(
+                        "{} · {} from {}",
+                        event.created_at,
+                        who,
+                        event.ip.unwrap_or_else(|| "unknown IP".to_string()),
+                    )}
+                </p>
+                {(!change.is_empty()).then(|| view! { <p class="text-sm text-gray-700">{change}</p> })}
+            </div>
+        }
+    };
+
+    view! {
+        <div class="container py-12 max-w-4xl">
+            <div class="flex justify-between items-center mb-8">
+                <h1 class="text-3xl font-bold">"Audit Log"</h1>
+                <div class="flex gap-2">
+                    <a href=export_href class="btn btn-secondary" download="audit-log.json">
+                        "Export JSON"
+                    </a>
+                    <a href="/admin/dashboard" class="btn btn-secondary">"Back"</a>
+                </div>
+            </div>
+
+            <form class="grid grid-cols-2 md:grid-cols-3 gap-2 mb-6" on:submit=apply>
+                <select
+                    class="border rounded px-2 py-1"
+                    on:change=move |ev| set_action.set(event_target_value(&ev))
+                >
+                    <option value="">"All actions"</option>
+                    {AuditAction::ALL
+                        .into_iter()
+                        .map(|a| view! { <option value=a.as_str()>{a.as_str()}</option> })
+                        .collect_view()}
+                </select>
+                <input
+                    type="text"
+                    class="border rounded px-2 py-1"
+                    placeholder="Actor (username)"
+                    on:input=move |ev| set_actor.set(event_target_value(&ev))
+                />
+                <input
+                    type="text"
+                    class="border rounded px-2 py-1"
+                    placeholder="Target contains"
+                    on:input=move |ev| set_target.set(event_target_value(&ev))
+                />
+                <input
+                    type="date"
+                    class="border rounded px-2 py-1"
+                    on:input=move |ev| set_since.set(event_target_value(&ev))
+                />
+                <input
+                    type="date"
+                    class="border rounded px-2 py-1"
+                    on:input=move |ev| set_until.set(event_target_value(&ev))
+                />
+                <button type="submit" class="btn btn-primary">"Filter"</button>
+            </form>
+
+            <Suspense fallback=move || view! { <p>"Loading events..."</p> }>
+                {move || {
+                    events_resource.get().map(|res| match res {
+                        Ok(events) if events.is_empty() => {
+                            view! { <p class="text-gray-500">"No matching events."</p> }.into_any()
+                        }
+                        Ok(events) => view! {
+                            <div class="divide-y border rounded-lg overflow-hidden bg-white">
+                                {events.into_iter().map(render_event).collect_view()}
+                            </div>
+                        }.into_any(),
+                        Err(e) => view! { <p class="text-red-500">"Error loading events: " {e.to_string()}</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
@@ -0,0 +1,173 @@
+use crate::api::archive::{create_backup, list_backups, restore_backup, ArchiveFormat, BackupInfo};
+use crate::utils::session::{current_role, ensure_session, has_session};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use shared::auth::Role;
+
+fn format_size(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1 << 20 => format!("{:.1} MB", b as f64 / (1 << 20) as f64),
+        b if b >= 1 << 10 => format!("{:.1} KB", b as f64 / (1 << 10) as f64),
+        b => format!("{} B", b),
+    }
+}
+
+#[component]
+pub fn AdminBackups() -> impl IntoView {
+    let (signed_in, set_signed_in) = signal(false);
+    let (refresh_counter, set_refresh_counter) = signal(0);
+    let (status, set_status) = signal(String::new());
+    let (busy, set_busy) = signal(false);
+    let (format, set_format) = signal(ArchiveFormat::Json);
+    let (include_media, set_include_media) = signal(true);
+
+    Effect::new(move || {
+        if has_session() && current_role().is_some_and(Role::can_manage_users) {
+            set_signed_in.set(true);
+        } else {
+            #[cfg(target_arch = "wasm32")]
+            leptos_router::hooks::use_navigate()("/admin/dashboard", Default::default());
+        }
+    });
+
+    let backups_resource = Resource::new(
+        move || (signed_in.get(), refresh_counter.get()),
+        |(signed_in, _)| async move {
+            if !signed_in {
+                Ok(Vec::new())
+            } else {
+                ensure_session().await;
+                list_backups().await
+            }
+        },
+    );
+
+    let create = move |_| {
+        set_busy.set(true);
+        set_status.set("Creating backup...".to_string());
+        spawn_local(async move {
+            ensure_session().await;
+            match create_backup(format.get_untracked(), include_media.get_untracked()).await {
+                Ok(backup) => {
+                    set_status.set(format!("Created {}.", backup.name));
+                    set_refresh_counter.update(|c| *c += 1);
+                }
+                Err(e) => set_status.set(format!("Error creating backup: {}", e)),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    let restore = move |name: String| {
+        #[cfg(target_arch = "wasm32")]
+        {
+            let prompt = format!(
+                "Restore {}? Content with the same ids is overwritten by the backup's copy.",
+                name
+            );
+            if let Some(win) = web_sys::window() {
+                if !win.confirm_with_message(&prompt).unwrap_or(false) {
+                    return;
+                }
+            }
+        }
+        set_busy.set(true);
+        set_status.set(format!("Restoring {}...", name));
+        spawn_local(async move {
+            ensure_session().await;
+            match restore_backup(name.clone()).await {
+                Ok(summary) => {
+                    let rows: usize = summary.tables.iter().map(|(_, rows)| rows).sum();
+                    set_status.set(format!(
+                        "Restored {}: {} rows and {} media files.",
+                        name, rows, summary.media
+                    ));
+                }
+                Err(e) => set_status.set(format!("Error restoring backup: {}", e)),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    let render_backup = move |backup: BackupInfo| {
+        let name = backup.name.clone();
+        view! {
+            <div class="p-4 flex items-center justify-between gap-4">
+                <div class="min-w-0">
+                    <p class="font-semibold truncate">{backup.name.clone()}</p>
+                    <p class="text-xs text-gray-500">
+                        {format!("{} · {}", backup.created_at, format_size(backup.size))}
+                    </p>
+                </div>
+                <div class="flex gap-2">
+                    <a
+                        href=format!("/admin/backups/{}", backup.name)
+                        class="btn btn-sm btn-secondary"
+                        download=backup.name.clone()
+                    >
+                        "Download"
+                    </a>
+                    <button
+                        class="btn btn-sm btn-secondary text-red-600 hover:text-red-700"
+                        disabled=move || busy.get()
+                        on:click=move |_| restore(name.clone())
+                    >
+                        "Restore"
+                    </button>
+                </div>
+            </div>
+        }
+    };
+
+    view! {
+        <div class="container py-12 max-w-4xl">
+            <div class="flex justify-between items-center mb-8">
+                <h1 class="text-3xl font-bold">"Backups"</h1>
+                <a href="/admin/dashboard" class="btn btn-secondary">"Back"</a>
+            </div>
+
+            <div class="flex flex-wrap items-center gap-4 mb-4">
+                <select
+                    class="border rounded px-2 py-1"
+                    on:change=move |ev| {
+                        if let Ok(f) = event_target_value(&ev).parse() {
+                            set_format.set(f);
+                        }
+                    }
+                >
+                    <option value="json">"JSON"</option>
+                    <option value="markdown">"Markdown"</option>
+                </select>
+                <label class="flex items-center gap-2 text-sm">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || include_media.get()
+                        on:change=move |ev| set_include_media.set(event_target_checked(&ev))
+                    />
+                    "Include media files"
+                </label>
+                <button class="btn btn-primary" disabled=move || busy.get() on:click=create>
+                    "Create backup"
+                </button>
+            </div>
+
+            <p class="text-sm text-sky-700 mb-4">{move || status.get()}</p>
+
+            <Suspense fallback=move || view! { <p>"Loading backups..."</p> }>
+                {move || {
+                    backups_resource.get().map(|res| match res {
+                        Ok(backups) if backups.is_empty() => {
+                            view! { <p class="text-gray-500">"No backups yet."</p> }.into_any()
+                        }
+                        Ok(backups) => view! {
+                            <div class="divide-y border rounded-lg overflow-hidden bg-white">
+                                {backups.into_iter().map(render_backup).collect_view()}
+                            </div>
+                        }.into_any(),
+                        Err(e) => view! { <p class="text-red-500">"Error loading backups: " {e.to_string()}</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
@@ -7,8 +7,10 @@ pub use types::{
 };
 
 use crate::api::articles::{get_article, save_article, Article};
+use crate::api::blog::{get_blog_post_for_edit, parse_tags, save_blog_post, BlogPost};
 use crate::components::media_picker::MediaPicker;
 use crate::components::rich_editor::RichTextEditor;
+use crate::utils::session::{current_role, ensure_session, has_session};
 use crate::utils::slug::sanitize_slug;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
@@ -19,8 +21,6 @@ use std::sync::Arc;
 pub fn AdminComposer() -> impl IntoView {
     let navigate = use_navigate();
     let location = use_location();
-    let (token, set_token) = signal(String::new());
-    let _ = &set_token;
 
     let (title, set_title) = signal(String::new());
     let (slug, set_slug) = signal(String::new());
@@ -30,6 +30,9 @@ pub fn AdminComposer() -> impl IntoView {
     let (byline, set_byline) = signal("By Jake Wray".to_string());
     let (content, set_content) = signal("<p>Start writing post...</p>".to_string());
     let (_post_status, set_post_status) = signal("draft".to_string());
+    // "article" publishes to /journalism, "blog" to /blog
+    let (post_kind, set_post_kind) = signal("article".to_string());
+    let (tags, set_tags) = signal(String::new());
 
     let (show_media_picker, set_show_media_picker) = signal(false);
     let (save_status, set_save_status) = signal(String::new());
@@ -40,6 +43,14 @@ pub fn AdminComposer() -> impl IntoView {
     let (show_schedule_modal, set_show_schedule_modal) = signal(false);
     let (scheduled_datetime, set_scheduled_datetime) = signal(current_iso_datetime_local());
 
+    // Contributors may only save drafts; the server enforces this as well
+    let (can_publish, set_can_publish) = signal(true);
+    Effect::new(move || {
+        if let Some(role) = current_role() {
+            set_can_publish.set(role.can_publish());
+        }
+    });
+
     // Flag to prevent overwriting during initial load
     let (is_loaded, set_is_loaded) = signal(false);
     let _ = &is_loaded;
@@ -48,24 +59,37 @@ pub fn AdminComposer() -> impl IntoView {
     Effect::new(move || {
         #[cfg(target_arch = "wasm32")]
         {
-            if let Ok(Some(storage)) = web_sys::window().unwrap().local_storage() {
-                if let Ok(Some(t)) = storage.get_item("admin_token") {
-                    if !t.is_empty() && !shared::auth::is_token_expired(&t) {
-                        set_token.set(t);
-                    } else {
-                        _nav_auth("/admin/login", Default::default());
-                    }
-                } else {
-                    _nav_auth("/admin/login", Default::default());
-                }
+            if !has_session() {
+                _nav_auth("/admin/login", Default::default());
             }
         }
     });
 
     // Check for query slug or load local draft on mount
     Effect::new(move || {
-        let query_slug = location.query.get().get("slug");
+        let query = location.query.get();
+        let is_blog = query.get("type").as_deref() == Some("blog");
+        if is_blog {
+            set_post_kind.set("blog".to_string());
+        }
+        let query_slug = query.get("slug");
         if let Some(s) = query_slug {
+            if !s.is_empty() && is_blog {
+                spawn_local(async move {
+                    if let Ok(Some(post)) = get_blog_post_for_edit(s).await {
+                        set_title.set(post.title);
+                        set_slug.set(post.slug);
+                        set_display_date.set(post.display_date);
+                        set_tags.set(post.tags.join(", "));
+                        set_content.set(post.content_html);
+                        if let Some(st) = post.status {
+                            set_post_status.set(st);
+                        }
+                        set_is_loaded.set(true);
+                    }
+                });
+                return;
+            }
             if !s.is_empty() {
                 spawn_local(async move {
                     if let Ok(Some(article)) = get_article(s).await {
@@ -113,6 +137,10 @@ pub fn AdminComposer() -> impl IntoView {
                             if !draft.content.is_empty() {
                                 set_content.set(draft.content);
                             }
+                            if !is_blog && !draft.kind.is_empty() {
+                                set_post_kind.set(draft.kind);
+                            }
+                            set_tags.set(draft.tags);
                             set_autosave_status.set(format!("Draft restored from local cache"));
                         }
                     }
@@ -135,6 +163,8 @@ pub fn AdminComposer() -> impl IntoView {
                 byline: byline.get(),
                 content: content.get(),
                 updated_at: current_iso_datetime_local(),
+                kind: post_kind.get(),
+                tags: tags.get(),
             };
 
             if let Ok(json_str) = serde_json::to_string(&draft) {
@@ -160,19 +190,13 @@ pub fn AdminComposer() -> impl IntoView {
         set_display_date.set(current_date_string());
         set_byline.set("By Jake Wray".to_string());
         set_content.set("<p>Start writing post...</p>".to_string());
+        set_tags.set(String::new());
         set_autosave_status.set("Local draft cleared".to_string());
     };
 
     let save_post_with_status = Arc::new(
         move |target_status: &'static str, scheduled_iso: Option<String>| {
-            let t = token.get();
-            if shared::auth::is_token_expired(&t) {
-                #[cfg(target_arch = "wasm32")]
-                if let Some(window) = web_sys::window() {
-                    if let Ok(Some(storage)) = window.local_storage() {
-                        let _ = storage.remove_item("admin_token");
-                    }
-                }
+            if !has_session() {
                 set_save_status.set("Session expired. Please log in again.".to_string());
                 return;
             }
@@ -201,7 +225,56 @@ pub fn AdminComposer() -> impl IntoView {
                 final_display_date.clone()
             };
 
+            if post_kind.get() == "blog" {
+                let new_post = BlogPost {
+                    slug: final_slug.clone(),
+                    title: post_title.clone(),
+                    iso_date: String::new(),
+                    display_date: date_for_db.clone(),
+                    content_html: content.get(),
+                    excerpt: String::new(),
+                    tags: parse_tags(&tags.get()),
+                    status: Some(target_status.to_string()),
+                };
+
+                spawn_local(async move {
+                    ensure_session().await;
+                    match save_blog_post(new_post).await {
+                        Ok(_) => {
+                            #[cfg(target_arch = "wasm32")]
+                            {
+                                if let Ok(Some(storage)) =
+                                    web_sys::window().unwrap().local_storage()
+                                {
+                                    let _ = storage.remove_item("composer_draft_data");
+                                }
+                            }
+
+                            if target_status == "published" {
+                                set_save_status
+                                    .set("Published successfully! Redirecting...".to_string());
+                                nav(&format!("/blog/{}", final_slug), Default::default());
+                            } else if target_status == "scheduled" {
+                                set_save_status
+                                    .set("Scheduled successfully! Redirecting...".to_string());
+                                nav("/admin/dashboard", Default::default());
+                            } else {
+                                set_save_status.set("Draft saved to database.".to_string());
+                                set_post_status.set("draft".to_string());
+                                set_is_saving.set(false);
+                            }
+                        }
+                        Err(e) => {
+                            set_save_status.set(format!("Error saving post: {}", e));
+                            set_is_saving.set(false);
+                        }
+                    }
+                });
+                return;
+            }
+
             let new_article = Article {
+                id: None,
                 slug: final_slug,
                 title: post_title,
                 iso_date: String::new(),
@@ -221,10 +294,12 @@ pub fn AdminComposer() -> impl IntoView {
                     Some(byline.get())
                 },
                 status: Some(target_status.to_string()),
+                image_variants: Default::default(),
             };
 
             spawn_local(async move {
-                match save_article(t, new_article).await {
+                ensure_session().await;
+                match save_article(new_article).await {
                     Ok(_) => {
                         // Clear local storage draft after successful save/publish/schedule
                         #[cfg(target_arch = "wasm32")]
@@ -237,7 +312,7 @@ pub fn AdminComposer() -> impl IntoView {
                         if target_status == "published" {
                             set_save_status
                                 .set("Published successfully! Redirecting...".to_string());
-                            nav("/blog", Default::default());
+                            nav("/journalism", Default::default());
                         } else if target_status == "scheduled" {
                             set_save_status
                                 .set("Scheduled successfully! Redirecting...".to_string());
@@ -292,6 +367,17 @@ pub fn AdminComposer() -> impl IntoView {
                         </div>
                     </div>
 
+                    <div class="form-group mb-6">
+                        <label class="block font-bold mb-2 text-gray-700">"Post Type"</label>
+                        <select class="w-full p-3 border rounded-lg bg-white"
+                            prop:value=move || post_kind.get()
+                            on:change=move |ev| set_post_kind.set(event_target_value(&ev))
+                        >
+                            <option value="article">"Journalism Article"</option>
+                            <option value="blog">"Blog Post"</option>
+                        </select>
+                    </div>
+
                     <div class="form-group mb-6">
                         <label class="block font-bold mb-2 text-gray-700">"Headline"</label>
                         <textarea class="w-full p-3 border rounded-lg text-2xl font-bold resize-none" rows="2"
@@ -310,7 +396,7 @@ pub fn AdminComposer() -> impl IntoView {
                         />
                     </div>
 
-                    <div class="form-group mb-6">
+                    <div class="form-group mb-6" class:hidden=move || post_kind.get() == "blog">
                         <label class="block font-bold mb-2 text-gray-700">"Photo"</label>
                         <div class="flex flex-col gap-4 mb-2">
                             {move || {
@@ -358,7 +444,6 @@ pub fn AdminComposer() -> impl IntoView {
                             Some(view! {
                                 <div class="mt-4 border rounded p-4 bg-gray-50">
                                     <MediaPicker
-                                        token=token.into()
                                         current_image=current
                                         on_select=move |url| {
                                             set_images.set(vec![url]);
@@ -370,7 +455,7 @@ pub fn AdminComposer() -> impl IntoView {
                         } else { None }}
                     </div>
 
-                    <div class="form-group mb-6">
+                    <div class="form-group mb-6" class:hidden=move || post_kind.get() == "blog">
                         <label class="block font-bold mb-2 text-gray-700">"Caption"</label>
                         <textarea class="w-full p-3 border rounded-lg resize-y" rows="2"
                             prop:value=caption.get()
@@ -387,7 +472,7 @@ pub fn AdminComposer() -> impl IntoView {
                         ></textarea>
                     </div>
 
-                    <div class="form-group mb-6">
+                    <div class="form-group mb-6" class:hidden=move || post_kind.get() == "blog">
                         <label class="block font-bold mb-2 text-gray-700">"Byline"</label>
                         <textarea class="w-full p-3 border rounded-lg resize-none font-bold" rows="1"
                             prop:value=byline.get()
@@ -395,6 +480,15 @@ pub fn AdminComposer() -> impl IntoView {
                         ></textarea>
                     </div>
 
+                    <div class="form-group mb-6" class:hidden=move || post_kind.get() != "blog">
+                        <label class="block font-bold mb-2 text-gray-700">"Tags"</label>
+                        <input type="text" class="w-full p-3 border rounded-lg text-sm"
+                            prop:value=move || tags.get()
+                            on:input=move |ev| set_tags.set(event_target_value(&ev))
+                            placeholder="Comma-separated, e.g. travel, rust"
+                        />
+                    </div>
+
                     <div class="form-group mb-6">
                         <label class="block font-bold mb-2 text-gray-700">"Article Text"</label>
                         <RichTextEditor
@@ -418,6 +512,7 @@ pub fn AdminComposer() -> impl IntoView {
                         <button
                             type="button"
                             class="btn btn-primary flex items-center gap-2"
+                            class:hidden=move || !can_publish.get()
                             on:click=move |_| save_pub("published", None)
                             disabled=move || is_saving.get()
                         >
@@ -430,6 +525,7 @@ pub fn AdminComposer() -> impl IntoView {
                         <button
                             type="button"
                             class="btn btn-secondary flex items-center gap-2"
+                            class:hidden=move || !can_publish.get()
                             on:click=move |_| set_show_schedule_modal.set(true)
                             disabled=move || is_saving.get()
                         >
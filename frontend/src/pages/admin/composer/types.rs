@@ -11,6 +11,10 @@ pub struct ComposerDraftData {
     pub byline: String,
     pub content: String,
     pub updated_at: String,
+    #[serde(default)]
+    pub kind: String,
+    #[serde(default)]
+    pub tags: String,
 }
 
 pub fn current_date_string() -> String {
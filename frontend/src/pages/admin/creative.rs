@@ -0,0 +1,251 @@
+use crate::api::creative::{
+    get_creative_work, save_creative_work, Chapter, CreativeType, CreativeWork,
+};
+use crate::components::rich_editor::RichTextEditor;
+use crate::pages::admin::composer::current_date_string;
+use crate::utils::session::{ensure_session, has_session};
+use crate::utils::slug::sanitize_slug;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos_router::hooks::{use_navigate, use_query_map};
+
+fn empty_chapter() -> Chapter {
+    Chapter {
+        title: String::new(),
+        content_html: "<p></p>".to_string(),
+    }
+}
+
+#[component]
+pub fn AdminCreativeEditor() -> impl IntoView {
+    let navigate = use_navigate();
+    let query = use_query_map();
+
+    let (title, set_title) = signal(String::new());
+    let (slug, set_slug) = signal(String::new());
+    let (work_type, set_work_type) = signal(CreativeType::Story);
+    let (synopsis, set_synopsis) = signal(String::new());
+    let (display_date, set_display_date) = signal(current_date_string());
+    let chapters = RwSignal::new(vec![empty_chapter()]);
+    let (selected, set_selected) = signal(0usize);
+    // Bumped whenever chapters are replaced wholesale so the editor re-mounts with new content
+    let (editor_key, set_editor_key) = signal(0u32);
+
+    let (save_status, set_save_status) = signal(String::new());
+    let (is_saving, set_is_saving) = signal(false);
+
+    let nav_auth = navigate.clone();
+    Effect::new(move || {
+        if !has_session() {
+            nav_auth("/admin/login", Default::default());
+        }
+    });
+
+    Effect::new(move || {
+        let query_slug = query.with(|q| q.get("slug"));
+        if let Some(s) = query_slug.filter(|s| !s.is_empty()) {
+            spawn_local(async move {
+                if let Ok(Some(work)) = get_creative_work(s).await {
+                    set_title.set(work.title);
+                    set_slug.set(work.slug);
+                    set_work_type.set(work.work_type);
+                    set_synopsis.set(work.synopsis);
+                    set_display_date.set(work.display_date);
+                    if !work.chapters.is_empty() {
+                        chapters.set(work.chapters);
+                    }
+                    set_selected.set(0);
+                    set_editor_key.update(|k| *k += 1);
+                }
+            });
+        }
+    });
+
+    let add_chapter = move |_| {
+        chapters.update(|c| c.push(empty_chapter()));
+        set_selected.set(chapters.with_untracked(|c| c.len() - 1));
+    };
+
+    let remove_chapter = move |_| {
+        let idx = selected.get_untracked();
+        chapters.update(|c| {
+            if c.len() > 1 {
+                c.remove(idx);
+            }
+        });
+        set_selected.set(idx.saturating_sub(1));
+    };
+
+    let save = move |target_status: &'static str| {
+        let work_title = title.get();
+        if work_title.trim().is_empty() {
+            set_save_status.set("Please enter a title.".to_string());
+            return;
+        }
+        let final_slug = if slug.get().trim().is_empty() {
+            sanitize_slug(&work_title)
+        } else {
+            sanitize_slug(&slug.get())
+        };
+
+        let kind = work_type.get();
+        let mut parts = chapters.get();
+        // Only novels are split into chapters; other works keep a single body
+        if kind != CreativeType::Novel {
+            parts.truncate(1);
+        }
+
+        let work = CreativeWork {
+            slug: final_slug.clone(),
+            title: work_title,
+            work_type: kind,
+            synopsis: synopsis.get(),
+            chapters: parts,
+            iso_date: String::new(),
+            display_date: display_date.get(),
+            status: Some(target_status.to_string()),
+        };
+
+        set_is_saving.set(true);
+        set_save_status.set(format!("Saving as {}...", target_status));
+        let nav = navigate.clone();
+        spawn_local(async move {
+            ensure_session().await;
+            match save_creative_work(work).await {
+                Ok(_) if target_status == "published" => {
+                    nav(&format!("/writing/{}", final_slug), Default::default());
+                }
+                Ok(_) => {
+                    set_slug.set(final_slug);
+                    set_save_status.set("Draft saved to database.".to_string());
+                    set_is_saving.set(false);
+                }
+                Err(e) => {
+                    set_save_status.set(format!("Error saving work: {}", e));
+                    set_is_saving.set(false);
+                }
+            }
+        });
+    };
+    let save_draft = save.clone();
+
+    view! {
+        <div class="container py-12 max-w-4xl">
+            <div class="flex justify-between items-center mb-8">
+                <h1 class="text-3xl font-bold">"Creative Work"</h1>
+                <a href="/admin/dashboard" class="btn btn-secondary">"Back to Dashboard"</a>
+            </div>
+
+            <div class="form-group mb-6">
+                <label class="block font-bold mb-2 text-gray-700">"Type"</label>
+                <select class="w-full p-3 border rounded-lg bg-white"
+                    prop:value=move || work_type.get().to_string()
+                    on:change=move |ev| {
+                        if let Ok(t) = event_target_value(&ev).parse() {
+                            set_work_type.set(t);
+                        }
+                    }
+                >
+                    <option value="story">"Story"</option>
+                    <option value="novel">"Novel"</option>
+                    <option value="poetry">"Poetry"</option>
+                </select>
+            </div>
+
+            <div class="form-group mb-6">
+                <label class="block font-bold mb-2 text-gray-700">"Title"</label>
+                <input type="text" class="w-full p-3 border rounded-lg text-lg"
+                    prop:value=move || title.get()
+                    on:input=move |ev| set_title.set(event_target_value(&ev))
+                />
+            </div>
+
+            <div class="form-group mb-6">
+                <label class="block font-bold mb-2 text-gray-700">"Slug (Optional)"</label>
+                <input type="text" class="w-full p-3 border rounded-lg text-sm text-gray-600"
+                    prop:value=move || slug.get()
+                    on:input=move |ev| set_slug.set(event_target_value(&ev))
+                    placeholder="Auto-generated from title if blank..."
+                />
+            </div>
+
+            <div class="form-group mb-6">
+                <label class="block font-bold mb-2 text-gray-700">"Synopsis"</label>
+                <textarea class="w-full p-3 border rounded-lg resize-y" rows="3"
+                    prop:value=move || synopsis.get()
+                    on:input=move |ev| set_synopsis.set(event_target_value(&ev))
+                ></textarea>
+            </div>
+
+            <div class="form-group mb-6">
+                <label class="block font-bold mb-2 text-gray-700">"Display Date"</label>
+                <input type="text" class="w-full p-3 border rounded-lg"
+                    prop:value=move || display_date.get()
+                    on:input=move |ev| set_display_date.set(event_target_value(&ev))
+                />
+            </div>
+
+            {move || (work_type.get() == CreativeType::Novel).then(|| view! {
+                <div class="form-group mb-4">
+                    <label class="block font-bold mb-2 text-gray-700">"Chapters"</label>
+                    <div class="flex flex-wrap gap-2 mb-3">
+                        {move || (0..chapters.with(|c| c.len())).map(|i| view! {
+                            <button type="button"
+                                class=move || if selected.get() == i { "btn btn-sm btn-primary" } else { "btn btn-sm btn-secondary" }
+                                on:click=move |_| set_selected.set(i)
+                            >
+                                {format!("{}", i + 1)}
+                            </button>
+                        }).collect_view()}
+                        <button type="button" class="btn btn-sm btn-secondary" on:click=add_chapter>"+ Add Chapter"</button>
+                        <button type="button" class="btn btn-sm btn-secondary text-red-600" on:click=remove_chapter>"Remove Chapter"</button>
+                    </div>
+                    <input type="text" class="w-full p-3 border rounded-lg"
+                        placeholder="Chapter title"
+                        prop:value=move || chapters.with(|c| c.get(selected.get()).map(|ch| ch.title.clone()).unwrap_or_default())
+                        on:input=move |ev| {
+                            let value = event_target_value(&ev);
+                            let idx = selected.get_untracked();
+                            chapters.update(|c| {
+                                if let Some(ch) = c.get_mut(idx) {
+                                    ch.title = value;
+                                }
+                            });
+                        }
+                    />
+                </div>
+            })}
+
+            <div class="form-group mb-6">
+                <label class="block font-bold mb-2 text-gray-700">"Text"</label>
+                {move || {
+                    editor_key.track();
+                    let idx = selected.get();
+                    let content = Signal::derive(move || {
+                        chapters.with(|c| c.get(idx).map(|ch| ch.content_html.clone()).unwrap_or_default())
+                    });
+                    view! {
+                        <RichTextEditor
+                            value=content
+                            on_change=move |new_val: String| chapters.update(|c| {
+                                if let Some(ch) = c.get_mut(idx) {
+                                    ch.content_html = new_val;
+                                }
+                            })
+                        />
+                    }
+                }}
+            </div>
+
+            <div class="flex items-center gap-3">
+                <button class="btn btn-secondary" disabled=move || is_saving.get() on:click=move |_| save_draft("draft")>
+                    "Save Draft"
+                </button>
+                <button class="btn btn-primary" disabled=move || is_saving.get() on:click=move |_| save("published")>
+                    "Publish"
+                </button>
+                <span class="text-sm text-gray-600">{move || save_status.get()}</span>
+            </div>
+        </div>
+    }
+}
@@ -1,71 +1,100 @@
 use crate::api::articles::{delete_article, get_drafts_and_scheduled, save_article, Article};
+use crate::api::blog::{delete_blog_post, get_blog_drafts_and_scheduled, BlogPost};
+use crate::api::creative::{delete_creative_work, get_creative_drafts_and_scheduled, CreativeWork};
+use crate::utils::session::{current_role, ensure_session, has_session};
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 #[cfg(target_arch = "wasm32")]
 use leptos_router::hooks::*;
+use shared::auth::Role;
 
 #[component]
 pub fn AdminDashboard() -> impl IntoView {
     #[cfg(target_arch = "wasm32")]
     let navigate = use_navigate();
-    let (token, set_token) = signal(String::new());
-    let _ = &set_token;
+    let (signed_in, set_signed_in) = signal(false);
     let (refresh_counter, set_refresh_counter) = signal(0);
     let (action_message, set_action_message) = signal(String::new());
+    let (role, set_role) = signal(None::<Role>);
+    // Hides controls the server would refuse anyway
+    let can_publish = move || role.get().is_some_and(Role::can_publish);
 
     #[cfg(target_arch = "wasm32")]
     let nav_auth = navigate.clone();
     Effect::new(move || {
-        #[cfg(target_arch = "wasm32")]
-        {
-            let window = web_sys::window().unwrap();
-            let local_storage = window.local_storage().unwrap().unwrap();
-            let tok = local_storage.get_item("admin_token").unwrap_or(None);
-
-            if let Some(ref t) = tok {
-                if shared::auth::is_token_expired(t) {
-                    let _ = local_storage.remove_item("admin_token");
-                    nav_auth("/admin/login", Default::default());
-                } else {
-                    set_token.set(t.clone());
-                }
-            } else {
-                nav_auth("/admin/login", Default::default());
-            }
+        if has_session() {
+            set_signed_in.set(true);
+            set_role.set(current_role());
+        } else {
+            #[cfg(target_arch = "wasm32")]
+            nav_auth("/admin/login", Default::default());
         }
     });
 
     let drafts_resource = Resource::new(
-        move || (token.get(), refresh_counter.get()),
-        |(t, _)| async move {
-            if t.is_empty() {
+        move || (signed_in.get(), refresh_counter.get()),
+        |(signed_in, _)| async move {
+            if !signed_in {
                 Ok(Vec::<Article>::new())
             } else {
-                get_drafts_and_scheduled(t).await
+                ensure_session().await;
+                get_drafts_and_scheduled().await
+            }
+        },
+    );
+
+    let blog_drafts_resource = Resource::new(
+        move || (signed_in.get(), refresh_counter.get()),
+        |(signed_in, _)| async move {
+            if !signed_in {
+                Ok(Vec::<DraftEntry>::new())
+            } else {
+                ensure_session().await;
+                get_blog_drafts_and_scheduled()
+                    .await
+                    .map(|posts| posts.into_iter().map(DraftEntry::from).collect())
+            }
+        },
+    );
+
+    let creative_drafts_resource = Resource::new(
+        move || (signed_in.get(), refresh_counter.get()),
+        |(signed_in, _)| async move {
+            if !signed_in {
+                Ok(Vec::<DraftEntry>::new())
+            } else {
+                ensure_session().await;
+                get_creative_drafts_and_scheduled()
+                    .await
+                    .map(|works| works.into_iter().map(DraftEntry::from).collect())
             }
         },
     );
 
     #[cfg(target_arch = "wasm32")]
     let logout = move |_| {
-        let window = web_sys::window().unwrap();
-        let local_storage = window.local_storage().unwrap().unwrap();
-        let _ = local_storage.remove_item("admin_token");
-        navigate("/admin/login", Default::default());
+        let navigate = navigate.clone();
+        spawn_local(async move {
+            // Revoke the session server-side; the local session is dropped either way
+            ensure_session().await;
+            let _ = gloo_net::http::Request::post("/admin/logout").send().await;
+            crate::utils::session::clear_session();
+            navigate("/admin/login", Default::default());
+        });
     };
 
     #[cfg(not(target_arch = "wasm32"))]
     let logout = move |_| {};
 
     let publish_now = move |mut article: Article| {
-        let tok = token.get();
-        if tok.is_empty() {
+        if !signed_in.get() {
             return;
         }
         set_action_message.set("Publishing...".to_string());
         article.status = Some("published".to_string());
         spawn_local(async move {
-            match save_article(tok, article).await {
+            ensure_session().await;
+            match save_article(article).await {
                 Ok(_) => {
                     set_action_message.set("Post published successfully!".to_string());
                     set_refresh_counter.update(|c| *c += 1);
@@ -78,13 +107,13 @@ pub fn AdminDashboard() -> impl IntoView {
     };
 
     let delete_draft = move |slug: String| {
-        let tok = token.get();
-        if tok.is_empty() {
+        if !signed_in.get() {
             return;
         }
         set_action_message.set("Deleting...".to_string());
         spawn_local(async move {
-            match delete_article(tok, slug).await {
+            ensure_session().await;
+            match delete_article(slug).await {
                 Ok(_) => {
                     set_action_message.set("Post deleted successfully!".to_string());
                     set_refresh_counter.update(|c| *c += 1);
@@ -96,11 +125,83 @@ pub fn AdminDashboard() -> impl IntoView {
         });
     };
 
+    let delete_blog_draft = move |slug: String| {
+        if !signed_in.get() {
+            return;
+        }
+        set_action_message.set("Deleting...".to_string());
+        spawn_local(async move {
+            ensure_session().await;
+            match delete_blog_post(slug).await {
+                Ok(_) => {
+                    set_action_message.set("Blog post deleted successfully!".to_string());
+                    set_refresh_counter.update(|c| *c += 1);
+                }
+                Err(e) => {
+                    set_action_message.set(format!("Error deleting blog post: {}", e));
+                }
+            }
+        });
+    };
+
+    let delete_creative_draft = move |slug: String| {
+        if !signed_in.get() {
+            return;
+        }
+        set_action_message.set("Deleting...".to_string());
+        spawn_local(async move {
+            ensure_session().await;
+            match delete_creative_work(slug).await {
+                Ok(_) => {
+                    set_action_message.set("Creative work deleted successfully!".to_string());
+                    set_refresh_counter.update(|c| *c += 1);
+                }
+                Err(e) => {
+                    set_action_message.set(format!("Error deleting creative work: {}", e));
+                }
+            }
+        });
+    };
+
     view! {
         <div class="container py-12">
             <div class="flex justify-between items-center mb-8">
                 <h1 class="text-4xl font-bold">"Admin Dashboard"</h1>
                 <div class="flex gap-2">
+                    <a
+                        href="/admin/users"
+                        class="btn btn-secondary"
+                        class:hidden=move || !role.get().is_some_and(Role::can_manage_users)
+                    >
+                        "Users"
+                    </a>
+                    <a
+                        href="/admin/lockouts"
+                        class="btn btn-secondary"
+                        class:hidden=move || !role.get().is_some_and(Role::can_manage_users)
+                    >
+                        "Lockouts"
+                    </a>
+                    <a
+                        href="/admin/audit"
+                        class="btn btn-secondary"
+                        class:hidden=move || !role.get().is_some_and(Role::can_manage_users)
+                    >
+                        "Audit Log"
+                    </a>
+                    <a
+                        href="/admin/backups"
+                        class="btn btn-secondary"
+                        class:hidden=move || !role.get().is_some_and(Role::can_manage_users)
+                    >
+                        "Backups"
+                    </a>
+                    <a href="/admin/sessions" class="btn btn-secondary">
+                        "Sessions"
+                    </a>
+                    <a href="/admin/security" class="btn btn-secondary">
+                        "Security"
+                    </a>
                     <a href="/admin/password-change" class="btn btn-secondary">
                         "Change Password"
                     </a>
@@ -138,6 +239,54 @@ pub fn AdminDashboard() -> impl IntoView {
                     <p class="text-gray-600 text-sm">"Upload and manage journalism photos and media."</p>
                 </a>
 
+                <a href="/admin/writing" class="card hover:shadow-md transition bg-white border p-6 rounded-xl">
+                    <div class="flex items-center gap-3 mb-2">
+                        <div class="p-2 bg-amber-50 text-amber-600 rounded-lg">
+                            <svg xmlns="http://www.w3.org/2000/svg" class="h-6 w-6" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M12 6.253v13m0-13C10.832 5.477 9.246 5 7.5 5S4.168 5.477 3 6.253v13C4.168 18.477 5.754 18 7.5 18s3.332.477 4.5 1.253m0-13C13.168 5.477 14.754 5 16.5 5c1.747 0 3.332.477 4.5 1.253v13C19.832 18.477 18.247 18 16.5 18c-1.746 0-3.332.477-4.5 1.253" />
+                            </svg>
+                        </div>
+                        <h3 class="text-xl font-bold">"Creative Writing"</h3>
+                    </div>
+                    <p class="text-gray-600 text-sm">"Write stories, poetry, and multi-chapter novels."</p>
+                </a>
+
+                <a href="/admin/projects" class="card hover:shadow-md transition bg-white border p-6 rounded-xl">
+                    <div class="flex items-center gap-3 mb-2">
+                        <div class="p-2 bg-slate-100 text-slate-700 rounded-lg">
+                            <svg xmlns="http://www.w3.org/2000/svg" class="h-6 w-6" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M10 20l4-16m4 4l4 4-4 4M6 16l-4-4 4-4" />
+                            </svg>
+                        </div>
+                        <h3 class="text-xl font-bold">"Projects"</h3>
+                    </div>
+                    <p class="text-gray-600 text-sm">"Curate the /code showcase and sync stats from GitHub."</p>
+                </a>
+
+                <a href="/admin/albums" class="card hover:shadow-md transition bg-white border p-6 rounded-xl" class:hidden=move || !can_publish()>
+                    <div class="flex items-center gap-3 mb-2">
+                        <div class="p-2 bg-rose-50 text-rose-600 rounded-lg">
+                            <svg xmlns="http://www.w3.org/2000/svg" class="h-6 w-6" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M4 16l4.586-4.586a2 2 0 012.828 0L16 16m-2-2l1.586-1.586a2 2 0 012.828 0L20 14m-6-6h.01M6 20h12a2 2 0 002-2V6a2 2 0 00-2-2H6a2 2 0 00-2 2v12a2 2 0 002 2z" />
+                            </svg>
+                        </div>
+                        <h3 class="text-xl font-bold">"Albums"</h3>
+                    </div>
+                    <p class="text-gray-600 text-sm">"Arrange gallery images into ordered photography and visual art albums."</p>
+                </a>
+
+                <a href="/admin/music" class="card hover:shadow-md transition bg-white border p-6 rounded-xl" class:hidden=move || !can_publish()>
+                    <div class="flex items-center gap-3 mb-2">
+                        <div class="p-2 bg-violet-50 text-violet-600 rounded-lg">
+                            <svg xmlns="http://www.w3.org/2000/svg" class="h-6 w-6" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M9 19V6l12-3v13M9 19c0 1.105-1.343 2-3 2s-3-.895-3-2 1.343-2 3-2 3 .895 3 2zm12-3c0 1.105-1.343 2-3 2s-3-.895-3-2 1.343-2 3-2 3 .895 3 2zM9 10l12-3" />
+                            </svg>
+                        </div>
+                        <h3 class="text-xl font-bold">"Music"</h3>
+                    </div>
+                    <p class="text-gray-600 text-sm">"Publish tracks with self-hosted audio or SoundCloud, Spotify and Bandcamp players."</p>
+                </a>
+
                 <a href="/about" class="card hover:shadow-md transition bg-white border p-6 rounded-xl">
                     <div class="flex items-center gap-3 mb-2">
                         <div class="p-2 bg-emerald-50 text-emerald-600 rounded-lg">
@@ -232,6 +381,7 @@ pub fn AdminDashboard() -> impl IntoView {
                                                         <button
                                                             type="button"
                                                             class="btn btn-sm btn-secondary text-emerald-700 hover:text-emerald-800 flex items-center gap-1"
+                                                            class:hidden=move || !can_publish()
                                                             on:click=move |_| publish_now(item_clone.clone())
                                                         >
                                                             <svg xmlns="http://www.w3.org/2000/svg" class="h-4 w-4" fill="none" viewBox="0 0 24 24" stroke="currentColor">
@@ -243,6 +393,7 @@ pub fn AdminDashboard() -> impl IntoView {
                                                         <button
                                                             type="button"
                                                             class="btn btn-sm btn-secondary text-red-600 hover:text-red-700 flex items-center gap-1"
+                                                            class:hidden=move || !can_publish()
                                                             on:click=move |_| delete_draft(slug_for_delete.clone())
                                                         >
                                                             <svg xmlns="http://www.w3.org/2000/svg" class="h-4 w-4" fill="none" viewBox="0 0 24 24" stroke="currentColor">
@@ -275,6 +426,133 @@ pub fn AdminDashboard() -> impl IntoView {
                     }}
                 </Suspense>
             </div>
+
+            <SimpleDraftList
+                heading="Blog Drafts"
+                description="Personal blog posts that are not yet public."
+                compose_href="/admin/compose?type=blog"
+                compose_label="Write a blog post →"
+                entries=Signal::derive(move || blog_drafts_resource.get())
+                on_delete=Callback::new(move |(slug,): (String,)| delete_blog_draft(slug))
+                can_delete=Signal::derive(can_publish)
+            />
+
+            <SimpleDraftList
+                heading="Creative Writing Drafts"
+                description="Stories, novels, and poetry that are not yet public."
+                compose_href="/admin/writing"
+                compose_label="Start a new work →"
+                entries=Signal::derive(move || creative_drafts_resource.get())
+                on_delete=Callback::new(move |(slug,): (String,)| delete_creative_draft(slug))
+                can_delete=Signal::derive(can_publish)
+            />
+        </div>
+    }
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct DraftEntry {
+    slug: String,
+    title: String,
+    status: String,
+    display_date: String,
+    edit_href: String,
+}
+
+impl From<BlogPost> for DraftEntry {
+    fn from(post: BlogPost) -> Self {
+        Self {
+            edit_href: format!("/admin/compose?type=blog&slug={}", post.slug),
+            slug: post.slug,
+            title: post.title,
+            status: post.status.unwrap_or_else(|| "draft".to_string()),
+            display_date: post.display_date,
+        }
+    }
+}
+
+impl From<CreativeWork> for DraftEntry {
+    fn from(work: CreativeWork) -> Self {
+        Self {
+            edit_href: format!("/admin/writing?slug={}", work.slug),
+            slug: work.slug,
+            title: work.title,
+            status: work.status.unwrap_or_else(|| "draft".to_string()),
+            display_date: work.display_date,
+        }
+    }
+}
+
+/// Compact drafts list used for content types that don't need the "Publish Now" shortcut.
+#[component]
+fn SimpleDraftList(
+    heading: &'static str,
+    description: &'static str,
+    compose_href: &'static str,
+    compose_label: &'static str,
+    #[prop(into)] entries: Signal<Option<Result<Vec<DraftEntry>, ServerFnError>>>,
+    on_delete: Callback<(String,)>,
+    can_delete: Signal<bool>,
+) -> impl IntoView {
+    view! {
+        <div class="bg-white border rounded-xl p-6 shadow-sm mb-8">
+            <div class="mb-6 pb-4 border-b">
+                <h2 class="text-2xl font-bold text-gray-900">{heading}</h2>
+                <p class="text-sm text-gray-500">{description}</p>
+            </div>
+
+            <Suspense fallback=move || view! { <p class="text-gray-500 py-4 text-center">"Loading drafts..."</p> }>
+                {move || {
+                    match entries.get() {
+                        Some(Ok(items)) if !items.is_empty() => {
+                            view! {
+                                <div class="divide-y border rounded-lg overflow-hidden">
+                                    {items.into_iter().map(|item| {
+                                        let slug_for_delete = item.slug.clone();
+                                        view! {
+                                            <div class="p-4 flex items-center justify-between gap-4 hover:bg-gray-50/80 transition-colors">
+                                                <div>
+                                                    <h4 class="text-lg font-bold text-gray-900">
+                                                        {if item.title.trim().is_empty() { "(Untitled)".to_string() } else { item.title }}
+                                                    </h4>
+                                                    <p class="text-xs text-gray-500">
+                                                        {if item.status == "scheduled" { format!("Scheduled for {}", item.display_date) } else { "Draft".to_string() }}
+                                                    </p>
+                                                </div>
+                                                <div class="flex items-center gap-2">
+                                                    <a href=item.edit_href class="btn btn-sm btn-secondary text-gray-700">
+                                                        "Edit"
+                                                    </a>
+                                                    <button
+                                                        type="button"
+                                                        class="btn btn-sm btn-secondary text-red-600 hover:text-red-700"
+                                                        class:hidden=move || !can_delete.get()
+                                                        on:click=move |_| on_delete.run((slug_for_delete.clone(),))
+                                                    >
+                                                        "Delete"
+                                                    </button>
+                                                </div>
+                                            </div>
+                                        }
+                                    }).collect::<Vec<_>>()}
+                                </div>
+                            }.into_any()
+                        }
+                        Some(Ok(_)) => {
+                            view! {
+                                <p class="text-center py-6 text-gray-500">
+                                    "No drafts. "
+                                    <a href=compose_href class="text-sky-600 font-semibold hover:underline">{compose_label}</a>
+                                </p>
+                            }.into_any()
+                        }
+                        Some(Err(e)) => {
+                            view! { <p class="text-red-600 py-4 text-center">{format!("Error loading drafts: {}", e)}</p> }.into_any()
+                        }
+                        None => view! { <p class="text-gray-500 py-4 text-center">"Loading..."</p> }.into_any(),
+                    }
+                }}
+            </Suspense>
         </div>
     }
 }
@@ -0,0 +1,96 @@
+use crate::utils::session::{admin_request, current_role, has_session};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use shared::auth::{LockoutInfo, Role, UnlockRequest};
+
+#[component]
+pub fn AdminLockouts() -> impl IntoView {
+    let (lockouts, set_lockouts) = signal(Vec::<LockoutInfo>::new());
+    let (message, set_message) = signal(String::new());
+
+    let load_lockouts = move || {
+        spawn_local(async move {
+            match admin_request::<Vec<LockoutInfo>>("GET", "/admin/api/lockouts", None).await {
+                Ok(list) => set_lockouts.set(list.unwrap_or_default()),
+                Err(e) => set_message.set(format!("Error loading lockouts: {}", e)),
+            }
+        });
+    };
+
+    Effect::new(move || {
+        if has_session() && current_role().is_some_and(Role::can_manage_users) {
+            load_lockouts();
+        } else {
+            #[cfg(target_arch = "wasm32")]
+            leptos_router::hooks::use_navigate()("/admin/dashboard", Default::default());
+        }
+    });
+
+    let unlock = move |kind: String, key: String| {
+        spawn_local(async move {
+            let request = UnlockRequest {
+                kind,
+                key: key.clone(),
+            };
+            match admin_request::<()>(
+                "POST",
+                "/admin/api/lockouts/unlock",
+                serde_json::to_value(&request).ok(),
+            )
+            .await
+            {
+                Ok(_) => set_message.set(format!("Unlocked {}.", key)),
+                Err(e) => set_message.set(format!("Error unlocking: {}", e)),
+            }
+            load_lockouts();
+        });
+    };
+
+    view! {
+        <div class="container py-12 max-w-3xl">
+            <div class="flex justify-between items-center mb-8">
+                <h1 class="text-3xl font-bold">"Login lockouts"</h1>
+                <a href="/admin/dashboard" class="btn btn-secondary">"Back"</a>
+            </div>
+
+            <p class="text-sm text-gray-600 mb-4">
+                "Client IPs and accounts with recent failed sign-ins. Repeated failures lock them out for a period that doubles each time; unlocking also clears the failure count."
+            </p>
+            <p class="text-sm text-sky-700 mb-4">{move || message.get()}</p>
+
+            <div class="divide-y border rounded-lg overflow-hidden bg-white">
+                {move || {
+                    let list = lockouts.get();
+                    if list.is_empty() {
+                        return view! { <p class="p-3 text-gray-500">"No recent failed sign-ins."</p> }
+                            .into_any();
+                    }
+                    list.into_iter().map(|entry| {
+                        let kind = entry.kind.clone();
+                        let key = entry.key.clone();
+                        let status = match &entry.locked_until {
+                            Some(until) => format!("Locked until {}", until),
+                            None => "Not locked".to_string(),
+                        };
+                        view! {
+                            <div class="p-3 flex items-center justify-between gap-4">
+                                <div>
+                                    <p class="font-semibold">
+                                        <span class="text-xs uppercase text-gray-500 mr-2">{entry.kind}</span>
+                                        {entry.key}
+                                    </p>
+                                    <p class="text-xs text-gray-500">
+                                        {format!("{} failures, last at {} · {}", entry.failures, entry.last_failure_at, status)}
+                                    </p>
+                                </div>
+                                <button class="btn btn-sm btn-secondary"
+                                    on:click=move |_| unlock(kind.clone(), key.clone())
+                                >"Unlock"</button>
+                            </div>
+                        }
+                    }).collect_view().into_any()
+                }}
+            </div>
+        </div>
+    }
+}
@@ -13,18 +13,15 @@ struct LoginRequest {
     password: String,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
-#[allow(dead_code)]
-struct LoginResponse {
-    token: String,
-}
-
 #[component]
 pub fn AdminLoginPage() -> impl IntoView {
     let (_username, _set_username) = signal("".to_string());
     let (_password, _set_password) = signal("".to_string());
     let (error, set_error) = signal("".to_string());
     let (_loading, _set_loading) = signal(false);
+    // Set once the password is accepted for a user with two-factor enabled
+    let (challenge, _set_challenge) = signal(String::new());
+    let (_code, _set_code) = signal(String::new());
 
     let location = use_location();
 
@@ -90,11 +87,19 @@ pub fn AdminLoginPage() -> impl IntoView {
                         &format!("[Login] Response status: {}", resp.status()).into(),
                     );
 
+                    if resp.status() == 202 {
+                        let data: shared::auth::TotpChallenge = resp
+                            .json()
+                            .await
+                            .map_err(|_| "Failed to parse response".to_string())?;
+                        return Ok(Some(data.challenge));
+                    }
+
                     if !resp.ok() {
                         return Err("Invalid username or password".to_string());
                     }
 
-                    let data: LoginResponse = resp.json().await.map_err(|e| {
+                    let expiry: shared::auth::SessionExpiry = resp.json().await.map_err(|e| {
                         #[cfg(debug_assertions)]
                         web_sys::console::log_1(&format!("[Login] Parse error: {:?}", e).into());
                         "Failed to parse response".to_string()
@@ -102,20 +107,19 @@ pub fn AdminLoginPage() -> impl IntoView {
 
                     #[cfg(debug_assertions)]
                     web_sys::console::log_1(
-                        &"[Login] Token received, storing in localStorage".into(),
+                        &"[Login] Session started, storing expiry in localStorage".into(),
                     );
 
-                    // Store token in localStorage
-                    let window = web_sys::window().unwrap();
-                    let local_storage = window.local_storage().unwrap().unwrap();
-                    let _ = local_storage.set_item("admin_token", &data.token);
+                    // The tokens themselves arrive as HttpOnly cookies
+                    crate::utils::session::store_session(&expiry);
 
-                    Ok(())
+                    Ok(None)
                 }
                 .await;
 
                 match result {
-                    Ok(()) => {
+                    Ok(Some(challenge)) => _set_challenge.set(challenge),
+                    Ok(None) => {
                         #[cfg(debug_assertions)]
                         web_sys::console::log_1(&"[Login] Success, navigating to dashboard".into());
                         navigate("/admin/dashboard", Default::default())
@@ -135,6 +139,105 @@ pub fn AdminLoginPage() -> impl IntoView {
     #[cfg(not(target_arch = "wasm32"))]
     let on_submit = move |_ev: leptos::ev::SubmitEvent| {};
 
+    #[cfg(target_arch = "wasm32")]
+    let on_code_submit = {
+        let navigate = use_navigate();
+        move |ev: leptos::ev::SubmitEvent| {
+            ev.prevent_default();
+            _set_loading.set(true);
+            set_error.set("".to_string());
+
+            let req = shared::auth::TotpLoginRequest {
+                challenge: challenge.get(),
+                code: _code.get(),
+            };
+            let navigate = navigate.clone();
+
+            spawn_local(async move {
+                let resp = match Request::post("/admin/login/totp").json(&req) {
+                    Ok(r) => r.send().await,
+                    Err(_) => {
+                        set_error.set("Failed to serialize request".to_string());
+                        _set_loading.set(false);
+                        return;
+                    }
+                };
+
+                match resp {
+                    Ok(r) if r.ok() => match r.json::<shared::auth::SessionExpiry>().await {
+                        Ok(expiry) => {
+                            crate::utils::session::store_session(&expiry);
+                            navigate("/admin/dashboard", Default::default());
+                        }
+                        Err(_) => set_error.set("Failed to parse response".to_string()),
+                    },
+                    // Too many wrong codes or too slow: start over from the password
+                    Ok(r) if r.status() == 410 => {
+                        _set_challenge.set(String::new());
+                        _set_code.set(String::new());
+                        set_error.set("Sign-in expired. Please log in again.".to_string());
+                    }
+                    Ok(_) => set_error.set("Invalid code".to_string()),
+                    Err(_) => set_error.set("Failed to connect to server".to_string()),
+                }
+                _set_loading.set(false);
+            });
+        }
+    };
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let on_code_submit = move |_ev: leptos::ev::SubmitEvent| {};
+
+    #[cfg(target_arch = "wasm32")]
+    let on_passkey = {
+        let navigate = use_navigate();
+        move |_| {
+            _set_loading.set(true);
+            set_error.set("".to_string());
+            let navigate = navigate.clone();
+
+            spawn_local(async move {
+                let result = async {
+                    let options: shared::auth::PasskeyLoginOptions =
+                        Request::post("/admin/webauthn/login/options")
+                            .send()
+                            .await
+                            .map_err(|_| "Failed to connect to server".to_string())?
+                            .json()
+                            .await
+                            .map_err(|_| "Failed to parse response".to_string())?;
+                    let assertion = crate::utils::passkey::get_passkey_assertion(&options).await?;
+
+                    let resp = Request::post("/admin/webauthn/login")
+                        .json(&assertion)
+                        .map_err(|_| "Failed to serialize request".to_string())?
+                        .send()
+                        .await
+                        .map_err(|_| "Failed to connect to server".to_string())?;
+                    if !resp.ok() {
+                        return Err("This passkey was not accepted".to_string());
+                    }
+                    let expiry: shared::auth::SessionExpiry = resp
+                        .json()
+                        .await
+                        .map_err(|_| "Failed to parse response".to_string())?;
+                    crate::utils::session::store_session(&expiry);
+                    Ok(())
+                }
+                .await;
+
+                match result {
+                    Ok(()) => navigate("/admin/dashboard", Default::default()),
+                    Err(msg) => set_error.set(msg),
+                }
+                _set_loading.set(false);
+            });
+        }
+    };
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let on_passkey = move |_: leptos::ev::MouseEvent| {};
+
     view! {
         <div class="center-page">
             <div class="card form-card">
@@ -149,7 +252,36 @@ pub fn AdminLoginPage() -> impl IntoView {
                     })
                 }}
 
-                <form autocomplete="on" method="post" action="/admin/login" on:submit=on_submit>
+                <Show when=move || !challenge.get().is_empty()>
+                    <form autocomplete="off" on:submit=on_code_submit>
+                        <div class="form-group">
+                            <label for="code">"Authentication code"</label>
+                            <input
+                                id="code"
+                                name="code"
+                                type="text"
+                                inputmode="numeric"
+                                placeholder="6-digit code or recovery code"
+                                autocomplete="one-time-code"
+                                prop:value=move || _code.get()
+                                on:input=move |ev| _set_code.set(event_target_value(&ev))
+                                disabled=move || _loading.get()
+                            />
+                        </div>
+
+                        <button type="submit" class="btn btn-primary" disabled=move || _loading.get()>
+                            {move || if _loading.get() { "Verifying..." } else { "Verify" }}
+                        </button>
+                    </form>
+                </Show>
+
+                <form
+                    autocomplete="on"
+                    method="post"
+                    action="/admin/login"
+                    on:submit=on_submit
+                    class:hidden=move || !challenge.get().is_empty()
+                >
                     <div class="form-group">
                         <label for="username">"Username"</label>
                         <input
@@ -179,6 +311,15 @@ pub fn AdminLoginPage() -> impl IntoView {
                     <button type="submit" class="btn btn-primary" disabled=move || _loading.get()>
                         {move || if _loading.get() { "Logging in..." } else { "Login" }}
                     </button>
+
+                    <button
+                        type="button"
+                        class="btn btn-secondary mt-4"
+                        on:click=on_passkey
+                        disabled=move || _loading.get()
+                    >
+                        "Sign in with a passkey"
+                    </button>
                 </form>
             </div>
         </div>
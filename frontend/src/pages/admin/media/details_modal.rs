@@ -0,0 +1,140 @@
+use crate::api::media::{update_media, MediaDetails, MediaItem};
+use crate::utils::session::ensure_session;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use shared::{MediaCategory, MediaContext};
+
+#[component]
+pub fn MediaDetailsModal(
+    item: MediaItem,
+    on_close: Callback<()>,
+    on_saved: Callback<MediaItem>,
+) -> impl IntoView {
+    let details = MediaDetails::from(&item);
+    let (title, set_title) = signal(details.title.unwrap_or_default());
+    let (description, set_description) = signal(details.description.unwrap_or_default());
+    let (alt_text, set_alt_text) = signal(details.alt_text.unwrap_or_default());
+    let (category, set_category) = signal(details.category);
+    let (context, set_context) = signal(details.context);
+    let (show_in_gallery, set_show_in_gallery) = signal(details.show_in_gallery);
+    let (saving, set_saving) = signal(false);
+    let (error_msg, set_error_msg) = signal(String::new());
+
+    let object_name = item.object_name.clone();
+    let save = move |_| {
+        let details = MediaDetails {
+            title: Some(title.get_untracked()),
+            description: Some(description.get_untracked()),
+            alt_text: Some(alt_text.get_untracked()),
+            category: category.get_untracked(),
+            context: context.get_untracked(),
+            show_in_gallery: show_in_gallery.get_untracked(),
+        };
+        let object_name = object_name.clone();
+        set_saving.set(true);
+        spawn_local(async move {
+            ensure_session().await;
+            match update_media(object_name, details).await {
+                Ok(item) => on_saved.run(item),
+                Err(e) => set_error_msg.set(format!("Error saving details: {}", e)),
+            }
+            set_saving.set(false);
+        });
+    };
+
+    view! {
+        <div
+            class="fixed inset-0 bg-black/50 backdrop-blur-sm flex items-center justify-center z-50 p-4"
+            on:click=move |ev| ev.stop_propagation()
+        >
+            <div class="bg-white rounded-xl shadow-2xl border max-w-lg w-full p-6">
+                <h3 class="text-xl font-bold mb-1 text-gray-900">"Media Details"</h3>
+                <p class="text-xs text-gray-500 mb-4 truncate" title=item.name.clone()>{item.name.clone()}</p>
+
+                <div class="flex flex-col gap-3 mb-4">
+                    <label class="block text-sm font-bold text-gray-700">
+                        "Title"
+                        <input
+                            type="text"
+                            class="w-full mt-1 p-2 border rounded-lg font-normal"
+                            prop:value=move || title.get()
+                            on:input=move |ev| set_title.set(event_target_value(&ev))
+                        />
+                    </label>
+                    <label class="block text-sm font-bold text-gray-700">
+                        "Alt text"
+                        <input
+                            type="text"
+                            class="w-full mt-1 p-2 border rounded-lg font-normal"
+                            placeholder="Describe the image for screen readers"
+                            prop:value=move || alt_text.get()
+                            on:input=move |ev| set_alt_text.set(event_target_value(&ev))
+                        />
+                    </label>
+                    <label class="block text-sm font-bold text-gray-700">
+                        "Description"
+                        <textarea
+                            class="w-full mt-1 p-2 border rounded-lg font-normal"
+                            rows="3"
+                            prop:value=move || description.get()
+                            on:input=move |ev| set_description.set(event_target_value(&ev))
+                        />
+                    </label>
+                    <div class="flex gap-3">
+                        <label class="flex-1 block text-sm font-bold text-gray-700">
+                            "Category"
+                            <select
+                                class="w-full mt-1 p-2 border rounded-lg font-normal bg-white"
+                                on:change=move |ev| {
+                                    if let Ok(c) = event_target_value(&ev).parse() {
+                                        set_category.set(c);
+                                    }
+                                }
+                            >
+                                {MediaCategory::ALL.into_iter().map(|c| view! {
+                                    <option value=c.to_string() selected=move || category.get() == c>{c.label()}</option>
+                                }).collect_view()}
+                            </select>
+                        </label>
+                        <label class="flex-1 block text-sm font-bold text-gray-700">
+                            "Context"
+                            <select
+                                class="w-full mt-1 p-2 border rounded-lg font-normal bg-white"
+                                on:change=move |ev| {
+                                    if let Ok(c) = event_target_value(&ev).parse() {
+                                        set_context.set(c);
+                                    }
+                                }
+                            >
+                                {MediaContext::ALL.into_iter().map(|c| view! {
+                                    <option value=c.to_string() selected=move || context.get() == c>{c.label()}</option>
+                                }).collect_view()}
+                            </select>
+                        </label>
+                    </div>
+                    <label class="flex items-center gap-2 text-sm text-gray-700">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || show_in_gallery.get()
+                            on:change=move |ev| set_show_in_gallery.set(event_target_checked(&ev))
+                        />
+                        "Show in the public gallery for this category"
+                    </label>
+                </div>
+
+                {move || (!error_msg.get().is_empty()).then(|| view! {
+                    <p class="text-sm text-red-600 mb-3">{error_msg.get()}</p>
+                })}
+
+                <div class="flex justify-end gap-3">
+                    <button type="button" class="btn btn-secondary" on:click=move |_| on_close.run(())>
+                        "Cancel"
+                    </button>
+                    <button type="button" class="btn btn-primary" disabled=move || saving.get() on:click=save>
+                        {move || if saving.get() { "Saving..." } else { "Save" }}
+                    </button>
+                </div>
+            </div>
+        </div>
+    }
+}
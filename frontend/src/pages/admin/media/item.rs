@@ -1,6 +1,9 @@
-use crate::api::media::MediaItem;
+use super::MediaDetailsModal;
+use crate::api::media::{set_metadata_stripping, MediaItem};
+use crate::utils::session::ensure_session;
 use leptos::ev;
 use leptos::prelude::*;
+use leptos::task::spawn_local;
 
 #[component]
 pub fn AdminMediaItem(
@@ -12,9 +15,52 @@ pub fn AdminMediaItem(
     let (aspect_ratio, set_aspect_ratio) = signal(1.5);
 
     let url = item.url.clone();
-    let name = item.name.clone();
-    let name_title = name.clone();
-    let is_video = url.to_lowercase().ends_with(".mp4");
+    let thumbnail_url = item.thumbnail_url.clone().unwrap_or_else(|| url.clone());
+    let is_video = item.is_video();
+    // Details edited in the modal show without reloading the library
+    let (current, set_current) = signal(item.clone());
+    let (editing, set_editing) = signal(false);
+    let display_name = move || current.with(|item| item.display_name().to_string());
+    let alt = move || {
+        current.with(|item| {
+            item.alt_text
+                .clone()
+                .unwrap_or_else(|| item.display_name().to_string())
+        })
+    };
+    let details = [
+        item.camera.clone(),
+        item.lens.clone(),
+        item.taken_at
+            .as_ref()
+            .map(|t| t.get(..10).unwrap_or(t).to_string()),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" · ");
+
+    // Only images that came with GPS or serial-number tags get the toggle
+    let object_name = item.object_name.clone();
+    let (strip, set_strip) = signal(item.strip_metadata);
+    let (strip_error, set_strip_error) = signal(None::<String>);
+    let toggle_strip = move |ev: ev::MouseEvent| {
+        ev.stop_propagation();
+        let Some(stripped) = strip.get_untracked() else {
+            return;
+        };
+        let object_name = object_name.clone();
+        spawn_local(async move {
+            ensure_session().await;
+            match set_metadata_stripping(object_name, !stripped).await {
+                Ok(()) => {
+                    set_strip.set(Some(!stripped));
+                    set_strip_error.set(None);
+                }
+                Err(e) => set_strip_error.set(Some(e.to_string())),
+            }
+        });
+    };
 
     let on_img_load = move |ev: ev::Event| {
         let img: web_sys::HtmlImageElement = event_target(&ev);
@@ -81,8 +127,8 @@ pub fn AdminMediaItem(
                 } else {
                     view! {
                         <img
-                            src=url.clone()
-                            alt=name.clone()
+                            src=thumbnail_url.clone()
+                            alt=alt
                             on:load=on_img_load
                         />
                     }.into_any()
@@ -91,8 +137,54 @@ pub fn AdminMediaItem(
 
             // Asset Details (Title/Name)
             <div class="pt-1 px-1 mt-auto">
-                <span class="text-xs font-semibold truncate text-gray-700 block" title=name_title>{name}</span>
+                <div class="flex items-center gap-1">
+                    <span class="text-xs font-semibold truncate text-gray-700 block flex-1" title=display_name>{display_name}</span>
+                    <button
+                        type="button"
+                        class="text-xs text-sky-700 hover:underline"
+                        on:click=move |ev| {
+                            ev.stop_propagation();
+                            set_editing.set(true);
+                        }
+                    >
+                        "Edit"
+                    </button>
+                </div>
+                <span class="text-xs text-gray-500 truncate block">
+                    {move || current.with(|item| {
+                        let gallery = if item.show_in_gallery { " · In gallery" } else { "" };
+                        format!("{} · {}{}", item.category.label(), item.context.label(), gallery)
+                    })}
+                </span>
+                {(!details.is_empty()).then(|| view! {
+                    <span class="text-xs text-gray-500 truncate block" title=details.clone()>{details.clone()}</span>
+                })}
+                {move || strip.get().map(|stripped| view! {
+                    <button
+                        class="text-xs underline"
+                        class:text-gray-500=stripped
+                        class:text-red-600=!stripped
+                        title="GPS location and camera/lens serial numbers in the public file"
+                        on:click=toggle_strip.clone()
+                    >
+                        {if stripped { "Location & serials: stripped" } else { "Location & serials: public" }}
+                    </button>
+                })}
+                {move || strip_error.get().map(|e| view! {
+                    <span class="text-xs text-red-500 block">{e}</span>
+                })}
             </div>
         </div>
+
+        {move || editing.get().then(|| view! {
+            <MediaDetailsModal
+                item=current.get_untracked()
+                on_close=Callback::new(move |_| set_editing.set(false))
+                on_saved=Callback::new(move |item| {
+                    set_current.set(item);
+                    set_editing.set(false);
+                })
+            />
+        })}
     }
 }
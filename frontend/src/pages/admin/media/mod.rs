@@ -1,20 +1,26 @@
+pub mod details_modal;
 pub mod item;
 
+pub use details_modal::MediaDetailsModal;
 pub use item::AdminMediaItem;
 
-use crate::api::media::{delete_media, list_media, upload_media, MediaItem};
+use crate::api::media::{delete_media, list_media, upload_media, MediaItem, MediaQuery};
+use crate::utils::session::{ensure_session, has_session};
 use leptos::ev;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
+use shared::{MediaCategory, MediaContext};
 use std::collections::HashSet;
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{FileList, HtmlInputElement};
 
 #[component]
 pub fn AdminMedia() -> impl IntoView {
-    let (token, set_token) = signal(String::new());
-    let _ = &set_token;
+    let (signed_in, set_signed_in) = signal(false);
     let (items, set_items) = signal(Vec::<MediaItem>::new());
+    let (total, set_total) = signal(0u32);
+    let (page, set_page) = signal(0u32);
+    let (page_count, set_page_count) = signal(1u32);
     let (loading, set_loading) = signal(true);
     let (uploading, set_uploading) = signal(false);
     let (upload_status, set_upload_status) = signal(String::new());
@@ -25,42 +31,40 @@ pub fn AdminMedia() -> impl IntoView {
 
     // Filter signals
     let (search_query, set_search_query) = signal(String::new());
-    let (filter_type, set_filter_type) = signal("all".to_string());
+    let (category_filter, set_category_filter) = signal(None::<MediaCategory>);
+    let (context_filter, set_context_filter) = signal(None::<MediaContext>);
 
-    // Check token on mount
+    // Check session on mount
     Effect::new(move || {
-        #[cfg(target_arch = "wasm32")]
-        {
-            if let Ok(Some(storage)) = web_sys::window().unwrap().local_storage() {
-                if let Ok(Some(t)) = storage.get_item("admin_token") {
-                    if !t.is_empty() && !shared::auth::is_token_expired(&t) {
-                        set_token.set(t);
-                    } else {
-                        let navigate = leptos_router::hooks::use_navigate();
-                        navigate("/admin/login", Default::default());
-                    }
-                } else {
-                    let navigate = leptos_router::hooks::use_navigate();
-                    navigate("/admin/login", Default::default());
-                }
-            }
+        if has_session() {
+            set_signed_in.set(true);
+        } else {
+            let navigate = leptos_router::hooks::use_navigate();
+            navigate("/admin/login", Default::default());
         }
     });
 
     let fetch_media = move || {
         set_loading.set(true);
-        let t = token.get();
-        if t.is_empty() {
+        if !signed_in.get() {
             set_loading.set(false);
             return;
         }
 
+        let query = MediaQuery {
+            search: Some(search_query.get_untracked()),
+            category: category_filter.get_untracked(),
+            context: context_filter.get_untracked(),
+            in_gallery: None,
+            page: page.get_untracked(),
+        };
         spawn_local(async move {
-            match list_media(t).await {
-                Ok(mut res) => {
-                    // Sort by name or date if possible, currently keep default order
-                    res.reverse(); // Newest first based on naming/timestamp convention
-                    set_items.set(res);
+            ensure_session().await;
+            match list_media(query).await {
+                Ok(res) => {
+                    set_total.set(res.total);
+                    set_page_count.set(res.page_count());
+                    set_items.set(res.items);
                 }
                 Err(e) => {
                     set_error_msg.set(format!("Error loading media: {}", e));
@@ -70,9 +74,13 @@ pub fn AdminMedia() -> impl IntoView {
         });
     };
 
-    // Trigger initial fetch when token is set
+    // Fetch once signed in, and again whenever the filters or page change
     Effect::new(move || {
-        if !token.get().is_empty() {
+        search_query.track();
+        category_filter.track();
+        context_filter.track();
+        page.track();
+        if signed_in.get() {
             fetch_media();
         }
     });
@@ -86,12 +94,12 @@ pub fn AdminMedia() -> impl IntoView {
                 return;
             }
 
-            let t = token.get();
             set_uploading.set(true);
             set_upload_status.set(format!("Uploading {} item(s)...", count));
 
             let input_clone = input.clone();
             spawn_local(async move {
+                ensure_session().await;
                 let mut success_count = 0;
                 let mut errors = Vec::new();
 
@@ -104,7 +112,7 @@ pub fn AdminMedia() -> impl IntoView {
                                 let uint8_array = js_sys::Uint8Array::new(&array_buffer);
                                 let bytes = uint8_array.to_vec();
 
-                                match upload_media(t.clone(), filename.clone(), bytes).await {
+                                match upload_media(filename.clone(), bytes).await {
                                     Ok(_) => success_count += 1,
                                     Err(e) => errors.push(format!("{}: {}", filename, e)),
                                 }
@@ -180,21 +188,26 @@ pub fn AdminMedia() -> impl IntoView {
         }
 
         set_is_deleting.set(true);
-        let t = token.get();
-
-        spawn_local(async move {
-            let mut failed = 0;
-            for url in selected.iter() {
-                let object_name = if let Some(path) =
-                    url.strip_prefix("https://storage.googleapis.com/jakewray-portfolio/")
-                {
-                    path.to_string()
+        let object_names: Vec<String> = items
+            .get()
+            .into_iter()
+            .filter(|item| selected.contains(&item.url))
+            .map(|item| {
+                if item.object_name.is_empty() {
+                    item.url
                 } else {
-                    url.clone()
-                };
+                    item.object_name
+                }
+            })
+            .collect();
 
-                if delete_media(t.clone(), object_name).await.is_err() {
-                    failed += 1;
+        spawn_local(async move {
+            ensure_session().await;
+            let mut errors = Vec::new();
+            for object_name in object_names {
+                // Items still used by an article are refused, with the articles named
+                if let Err(e) = delete_media(object_name).await {
+                    errors.push(e.to_string());
                 }
             }
 
@@ -202,8 +215,12 @@ pub fn AdminMedia() -> impl IntoView {
             set_selected_urls.set(HashSet::new());
             set_show_options_menu.set(false);
 
-            if failed > 0 {
-                set_error_msg.set(format!("Failed to delete {} item(s)", failed));
+            if !errors.is_empty() {
+                set_error_msg.set(format!(
+                    "Failed to delete {} item(s): {}",
+                    errors.len(),
+                    errors.join("; ")
+                ));
             } else {
                 set_upload_status.set("Deleted selected item(s) successfully.".to_string());
             }
@@ -212,33 +229,6 @@ pub fn AdminMedia() -> impl IntoView {
         });
     };
 
-    // Derived filtered media list
-    let filtered_items = move || {
-        let query = search_query.get().trim().to_lowercase();
-        let ftype = filter_type.get();
-
-        items
-            .get()
-            .into_iter()
-            .filter(|item| {
-                let matches_query = if query.is_empty() {
-                    true
-                } else {
-                    item.name.to_lowercase().contains(&query)
-                };
-
-                let is_video = item.url.to_lowercase().ends_with(".mp4");
-                let matches_type = match ftype.as_str() {
-                    "photos" => !is_video,
-                    "videos" => is_video,
-                    _ => true,
-                };
-
-                matches_query && matches_type
-            })
-            .collect::<Vec<_>>()
-    };
-
     view! {
         <div class="container py-12">
             // Header with back link and title
@@ -332,10 +322,13 @@ pub fn AdminMedia() -> impl IntoView {
                 <div class="w-full sm:w-72 relative">
                     <input
                         type="text"
-                        placeholder="Search assets by name..."
+                        placeholder="Search titles, descriptions, alt text..."
                         class="w-full text-sm py-2 px-3 pl-9 rounded-md border border-gray-300 focus:outline-none focus:ring-2 focus:ring-sky-500"
-                        prop:value=search_query.get()
-                        on:input=move |ev| set_search_query.set(event_target_value(&ev))
+                        prop:value=search_query.get_untracked()
+                        on:input=move |ev| {
+                            set_page.set(0);
+                            set_search_query.set(event_target_value(&ev));
+                        }
                     />
                     <svg xmlns="http://www.w3.org/2000/svg" class="h-4 w-4 text-gray-400 absolute left-3 top-3" fill="none" viewBox="0 0 24 24" stroke="currentColor">
                         <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M21 21l-6-6m2-5a7 7 0 11-14 0 7 7 0 0114 0z" />
@@ -345,23 +338,38 @@ pub fn AdminMedia() -> impl IntoView {
                 <div class="flex items-center gap-2 w-full sm:w-auto">
                     <select
                         class="text-sm py-2 px-3 rounded-md border border-gray-300 bg-white"
-                        prop:value=filter_type.get()
-                        on:change=move |ev| set_filter_type.set(event_target_value(&ev))
+                        on:change=move |ev| {
+                            set_page.set(0);
+                            set_category_filter.set(event_target_value(&ev).parse().ok());
+                        }
                     >
-                        <option value="all">"All Assets"</option>
-                        <option value="photos">"Photos Only"</option>
-                        <option value="videos">"Videos Only"</option>
+                        <option value="">"All Categories"</option>
+                        {MediaCategory::ALL.into_iter().map(|c| view! {
+                            <option value=c.to_string()>{c.label()}</option>
+                        }).collect_view()}
+                    </select>
+                    <select
+                        class="text-sm py-2 px-3 rounded-md border border-gray-300 bg-white"
+                        on:change=move |ev| {
+                            set_page.set(0);
+                            set_context_filter.set(event_target_value(&ev).parse().ok());
+                        }
+                    >
+                        <option value="">"Any Context"</option>
+                        {MediaContext::ALL.into_iter().map(|c| view! {
+                            <option value=c.to_string()>{c.label()}</option>
+                        }).collect_view()}
                     </select>
                 </div>
 
                 <div class="sm:ml-auto text-xs text-gray-500 font-medium">
                     {move || {
-                        let total = items.get().len();
-                        let shown = filtered_items().len();
+                        let total = total.get();
+                        let shown = items.get().len();
                         let sel = selected_urls.get().len();
                         if sel > 0 {
                             format!("Selected {} of {} assets", sel, total)
-                        } else if shown == total {
+                        } else if shown as u32 == total {
                             format!("Showing all {} assets", total)
                         } else {
                             format!("Showing {} of {} assets", shown, total)
@@ -409,7 +417,7 @@ pub fn AdminMedia() -> impl IntoView {
                         </div>
                     }.into_any()
                 } else {
-                    let list = filtered_items();
+                    let list = items.get();
                     if list.is_empty() {
                         view! {
                             <div class="py-16 text-center text-gray-500 bg-gray-50 border border-dashed rounded-xl">
@@ -442,6 +450,31 @@ pub fn AdminMedia() -> impl IntoView {
                     }
                 }
             }}
+
+            // Pagination
+            {move || (page_count.get() > 1).then(|| view! {
+                <div class="flex items-center justify-center gap-4 mt-8 text-sm">
+                    <button
+                        type="button"
+                        class="btn btn-secondary btn-sm"
+                        disabled=move || page.get() == 0
+                        on:click=move |_| set_page.update(|p| *p = p.saturating_sub(1))
+                    >
+                        "← Previous"
+                    </button>
+                    <span class="text-gray-600">
+                        {move || format!("Page {} of {}", page.get() + 1, page_count.get())}
+                    </span>
+                    <button
+                        type="button"
+                        class="btn btn-secondary btn-sm"
+                        disabled=move || page.get() + 1 >= page_count.get()
+                        on:click=move |_| set_page.update(|p| *p += 1)
+                    >
+                        "Next →"
+                    </button>
+                </div>
+            })}
         </div>
     }
 }
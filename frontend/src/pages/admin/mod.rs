@@ -1,5 +1,16 @@
+pub mod albums;
+pub mod audit;
+pub mod backups;
 pub mod composer;
+pub mod creative;
 pub mod dashboard;
+pub mod lockouts;
 pub mod login;
 pub mod media;
+pub mod music;
 pub mod password_change;
+pub mod projects;
+pub mod revisions;
+pub mod security;
+pub mod sessions;
+pub mod users;
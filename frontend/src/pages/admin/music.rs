@@ -0,0 +1,239 @@
+use crate::api::music::{delete_track, get_all_tracks, save_track, MusicTrack};
+use crate::utils::embed::{is_media_url, parse_embed, supported_providers};
+use crate::utils::session::{current_role, ensure_session, has_session};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use shared::auth::Role;
+
+#[component]
+pub fn AdminMusic() -> impl IntoView {
+    let (signed_in, set_signed_in) = signal(false);
+    let (refresh_counter, set_refresh_counter) = signal(0);
+    let (status, set_status) = signal(String::new());
+
+    // Form state; `editing_id` is empty while creating a new track
+    let (editing_id, set_editing_id) = signal(String::new());
+    let (title, set_title) = signal(String::new());
+    let (slug, set_slug) = signal(String::new());
+    let (description, set_description) = signal(String::new());
+    let (audio_url, set_audio_url) = signal(String::new());
+    let (embed_code, set_embed_code) = signal(String::new());
+    // Kept so re-saving a published track doesn't move its date
+    let (published_at, set_published_at) = signal(None::<String>);
+    let (is_published, set_is_published) = signal(false);
+
+    Effect::new(move || {
+        if has_session() && current_role().is_some_and(Role::can_publish) {
+            set_signed_in.set(true);
+        } else {
+            #[cfg(target_arch = "wasm32")]
+            leptos_router::hooks::use_navigate()("/admin/dashboard", Default::default());
+        }
+    });
+
+    let tracks_resource = Resource::new(
+        move || (signed_in.get(), refresh_counter.get()),
+        |(signed_in, _)| async move {
+            if !signed_in {
+                return Ok(Vec::new());
+            }
+            ensure_session().await;
+            get_all_tracks().await
+        },
+    );
+
+    // What the pasted embed code becomes, checked as it's typed
+    let embed_preview = move || {
+        let code = embed_code.get();
+        if code.trim().is_empty() {
+            return None;
+        }
+        Some(match parse_embed(&code) {
+            Some(embed) => (
+                "text-sm text-green-700",
+                format!("{} player: {}", embed.provider.label(), embed.src),
+            ),
+            None => (
+                "text-sm text-red-600",
+                format!("Not a supported embed. Use {}.", supported_providers()),
+            ),
+        })
+    };
+    let audio_invalid = move || {
+        let url = audio_url.get();
+        !url.trim().is_empty() && !is_media_url(&url)
+    };
+
+    let reset_form = move || {
+        set_editing_id.set(String::new());
+        set_title.set(String::new());
+        set_slug.set(String::new());
+        set_description.set(String::new());
+        set_audio_url.set(String::new());
+        set_embed_code.set(String::new());
+        set_published_at.set(None);
+        set_is_published.set(false);
+    };
+
+    let edit = move |track: MusicTrack| {
+        set_editing_id.set(track.id);
+        set_title.set(track.title);
+        set_slug.set(track.slug);
+        set_description.set(track.description.unwrap_or_default());
+        set_audio_url.set(track.audio_url.unwrap_or_default());
+        set_embed_code.set(track.embed_code.unwrap_or_default());
+        set_is_published.set(track.published_at.is_some());
+        set_published_at.set(track.published_at);
+    };
+
+    let on_save = move |_| {
+        let track = MusicTrack {
+            id: editing_id.get(),
+            slug: slug.get(),
+            title: title.get(),
+            description: Some(description.get()),
+            audio_url: Some(audio_url.get()),
+            embed_code: Some(embed_code.get()),
+            published_at: is_published
+                .get()
+                .then(|| published_at.get().unwrap_or_default()),
+            published_date: None,
+        };
+        set_status.set("Saving...".to_string());
+        spawn_local(async move {
+            ensure_session().await;
+            match save_track(track).await {
+                Ok(track) => {
+                    set_status.set(format!("Track saved as /music/{}.", track.slug));
+                    reset_form();
+                    set_refresh_counter.update(|c| *c += 1);
+                }
+                Err(e) => set_status.set(format!("Error saving track: {}", e)),
+            }
+        });
+    };
+
+    let on_delete = move |id: String| {
+        #[cfg(target_arch = "wasm32")]
+        {
+            if let Some(win) = web_sys::window() {
+                if !win
+                    .confirm_with_message("Are you sure you want to delete this track?")
+                    .unwrap_or(false)
+                {
+                    return;
+                }
+            }
+        }
+        spawn_local(async move {
+            ensure_session().await;
+            match delete_track(id).await {
+                Ok(_) => {
+                    set_status.set("Track deleted.".to_string());
+                    set_refresh_counter.update(|c| *c += 1);
+                }
+                Err(e) => set_status.set(format!("Error deleting track: {}", e)),
+            }
+        });
+    };
+
+    view! {
+        <div class="container py-12 max-w-4xl">
+            <div class="flex justify-between items-center mb-8">
+                <h1 class="text-3xl font-bold">"Music"</h1>
+                <a href="/admin/dashboard" class="btn btn-secondary">"Back to Dashboard"</a>
+            </div>
+
+            <p class="text-sm text-sky-700 mb-4">{move || status.get()}</p>
+
+            <div class="bg-white border rounded-xl p-6 shadow-sm mb-8">
+                <h2 class="text-xl font-bold mb-4">
+                    {move || if editing_id.get().is_empty() { "New Track" } else { "Edit Track" }}
+                </h2>
+                <div class="grid gap-4 md:grid-cols-2">
+                    <input type="text" class="p-3 border rounded-lg" placeholder="Title"
+                        prop:value=move || title.get()
+                        on:input=move |ev| set_title.set(event_target_value(&ev))
+                    />
+                    <input type="text" class="p-3 border rounded-lg" placeholder="Slug (made from the title if empty)"
+                        prop:value=move || slug.get()
+                        on:input=move |ev| set_slug.set(event_target_value(&ev))
+                    />
+                </div>
+                <textarea class="w-full p-3 border rounded-lg mt-4" rows="3" placeholder="Description"
+                    prop:value=move || description.get()
+                    on:input=move |ev| set_description.set(event_target_value(&ev))
+                ></textarea>
+                <input type="text" class="w-full p-3 border rounded-lg mt-4" placeholder="Audio URL, e.g. /media/uploads/track.mp3"
+                    prop:value=move || audio_url.get()
+                    on:input=move |ev| set_audio_url.set(event_target_value(&ev))
+                />
+                <p class="text-xs text-gray-500 mt-1">
+                    "Upload the file in the media library and paste its URL; it plays in the browser's own audio player."
+                </p>
+                {move || audio_invalid().then(|| view! {
+                    <p class="text-sm text-red-600">"Use a path on this site or an https:// URL."</p>
+                })}
+                <textarea class="w-full p-3 border rounded-lg mt-4 font-mono text-sm" rows="3"
+                    placeholder=format!("Embed code or share link from {}", supported_providers())
+                    prop:value=move || embed_code.get()
+                    on:input=move |ev| set_embed_code.set(event_target_value(&ev))
+                ></textarea>
+                {move || embed_preview().map(|(class, message)| view! {
+                    <p class=class>{message}</p>
+                })}
+                <label class="flex items-center gap-2 mt-4">
+                    <input type="checkbox"
+                        prop:checked=move || is_published.get()
+                        on:change=move |ev| set_is_published.set(event_target_checked(&ev))
+                    />
+                    "Published"
+                </label>
+                <div class="flex gap-2 mt-4">
+                    <button class="btn btn-primary" on:click=on_save>"Save Track"</button>
+                    <button class="btn btn-secondary" on:click=move |_| reset_form()>"Clear"</button>
+                </div>
+            </div>
+
+            <Suspense fallback=move || view! { <p>"Loading tracks..."</p> }>
+                {move || {
+                    tracks_resource.get().map(|res| match res {
+                        Ok(tracks) if tracks.is_empty() => {
+                            view! { <p class="text-gray-500">"No tracks yet."</p> }.into_any()
+                        }
+                        Ok(tracks) => view! {
+                            <div class="divide-y border rounded-lg overflow-hidden bg-white">
+                                {tracks.into_iter().map(|track| {
+                                    let id = track.id.clone();
+                                    let sources = [
+                                        track.audio_url.as_ref().map(|_| "Audio".to_string()),
+                                        track.embed().map(|e| e.provider.label().to_string()),
+                                    ]
+                                    .into_iter()
+                                    .flatten()
+                                    .collect::<Vec<_>>();
+                                    let label = format!(
+                                        "{} · {} · {}",
+                                        track.title,
+                                        track.published_date.clone().unwrap_or_else(|| "Draft".to_string()),
+                                        if sources.is_empty() { "No player".to_string() } else { sources.join(" + ") }
+                                    );
+                                    view! {
+                                        <div class="p-4 flex items-center justify-between gap-4">
+                                            <span class="font-semibold">{label}</span>
+                                            <div class="flex gap-2">
+                                                <button class="btn btn-sm btn-secondary" on:click=move |_| edit(track.clone())>"Edit"</button>
+                                                <button class="btn btn-sm btn-secondary text-red-600" on:click=move |_| on_delete(id.clone())>"Delete"</button>
+                                            </div>
+                                        </div>
+                                    }
+                                }).collect_view()}
+                            </div>
+                        }.into_any(),
+                        Err(e) => view! { <p class="text-red-500">"Error loading tracks: " {e.to_string()}</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
@@ -51,20 +51,10 @@ pub fn AdminPasswordChange() -> impl IntoView {
 
             let navigate = navigate.clone();
             spawn_local(async move {
-                let window = web_sys::window().unwrap();
-                let local_storage = window.local_storage().unwrap().unwrap();
-                let token = local_storage.get_item("admin_token").unwrap_or(None);
-
-                if token.is_none()
-                    || token
-                        .as_ref()
-                        .map_or(true, |t| shared::auth::is_token_expired(t))
-                {
-                    let _ = local_storage.remove_item("admin_token");
+                if !crate::utils::session::ensure_session().await {
                     navigate("/admin/login", Default::default());
                     return;
                 }
-                let token = token.unwrap();
 
                 let req = ChangePasswordRequest {
                     current_password: current,
@@ -102,7 +92,6 @@ pub fn AdminPasswordChange() -> impl IntoView {
                     // Let me check `backend/src/api/mod.rs`.
                     // If `login.rs` works with `/admin/login`, then the backend MUST be serving it there.
                     // I will check `backend/src/api/mod.rs` to see if it nests `admin::router`.
-                    .header("Authorization", &format!("Bearer {}", token))
                     .header("Content-Type", "application/json")
                     .json(&req)
                     .unwrap()
@@ -0,0 +1,193 @@
+use crate::api::blog::parse_tags;
+use crate::api::projects::{
+    delete_project, get_projects, save_project, sync_github_projects, Project,
+};
+use crate::utils::session::{ensure_session, has_session};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+#[cfg(target_arch = "wasm32")]
+use leptos_router::hooks::use_navigate;
+
+#[component]
+pub fn AdminProjects() -> impl IntoView {
+    let (refresh_counter, set_refresh_counter) = signal(0);
+    let (status, set_status) = signal(String::new());
+
+    // Form state; `editing_id` is empty while creating a new project
+    let (editing_id, set_editing_id) = signal(String::new());
+    let (name, set_name) = signal(String::new());
+    let (description, set_description) = signal(String::new());
+    let (github_url, set_github_url) = signal(String::new());
+    let (demo_url, set_demo_url) = signal(String::new());
+    let (technologies, set_technologies) = signal(String::new());
+    let (is_featured, set_is_featured) = signal(false);
+    let (stars, set_stars) = signal(0i64);
+
+    #[cfg(target_arch = "wasm32")]
+    let navigate = use_navigate();
+    Effect::new(move || {
+        if !has_session() {
+            #[cfg(target_arch = "wasm32")]
+            navigate("/admin/login", Default::default());
+        }
+    });
+
+    let projects_resource = Resource::new(move || refresh_counter.get(), |_| get_projects());
+
+    let reset_form = move || {
+        set_editing_id.set(String::new());
+        set_name.set(String::new());
+        set_description.set(String::new());
+        set_github_url.set(String::new());
+        set_demo_url.set(String::new());
+        set_technologies.set(String::new());
+        set_is_featured.set(false);
+        set_stars.set(0);
+    };
+
+    let edit = move |project: Project| {
+        set_editing_id.set(project.id);
+        set_name.set(project.name);
+        set_description.set(project.description);
+        set_github_url.set(project.github_url.unwrap_or_default());
+        set_demo_url.set(project.demo_url.unwrap_or_default());
+        set_technologies.set(project.technologies.join(", "));
+        set_is_featured.set(project.is_featured);
+        set_stars.set(project.stars);
+    };
+
+    let on_save = move |_| {
+        let project = Project {
+            id: editing_id.get(),
+            name: name.get(),
+            description: description.get(),
+            github_url: Some(github_url.get()),
+            demo_url: Some(demo_url.get()),
+            technologies: parse_tags(&technologies.get()),
+            stars: stars.get(),
+            is_featured: is_featured.get(),
+        };
+        set_status.set("Saving...".to_string());
+        spawn_local(async move {
+            ensure_session().await;
+            match save_project(project).await {
+                Ok(_) => {
+                    set_status.set("Project saved.".to_string());
+                    reset_form();
+                    set_refresh_counter.update(|c| *c += 1);
+                }
+                Err(e) => set_status.set(format!("Error saving project: {}", e)),
+            }
+        });
+    };
+
+    let on_delete = move |id: String| {
+        spawn_local(async move {
+            ensure_session().await;
+            match delete_project(id).await {
+                Ok(_) => {
+                    set_status.set("Project deleted.".to_string());
+                    set_refresh_counter.update(|c| *c += 1);
+                }
+                Err(e) => set_status.set(format!("Error deleting project: {}", e)),
+            }
+        });
+    };
+
+    let on_sync = move |_| {
+        set_status.set("Syncing with GitHub...".to_string());
+        spawn_local(async move {
+            ensure_session().await;
+            match sync_github_projects().await {
+                Ok(n) => {
+                    set_status.set(format!("Refreshed {} projects from GitHub.", n));
+                    set_refresh_counter.update(|c| *c += 1);
+                }
+                Err(e) => set_status.set(format!("GitHub sync failed: {}", e)),
+            }
+        });
+    };
+
+    view! {
+        <div class="container py-12 max-w-4xl">
+            <div class="flex justify-between items-center mb-8">
+                <h1 class="text-3xl font-bold">"Projects"</h1>
+                <div class="flex gap-2">
+                    <button class="btn btn-secondary" on:click=on_sync>"Sync from GitHub"</button>
+                    <a href="/admin/dashboard" class="btn btn-secondary">"Back to Dashboard"</a>
+                </div>
+            </div>
+
+            <p class="text-sm text-sky-700 mb-4">{move || status.get()}</p>
+
+            <div class="bg-white border rounded-xl p-6 shadow-sm mb-8">
+                <h2 class="text-xl font-bold mb-4">
+                    {move || if editing_id.get().is_empty() { "New Project" } else { "Edit Project" }}
+                </h2>
+                <div class="grid gap-4 md:grid-cols-2">
+                    <input type="text" class="p-3 border rounded-lg" placeholder="Name"
+                        prop:value=move || name.get()
+                        on:input=move |ev| set_name.set(event_target_value(&ev))
+                    />
+                    <input type="text" class="p-3 border rounded-lg" placeholder="Technologies (comma-separated)"
+                        prop:value=move || technologies.get()
+                        on:input=move |ev| set_technologies.set(event_target_value(&ev))
+                    />
+                    <input type="url" class="p-3 border rounded-lg" placeholder="https://github.com/owner/repo"
+                        prop:value=move || github_url.get()
+                        on:input=move |ev| set_github_url.set(event_target_value(&ev))
+                    />
+                    <input type="url" class="p-3 border rounded-lg" placeholder="Demo URL"
+                        prop:value=move || demo_url.get()
+                        on:input=move |ev| set_demo_url.set(event_target_value(&ev))
+                    />
+                </div>
+                <textarea class="w-full p-3 border rounded-lg mt-4" rows="3" placeholder="Description (refreshed from GitHub on sync)"
+                    prop:value=move || description.get()
+                    on:input=move |ev| set_description.set(event_target_value(&ev))
+                ></textarea>
+                <label class="flex items-center gap-2 mt-4">
+                    <input type="checkbox"
+                        prop:checked=move || is_featured.get()
+                        on:change=move |ev| set_is_featured.set(event_target_checked(&ev))
+                    />
+                    "Featured"
+                </label>
+                <div class="flex gap-2 mt-4">
+                    <button class="btn btn-primary" on:click=on_save>"Save Project"</button>
+                    <button class="btn btn-secondary" on:click=move |_| reset_form()>"Clear"</button>
+                </div>
+            </div>
+
+            <Suspense fallback=move || view! { <p>"Loading projects..."</p> }>
+                {move || {
+                    projects_resource.get().map(|res| match res {
+                        Ok(projects) => view! {
+                            <div class="divide-y border rounded-lg overflow-hidden bg-white">
+                                {projects.into_iter().map(|project| {
+                                    let id = project.id.clone();
+                                    let label = format!(
+                                        "{}{} · ★ {}",
+                                        project.name,
+                                        if project.is_featured { " (featured)" } else { "" },
+                                        project.stars
+                                    );
+                                    view! {
+                                        <div class="p-4 flex items-center justify-between gap-4">
+                                            <span class="font-semibold">{label}</span>
+                                            <div class="flex gap-2">
+                                                <button class="btn btn-sm btn-secondary" on:click=move |_| edit(project.clone())>"Edit"</button>
+                                                <button class="btn btn-sm btn-secondary text-red-600" on:click=move |_| on_delete(id.clone())>"Delete"</button>
+                                            </div>
+                                        </div>
+                                    }
+                                }).collect_view()}
+                            </div>
+                        }.into_any(),
+                        Err(e) => view! { <p class="text-red-500">"Error loading projects: " {e.to_string()}</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
@@ -0,0 +1,146 @@
+use crate::api::revisions::{
+    diff_revisions, list_revisions, restore_revision, RevisionKind, RevisionSummary,
+};
+use crate::utils::session::{ensure_session, has_session};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+#[cfg(target_arch = "wasm32")]
+use leptos_router::hooks::use_navigate;
+use leptos_router::hooks::use_query_map;
+
+#[component]
+pub fn AdminRevisions() -> impl IntoView {
+    let query = use_query_map();
+    let kind = move || {
+        query
+            .with(|q| q.get("kind"))
+            .and_then(|k| k.parse::<RevisionKind>().ok())
+            .unwrap_or(RevisionKind::Article)
+    };
+    let slug = move || query.with(|q| q.get("slug")).unwrap_or_default();
+
+    let (signed_in, set_signed_in) = signal(false);
+    let (refresh_counter, set_refresh_counter) = signal(0);
+    let (status, set_status) = signal(String::new());
+    let (diff_html, set_diff_html) = signal(String::new());
+
+    #[cfg(target_arch = "wasm32")]
+    let navigate = use_navigate();
+    Effect::new(move || {
+        if has_session() {
+            set_signed_in.set(true);
+        } else {
+            #[cfg(target_arch = "wasm32")]
+            navigate("/admin/login", Default::default());
+        }
+    });
+
+    let revisions_resource = Resource::new(
+        move || (signed_in.get(), kind(), slug(), refresh_counter.get()),
+        |(signed_in, kind, slug, _)| async move {
+            if !signed_in || slug.is_empty() {
+                Ok(Vec::new())
+            } else {
+                ensure_session().await;
+                list_revisions(kind, slug).await
+            }
+        },
+    );
+
+    let show_diff = move |from_id: String, to_id: String| {
+        let k = kind();
+        set_status.set("Loading diff...".to_string());
+        spawn_local(async move {
+            ensure_session().await;
+            match diff_revisions(k, from_id, to_id).await {
+                Ok(html) => {
+                    set_diff_html.set(html);
+                    set_status.set(String::new());
+                }
+                Err(e) => set_status.set(format!("Error loading diff: {}", e)),
+            }
+        });
+    };
+
+    let restore = move |revision_id: String| {
+        let k = kind();
+        set_status.set("Restoring...".to_string());
+        spawn_local(async move {
+            ensure_session().await;
+            match restore_revision(k, revision_id).await {
+                Ok(_) => {
+                    set_status.set("Revision restored.".to_string());
+                    set_diff_html.set(String::new());
+                    set_refresh_counter.update(|c| *c += 1);
+                }
+                Err(e) => set_status.set(format!("Error restoring revision: {}", e)),
+            }
+        });
+    };
+
+    let render_revision = move |(revision, previous): (
+        RevisionSummary,
+        Option<RevisionSummary>,
+    )| {
+        let to_id = revision.id.clone();
+        let restore_id = revision.id.clone();
+        view! {
+            <div class="p-4 flex items-center justify-between gap-4">
+                <div>
+                    <p class="font-semibold">{revision.title}</p>
+                    <p class="text-xs text-gray-500">{format!("{} · {}", revision.created_at, revision.author)}</p>
+                </div>
+                <div class="flex gap-2">
+                    {previous.map(|prev| view! {
+                        <button class="btn btn-sm btn-secondary"
+                            on:click=move |_| show_diff(prev.id.clone(), to_id.clone())
+                        >"Changes"</button>
+                    })}
+                    <button class="btn btn-sm btn-secondary" on:click=move |_| restore(restore_id.clone())>"Restore"</button>
+                </div>
+            </div>
+        }
+    };
+
+    view! {
+        <div class="container py-12 max-w-4xl">
+            <div class="flex justify-between items-center mb-8">
+                <h1 class="text-3xl font-bold">"Revision History"</h1>
+                <a href=move || kind().public_url(&slug()) class="btn btn-secondary">"Back"</a>
+            </div>
+
+            <p class="text-sm text-sky-700 mb-4">{move || status.get()}</p>
+
+            <Suspense fallback=move || view! { <p>"Loading revisions..."</p> }>
+                {move || {
+                    revisions_resource.get().map(|res| match res {
+                        Ok(revisions) if revisions.is_empty() => {
+                            view! { <p class="text-gray-500">"No revisions recorded yet."</p> }.into_any()
+                        }
+                        Ok(revisions) => {
+                            // Newest first: each revision is compared with the one saved before it
+                            let previous: Vec<Option<RevisionSummary>> = revisions.iter().skip(1).cloned().map(Some).chain(std::iter::once(None)).collect();
+                            let pairs: Vec<_> = revisions.into_iter().zip(previous).collect();
+                            view! {
+                                <div class="divide-y border rounded-lg overflow-hidden bg-white mb-8">
+                                    {pairs.into_iter().map(render_revision).collect_view()}
+                                </div>
+                            }.into_any()
+                        }
+                        Err(e) => view! { <p class="text-red-500">"Error loading revisions: " {e.to_string()}</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+
+            {move || (!diff_html.get().is_empty()).then(|| view! {
+                <div class="bg-white border rounded-xl p-6 shadow-sm">
+                    <div class="flex justify-between items-center mb-4">
+                        <h2 class="text-xl font-bold">"Changes"</h2>
+                        <button class="btn btn-sm btn-secondary" on:click=move |_| set_diff_html.set(String::new())>"Close"</button>
+                    </div>
+                    <div class="article-content revision-diff" inner_html=move || diff_html.get()></div>
+                </div>
+            })}
+        </div>
+    }
+}
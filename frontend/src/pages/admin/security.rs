@@ -0,0 +1,257 @@
+use crate::utils::passkey::create_passkey;
+use crate::utils::session::{admin_request, has_session};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use shared::auth::{
+    PasskeyInfo, PasskeyRegistrationOptions, RecoveryCodes, TotpCodeRequest, TotpSetup, TotpStatus,
+};
+
+#[component]
+pub fn AdminSecurity() -> impl IntoView {
+    let (totp_status, set_totp_status) = signal(None::<TotpStatus>);
+    let (setup, set_setup) = signal(None::<TotpSetup>);
+    let (recovery_codes, set_recovery_codes) = signal(Vec::<String>::new());
+    let (code, set_code) = signal(String::new());
+    let (message, set_message) = signal(String::new());
+    let (busy, set_busy) = signal(false);
+    let (passkeys, set_passkeys) = signal(Vec::<PasskeyInfo>::new());
+    let (passkey_name, set_passkey_name) = signal(String::new());
+
+    let load_status = move || {
+        spawn_local(async move {
+            match admin_request::<TotpStatus>("GET", "/admin/totp", None).await {
+                Ok(status) => set_totp_status.set(status),
+                Err(e) => set_message.set(format!("Error loading two-factor status: {}", e)),
+            }
+        });
+    };
+
+    let load_passkeys = move || {
+        spawn_local(async move {
+            match admin_request::<Vec<PasskeyInfo>>("GET", "/admin/webauthn/credentials", None)
+                .await
+            {
+                Ok(list) => set_passkeys.set(list.unwrap_or_default()),
+                Err(e) => set_message.set(format!("Error loading passkeys: {}", e)),
+            }
+        });
+    };
+
+    Effect::new(move || {
+        if has_session() {
+            load_status();
+            load_passkeys();
+        } else {
+            #[cfg(target_arch = "wasm32")]
+            leptos_router::hooks::use_navigate()("/admin/login", Default::default());
+        }
+    });
+
+    let start_setup = move |_| {
+        set_busy.set(true);
+        set_message.set(String::new());
+        set_recovery_codes.set(Vec::new());
+        spawn_local(async move {
+            match admin_request::<TotpSetup>("POST", "/admin/totp/setup", None).await {
+                Ok(data) => set_setup.set(data),
+                Err(e) => set_message.set(format!("Error starting setup: {}", e)),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    // Enabling, disabling and regenerating codes all take the current code as confirmation
+    let submit_code = move |url: &'static str, done: &'static str| {
+        let entered = code.get();
+        if entered.trim().is_empty() {
+            set_message.set("Enter a code from your authenticator app.".to_string());
+            return;
+        }
+        set_busy.set(true);
+        set_message.set(String::new());
+        spawn_local(async move {
+            match admin_request::<RecoveryCodes>(
+                "POST",
+                url,
+                serde_json::to_value(TotpCodeRequest { code: entered }).ok(),
+            )
+            .await
+            {
+                Ok(codes) => {
+                    set_recovery_codes.set(codes.map(|c| c.codes).unwrap_or_default());
+                    set_setup.set(None);
+                    set_code.set(String::new());
+                    set_message.set(done.to_string());
+                    load_status();
+                }
+                Err(e) => set_message.set(format!("Error: {}", e)),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    let add_passkey = move |_| {
+        let name = passkey_name.get();
+        set_busy.set(true);
+        set_message.set(String::new());
+        spawn_local(async move {
+            let result = async {
+                let options = admin_request::<PasskeyRegistrationOptions>(
+                    "POST",
+                    "/admin/webauthn/register/options",
+                    None,
+                )
+                .await?
+                .ok_or_else(|| "Missing passkey options".to_string())?;
+                let registration = create_passkey(&options, name).await?;
+                admin_request::<()>(
+                    "POST",
+                    "/admin/webauthn/register",
+                    serde_json::to_value(&registration).ok(),
+                )
+                .await
+            }
+            .await;
+            match result {
+                Ok(_) => {
+                    set_passkey_name.set(String::new());
+                    set_message.set("Passkey added.".to_string());
+                    load_passkeys();
+                }
+                Err(e) => set_message.set(format!("Error adding passkey: {}", e)),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    let remove_passkey = move |id: String| {
+        spawn_local(async move {
+            let url = format!("/admin/webauthn/credentials/{}", id);
+            match admin_request::<()>("DELETE", &url, None).await {
+                Ok(_) => {
+                    set_message.set("Passkey removed.".to_string());
+                    load_passkeys();
+                }
+                Err(e) => set_message.set(format!("Error removing passkey: {}", e)),
+            }
+        });
+    };
+
+    let code_input = move || {
+        view! {
+            <input
+                type="text"
+                inputmode="numeric"
+                autocomplete="one-time-code"
+                class="p-2 border rounded"
+                placeholder="123456"
+                prop:value=move || code.get()
+                on:input=move |ev| set_code.set(event_target_value(&ev))
+                disabled=move || busy.get()
+            />
+        }
+    };
+
+    view! {
+        <div class="container py-12 max-w-2xl">
+            <div class="flex justify-between items-center mb-8">
+                <h1 class="text-3xl font-bold">"Security"</h1>
+                <a href="/admin/dashboard" class="btn btn-secondary">"Back"</a>
+            </div>
+
+            <p class="text-sm text-sky-700 mb-4">{move || message.get()}</p>
+
+            <div class="card">
+                <h2 class="text-xl font-bold mb-4">"Two-factor authentication"</h2>
+                {move || match (totp_status.get(), setup.get()) {
+                    (None, _) => view! { <p>"Loading..."</p> }.into_any(),
+                    (Some(_), Some(pending)) => view! {
+                        <p class="mb-2">
+                            "Scan this QR code with your authenticator app. On your phone, open the link instead, or type the secret by hand."
+                        </p>
+                        <div class="w-52 mb-2" inner_html=pending.qr_code_svg.clone()></div>
+                        <p class="mb-2">
+                            <a href=pending.provisioning_uri.clone() class="text-blue-600 hover:underline">"Open in authenticator app"</a>
+                        </p>
+                        <p class="mb-4">"Secret: " <code>{pending.secret.clone()}</code></p>
+                        <div class="flex gap-2">
+                            {code_input()}
+                            <button class="btn btn-primary" disabled=move || busy.get()
+                                on:click=move |_| submit_code("/admin/totp/enable", "Two-factor authentication enabled.")
+                            >"Confirm"</button>
+                        </div>
+                    }.into_any(),
+                    (Some(status), None) if status.enabled => view! {
+                        <p class="mb-4">
+                            {format!("Enabled. {} unused recovery codes left.", status.recovery_codes_remaining)}
+                        </p>
+                        <p class="mb-2 text-sm text-gray-600">"Enter a current code to make changes."</p>
+                        <div class="flex flex-wrap gap-2">
+                            {code_input()}
+                            <button class="btn btn-secondary" disabled=move || busy.get()
+                                on:click=move |_| submit_code("/admin/totp/recovery-codes", "New recovery codes generated; the old ones no longer work.")
+                            >"New recovery codes"</button>
+                            <button class="btn btn-secondary text-red-600 hover:text-red-700" disabled=move || busy.get()
+                                on:click=move |_| submit_code("/admin/totp/disable", "Two-factor authentication disabled.")
+                            >"Disable"</button>
+                        </div>
+                    }.into_any(),
+                    (Some(_), None) => view! {
+                        <p class="mb-4">"Require a code from an authenticator app in addition to your password."</p>
+                        <button class="btn btn-primary" disabled=move || busy.get() on:click=start_setup>
+                            "Set up two-factor authentication"
+                        </button>
+                    }.into_any(),
+                }}
+
+                <Show when=move || !recovery_codes.get().is_empty()>
+                    <div class="mt-6 p-4 bg-yellow-50 border border-yellow-200 rounded">
+                        <p class="font-bold mb-2">"Recovery codes"</p>
+                        <p class="text-sm mb-2">
+                            "Each code signs you in once if you lose your authenticator. Store them somewhere safe; they will not be shown again."
+                        </p>
+                        <ul class="font-mono text-sm grid grid-cols-2 gap-1">
+                            {move || recovery_codes.get().into_iter().map(|c| view! { <li>{c}</li> }).collect_view()}
+                        </ul>
+                    </div>
+                </Show>
+            </div>
+
+            <div class="card mt-8">
+                <h2 class="text-xl font-bold mb-4">"Passkeys"</h2>
+                <p class="mb-4">"Sign in with your device's fingerprint, face or screen lock instead of a password."</p>
+                <div class="divide-y border rounded-lg overflow-hidden bg-white mb-4">
+                    {move || passkeys.get().into_iter().map(|passkey| {
+                        let id = passkey.id.clone();
+                        view! {
+                            <div class="p-3 flex items-center justify-between gap-4">
+                                <div>
+                                    <p class="font-semibold">{passkey.name}</p>
+                                    <p class="text-xs text-gray-500">
+                                        {format!(
+                                            "Added {} · last used {}",
+                                            passkey.created_at,
+                                            passkey.last_used_at.unwrap_or_else(|| "never".to_string())
+                                        )}
+                                    </p>
+                                </div>
+                                <button class="btn btn-sm btn-secondary" on:click=move |_| remove_passkey(id.clone())>"Remove"</button>
+                            </div>
+                        }
+                    }).collect_view()}
+                </div>
+                <div class="flex gap-2">
+                    <input
+                        type="text"
+                        class="p-2 border rounded"
+                        placeholder="Name, e.g. Laptop"
+                        prop:value=move || passkey_name.get()
+                        on:input=move |ev| set_passkey_name.set(event_target_value(&ev))
+                        disabled=move || busy.get()
+                    />
+                    <button class="btn btn-primary" disabled=move || busy.get() on:click=add_passkey>"Add passkey"</button>
+                </div>
+            </div>
+        </div>
+    }
+}
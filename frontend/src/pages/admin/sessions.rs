@@ -0,0 +1,128 @@
+use crate::api::sessions::{list_sessions, revoke_session, SessionInfo};
+use crate::utils::session::{ensure_session, has_session};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+#[cfg(target_arch = "wasm32")]
+use leptos_router::hooks::use_navigate;
+
+#[component]
+pub fn AdminSessions() -> impl IntoView {
+    let (signed_in, set_signed_in) = signal(false);
+    let (refresh_counter, set_refresh_counter) = signal(0);
+    let (status, set_status) = signal(String::new());
+
+    #[cfg(target_arch = "wasm32")]
+    let navigate = use_navigate();
+    #[cfg(target_arch = "wasm32")]
+    let nav_auth = navigate.clone();
+    Effect::new(move || {
+        if has_session() {
+            set_signed_in.set(true);
+        } else {
+            #[cfg(target_arch = "wasm32")]
+            nav_auth("/admin/login", Default::default());
+        }
+    });
+
+    let sessions_resource = Resource::new(
+        move || (signed_in.get(), refresh_counter.get()),
+        |(signed_in, _)| async move {
+            if !signed_in {
+                Ok(Vec::new())
+            } else {
+                ensure_session().await;
+                list_sessions().await
+            }
+        },
+    );
+
+    let revoke = move |jti: String| {
+        set_status.set("Revoking...".to_string());
+        spawn_local(async move {
+            ensure_session().await;
+            match revoke_session(jti).await {
+                Ok(_) => {
+                    set_status.set("Session revoked.".to_string());
+                    set_refresh_counter.update(|c| *c += 1);
+                }
+                Err(e) => set_status.set(format!("Error revoking session: {}", e)),
+            }
+        });
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    let logout_all = move |_| {
+        let navigate = navigate.clone();
+        set_status.set("Logging out all sessions...".to_string());
+        spawn_local(async move {
+            ensure_session().await;
+            let resp = gloo_net::http::Request::post("/admin/logout-all")
+                .send()
+                .await;
+            match resp {
+                Ok(r) if r.ok() => {
+                    crate::utils::session::clear_session();
+                    navigate("/admin/login", Default::default());
+                }
+                Ok(r) => set_status.set(format!("Error: HTTP {}", r.status())),
+                Err(e) => set_status.set(format!("Network error: {}", e)),
+            }
+        });
+    };
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let logout_all = move |_| {};
+
+    let render_session = move |session: SessionInfo| {
+        let jti = session.jti.clone();
+        view! {
+            <div class="p-4 flex items-center justify-between gap-4">
+                <div class="min-w-0">
+                    <p class="font-semibold">
+                        {session.ip.unwrap_or_else(|| "Unknown IP".to_string())}
+                        {session.current.then(|| view! { <span class="ml-2 text-xs text-green-700">"(this session)"</span> })}
+                    </p>
+                    <p class="text-xs text-gray-500 truncate">{session.user_agent.unwrap_or_default()}</p>
+                    <p class="text-xs text-gray-500">
+                        {format!("Signed in {} · last seen {} · expires {}", session.created_at, session.last_seen_at, session.expires_at)}
+                    </p>
+                </div>
+                {(!session.current).then(|| view! {
+                    <button class="btn btn-sm btn-secondary" on:click=move |_| revoke(jti.clone())>"Revoke"</button>
+                })}
+            </div>
+        }
+    };
+
+    view! {
+        <div class="container py-12 max-w-4xl">
+            <div class="flex justify-between items-center mb-8">
+                <h1 class="text-3xl font-bold">"Active Sessions"</h1>
+                <div class="flex gap-2">
+                    <a href="/admin/dashboard" class="btn btn-secondary">"Back"</a>
+                    <button class="btn btn-secondary text-red-600 hover:text-red-700" on:click=logout_all>
+                        "Log out all sessions"
+                    </button>
+                </div>
+            </div>
+
+            <p class="text-sm text-sky-700 mb-4">{move || status.get()}</p>
+
+            <Suspense fallback=move || view! { <p>"Loading sessions..."</p> }>
+                {move || {
+                    sessions_resource.get().map(|res| match res {
+                        Ok(sessions) if sessions.is_empty() => {
+                            view! { <p class="text-gray-500">"No active sessions."</p> }.into_any()
+                        }
+                        Ok(sessions) => view! {
+                            <div class="divide-y border rounded-lg overflow-hidden bg-white">
+                                {sessions.into_iter().map(render_session).collect_view()}
+                            </div>
+                        }.into_any(),
+                        Err(e) => view! { <p class="text-red-500">"Error loading sessions: " {e.to_string()}</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
@@ -0,0 +1,186 @@
+use crate::utils::session::{admin_request, current_role, has_session};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use shared::auth::{CreateUserRequest, Role, UpdateRoleRequest, UserInfo};
+
+#[component]
+pub fn AdminUsers() -> impl IntoView {
+    let (users, set_users) = signal(Vec::<UserInfo>::new());
+    let (message, set_message) = signal(String::new());
+    let (busy, set_busy) = signal(false);
+    let (username, set_username) = signal(String::new());
+    let (password, set_password) = signal(String::new());
+    let (new_role, set_new_role) = signal(Role::Contributor);
+
+    let load_users = move || {
+        spawn_local(async move {
+            match admin_request::<Vec<UserInfo>>("GET", "/admin/api/users", None).await {
+                Ok(list) => set_users.set(list.unwrap_or_default()),
+                Err(e) => set_message.set(format!("Error loading users: {}", e)),
+            }
+        });
+    };
+
+    Effect::new(move || {
+        if has_session() && current_role().is_some_and(Role::can_manage_users) {
+            load_users();
+        } else {
+            #[cfg(target_arch = "wasm32")]
+            leptos_router::hooks::use_navigate()("/admin/dashboard", Default::default());
+        }
+    });
+
+    let create_user = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        let request = CreateUserRequest {
+            username: username.get(),
+            password: password.get(),
+            role: new_role.get(),
+        };
+        set_busy.set(true);
+        set_message.set(String::new());
+        spawn_local(async move {
+            match admin_request::<()>(
+                "POST",
+                "/admin/api/users",
+                serde_json::to_value(&request).ok(),
+            )
+            .await
+            {
+                Ok(_) => {
+                    set_username.set(String::new());
+                    set_password.set(String::new());
+                    set_message.set(format!("Created {}.", request.username));
+                    load_users();
+                }
+                Err(e) => set_message.set(format!("Error creating user: {}", e)),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    let change_role = move |id: String, role: Role| {
+        spawn_local(async move {
+            let url = format!("/admin/api/users/{}/role", id);
+            match admin_request::<()>(
+                "POST",
+                &url,
+                serde_json::to_value(UpdateRoleRequest { role }).ok(),
+            )
+            .await
+            {
+                Ok(_) => {
+                    set_message.set("Role updated; that user has been signed out.".to_string())
+                }
+                Err(e) => set_message.set(format!("Error changing role: {}", e)),
+            }
+            load_users();
+        });
+    };
+
+    let remove_user = move |id: String| {
+        spawn_local(async move {
+            let url = format!("/admin/api/users/{}", id);
+            match admin_request::<()>("DELETE", &url, None).await {
+                Ok(_) => {
+                    set_message.set("User deleted.".to_string());
+                    load_users();
+                }
+                Err(e) => set_message.set(format!("Error deleting user: {}", e)),
+            }
+        });
+    };
+
+    let role_options = move |selected: Role| {
+        Role::ALL
+            .into_iter()
+            .map(|role| {
+                view! {
+                    <option value=role.as_str() selected=role == selected>{role.as_str()}</option>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <div class="container py-12 max-w-3xl">
+            <div class="flex justify-between items-center mb-8">
+                <h1 class="text-3xl font-bold">"Users"</h1>
+                <a href="/admin/dashboard" class="btn btn-secondary">"Back"</a>
+            </div>
+
+            <p class="text-sm text-gray-600 mb-4">
+                "Contributors can write drafts and upload media. Editors can also publish and delete content. Admins can also delete media and manage users."
+            </p>
+            <p class="text-sm text-sky-700 mb-4">{move || message.get()}</p>
+
+            <div class="divide-y border rounded-lg overflow-hidden bg-white mb-8">
+                {move || users.get().into_iter().map(|user| {
+                    let id_for_role = user.id.clone();
+                    let id_for_delete = user.id.clone();
+                    view! {
+                        <div class="p-3 flex items-center justify-between gap-4">
+                            <div>
+                                <p class="font-semibold">{user.username}</p>
+                                <p class="text-xs text-gray-500">{format!("Created {}", user.created_at)}</p>
+                            </div>
+                            <div class="flex items-center gap-2">
+                                <select
+                                    class="p-1 border rounded"
+                                    on:change=move |ev| {
+                                        if let Ok(role) = event_target_value(&ev).parse::<Role>() {
+                                            change_role(id_for_role.clone(), role);
+                                        }
+                                    }
+                                >
+                                    {role_options(user.role)}
+                                </select>
+                                <button class="btn btn-sm btn-secondary text-red-600 hover:text-red-700"
+                                    on:click=move |_| remove_user(id_for_delete.clone())
+                                >"Delete"</button>
+                            </div>
+                        </div>
+                    }
+                }).collect_view()}
+            </div>
+
+            <div class="card">
+                <h2 class="text-xl font-bold mb-4">"Add a user"</h2>
+                <form on:submit=create_user class="flex flex-col gap-3">
+                    <input
+                        type="text"
+                        class="p-2 border rounded"
+                        placeholder="Username"
+                        autocomplete="off"
+                        prop:value=move || username.get()
+                        on:input=move |ev| set_username.set(event_target_value(&ev))
+                        disabled=move || busy.get()
+                    />
+                    <input
+                        type="password"
+                        class="p-2 border rounded"
+                        placeholder="Initial password (at least 12 characters)"
+                        autocomplete="new-password"
+                        prop:value=move || password.get()
+                        on:input=move |ev| set_password.set(event_target_value(&ev))
+                        disabled=move || busy.get()
+                    />
+                    <select
+                        class="p-2 border rounded"
+                        on:change=move |ev| {
+                            if let Ok(role) = event_target_value(&ev).parse::<Role>() {
+                                set_new_role.set(role);
+                            }
+                        }
+                        disabled=move || busy.get()
+                    >
+                        {role_options(Role::Contributor)}
+                    </select>
+                    <button type="submit" class="btn btn-primary self-start" disabled=move || busy.get()>
+                        "Create user"
+                    </button>
+                </form>
+            </div>
+        </div>
+    }
+}
@@ -1,30 +1,47 @@
+use crate::api::blog::{delete_blog_post, get_blog_post, get_blog_posts, BlogPost};
+use crate::utils::session::{ensure_session, has_session};
 use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos_router::components::A;
+use leptos_router::hooks::use_params_map;
+
+fn render_blog_card(post: BlogPost) -> impl IntoView {
+    let href = format!("/blog/{}", post.slug);
+    let tags = post.tags;
+
+    view! {
+        <A href=href attr:class="card block mb-6 hover:shadow-lg transition-shadow">
+            <p class="text-sm text-gray-500 mb-1">{post.display_date}</p>
+            <h2 class="text-2xl font-bold mb-2">{post.title}</h2>
+            <p class="text-gray-700 mb-3">{post.excerpt}</p>
+            {(!tags.is_empty()).then(|| view! {
+                <div class="flex flex-wrap gap-2">
+                    {tags.into_iter().map(|tag| view! {
+                        <span class="text-xs bg-gray-100 text-gray-700 px-2 py-0.5 rounded-full">{tag}</span>
+                    }).collect_view()}
+                </div>
+            })}
+        </A>
+    }
+}
 
 #[component]
 pub fn PersonalBlogPage() -> impl IntoView {
     let (is_admin, _set_is_admin) = signal(false);
+    let posts_resource = Resource::new(|| (), |_| get_blog_posts());
 
     Effect::new(move || {
-        #[cfg(target_arch = "wasm32")]
-        {
-            if let Ok(Some(storage)) = web_sys::window().unwrap().local_storage() {
-                if let Ok(Some(t)) = storage.get_item("admin_token") {
-                    if !t.is_empty() && !shared::auth::is_token_expired(&t) {
-                        _set_is_admin.set(true);
-                    }
-                }
-            }
-        }
+        _set_is_admin.set(has_session());
     });
 
     view! {
-        <div class="container py-12">
+        <div class="container py-12 max-w-3xl">
             <div class="flex justify-between items-center mb-6">
                 <h1 class="text-4xl">"Blog"</h1>
                 {move || {
                     if is_admin.get() {
                         view! {
-                            <a href="/admin/compose" class="btn btn-primary">
+                            <a href="/admin/compose?type=blog" class="btn btn-primary">
                                 "Compose New Post"
                             </a>
                         }
@@ -34,7 +51,115 @@ pub fn PersonalBlogPage() -> impl IntoView {
                     }
                 }}
             </div>
-            <p class="text-muted">"Personal thoughts and musings."</p>
+            <p class="text-muted mb-8">"Personal thoughts and musings."</p>
+
+            <Suspense fallback=move || view! { <p>"Loading posts..."</p> }>
+                {move || {
+                    posts_resource.get().map(|res| match res {
+                        Ok(posts) if posts.is_empty() => {
+                            view! { <p class="text-gray-500">"No posts yet."</p> }.into_any()
+                        }
+                        Ok(posts) => {
+                            view! { <div>{posts.into_iter().map(render_blog_card).collect_view()}</div> }.into_any()
+                        }
+                        Err(e) => view! { <p class="text-red-500">"Error loading posts: " {e.to_string()}</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+pub fn BlogPostPage() -> impl IntoView {
+    let params = use_params_map();
+    let slug = move || params.with(|p| p.get("slug").map(|s| s.to_string()).unwrap_or_default());
+
+    let post_resource = Resource::new(slug, get_blog_post);
+
+    let (is_admin, _set_is_admin) = signal(false);
+
+    Effect::new(move || {
+        _set_is_admin.set(has_session());
+    });
+
+    let on_delete = move |slug: String| {
+        #[cfg(target_arch = "wasm32")]
+        {
+            if let Some(win) = web_sys::window() {
+                if !win
+                    .confirm_with_message("Are you sure you want to delete this post?")
+                    .unwrap_or(false)
+                {
+                    return;
+                }
+            }
+        }
+        spawn_local(async move {
+            ensure_session().await;
+            match delete_blog_post(slug).await {
+                Ok(_) => {
+                    let navigate = leptos_router::hooks::use_navigate();
+                    navigate("/blog", Default::default());
+                }
+                Err(e) => {
+                    #[cfg(target_arch = "wasm32")]
+                    let _ = web_sys::window()
+                        .unwrap()
+                        .alert_with_message(&format!("Error deleting: {}", e));
+                    #[cfg(not(target_arch = "wasm32"))]
+                    leptos::logging::error!("Error deleting: {}", e);
+                }
+            }
+        });
+    };
+
+    view! {
+        <div class="container py-12 max-w-3xl">
+            <Suspense fallback=move || view! { <p>"Loading post..."</p> }>
+                {move || {
+                    post_resource.get().map(|res| match res {
+                        Ok(Some(post)) => {
+                            let edit_href = format!("/admin/compose?type=blog&slug={}", post.slug);
+                            let delete_slug = post.slug.clone();
+                            let tags = post.tags.clone();
+
+                            view! {
+                                <article class="article-container">
+                                    {move || {
+                                        let edit_href = edit_href.clone();
+                                        let delete_slug = delete_slug.clone();
+                                        is_admin.get().then(move || view! {
+                                            <div class="mb-4 p-4 bg-gray-100 border rounded flex gap-2">
+                                                <span class="font-bold text-gray-500">"Admin Mode"</span>
+                                                <a href=edit_href class="btn btn-sm btn-primary">"Edit Post"</a>
+                                                <button class="btn btn-sm btn-secondary text-red-600" on:click=move |_| on_delete(delete_slug.clone())>
+                                                    "Delete Post"
+                                                </button>
+                                            </div>
+                                        })
+                                    }}
+                                    <h1 class="mb-2 text-4xl font-bold text-black">{post.title.clone()}</h1>
+                                    <p class="text-sm text-gray-500 mb-6">{post.display_date.clone()}</p>
+                                    <div class="article-content prose" inner_html=post.content_html.clone()></div>
+                                    {(!tags.is_empty()).then(|| view! {
+                                        <div class="flex flex-wrap gap-2 mt-8">
+                                            {tags.into_iter().map(|tag| view! {
+                                                <span class="text-xs bg-gray-100 text-gray-700 px-2 py-0.5 rounded-full">{tag}</span>
+                                            }).collect_view()}
+                                        </div>
+                                    })}
+                                    <div class="mt-10">
+                                        <A href="/blog" attr:class="text-sky-600 hover:underline">"← All posts"</A>
+                                    </div>
+                                </article>
+                            }.into_any()
+                        }
+                        Ok(None) => view! { <div><p>"Post not found."</p></div> }.into_any(),
+                        Err(e) => view! { <p class="text-red-500">"Error loading post: " {e.to_string()}</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
         </div>
     }
 }
@@ -1,63 +1,275 @@
+use crate::api::creative::{
+    delete_creative_work, get_creative_work, get_creative_works, CreativeType, CreativeWork,
+};
+use crate::utils::session::{ensure_session, has_session};
 use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos_router::components::A;
+use leptos_router::hooks::{use_params_map, use_query_map};
 
 #[component]
 pub fn PersonalPage() -> impl IntoView {
     view! {
         <div class="container py-12">
             <h1 class="text-4xl mb-6">"Personal"</h1>
-            <p class="text-gray-600 mb-8">"Blog, Creative Writing, Photography, and Videography."</p>
+            <p class="text-gray-600 mb-8">"Blog, Creative Writing, Photography, Visual Art, Videography, and Music."</p>
 
             <div class="grid gap-4 md:grid-cols-2 lg:grid-cols-3">
-                <a href="/personal/blog" class="card hover:shadow-lg transition-shadow">
+                <a href="/blog" class="card hover:shadow-lg transition-shadow">
                     <h3 class="text-xl font-bold mb-2">"Blog"</h3>
                     <p class="text-muted">"Personal thoughts and musings"</p>
                 </a>
 
-                <a href="/personal/writing" class="card hover:shadow-lg transition-shadow">
+                <a href="/writing" class="card hover:shadow-lg transition-shadow">
                     <h3 class="text-xl font-bold mb-2">"Creative Writing"</h3>
                     <p class="text-muted">"Stories, novels, and poetry"</p>
                 </a>
 
-                <div class="card opacity-50">
+                <a href="/photography" class="card hover:shadow-lg transition-shadow">
                     <h3 class="text-xl font-bold mb-2">"Photography"</h3>
-                    <p class="text-muted">"Coming soon"</p>
-                </div>
+                    <p class="text-muted">"Personal and professional photography"</p>
+                </a>
+
+                <a href="/visual-art" class="card hover:shadow-lg transition-shadow">
+                    <h3 class="text-xl font-bold mb-2">"Visual Art"</h3>
+                    <p class="text-muted">"Drawings and digital art"</p>
+                </a>
 
-                <div class="card opacity-50">
+                <a href="/video" class="card hover:shadow-lg transition-shadow">
                     <h3 class="text-xl font-bold mb-2">"Videography"</h3>
-                    <p class="text-muted">"Coming soon"</p>
-                </div>
+                    <p class="text-muted">"Personal and professional video"</p>
+                </a>
+
+                <a href="/music" class="card hover:shadow-lg transition-shadow">
+                    <h3 class="text-xl font-bold mb-2">"Music"</h3>
+                    <p class="text-muted">"Original compositions"</p>
+                </a>
             </div>
         </div>
     }
 }
 
-#[component]
-pub fn CreativeWritingPage() -> impl IntoView {
+fn creative_type_label(work_type: &CreativeType) -> &'static str {
+    match work_type {
+        CreativeType::Story => "Story",
+        CreativeType::Novel => "Novel",
+        CreativeType::Poetry => "Poetry",
+    }
+}
+
+fn render_creative_card(work: CreativeWork) -> impl IntoView {
+    let href = format!("/writing/{}", work.slug);
+    let chapter_count = work.chapters.len();
+    let meta = if work.work_type == CreativeType::Novel && chapter_count > 1 {
+        format!(
+            "{} · {} chapters",
+            creative_type_label(&work.work_type),
+            chapter_count
+        )
+    } else {
+        creative_type_label(&work.work_type).to_string()
+    };
+
     view! {
-        <div class="container py-12">
-            <h1 class="text-4xl mb-6">"Creative Writing"</h1>
-             <p class="text-muted">"Stories, Novels, and Poetry."</p>
-        </div>
+        <A href=href attr:class="card block mb-6 hover:shadow-lg transition-shadow">
+            <p class="text-xs uppercase tracking-wide text-gray-500 mb-1">{meta}</p>
+            <h2 class="text-2xl font-bold mb-2">{work.title}</h2>
+            <p class="text-gray-700">{work.synopsis}</p>
+        </A>
     }
 }
 
 #[component]
-pub fn MusicPage() -> impl IntoView {
+pub fn CreativeWritingPage() -> impl IntoView {
+    let query = use_query_map();
+    let filter = move || query.with(|q| q.get("type").and_then(|t| t.parse::<CreativeType>().ok()));
+    let works_resource = Resource::new(filter, get_creative_works);
+
+    let (is_admin, _set_is_admin) = signal(false);
+    Effect::new(move || {
+        _set_is_admin.set(has_session());
+    });
+
+    let tab_class = move |tab: Option<CreativeType>| {
+        move || {
+            if filter() == tab {
+                "px-3 py-1 rounded-full bg-gray-900 text-white text-sm"
+            } else {
+                "px-3 py-1 rounded-full bg-gray-100 text-gray-700 text-sm hover:bg-gray-200"
+            }
+        }
+    };
+
     view! {
-        <div class="container py-12">
-            <h1 class="text-4xl mb-6">"Music"</h1>
-             <p class="text-muted">"Original compositions."</p>
+        <div class="container py-12 max-w-3xl">
+            <div class="flex justify-between items-center mb-6">
+                <h1 class="text-4xl">"Creative Writing"</h1>
+                {move || is_admin.get().then(|| view! {
+                    <a href="/admin/writing" class="btn btn-primary">"New Work"</a>
+                })}
+            </div>
+            <p class="text-muted mb-6">"Stories, Novels, and Poetry."</p>
+
+            <nav class="flex gap-2 mb-8">
+                <A href="/writing" attr:class=tab_class(None)>"All"</A>
+                <A href="/writing?type=story" attr:class=tab_class(Some(CreativeType::Story))>"Stories"</A>
+                <A href="/writing?type=novel" attr:class=tab_class(Some(CreativeType::Novel))>"Novels"</A>
+                <A href="/writing?type=poetry" attr:class=tab_class(Some(CreativeType::Poetry))>"Poetry"</A>
+            </nav>
+
+            <Suspense fallback=move || view! { <p>"Loading..."</p> }>
+                {move || {
+                    works_resource.get().map(|res| match res {
+                        Ok(works) if works.is_empty() => {
+                            view! { <p class="text-gray-500">"Nothing here yet."</p> }.into_any()
+                        }
+                        Ok(works) => {
+                            view! { <div>{works.into_iter().map(render_creative_card).collect_view()}</div> }.into_any()
+                        }
+                        Err(e) => view! { <p class="text-red-500">"Error loading works: " {e.to_string()}</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
         </div>
     }
 }
 
 #[component]
-pub fn VisualArtPage() -> impl IntoView {
+pub fn CreativeWorkPage() -> impl IntoView {
+    let params = use_params_map();
+    let query = use_query_map();
+    let slug = move || params.with(|p| p.get("slug").map(|s| s.to_string()).unwrap_or_default());
+    // 1-based chapter number from `?chapter=`; defaults to the first chapter
+    let chapter = move || {
+        query.with(|q| {
+            q.get("chapter")
+                .and_then(|c| c.parse::<usize>().ok())
+                .unwrap_or(1)
+                .max(1)
+        })
+    };
+
+    let work_resource = Resource::new(slug, get_creative_work);
+
+    let (is_admin, _set_is_admin) = signal(false);
+
+    Effect::new(move || {
+        _set_is_admin.set(has_session());
+    });
+
+    let on_delete = move |slug: String| {
+        #[cfg(target_arch = "wasm32")]
+        {
+            if let Some(win) = web_sys::window() {
+                if !win
+                    .confirm_with_message("Are you sure you want to delete this work?")
+                    .unwrap_or(false)
+                {
+                    return;
+                }
+            }
+        }
+        spawn_local(async move {
+            ensure_session().await;
+            match delete_creative_work(slug).await {
+                Ok(_) => {
+                    let navigate = leptos_router::hooks::use_navigate();
+                    navigate("/writing", Default::default());
+                }
+                Err(e) => {
+                    #[cfg(target_arch = "wasm32")]
+                    let _ = web_sys::window()
+                        .unwrap()
+                        .alert_with_message(&format!("Error deleting: {}", e));
+                    #[cfg(not(target_arch = "wasm32"))]
+                    leptos::logging::error!("Error deleting: {}", e);
+                }
+            }
+        });
+    };
+
     view! {
-        <div class="container py-12">
-            <h1 class="text-4xl mb-6">"Visual Art"</h1>
-             <p class="text-muted">"Drawings and Digital Art."</p>
+        <div class="container py-12 max-w-3xl">
+            <Suspense fallback=move || view! { <p>"Loading..."</p> }>
+                {move || {
+                    work_resource.get().map(|res| match res {
+                        Ok(Some(work)) => {
+                            let edit_href = format!("/admin/writing?slug={}", work.slug);
+                            let delete_slug = work.slug.clone();
+                            let total = work.chapters.len();
+                            let index = chapter().min(total.max(1)) - 1;
+                            let current = work.chapters.get(index).cloned().unwrap_or_default();
+                            let base = format!("/writing/{}", work.slug);
+                            let chapter_href = move |n: usize| format!("{}?chapter={}", base, n);
+
+                            let toc = (total > 1).then(|| {
+                                let links = work
+                                    .chapters
+                                    .iter()
+                                    .enumerate()
+                                    .map(|(i, c)| {
+                                        let label = if c.title.is_empty() {
+                                            format!("Chapter {}", i + 1)
+                                        } else {
+                                            c.title.clone()
+                                        };
+                                        let class = if i == index { "font-bold" } else { "text-sky-600 hover:underline" };
+                                        view! { <li><A href=chapter_href(i + 1) attr:class=class>{label}</A></li> }
+                                    })
+                                    .collect_view();
+                                view! {
+                                    <details class="mb-8 border rounded p-4">
+                                        <summary class="cursor-pointer font-bold">"Chapters"</summary>
+                                        <ol class="list-decimal ml-6 mt-2 space-y-1">{links}</ol>
+                                    </details>
+                                }
+                            });
+
+                            let pager = (total > 1).then(|| view! {
+                                <div class="flex justify-between mt-10">
+                                    {(index > 0).then(|| view! {
+                                        <A href=chapter_href(index) attr:class="text-sky-600 hover:underline">"← Previous chapter"</A>
+                                    })}
+                                    <span></span>
+                                    {(index + 1 < total).then(|| view! {
+                                        <A href=chapter_href(index + 2) attr:class="text-sky-600 hover:underline">"Next chapter →"</A>
+                                    })}
+                                </div>
+                            });
+
+                            view! {
+                                <article class="article-container">
+                                    {move || {
+                                        let edit_href = edit_href.clone();
+                                        let delete_slug = delete_slug.clone();
+                                        is_admin.get().then(move || view! {
+                                            <div class="mb-4 p-4 bg-gray-100 border rounded flex gap-2">
+                                                <span class="font-bold text-gray-500">"Admin Mode"</span>
+                                                <a href=edit_href class="btn btn-sm btn-primary">"Edit Work"</a>
+                                                <button class="btn btn-sm btn-secondary text-red-600" on:click=move |_| on_delete(delete_slug.clone())>
+                                                    "Delete Work"
+                                                </button>
+                                            </div>
+                                        })
+                                    }}
+                                    <p class="text-xs uppercase tracking-wide text-gray-500 mb-1">{creative_type_label(&work.work_type)}</p>
+                                    <h1 class="mb-2 text-4xl font-bold text-black">{work.title}</h1>
+                                    <p class="text-gray-600 italic mb-6">{work.synopsis}</p>
+                                    {toc}
+                                    {(!current.title.is_empty()).then(|| view! { <h2 class="text-2xl font-bold mb-4">{current.title}</h2> })}
+                                    <div class="article-content prose" inner_html=current.content_html></div>
+                                    {pager}
+                                    <div class="mt-10">
+                                        <A href="/writing" attr:class="text-sky-600 hover:underline">"← All writing"</A>
+                                    </div>
+                                </article>
+                            }.into_any()
+                        }
+                        Ok(None) => view! { <div><p>"Work not found."</p></div> }.into_any(),
+                        Err(e) => view! { <p class="text-red-500">"Error loading work: " {e.to_string()}</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
         </div>
     }
 }
@@ -0,0 +1,258 @@
+use crate::api::gallery::{get_album, get_gallery, list_albums, Album, GalleryPage};
+use crate::components::lightbox::Lightbox;
+use crate::components::responsive_image::ResponsiveImage;
+use leptos::prelude::*;
+use leptos_router::components::A;
+use leptos_router::hooks::{use_params_map, use_query_map};
+use shared::{MediaCategory, MediaContext};
+
+const TILE_SIZES: &str = "(min-width: 1024px) 25vw, (min-width: 640px) 33vw, 50vw";
+
+/// `?page=` is 1-based in the URL and zero-based for the server.
+pub fn query_page(page: Option<String>) -> u32 {
+    page.and_then(|p| p.parse::<u32>().ok()).unwrap_or(1).max(1) - 1
+}
+
+pub fn page_href(base: &str, context: Option<MediaContext>, page: u32) -> String {
+    let mut params = Vec::new();
+    if let Some(context) = context {
+        params.push(format!("context={}", context));
+    }
+    if page > 0 {
+        params.push(format!("page={}", page + 1));
+    }
+    if params.is_empty() {
+        base.to_string()
+    } else {
+        format!("{}?{}", base, params.join("&"))
+    }
+}
+
+/// Thumbnail grid that opens a lightbox, with links to the other pages.
+/// Tiles link to the full image so the grid also works before hydration.
+fn render_gallery_grid(
+    page: GalleryPage,
+    page_href: impl Fn(u32) -> String + 'static,
+) -> impl IntoView {
+    let current = RwSignal::new(None::<usize>);
+    let page_count = page.page_count();
+    let current_page = page.page;
+
+    let tiles = page
+        .items
+        .iter()
+        .cloned()
+        .enumerate()
+        .map(|(i, item)| {
+            let alt = item.alt();
+            view! {
+                <a
+                    href=item.url.clone()
+                    class="gallery-tile"
+                    title=item.title.clone()
+                    on:click=move |ev| {
+                        ev.prevent_default();
+                        current.set(Some(i));
+                    }
+                >
+                    <ResponsiveImage src=item.url.clone() alt=alt variants=item.variants.clone() sizes=TILE_SIZES/>
+                </a>
+            }
+        })
+        .collect_view();
+
+    let pager = (page_count > 1).then(|| {
+        view! {
+            <nav class="flex justify-between items-center mt-8" aria-label="Gallery pages">
+                {(current_page > 0).then(|| view! {
+                    <A href=page_href(current_page - 1) attr:class="text-sky-600 hover:underline">"← Newer"</A>
+                })}
+                <span class="text-sm text-gray-500">{format!("Page {} of {}", current_page + 1, page_count)}</span>
+                {(current_page + 1 < page_count).then(|| view! {
+                    <A href=page_href(current_page + 1) attr:class="text-sky-600 hover:underline">"Older →"</A>
+                })}
+            </nav>
+        }
+    });
+
+    view! {
+        <div class="gallery-grid">{tiles}</div>
+        {pager}
+        <Lightbox items=page.items current=current/>
+    }
+}
+
+fn render_album_card(album: Album, base: &'static str) -> impl IntoView {
+    let href = format!("{}/albums/{}", base, album.slug);
+    let count = match album.item_count {
+        1 => "1 image".to_string(),
+        n => format!("{} images", n),
+    };
+    view! {
+        <A href=href attr:class="album-card">
+            <div class="album-cover">
+                {album.cover.map(|cover| view! {
+                    <ResponsiveImage src=cover.url.clone() alt=cover.alt() variants=cover.variants sizes=TILE_SIZES/>
+                })}
+            </div>
+            <h3 class="font-bold mt-2">{album.title}</h3>
+            <p class="text-xs text-gray-500">{count}</p>
+        </A>
+    }
+}
+
+/// Public gallery of one media category, with context tabs and the category's albums.
+#[component]
+fn Gallery(
+    category: MediaCategory,
+    base: &'static str,
+    title: &'static str,
+    intro: &'static str,
+) -> impl IntoView {
+    let query = use_query_map();
+    let context = move || {
+        query.with(|q| {
+            q.get("context")
+                .and_then(|c| c.parse::<MediaContext>().ok())
+        })
+    };
+    let page = move || query.with(|q| query_page(q.get("page")));
+
+    let gallery_resource = Resource::new(
+        move || (context(), page()),
+        move |(context, page)| get_gallery(category, context, page),
+    );
+    let albums_resource = Resource::new(|| (), move |_| list_albums(Some(category)));
+
+    let tab_class = move |tab: Option<MediaContext>| {
+        move || {
+            if context() == tab {
+                "px-3 py-1 rounded-full bg-gray-900 text-white text-sm"
+            } else {
+                "px-3 py-1 rounded-full bg-gray-100 text-gray-700 text-sm hover:bg-gray-200"
+            }
+        }
+    };
+
+    view! {
+        <div class="container py-12">
+            <h1 class="text-4xl mb-6">{title}</h1>
+            <p class="text-muted mb-6">{intro}</p>
+
+            <Suspense fallback=|| ()>
+                {move || {
+                    albums_resource.get().and_then(Result::ok).filter(|albums| !albums.is_empty()).map(|albums| view! {
+                        <section class="mb-10">
+                            <h2 class="text-2xl font-bold mb-4">"Albums"</h2>
+                            <div class="album-grid">
+                                {albums.into_iter().map(|album| render_album_card(album, base)).collect_view()}
+                            </div>
+                        </section>
+                    })
+                }}
+            </Suspense>
+
+            <nav class="flex gap-2 mb-8">
+                <A href=base attr:class=tab_class(None)>"All"</A>
+                {MediaContext::ALL.into_iter().map(|c| view! {
+                    <A href=page_href(base, Some(c), 0) attr:class=tab_class(Some(c))>{c.label()}</A>
+                }).collect_view()}
+            </nav>
+
+            <Suspense fallback=move || view! { <p>"Loading..."</p> }>
+                {move || {
+                    gallery_resource.get().map(|res| match res {
+                        Ok(page) if page.items.is_empty() => {
+                            view! { <p class="text-gray-500">"Nothing here yet."</p> }.into_any()
+                        }
+                        Ok(page) => {
+                            let context = context();
+                            render_gallery_grid(page, move |p| page_href(base, context, p)).into_any()
+                        }
+                        Err(e) => view! { <p class="text-red-500">"Error loading gallery: " {e.to_string()}</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+/// One album, its items in the order the admins arranged them.
+#[component]
+fn AlbumView(category: MediaCategory, base: &'static str, title: &'static str) -> impl IntoView {
+    let params = use_params_map();
+    let query = use_query_map();
+    let slug = move || params.with(|p| p.get("slug").map(|s| s.to_string()).unwrap_or_default());
+    let page = move || query.with(|q| query_page(q.get("page")));
+
+    let album_resource = Resource::new(
+        move || (slug(), page()),
+        |(slug, page)| get_album(slug, page),
+    );
+
+    view! {
+        <div class="container py-12">
+            <Suspense fallback=move || view! { <p>"Loading..."</p> }>
+                {move || {
+                    album_resource.get().map(|res| match res {
+                        Ok(Some(album_page)) if album_page.album.category == category => {
+                            let album = album_page.album;
+                            let album_href = format!("{}/albums/{}", base, album.slug);
+                            view! {
+                                <p class="text-xs uppercase tracking-wide text-gray-500 mb-1">
+                                    {format!("{} · {}", title, album.context.label())}
+                                </p>
+                                <h1 class="text-4xl mb-4">{album.title}</h1>
+                                {album.description.map(|d| view! { <p class="text-muted mb-6">{d}</p> })}
+                                {if album_page.items.items.is_empty() {
+                                    view! { <p class="text-gray-500">"Nothing here yet."</p> }.into_any()
+                                } else {
+                                    render_gallery_grid(album_page.items, move |p| page_href(&album_href, None, p)).into_any()
+                                }}
+                            }.into_any()
+                        }
+                        Ok(_) => view! { <p>"Album not found."</p> }.into_any(),
+                        Err(e) => view! { <p class="text-red-500">"Error loading album: " {e.to_string()}</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+            <div class="mt-10">
+                <A href=base attr:class="text-sky-600 hover:underline">{format!("← All {}", title.to_lowercase())}</A>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn PhotographyPage() -> impl IntoView {
+    view! {
+        <Gallery
+            category=MediaCategory::Photography
+            base="/photography"
+            title="Photography"
+            intro="Personal and professional photography."
+        />
+    }
+}
+
+#[component]
+pub fn PhotographyAlbumPage() -> impl IntoView {
+    view! { <AlbumView category=MediaCategory::Photography base="/photography" title="Photography"/> }
+}
+
+#[component]
+pub fn VisualArtPage() -> impl IntoView {
+    view! {
+        <Gallery
+            category=MediaCategory::VisualArt
+            base="/visual-art"
+            title="Visual Art"
+            intro="Drawings and Digital Art."
+        />
+    }
+}
+
+#[component]
+pub fn VisualArtAlbumPage() -> impl IntoView {
+    view! { <AlbumView category=MediaCategory::VisualArt base="/visual-art" title="Visual Art"/> }
+}
@@ -1,9 +1,14 @@
-use crate::api::articles::{delete_article, get_article, get_articles, save_article, Article};
+use crate::api::articles::{
+    delete_article, get_article, get_articles, sanitize_slug, save_article, Article,
+};
 use crate::components::media_picker::MediaPicker;
+use crate::components::responsive_image::ResponsiveImage;
 use crate::components::rich_editor::RichTextEditor;
 use crate::utils::html::{
     extract_printed_date, format_cp_style, process_article_content, replace_date_paragraph,
+    ARTICLE_IMAGE_SIZES,
 };
+use crate::utils::session::{clear_session, ensure_session, has_session, is_auth_error};
 use crate::utils::sorting::{next_article_index, prev_article_index};
 use leptos::prelude::*;
 use leptos::task::spawn_local;
@@ -23,37 +28,9 @@ pub fn JournalismArticlePage() -> impl IntoView {
 
     // Auth State
     let (is_admin, _set_is_admin) = signal(false);
-    let (token, _set_token) = signal(String::new());
 
     Effect::new(move || {
-        #[cfg(target_arch = "wasm32")]
-        {
-            #[cfg(debug_assertions)]
-            web_sys::console::log_1(&"Checking auth token...".into());
-            if let Ok(Some(storage)) = web_sys::window().unwrap().local_storage() {
-                if let Ok(Some(t)) = storage.get_item("admin_token") {
-                    #[cfg(debug_assertions)]
-                    web_sys::console::log_1(&format!("Found token: {}", t).into());
-                    if !t.is_empty() {
-                        if shared::auth::is_token_expired(&t) {
-                            let _ = storage.remove_item("admin_token");
-                            _set_is_admin.set(false);
-                            _set_token.set(String::new());
-                            #[cfg(debug_assertions)]
-                            web_sys::console::log_1(&"Expired token cleared from storage".into());
-                        } else {
-                            _set_token.set(t);
-                            _set_is_admin.set(true);
-                            #[cfg(debug_assertions)]
-                            web_sys::console::log_1(&"Admin mode enabled".into());
-                        }
-                    }
-                } else {
-                    #[cfg(debug_assertions)]
-                    web_sys::console::log_1(&"No token found in localStorage".into());
-                }
-            }
-        }
+        _set_is_admin.set(has_session());
     });
 
     // Edit State
@@ -61,6 +38,7 @@ pub fn JournalismArticlePage() -> impl IntoView {
 
     // Form Signals
     let (edit_title, set_edit_title) = signal(String::new());
+    let (edit_slug, set_edit_slug) = signal(String::new());
     let (edit_date, set_edit_date) = signal(String::new());
     let (edit_byline, set_edit_byline) = signal(String::new());
     let (edit_caption, set_edit_caption) = signal(String::new());
@@ -71,6 +49,7 @@ pub fn JournalismArticlePage() -> impl IntoView {
 
     let turn_on_edit = move |article: &Article| {
         set_edit_title.set(article.title.clone());
+        set_edit_slug.set(article.slug.clone());
         set_edit_date.set(article.display_date.clone());
         set_edit_byline.set(article.byline.clone().unwrap_or_default());
         set_edit_caption.set(article.captions.first().cloned().unwrap_or_default());
@@ -80,12 +59,12 @@ pub fn JournalismArticlePage() -> impl IntoView {
     };
 
     let on_save = move |original_article: Article| {
-        let t = token.get();
         spawn_local(async move {
             set_save_status.set("Saving...".to_string());
             let mut new_article = original_article.clone();
             let new_date_str = edit_date.get();
             new_article.title = edit_title.get();
+            new_article.slug = sanitize_slug(&edit_slug.get());
             new_article.display_date = new_date_str.clone();
 
             new_article.byline = Some(edit_byline.get());
@@ -97,24 +76,25 @@ pub fn JournalismArticlePage() -> impl IntoView {
             new_article.images = edit_images.get();
             new_article.content_html = replace_date_paragraph(&edit_html.get(), &new_date_str);
 
-            match save_article(t, new_article).await {
+            let new_slug = new_article.slug.clone();
+            ensure_session().await;
+            match save_article(new_article).await {
                 Ok(_) => {
                     set_save_status.set("Saved!".to_string());
                     set_is_editing.set(false);
-                    article_resource.refetch();
                     articles_resource.refetch();
+                    if new_slug != original_article.slug {
+                        // The old URL now redirects, but move the editor to the new one
+                        let navigate = leptos_router::hooks::use_navigate();
+                        navigate(&format!("/journalism/{}", new_slug), Default::default());
+                    } else {
+                        article_resource.refetch();
+                    }
                 }
                 Err(e) => {
-                    let err_str = e.to_string();
-                    if err_str.contains("Invalid token") || err_str.contains("ExpiredSignature") {
-                        #[cfg(target_arch = "wasm32")]
-                        if let Some(window) = web_sys::window() {
-                            if let Ok(Some(storage)) = window.local_storage() {
-                                let _ = storage.remove_item("admin_token");
-                            }
-                        }
+                    if is_auth_error(&e.to_string()) {
+                        clear_session();
                         _set_is_admin.set(false);
-                        _set_token.set(String::new());
                         set_save_status
                             .set("Save failed: Session expired. Please log in again.".to_string());
                     } else {
@@ -137,24 +117,17 @@ pub fn JournalismArticlePage() -> impl IntoView {
             }
         }
 
-        let t = token.get();
         spawn_local(async move {
-            match delete_article(t, slug).await {
+            ensure_session().await;
+            match delete_article(slug).await {
                 Ok(_) => {
                     let navigate = leptos_router::hooks::use_navigate();
                     navigate("/journalism", Default::default());
                 }
                 Err(e) => {
-                    let err_str = e.to_string();
-                    if err_str.contains("Invalid token") || err_str.contains("ExpiredSignature") {
-                        #[cfg(target_arch = "wasm32")]
-                        if let Some(window) = web_sys::window() {
-                            if let Ok(Some(storage)) = window.local_storage() {
-                                let _ = storage.remove_item("admin_token");
-                            }
-                        }
+                    if is_auth_error(&e.to_string()) {
+                        clear_session();
                         _set_is_admin.set(false);
-                        _set_token.set(String::new());
                     }
                     #[cfg(target_arch = "wasm32")]
                     let _ = web_sys::window()
@@ -188,7 +161,7 @@ pub fn JournalismArticlePage() -> impl IntoView {
                                     let article = article.clone();
                                     move || {
                                         let article = article.clone();
-                                        let content_html = process_article_content(&article.content_html);
+                                        let content_html = process_article_content(&article.content_html, &article.image_variants);
 
                                         view! {
                                             <div class="article-container">
@@ -197,10 +170,12 @@ pub fn JournalismArticlePage() -> impl IntoView {
                                                     move || {
                                                         let a = admin_article.clone();
                                                         is_admin.get().then(move || {
+                                                            let history_href = format!("/admin/revisions?kind=article&slug={}", a.slug);
                                                             view! {
                                                                 <div class="mb-4 p-4 bg-gray-100 border rounded flex gap-2">
                                                                     <span class="font-bold text-gray-500">"Admin Mode"</span>
                                                                     <button class="btn btn-sm btn-primary" on:click=move |_| turn_on_edit(&a)>"Edit Article"</button>
+                                                                    <a class="btn btn-sm btn-secondary" href=history_href>"History"</a>
                                                                 </div>
                                                             }
                                                         })
@@ -216,7 +191,13 @@ pub fn JournalismArticlePage() -> impl IntoView {
                                                             {images.first().map(|url| view! {
                                                                 <figure class="mb-4">
                                                                     <a href=url.clone() target="_blank" class="article-image-link">
-                                                                        <img src=url.clone() class="w-full h-auto rounded-lg" alt=title.clone() />
+                                                                        <ResponsiveImage
+                                                                            src=url.clone()
+                                                                            variants=article.image_variants.get(url).cloned().unwrap_or_default()
+                                                                            sizes=ARTICLE_IMAGE_SIZES
+                                                                            class="w-full h-auto rounded-lg"
+                                                                            alt=title.clone()
+                                                                        />
                                                                     </a>
                                                                     {captions.first().map(|cap| view! {
                                                                         <figcaption class="mt-2 text-sm text-gray-500 italic">
@@ -346,7 +327,6 @@ pub fn JournalismArticlePage() -> impl IntoView {
                                                             Some(view! {
                                                                 <div class="mt-4 border rounded p-4 bg-gray-50">
                                                                     <MediaPicker
-                                                                        token=token.into()
                                                                         current_image=current
                                                                         on_select=move |url| {
                                                                             set_edit_images.set(vec![url]);
@@ -366,6 +346,15 @@ pub fn JournalismArticlePage() -> impl IntoView {
                                                         ></textarea>
                                                     </div>
 
+                                                    <div class="form-group mb-6">
+                                                        <label class="block font-bold mb-2 text-gray-700">"Slug"</label>
+                                                        <input type="text" class="w-full p-3 border rounded-lg font-mono text-sm"
+                                                            prop:value=edit_slug.get()
+                                                            on:input=move |ev| set_edit_slug.set(event_target_value(&ev))
+                                                        />
+                                                        <p class="text-xs text-gray-500 mt-1">"Changing the slug keeps the old URL working via a permanent redirect."</p>
+                                                    </div>
+
                                                     <div class="form-group mb-6">
                                                         <label class="block font-bold mb-2 text-gray-700">"Display Date"</label>
                                                         <textarea class="w-full p-3 border rounded-lg resize-none" rows="1"
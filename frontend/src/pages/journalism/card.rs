@@ -1,14 +1,24 @@
 use crate::api::articles::Article;
+use crate::components::responsive_image::ResponsiveImage;
 use crate::utils::html::{extract_body_preview, extract_printed_date, format_cp_style};
 use leptos::prelude::*;
 use leptos_router::components::A;
 
+/// Cards fill a column of the journalism grid: one on phones, two on tablets, then three
+/// or more at up to about 360px each.
+const CARD_IMAGE_SIZES: &str = "(max-width: 640px) 100vw, (max-width: 1024px) 50vw, 360px";
+
 pub fn render_article_card(article: &Article) -> impl IntoView {
     let slug = article.slug.clone();
     let title = article.title.clone();
     let preview_text =
         extract_body_preview(&article.content_html).unwrap_or_else(|| article.excerpt.clone());
     let image = article.images.first().cloned();
+    let image_variants = image
+        .as_ref()
+        .and_then(|img| article.image_variants.get(img))
+        .cloned()
+        .unwrap_or_default();
     let date =
         extract_printed_date(&article.content_html).unwrap_or_else(|| article.display_date.clone());
     let date = format_cp_style(&date);
@@ -17,7 +27,15 @@ pub fn render_article_card(article: &Article) -> impl IntoView {
         <A href=format!("/journalism/{}", slug) attr:class="journalism-card">
             <div class="journalism-thumb">
                 {if let Some(ref img) = image {
-                    view! { <img src=img.clone() class="journalism-img" alt="article thumbnail"/> }.into_any()
+                    view! {
+                        <ResponsiveImage
+                            src=img.clone()
+                            variants=image_variants
+                            sizes=CARD_IMAGE_SIZES
+                            class="journalism-img"
+                            alt="article thumbnail"
+                        />
+                    }.into_any()
                 } else {
                     view! {
                         <svg class="journalism-img" xmlns="http://www.w3.org/2000/svg" width="400" height="300" viewBox="0 0 400 300">
@@ -1,50 +1,110 @@
 use crate::api::articles::get_articles;
+use crate::api::search::{search_content, SearchResult};
 use crate::pages::journalism::card::render_article_card;
 use crate::utils::sorting::{
     extract_highlight_articles, sort_articles_newest_first, HIGHLIGHT_SLUGS,
 };
 use leptos::prelude::*;
+use leptos_router::components::{Form, A};
+use leptos_router::hooks::use_query_map;
+
+fn render_search_result(result: SearchResult) -> impl IntoView {
+    view! {
+        <A href=result.url attr:class="block py-4">
+            <h3 class="text-xl font-semibold mb-1">{result.title}</h3>
+            <p class="text-gray-700 text-sm" inner_html=result.snippet></p>
+        </A>
+    }
+}
 
 #[component]
 pub fn JournalismPage() -> impl IntoView {
+    let query = use_query_map();
+    let search_term = move || {
+        query
+            .with(|q| q.get("q"))
+            .map(|q| q.trim().to_string())
+            .unwrap_or_default()
+    };
     let articles_resource = Resource::new(|| (), |_| get_articles());
+    let search_resource = Resource::new(search_term, |q| async move {
+        if q.is_empty() {
+            Ok(Vec::new())
+        } else {
+            search_content(q, Some("article".to_string())).await
+        }
+    });
 
     view! {
         <div class="container py-12">
-            <Suspense fallback=move || view! { <p>"Loading articles..."</p> }>
-                {move || {
-                    articles_resource.get().map(|res| {
-                        match res {
-                            Ok(mut articles) => {
-                                let highlight_articles = extract_highlight_articles(&articles, HIGHLIGHT_SLUGS);
-                                sort_articles_newest_first(&mut articles);
+            <Form method="GET" action="/journalism" attr:class="flex gap-2 mb-10 max-w-xl">
+                <input type="search" name="q" class="flex-1 p-3 border rounded-lg"
+                    placeholder="Search articles..."
+                    prop:value=search_term
+                />
+                <button type="submit" class="btn btn-primary">"Search"</button>
+            </Form>
 
-                                view! {
-                                    <div>
-                                        <section class="mb-12">
-                                            <h2 class="text-3xl font-bold mb-6">"Journalism Highlights"</h2>
-                                            <div class="journalism-grid">
-                                                {highlight_articles.iter().map(render_article_card).collect_view()}
-                                            </div>
-                                        </section>
-
-                                        <section>
-                                            <h2 class="text-3xl font-bold mb-4">"All Articles"</h2>
-                                            <p class="text-gray-700 mb-8 max-w-3xl">
-                                                "A collection of community news articles I have written, mostly for The Terrace Standard, but some articles are from my 2017 internship at The Spruce Grove Examiner and some are even older, from my years studying journalism at Langara College."
-                                            </p>
-                                            <div class="journalism-grid">
-                                                {articles.iter().map(render_article_card).collect_view()}
-                                            </div>
-                                        </section>
+            {move || (!search_term().is_empty()).then(|| view! {
+                <section class="mb-12">
+                    <div class="flex items-baseline justify-between mb-4">
+                        <h2 class="text-3xl font-bold">{move || format!("Results for \"{}\"", search_term())}</h2>
+                        <A href="/journalism" attr:class="text-sm text-sky-700">"Clear search"</A>
+                    </div>
+                    <Suspense fallback=move || view! { <p>"Searching..."</p> }>
+                        {move || {
+                            search_resource.get().map(|res| match res {
+                                Ok(results) if results.is_empty() => {
+                                    view! { <p class="text-gray-500">"No articles matched your search."</p> }.into_any()
+                                }
+                                Ok(results) => view! {
+                                    <div class="divide-y">
+                                        {results.into_iter().map(render_search_result).collect_view()}
                                     </div>
-                                }.into_any()
+                                }.into_any(),
+                                Err(e) => view! { <p class="text-red-500">"Search failed: " {e.to_string()}</p> }.into_any(),
+                            })
+                        }}
+                    </Suspense>
+                </section>
+            })}
+
+            <div class:hidden=move || !search_term().is_empty()>
+                <Suspense fallback=move || view! { <p>"Loading articles..."</p> }>
+                    {move || {
+                        articles_resource.get().map(|res| {
+                            match res {
+                                Ok(mut articles) => {
+                                    let highlight_articles = extract_highlight_articles(&articles, HIGHLIGHT_SLUGS);
+                                    sort_articles_newest_first(&mut articles);
+
+                                    view! {
+                                        <div>
+                                            <section class="mb-12">
+                                                <h2 class="text-3xl font-bold mb-6">"Journalism Highlights"</h2>
+                                                <div class="journalism-grid">
+                                                    {highlight_articles.iter().map(render_article_card).collect_view()}
+                                                </div>
+                                            </section>
+
+                                            <section>
+                                                <h2 class="text-3xl font-bold mb-4">"All Articles"</h2>
+                                                <p class="text-gray-700 mb-8 max-w-3xl">
+                                                    "A collection of community news articles I have written, mostly for The Terrace Standard, but some articles are from my 2017 internship at The Spruce Grove Examiner and some are even older, from my years studying journalism at Langara College."
+                                                </p>
+                                                <div class="journalism-grid">
+                                                    {articles.iter().map(render_article_card).collect_view()}
+                                                </div>
+                                            </section>
+                                        </div>
+                                    }.into_any()
+                                }
+                                Err(e) => view! { <p class="text-red-500">"Error loading articles: " {e.to_string()}</p> }.into_any()
                             }
-                            Err(e) => view! { <p class="text-red-500">"Error loading articles: " {e.to_string()}</p> }.into_any()
-                        }
-                    })
-                }}
-            </Suspense>
+                        })
+                    }}
+                </Suspense>
+            </div>
         </div>
     }
 }
@@ -3,14 +3,20 @@ pub mod admin;
 pub mod blog;
 pub mod contact;
 pub mod creative;
+pub mod gallery;
 pub mod home;
 pub mod journalism;
+pub mod music;
 pub mod programming;
 pub mod sections;
+pub mod video;
 
 pub use about::AboutPage;
-pub use blog::PersonalBlogPage;
-pub use creative::{CreativeWritingPage, MusicPage, PersonalPage, VisualArtPage};
+pub use blog::{BlogPostPage, PersonalBlogPage};
+pub use creative::{CreativeWorkPage, CreativeWritingPage, PersonalPage};
+pub use gallery::{PhotographyAlbumPage, PhotographyPage, VisualArtAlbumPage, VisualArtPage};
 pub use home::{AdminRedirect, HomePage, NotFound};
 pub use journalism::{render_article_card, JournalismArticlePage, JournalismPage};
+pub use music::{MusicPage, TrackPage};
 pub use programming::ProgrammingPage;
+pub use video::{VideoDetailPage, VideoPage};
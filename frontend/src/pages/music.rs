@@ -0,0 +1,92 @@
+use crate::api::music::{get_track, get_tracks, MusicTrack};
+use crate::components::player::{AudioPlayer, EmbedPlayer};
+use leptos::prelude::*;
+use leptos_router::components::A;
+use leptos_router::hooks::use_params_map;
+
+/// The track's players: self-hosted audio first, then the embed.
+fn render_players(track: &MusicTrack) -> impl IntoView {
+    let audio = track
+        .audio_url
+        .clone()
+        .map(|src| view! { <AudioPlayer src=src/> });
+    let embed = track
+        .embed()
+        .map(|embed| view! { <EmbedPlayer embed=embed title=track.title.clone()/> });
+    view! { {audio} {embed} }
+}
+
+fn render_track_card(track: MusicTrack) -> impl IntoView {
+    let href = format!("/music/{}", track.slug);
+    let players = render_players(&track);
+    view! {
+        <div class="card mb-6">
+            {track.published_date.map(|d| view! { <p class="text-xs uppercase tracking-wide text-gray-500 mb-1">{d}</p> })}
+            <h2 class="text-2xl font-bold mb-2">
+                <A href=href attr:class="hover:underline">{track.title}</A>
+            </h2>
+            {track.description.map(|d| view! { <p class="text-gray-700 mb-4">{d}</p> })}
+            {players}
+        </div>
+    }
+}
+
+#[component]
+pub fn MusicPage() -> impl IntoView {
+    let tracks_resource = Resource::new(|| (), |_| get_tracks());
+
+    view! {
+        <div class="container py-12 max-w-3xl">
+            <h1 class="text-4xl mb-6">"Music"</h1>
+            <p class="text-muted mb-8">"Original compositions."</p>
+
+            <Suspense fallback=move || view! { <p>"Loading..."</p> }>
+                {move || {
+                    tracks_resource.get().map(|res| match res {
+                        Ok(tracks) if tracks.is_empty() => {
+                            view! { <p class="text-gray-500">"Nothing here yet."</p> }.into_any()
+                        }
+                        Ok(tracks) => {
+                            view! { <div>{tracks.into_iter().map(render_track_card).collect_view()}</div> }.into_any()
+                        }
+                        Err(e) => view! { <p class="text-red-500">"Error loading music: " {e.to_string()}</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+pub fn TrackPage() -> impl IntoView {
+    let params = use_params_map();
+    let slug = move || params.with(|p| p.get("slug").map(|s| s.to_string()).unwrap_or_default());
+    let track_resource = Resource::new(slug, get_track);
+
+    view! {
+        <div class="container py-12 max-w-3xl">
+            <Suspense fallback=move || view! { <p>"Loading..."</p> }>
+                {move || {
+                    track_resource.get().map(|res| match res {
+                        Ok(Some(track)) => {
+                            let players = render_players(&track);
+                            view! {
+                                <article>
+                                    {track.published_date.map(|d| view! { <p class="text-xs uppercase tracking-wide text-gray-500 mb-1">{d}</p> })}
+                                    <h1 class="mb-4 text-4xl font-bold text-black">{track.title}</h1>
+                                    {track.description.map(|d| view! { <p class="text-gray-700 mb-6">{d}</p> })}
+                                    {players}
+                                </article>
+                            }.into_any()
+                        }
+                        Ok(None) => view! { <p>"Track not found."</p> }.into_any(),
+                        Err(e) => view! { <p class="text-red-500">"Error loading track: " {e.to_string()}</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+            <div class="mt-10">
+                <A href="/music" attr:class="text-sky-600 hover:underline">"← All music"</A>
+            </div>
+        </div>
+    }
+}
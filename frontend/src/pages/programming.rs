@@ -1,11 +1,77 @@
+use crate::api::projects::{get_projects, Project};
+use crate::utils::session::has_session;
 use leptos::prelude::*;
 
+fn render_project_card(project: Project) -> impl IntoView {
+    let card_class = if project.is_featured {
+        "card border-2 border-sky-200 flex flex-col"
+    } else {
+        "card flex flex-col"
+    };
+
+    view! {
+        <div class=card_class>
+            <div class="flex items-start justify-between gap-2 mb-2">
+                <h2 class="text-xl font-bold">{project.name}</h2>
+                <span class="text-sm text-gray-500 whitespace-nowrap" title="GitHub stars">
+                    {format!("★ {}", project.stars)}
+                </span>
+            </div>
+            {project.is_featured.then(|| view! {
+                <p class="text-xs uppercase tracking-wide text-sky-700 mb-2">"Featured"</p>
+            })}
+            <p class="text-gray-700 mb-4 flex-grow">{project.description}</p>
+            <div class="flex flex-wrap gap-2 mb-4">
+                {project.technologies.into_iter().map(|tech| view! {
+                    <span class="text-xs bg-gray-100 text-gray-700 px-2 py-0.5 rounded-full">{tech}</span>
+                }).collect_view()}
+            </div>
+            <div class="flex gap-4 text-sm">
+                {project.github_url.map(|url| view! {
+                    <a href=url target="_blank" rel="noopener noreferrer" class="text-sky-600 hover:underline">"Source"</a>
+                })}
+                {project.demo_url.map(|url| view! {
+                    <a href=url target="_blank" rel="noopener noreferrer" class="text-sky-600 hover:underline">"Live demo"</a>
+                })}
+            </div>
+        </div>
+    }
+}
+
 #[component]
 pub fn ProgrammingPage() -> impl IntoView {
+    let projects_resource = Resource::new(|| (), |_| get_projects());
+
+    let (is_admin, _set_is_admin) = signal(false);
+    Effect::new(move || {
+        _set_is_admin.set(has_session());
+    });
+
     view! {
         <div class="container py-12">
-             <h1 class="text-4xl mb-6">"Code"</h1>
-             <p class="text-muted">"GitHub Showcase. Coming soon..."</p>
+            <div class="flex justify-between items-center mb-6">
+                <h1 class="text-4xl">"Code"</h1>
+                {move || is_admin.get().then(|| view! {
+                    <a href="/admin/projects" class="btn btn-primary">"Manage Projects"</a>
+                })}
+            </div>
+            <p class="text-muted mb-8">"Things I've built, with live stats from GitHub."</p>
+
+            <Suspense fallback=move || view! { <p>"Loading projects..."</p> }>
+                {move || {
+                    projects_resource.get().map(|res| match res {
+                        Ok(projects) if projects.is_empty() => {
+                            view! { <p class="text-gray-500">"No projects yet."</p> }.into_any()
+                        }
+                        Ok(projects) => view! {
+                            <div class="grid gap-6 md:grid-cols-2 lg:grid-cols-3">
+                                {projects.into_iter().map(render_project_card).collect_view()}
+                            </div>
+                        }.into_any(),
+                        Err(e) => view! { <p class="text-red-500">"Error loading projects: " {e.to_string()}</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
         </div>
     }
 }
@@ -1,9 +1,14 @@
 //! Modular sections page definitions and re-exports.
 
-pub use crate::pages::blog::PersonalBlogPage;
-pub use crate::pages::creative::{CreativeWritingPage, MusicPage, PersonalPage, VisualArtPage};
+pub use crate::pages::blog::{BlogPostPage, PersonalBlogPage};
+pub use crate::pages::creative::{CreativeWorkPage, CreativeWritingPage, PersonalPage};
+pub use crate::pages::gallery::{
+    PhotographyAlbumPage, PhotographyPage, VisualArtAlbumPage, VisualArtPage,
+};
 pub use crate::pages::journalism::{render_article_card, JournalismArticlePage, JournalismPage};
+pub use crate::pages::music::{MusicPage, TrackPage};
 pub use crate::pages::programming::ProgrammingPage;
+pub use crate::pages::video::{VideoDetailPage, VideoPage};
 pub use crate::utils::html::{
     bold_byline, extract_between, extract_body_preview, extract_printed_date, extract_subhead,
     format_cp_style, italicize_origin_line, linkify_images, process_article_content,
@@ -0,0 +1,135 @@
+use crate::api::gallery::{get_gallery, get_gallery_item, GalleryItem};
+use crate::components::player::VideoPlayer;
+use crate::pages::gallery::{page_href, query_page};
+use leptos::prelude::*;
+use leptos_router::components::A;
+use leptos_router::hooks::{use_params_map, use_query_map};
+use shared::{MediaCategory, MediaContext};
+
+fn render_video_card(item: GalleryItem) -> impl IntoView {
+    let href = format!("/video/{}", item.id);
+    let title = item.title.clone().unwrap_or_else(|| "Untitled".to_string());
+    let alt = item.alt();
+    // Without a poster, the first frame stands in for a thumbnail
+    let preview = match item.thumbnail_url {
+        Some(poster) => view! { <img src=poster alt=alt/> }.into_any(),
+        None => view! { <video src=format!("{}#t=0.1", item.url) preload="metadata" muted=true></video> }
+            .into_any(),
+    };
+    view! {
+        <A href=href attr:class="album-card">
+            <div class="video-thumbnail">{preview}</div>
+            <h3 class="font-bold mt-2">{title}</h3>
+            {item.capture_date.map(|d| view! { <p class="text-xs text-gray-500">{d}</p> })}
+        </A>
+    }
+}
+
+#[component]
+pub fn VideoPage() -> impl IntoView {
+    let query = use_query_map();
+    let context = move || {
+        query.with(|q| {
+            q.get("context")
+                .and_then(|c| c.parse::<MediaContext>().ok())
+        })
+    };
+    let page = move || query.with(|q| query_page(q.get("page")));
+    let videos_resource = Resource::new(
+        move || (context(), page()),
+        |(context, page)| get_gallery(MediaCategory::Video, context, page),
+    );
+
+    let tab_class = move |tab: Option<MediaContext>| {
+        move || {
+            if context() == tab {
+                "px-3 py-1 rounded-full bg-gray-900 text-white text-sm"
+            } else {
+                "px-3 py-1 rounded-full bg-gray-100 text-gray-700 text-sm hover:bg-gray-200"
+            }
+        }
+    };
+
+    view! {
+        <div class="container py-12">
+            <h1 class="text-4xl mb-6">"Videography"</h1>
+            <p class="text-muted mb-6">"Personal and professional video."</p>
+
+            <nav class="flex gap-2 mb-8">
+                <A href="/video" attr:class=tab_class(None)>"All"</A>
+                {MediaContext::ALL.into_iter().map(|c| view! {
+                    <A href=page_href("/video", Some(c), 0) attr:class=tab_class(Some(c))>{c.label()}</A>
+                }).collect_view()}
+            </nav>
+
+            <Suspense fallback=move || view! { <p>"Loading..."</p> }>
+                {move || {
+                    videos_resource.get().map(|res| match res {
+                        Ok(page) if page.items.is_empty() => {
+                            view! { <p class="text-gray-500">"Nothing here yet."</p> }.into_any()
+                        }
+                        Ok(page) => {
+                            let context = context();
+                            let page_count = page.page_count();
+                            let current = page.page;
+                            view! {
+                                <div class="album-grid">
+                                    {page.items.into_iter().map(render_video_card).collect_view()}
+                                </div>
+                                {(page_count > 1).then(|| view! {
+                                    <nav class="flex justify-between items-center mt-8" aria-label="Video pages">
+                                        {(current > 0).then(|| view! {
+                                            <A href=page_href("/video", context, current - 1) attr:class="text-sky-600 hover:underline">"← Newer"</A>
+                                        })}
+                                        <span class="text-sm text-gray-500">{format!("Page {} of {}", current + 1, page_count)}</span>
+                                        {(current + 1 < page_count).then(|| view! {
+                                            <A href=page_href("/video", context, current + 1) attr:class="text-sky-600 hover:underline">"Older →"</A>
+                                        })}
+                                    </nav>
+                                })}
+                            }.into_any()
+                        }
+                        Err(e) => view! { <p class="text-red-500">"Error loading videos: " {e.to_string()}</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+pub fn VideoDetailPage() -> impl IntoView {
+    let params = use_params_map();
+    let id = move || params.with(|p| p.get("id").map(|s| s.to_string()).unwrap_or_default());
+    let video_resource = Resource::new(id, |id| get_gallery_item(MediaCategory::Video, id));
+
+    view! {
+        <div class="container py-12 max-w-4xl">
+            <Suspense fallback=move || view! { <p>"Loading..."</p> }>
+                {move || {
+                    video_resource.get().map(|res| match res {
+                        Ok(Some(item)) => view! {
+                            <article>
+                                <p class="text-xs uppercase tracking-wide text-gray-500 mb-1">
+                                    {[Some(item.context.label().to_string()), item.capture_date.clone()]
+                                        .into_iter()
+                                        .flatten()
+                                        .collect::<Vec<_>>()
+                                        .join(" · ")}
+                                </p>
+                                {item.title.clone().map(|t| view! { <h1 class="mb-4 text-4xl font-bold text-black">{t}</h1> })}
+                                <VideoPlayer src=item.url.clone() poster=item.thumbnail_url.clone()/>
+                                {item.description.clone().map(|d| view! { <p class="text-gray-700 mt-6">{d}</p> })}
+                            </article>
+                        }.into_any(),
+                        Ok(None) => view! { <p>"Video not found."</p> }.into_any(),
+                        Err(e) => view! { <p class="text-red-500">"Error loading video: " {e.to_string()}</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+            <div class="mt-10">
+                <A href="/video" attr:class="text-sky-600 hover:underline">"← All video"</A>
+            </div>
+        </div>
+    }
+}
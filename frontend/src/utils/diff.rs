@@ -0,0 +1,180 @@
+//! Word-level diff for HTML content, used to compare article and page revisions.
+//!
+//! Markup is tokenized separately from text so that a changed word is highlighted
+//! without breaking the surrounding tags. The result is the new HTML with removed text
+//! wrapped in `<del>` and added text in `<ins>`; removed tags are dropped so the output
+//! keeps the new document's structure.
+
+/// Above this many LCS cells the diff falls back to "everything in the middle changed".
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+fn is_tag(token: &str) -> bool {
+    token.starts_with('<') && token.ends_with('>')
+}
+
+/// Splits HTML into tags, runs of whitespace, and words.
+fn tokenize(html: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut chars = html.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if c == '<' {
+            if start < i {
+                tokens.push(&html[start..i]);
+            }
+            let end = html[i..].find('>').map(|p| i + p + 1).unwrap_or(html.len());
+            tokens.push(&html[i..end]);
+            while chars.peek().is_some_and(|&(j, _)| j < end) {
+                chars.next();
+            }
+            start = end;
+        } else {
+            let ws = c.is_whitespace();
+            if let Some(&(j, next)) = chars.peek() {
+                if next == '<' || next.is_whitespace() != ws {
+                    tokens.push(&html[start..j]);
+                    start = j;
+                }
+            }
+        }
+    }
+    if start < html.len() {
+        tokens.push(&html[start..]);
+    }
+    tokens
+}
+
+fn diff_tokens<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(Op, &'a str)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<(Op, &str)> = old[..prefix].iter().map(|t| (Op::Equal, *t)).collect();
+
+    let (n, m) = (old_mid.len(), new_mid.len());
+    if n.saturating_mul(m) > MAX_DIFF_CELLS {
+        ops.extend(old_mid.iter().map(|t| (Op::Delete, *t)));
+        ops.extend(new_mid.iter().map(|t| (Op::Insert, *t)));
+    } else {
+        // lcs[i][j] = LCS length of old_mid[i..] and new_mid[j..]
+        let mut lcs = vec![0u32; (n + 1) * (m + 1)];
+        let idx = |i: usize, j: usize| i * (m + 1) + j;
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[idx(i, j)] = if old_mid[i] == new_mid[j] {
+                    lcs[idx(i + 1, j + 1)] + 1
+                } else {
+                    lcs[idx(i + 1, j)].max(lcs[idx(i, j + 1)])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < n && j < m {
+            if old_mid[i] == new_mid[j] {
+                ops.push((Op::Equal, new_mid[j]));
+                i += 1;
+                j += 1;
+            } else if lcs[idx(i + 1, j)] >= lcs[idx(i, j + 1)] {
+                ops.push((Op::Delete, old_mid[i]));
+                i += 1;
+            } else {
+                ops.push((Op::Insert, new_mid[j]));
+                j += 1;
+            }
+        }
+        ops.extend(old_mid[i..].iter().map(|t| (Op::Delete, *t)));
+        ops.extend(new_mid[j..].iter().map(|t| (Op::Insert, *t)));
+    }
+
+    ops.extend(old[old.len() - suffix..].iter().map(|t| (Op::Equal, *t)));
+    ops
+}
+
+/// Renders `new_html` with the differences from `old_html` marked up.
+pub fn diff_html(old_html: &str, new_html: &str) -> String {
+    let old = tokenize(old_html);
+    let new = tokenize(new_html);
+
+    let mut out = String::with_capacity(new_html.len() + 64);
+    let mut open: Option<Op> = None;
+    let close = |out: &mut String, open: &mut Option<Op>| {
+        match open.take() {
+            Some(Op::Delete) => out.push_str("</del>"),
+            Some(Op::Insert) => out.push_str("</ins>"),
+            _ => {}
+        };
+    };
+
+    for (op, token) in diff_tokens(&old, &new) {
+        if is_tag(token) {
+            // Tags are structural: close any open change marker so nesting stays valid
+            close(&mut out, &mut open);
+            if op != Op::Delete {
+                out.push_str(token);
+            }
+            continue;
+        }
+        if op == Op::Equal {
+            close(&mut out, &mut open);
+            out.push_str(token);
+            continue;
+        }
+        if open != Some(op) {
+            close(&mut out, &mut open);
+            out.push_str(if op == Op::Delete { "<del>" } else { "<ins>" });
+            open = Some(op);
+        }
+        out.push_str(token);
+    }
+    close(&mut out, &mut open);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_separates_tags_words_and_spaces() {
+        assert_eq!(
+            tokenize("<p class=\"a\">Hello  world</p>"),
+            vec!["<p class=\"a\">", "Hello", "  ", "world", "</p>"]
+        );
+    }
+
+    #[test]
+    fn test_diff_html_marks_changed_words() {
+        assert_eq!(
+            diff_html(
+                "<p>The council voted no.</p>",
+                "<p>The council voted yes.</p>"
+            ),
+            "<p>The council voted <del>no.</del><ins>yes.</ins></p>"
+        );
+    }
+
+    #[test]
+    fn test_diff_html_keeps_new_structure() {
+        assert_eq!(
+            diff_html("<p>One</p><p>Two</p>", "<p>One</p><h2>Two</h2>"),
+            "<p>One</p><h2>Two</h2>"
+        );
+        assert_eq!(diff_html("", "<p>New</p>"), "<p><ins>New</ins></p>");
+        assert_eq!(diff_html("<p>Same</p>", "<p>Same</p>"), "<p>Same</p>");
+    }
+}
@@ -0,0 +1,400 @@
+//! Third-party players from pasted embed codes.
+//!
+//! Only the player URL is taken from what's pasted, whether that's a whole `<iframe>`
+//! snippet or a share link. It has to belong to a provider on the allowlist, and is rebuilt
+//! from the parts we recognise; pages then render their own sandboxed `<iframe>` around
+//! it, so pasted markup never reaches the page.
+
+/// Sandbox for embedded players. Scripts and same-origin access apply to the provider's
+/// own origin, which the players need to load their media; the frame still can't navigate
+/// the page or open forms.
+pub const EMBED_SANDBOX: &str = "allow-scripts allow-same-origin allow-popups allow-presentation";
+pub const EMBED_ALLOW: &str = "autoplay; encrypted-media; fullscreen; picture-in-picture";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmbedProvider {
+    YouTube,
+    Vimeo,
+    SoundCloud,
+    Spotify,
+    Bandcamp,
+}
+
+impl EmbedProvider {
+    pub const ALL: [EmbedProvider; 5] = [
+        EmbedProvider::YouTube,
+        EmbedProvider::Vimeo,
+        EmbedProvider::SoundCloud,
+        EmbedProvider::Spotify,
+        EmbedProvider::Bandcamp,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EmbedProvider::YouTube => "YouTube",
+            EmbedProvider::Vimeo => "Vimeo",
+            EmbedProvider::SoundCloud => "SoundCloud",
+            EmbedProvider::Spotify => "Spotify",
+            EmbedProvider::Bandcamp => "Bandcamp",
+        }
+    }
+
+    pub fn is_video(&self) -> bool {
+        matches!(self, EmbedProvider::YouTube | EmbedProvider::Vimeo)
+    }
+}
+
+/// A player URL rebuilt from an allowed provider's embed code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Embed {
+    pub provider: EmbedProvider,
+    pub src: String,
+}
+
+impl Embed {
+    /// Frame height in pixels for audio players; video players keep a 16:9 ratio instead.
+    pub fn height(&self) -> Option<u32> {
+        match self.provider {
+            EmbedProvider::YouTube | EmbedProvider::Vimeo => None,
+            EmbedProvider::SoundCloud => Some(166),
+            EmbedProvider::Spotify
+                if self.src.contains("/track/") || self.src.contains("/episode/") =>
+            {
+                Some(152)
+            }
+            EmbedProvider::Spotify => Some(352),
+            EmbedProvider::Bandcamp => Some(120),
+        }
+    }
+}
+
+/// The provider list as "YouTube, Vimeo, … or Bandcamp", for error messages.
+pub fn supported_providers() -> String {
+    let labels: Vec<_> = EmbedProvider::ALL.iter().map(|p| p.label()).collect();
+    let (last, rest) = labels.split_last().expect("providers");
+    format!("{} or {}", rest.join(", "), last)
+}
+
+/// The player for an embed code or share link, or `None` unless it's from an allowed
+/// provider.
+pub fn parse_embed(code: &str) -> Option<Embed> {
+    let url = extract_src(code.trim())?.replace("&amp;", "&");
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .or_else(|| url.strip_prefix("//"))?;
+    let host_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let host = rest[..host_end].to_ascii_lowercase();
+    let rest = &rest[host_end..];
+    let rest = rest.split('#').next().unwrap_or_default();
+    let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    match host.as_str() {
+        "youtube.com"
+        | "www.youtube.com"
+        | "m.youtube.com"
+        | "youtube-nocookie.com"
+        | "www.youtube-nocookie.com" => {
+            let id = match segments.as_slice() {
+                ["watch"] => query_param(query, "v")?,
+                ["embed" | "shorts" | "live", id] => *id,
+                _ => return None,
+            };
+            youtube(id)
+        }
+        "youtu.be" => match segments.as_slice() {
+            [id] => youtube(id),
+            _ => None,
+        },
+        "vimeo.com" | "www.vimeo.com" | "player.vimeo.com" => {
+            let id = match segments.as_slice() {
+                ["video", id] if host == "player.vimeo.com" => *id,
+                [id] if host != "player.vimeo.com" => *id,
+                _ => return None,
+            };
+            if id.is_empty() || !id.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            // Unlisted videos carry their privacy hash
+            let hash = query_param(query, "h")
+                .filter(|h| !h.is_empty() && h.bytes().all(|b| b.is_ascii_alphanumeric()))
+                .map(|h| format!("?h={}", h))
+                .unwrap_or_default();
+            Some(Embed {
+                provider: EmbedProvider::Vimeo,
+                src: format!("https://player.vimeo.com/video/{}{}", id, hash),
+            })
+        }
+        "open.spotify.com" => {
+            let mut segments = segments.as_slice();
+            if let ["embed", tail @ ..] = segments {
+                segments = tail;
+            }
+            if let [locale, tail @ ..] = segments {
+                if locale.starts_with("intl-") {
+                    segments = tail;
+                }
+            }
+            match segments {
+                [kind @ ("track" | "album" | "playlist" | "episode" | "show" | "artist"), id]
+                    if !id.is_empty() && id.bytes().all(|b| b.is_ascii_alphanumeric()) =>
+                {
+                    Some(Embed {
+                        provider: EmbedProvider::Spotify,
+                        src: format!("https://open.spotify.com/embed/{}/{}", kind, id),
+                    })
+                }
+                _ => None,
+            }
+        }
+        "soundcloud.com" | "www.soundcloud.com" | "m.soundcloud.com" => {
+            soundcloud(&format!("https://soundcloud.com/{}", segments.join("/")))
+        }
+        "w.soundcloud.com" if segments == ["player"] => {
+            soundcloud(&percent_decode(query_param(query, "url")?)?)
+        }
+        "bandcamp.com" => match segments.as_slice() {
+            ["EmbeddedPlayer", options @ ..] => {
+                let safe = |s: &&str| {
+                    s.bytes()
+                        .all(|b| b.is_ascii_alphanumeric() || b == b'=' || b == b'_' || b == b'-')
+                };
+                let has_release = options.iter().any(|o| {
+                    o.split_once('=').is_some_and(|(k, v)| {
+                        (k == "album" || k == "track")
+                            && !v.is_empty()
+                            && v.bytes().all(|b| b.is_ascii_digit())
+                    })
+                });
+                (has_release && options.iter().all(safe)).then(|| Embed {
+                    provider: EmbedProvider::Bandcamp,
+                    src: format!("https://bandcamp.com/EmbeddedPlayer/{}/", options.join("/")),
+                })
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The `src` of the first `<iframe>`, or the code itself when it's a bare URL.
+fn extract_src(code: &str) -> Option<&str> {
+    let lower = code.to_ascii_lowercase();
+    let Some(start) = lower.find("<iframe") else {
+        return (!code.is_empty() && !code.contains(char::is_whitespace) && !code.contains('<'))
+            .then_some(code);
+    };
+    let tag_end = start + lower[start..].find('>')?;
+    let tag = &lower[start..tag_end];
+    let mut search = 0;
+    let value_start = loop {
+        let at = search + tag[search..].find("src=")?;
+        // `data-src=` and the like aren't the frame's source
+        if tag[..at].ends_with(char::is_whitespace) {
+            break start + at + "src=".len();
+        }
+        search = at + 1;
+    };
+    let value = &code[value_start..tag_end];
+    match value.chars().next()? {
+        quote @ ('"' | '\'') => value[1..].split(quote).next(),
+        _ => value.split(char::is_whitespace).next(),
+    }
+}
+
+fn query_param<'a>(query: &'a str, key: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| v)
+}
+
+fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = value.get(i + 1..i + 3)?;
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).ok()
+}
+
+fn youtube(id: &str) -> Option<Embed> {
+    let valid = id.len() == 11
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-');
+    valid.then(|| Embed {
+        provider: EmbedProvider::YouTube,
+        src: format!("https://www.youtube-nocookie.com/embed/{}", id),
+    })
+}
+
+/// The SoundCloud widget for a track, playlist or profile URL.
+fn soundcloud(url: &str) -> Option<Embed> {
+    let path = url
+        .strip_prefix("https://soundcloud.com/")
+        .or_else(|| url.strip_prefix("https://api.soundcloud.com/"))?;
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let safe = segments.iter().all(|s| {
+        s.bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+    });
+    if segments.is_empty() || !safe {
+        return None;
+    }
+    let base = if url.starts_with("https://api.") {
+        "https%3A//api.soundcloud.com/"
+    } else {
+        "https%3A//soundcloud.com/"
+    };
+    Some(Embed {
+        provider: EmbedProvider::SoundCloud,
+        src: format!(
+            "https://w.soundcloud.com/player/?url={}{}",
+            base,
+            segments.join("/")
+        ),
+    })
+}
+
+/// Whether `url` can be a self-hosted audio or video source: a path on this site or an
+/// https URL, with nothing that could break out of an attribute.
+pub fn is_media_url(url: &str) -> bool {
+    let url = url.trim();
+    let well_formed = (url.starts_with('/') && !url.starts_with("//"))
+        || url
+            .strip_prefix("https://")
+            .is_some_and(|rest| !rest.is_empty());
+    well_formed
+        && !url
+            .chars()
+            .any(|c| c.is_whitespace() || c.is_control() || matches!(c, '"' | '\'' | '<' | '>'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn src(code: &str) -> Option<String> {
+        parse_embed(code).map(|e| e.src)
+    }
+
+    #[test]
+    fn test_parse_embed_providers() {
+        let youtube = Some("https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ".to_string());
+        assert_eq!(
+            src("https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=42s"),
+            youtube
+        );
+        assert_eq!(src("https://youtu.be/dQw4w9WgXcQ?si=abc"), youtube);
+        assert_eq!(
+            src(
+                r#"<iframe width="560" height="315" src="https://www.youtube.com/embed/dQw4w9WgXcQ?si=x" title="YouTube video player" allowfullscreen></iframe>"#
+            ),
+            youtube
+        );
+        assert_eq!(
+            src("https://vimeo.com/76979871"),
+            Some("https://player.vimeo.com/video/76979871".to_string())
+        );
+        assert_eq!(
+            src(
+                r#"<iframe src="https://player.vimeo.com/video/76979871?h=8272103f6e&amp;badge=0"></iframe>"#
+            ),
+            Some("https://player.vimeo.com/video/76979871?h=8272103f6e".to_string())
+        );
+        assert_eq!(
+            src("https://open.spotify.com/intl-fr/track/4uLU6hMCjMI75M1A2tKUQC?si=1"),
+            Some("https://open.spotify.com/embed/track/4uLU6hMCjMI75M1A2tKUQC".to_string())
+        );
+        assert_eq!(
+            src(
+                r#"<iframe style="border-radius:12px" src="https://open.spotify.com/embed/album/1DFixLWuPkv3KT3TnV35m3?utm_source=generator" height="352"></iframe>"#
+            ),
+            Some("https://open.spotify.com/embed/album/1DFixLWuPkv3KT3TnV35m3".to_string())
+        );
+        assert_eq!(
+            src(
+                r#"<iframe scrolling="no" src="https://w.soundcloud.com/player/?url=https%3A//api.soundcloud.com/tracks/293&color=%23ff5500&auto_play=false"></iframe>"#
+            ),
+            Some(
+                "https://w.soundcloud.com/player/?url=https%3A//api.soundcloud.com/tracks/293"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            src("https://soundcloud.com/jakewray/first-light"),
+            Some("https://w.soundcloud.com/player/?url=https%3A//soundcloud.com/jakewray/first-light".to_string())
+        );
+        assert_eq!(
+            src(r#"<iframe style="border: 0; width: 350px; height: 470px;" src="https://bandcamp.com/EmbeddedPlayer/album=1536701931/size=large/bgcol=ffffff/tracklist=false/transparent=true/" seamless><a href="https://x.bandcamp.com/album/y">Y</a></iframe>"#),
+            Some("https://bandcamp.com/EmbeddedPlayer/album=1536701931/size=large/bgcol=ffffff/tracklist=false/transparent=true/".to_string())
+        );
+
+        // Rebuilt players parse to themselves, so stored embeds stay valid
+        for code in [
+            "https://youtu.be/dQw4w9WgXcQ",
+            "https://soundcloud.com/jakewray/first-light",
+            "https://open.spotify.com/playlist/37i9dQZF1DXcBWIGoYBM5M",
+        ] {
+            let embed = parse_embed(code).unwrap();
+            assert_eq!(parse_embed(&embed.src), Some(embed));
+        }
+    }
+
+    #[test]
+    fn test_parse_embed_rejects_others() {
+        for code in [
+            "",
+            "<script>alert(1)</script>",
+            r#"<iframe src="https://evil.example/embed/dQw4w9WgXcQ"></iframe>"#,
+            r#"<iframe data-src="https://www.youtube.com/embed/dQw4w9WgXcQ" src="https://evil.example/"></iframe>"#,
+            "https://www.youtube.com.evil.example/embed/dQw4w9WgXcQ",
+            "https://www.youtube.com@evil.example/embed/dQw4w9WgXcQ",
+            "javascript:alert(1)//www.youtube.com/embed/dQw4w9WgXcQ",
+            "https://www.youtube.com/embed/dQw4w9WgXcQ\"onload=\"alert(1)",
+            "https://vimeo.com/channels/staffpicks",
+            "https://open.spotify.com/user/spotify",
+            "https://w.soundcloud.com/player/?url=https%3A//evil.example/tracks/1",
+            "https://soundcloud.com/a/b%22%3E",
+            "https://bandcamp.com/EmbeddedPlayer/size=large/",
+        ] {
+            assert_eq!(parse_embed(code), None, "{}", code);
+        }
+    }
+
+    #[test]
+    fn test_embed_heights_and_media_urls() {
+        let height = |code: &str| parse_embed(code).unwrap().height();
+        assert_eq!(height("https://youtu.be/dQw4w9WgXcQ"), None);
+        assert_eq!(
+            height("https://open.spotify.com/track/4uLU6hMCjMI75M1A2tKUQC"),
+            Some(152)
+        );
+        assert_eq!(
+            height("https://open.spotify.com/album/1DFixLWuPkv3KT3TnV35m3"),
+            Some(352)
+        );
+        assert_eq!(
+            supported_providers(),
+            "YouTube, Vimeo, SoundCloud, Spotify or Bandcamp"
+        );
+
+        assert!(is_media_url("/media/uploads/first-light.mp3"));
+        assert!(is_media_url("https://cdn.example.com/first-light.mp3"));
+        assert!(!is_media_url("//evil.example/a.mp3"));
+        assert!(!is_media_url("javascript:alert(1)"));
+        assert!(!is_media_url("http://cdn.example.com/a.mp3"));
+        assert!(!is_media_url("/a.mp3\" onerror=\"alert(1)"));
+    }
+}
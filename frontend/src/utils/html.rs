@@ -1,3 +1,9 @@
+use crate::api::media::{variant_srcset, ImageVariant, VariantFormat};
+use std::collections::BTreeMap;
+
+/// Sizes hint for images in an article body, which is at most 720px wide.
+pub const ARTICLE_IMAGE_SIZES: &str = "(max-width: 752px) 100vw, 720px";
+
 pub fn strip_tags(s: &str) -> String {
     let mut out = String::with_capacity(s.len());
     let mut in_tag = false;
@@ -165,7 +171,63 @@ pub fn bold_byline(html: &str) -> String {
     out
 }
 
-pub fn linkify_images(html: &str) -> String {
+/// Removes the attributes called `names` from every `<img>` in `html`.
+pub fn strip_image_attributes(html: &str, names: &[&str]) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut pos = 0;
+    while let Some(rel) = html[pos..].find("<img") {
+        let start = pos + rel;
+        let end = html[start..]
+            .find('>')
+            .map_or(html.len(), |i| start + i + 1);
+        out.push_str(&html[pos..start]);
+        let mut tag = html[start..end].to_string();
+        for name in names {
+            let pattern = format!(" {}=\"", name);
+            while let Some(attr_start) = tag.find(&pattern) {
+                let value_start = attr_start + pattern.len();
+                let Some(value_len) = tag[value_start..].find('"') else {
+                    break;
+                };
+                tag.replace_range(attr_start..value_start + value_len + 1, "");
+            }
+        }
+        out.push_str(&tag);
+        pos = end;
+    }
+    out.push_str(&html[pos..]);
+    out
+}
+
+/// `img_tag` inside a `<picture>` offering its `variants`: WebP where the browser takes
+/// it, JPEG otherwise. Returns the tag unchanged when there are no variants.
+pub fn responsive_image(img_tag: &str, variants: &[ImageVariant], sizes: &str) -> String {
+    let webp = variant_srcset(variants, VariantFormat::Webp);
+    let jpeg = variant_srcset(variants, VariantFormat::Jpeg);
+    if webp.is_empty() && jpeg.is_empty() {
+        return img_tag.to_string();
+    }
+    let tag = strip_image_attributes(img_tag, &["srcset", "sizes"]);
+    let img = if jpeg.is_empty() {
+        tag
+    } else {
+        let open = tag.trim_end_matches('>').trim_end_matches('/').trim_end();
+        format!("{} srcset=\"{}\" sizes=\"{}\">", open, jpeg, sizes)
+    };
+    let source = if webp.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "<source type=\"image/webp\" srcset=\"{}\" sizes=\"{}\">",
+            webp, sizes
+        )
+    };
+    format!("<picture>{}{}</picture>", source, img)
+}
+
+/// Links each image to its full-size original and, where `variants` has resized copies of
+/// it, offers those in a `<picture>`.
+pub fn linkify_images(html: &str, variants: &BTreeMap<String, Vec<ImageVariant>>) -> String {
     let mut out = String::with_capacity(html.len() + 256);
     let mut pos = 0;
 
@@ -189,22 +251,26 @@ pub fn linkify_images(html: &str) -> String {
             html.len()
         };
 
-        let img_tag = &html[img_start..img_end];
+        let original_tag = &html[img_start..img_end];
+        let responsive = tag_attribute(original_tag, "src")
+            .and_then(|src| variants.get(src))
+            .map(|src_variants| responsive_image(original_tag, src_variants, ARTICLE_IMAGE_SIZES));
+        let img_tag = responsive.as_deref().unwrap_or(original_tag);
 
         if already_linked {
             out.push_str(img_tag);
         } else {
             // Extract src attribute
             let mut src_val = None;
-            if let Some(src_idx) = img_tag.find("src=\"") {
+            if let Some(src_idx) = original_tag.find("src=\"") {
                 let after_src = src_idx + 5;
-                if let Some(end_quote) = img_tag[after_src..].find('"') {
-                    src_val = Some(&img_tag[after_src..after_src + end_quote]);
+                if let Some(end_quote) = original_tag[after_src..].find('"') {
+                    src_val = Some(&original_tag[after_src..after_src + end_quote]);
                 }
-            } else if let Some(src_idx) = img_tag.find("src=\'") {
+            } else if let Some(src_idx) = original_tag.find("src=\'") {
                 let after_src = src_idx + 5;
-                if let Some(end_quote) = img_tag[after_src..].find('\'') {
-                    src_val = Some(&img_tag[after_src..after_src + end_quote]);
+                if let Some(end_quote) = original_tag[after_src..].find('\'') {
+                    src_val = Some(&original_tag[after_src..after_src + end_quote]);
                 }
             }
 
@@ -281,9 +347,56 @@ pub fn extract_figcaption(content_html: &str) -> Option<String> {
     None
 }
 
-pub fn process_article_content(html: &str) -> String {
+/// Value of attribute `name` in a single start tag such as `<img src="a.jpg">`.
+pub fn tag_attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let mut from = 0;
+    while let Some(rel) = tag[from..].find(name) {
+        let start = from + rel;
+        from = start + name.len();
+        // Skip longer names that end in `name`, e.g. `data-src`
+        if !tag[..start].ends_with(char::is_whitespace) {
+            continue;
+        }
+        let Some(rest) = tag[from..].trim_start().strip_prefix('=') else {
+            continue;
+        };
+        let rest = rest.trim_start();
+        return match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => rest[1..].find(quote).map(|end| &rest[1..end + 1]),
+            _ => rest
+                .split(|c: char| c.is_whitespace() || c == '>')
+                .next()
+                .filter(|v| !v.is_empty()),
+        };
+    }
+    None
+}
+
+/// The `src` of every `<img>` in `html`, in order and without duplicates.
+pub fn image_sources(html: &str) -> Vec<String> {
+    let mut sources: Vec<String> = Vec::new();
+    let mut pos = 0;
+    while let Some(rel) = html[pos..].find("<img") {
+        let start = pos + rel;
+        let end = html[start..]
+            .find('>')
+            .map_or(html.len(), |i| start + i + 1);
+        if let Some(src) = tag_attribute(&html[start..end], "src").filter(|s| !s.is_empty()) {
+            if !sources.iter().any(|s| s == src) {
+                sources.push(src.to_string());
+            }
+        }
+        pos = end;
+    }
+    sources
+}
+
+pub fn process_article_content(
+    html: &str,
+    image_variants: &BTreeMap<String, Vec<ImageVariant>>,
+) -> String {
     let bolded = bold_byline(html);
-    let linked = linkify_images(&bolded);
+    let linked = linkify_images(&bolded, image_variants);
     italicize_origin_line(&linked)
 }
 
@@ -327,7 +440,7 @@ mod tests {
     #[test]
     fn test_process_article_content_preserves_initial_heading() {
         let content = "<h4>A Subtitle Here</h4><p>By Jake Wray</p><p>Some actual text.</p>";
-        let processed = process_article_content(content);
+        let processed = process_article_content(content, &BTreeMap::new());
         assert!(processed.contains("<h4>A Subtitle Here</h4>"));
         assert!(processed.contains("<p><strong>By Jake Wray</strong></p>"));
     }
@@ -335,7 +448,7 @@ mod tests {
     #[test]
     fn test_process_article_content_transforms() {
         let content = "<p>By Jake Wray</p><p><img src=\"/images/photo.jpg\" alt=\"Test\"></p><p>This article was originally published in The Terrace Standard.</p>";
-        let processed = process_article_content(content);
+        let processed = process_article_content(content, &BTreeMap::new());
         assert!(processed.contains("<p><strong>By Jake Wray</strong></p>"));
         assert!(processed.contains("<a href=\"/images/photo.jpg\" target=\"_blank\" rel=\"noopener noreferrer\" class=\"article-image-link\"><img src=\"/images/photo.jpg\" alt=\"Test\"></a>"));
         assert!(processed.contains(
@@ -352,4 +465,50 @@ mod tests {
             Some("First real sentence of the body.".to_string())
         );
     }
+
+    #[test]
+    fn test_image_sources() {
+        let html = "<p><img data-src=\"lazy.jpg\" src=\"a.jpg\" srcset=\"a-300.jpg 300w\"></p>\
+                    <img class='x' src='b.png'/><img src=a.jpg><img alt=\"none\">";
+        assert_eq!(image_sources(html), vec!["a.jpg", "b.png"]);
+        assert_eq!(tag_attribute("<img src=c.gif>", "src"), Some("c.gif"));
+        assert_eq!(tag_attribute("<img srcset=\"x\">", "src"), None);
+    }
+
+    #[test]
+    fn test_linkify_images_with_variants() {
+        let variant = |width, format| ImageVariant {
+            width,
+            format,
+            url: format!(
+                "/m/variants/a-{}.{}",
+                width,
+                if format == VariantFormat::Webp {
+                    "webp"
+                } else {
+                    "jpg"
+                }
+            ),
+        };
+        let variants = BTreeMap::from([(
+            "/m/a.jpg".to_string(),
+            vec![
+                variant(640, VariantFormat::Webp),
+                variant(640, VariantFormat::Jpeg),
+                variant(320, VariantFormat::Webp),
+                variant(320, VariantFormat::Jpeg),
+            ],
+        )]);
+        let html = "<p><img src=\"/m/a.jpg\" srcset=\"/old 1w\" alt=\"A\" /></p><p><img src=\"/m/b.jpg\"></p>";
+        assert_eq!(
+            linkify_images(html, &variants),
+            format!(
+                "<p><a href=\"/m/a.jpg\" target=\"_blank\" rel=\"noopener noreferrer\" class=\"article-image-link\">\
+                 <picture><source type=\"image/webp\" srcset=\"/m/variants/a-320.webp 320w, /m/variants/a-640.webp 640w\" sizes=\"{sizes}\">\
+                 <img src=\"/m/a.jpg\" alt=\"A\" srcset=\"/m/variants/a-320.jpg 320w, /m/variants/a-640.jpg 640w\" sizes=\"{sizes}\"></picture></a></p>\
+                 <p><a href=\"/m/b.jpg\" target=\"_blank\" rel=\"noopener noreferrer\" class=\"article-image-link\"><img src=\"/m/b.jpg\"></a></p>",
+                sizes = ARTICLE_IMAGE_SIZES
+            )
+        );
+    }
 }
@@ -1,4 +1,8 @@
+pub mod diff;
+pub mod embed;
 pub mod html;
+pub mod passkey;
+pub mod session;
 pub mod slug;
 pub mod sorting;
 
@@ -0,0 +1,172 @@
+//! Browser side of the passkey ceremonies: turns the server's options into
+//! `navigator.credentials` calls and the results back into base64url payloads.
+
+use shared::auth::{
+    PasskeyAssertion, PasskeyLoginOptions, PasskeyRegistration, PasskeyRegistrationOptions,
+};
+
+#[cfg(target_arch = "wasm32")]
+mod browser {
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use base64::Engine;
+    use wasm_bindgen::{JsCast, JsValue};
+    use web_sys::{CredentialsContainer, PublicKeyCredential};
+
+    pub fn bytes(value: &str) -> Result<js_sys::Uint8Array, String> {
+        let decoded = URL_SAFE_NO_PAD
+            .decode(value)
+            .map_err(|_| "Malformed passkey options from server".to_string())?;
+        Ok(js_sys::Uint8Array::from(decoded.as_slice()))
+    }
+
+    pub fn encode(buffer: &js_sys::ArrayBuffer) -> String {
+        URL_SAFE_NO_PAD.encode(js_sys::Uint8Array::new(buffer).to_vec())
+    }
+
+    pub fn js_error(err: JsValue) -> String {
+        // NotAllowedError covers both cancelling the prompt and timing out
+        err.dyn_ref::<js_sys::Error>()
+            .map(|e| String::from(e.message()))
+            .unwrap_or_else(|| "Passkey request failed".to_string())
+    }
+
+    pub fn credentials() -> Result<CredentialsContainer, String> {
+        web_sys::window()
+            .map(|w| w.navigator().credentials())
+            .ok_or_else(|| "Passkeys are not available here".to_string())
+    }
+
+    pub async fn resolve(promise: js_sys::Promise) -> Result<PublicKeyCredential, String> {
+        wasm_bindgen_futures::JsFuture::from(promise)
+            .await
+            .map_err(js_error)?
+            .dyn_into::<PublicKeyCredential>()
+            .map_err(|_| "The browser returned an unexpected credential".to_string())
+    }
+}
+
+/// Creates a discoverable, user-verified ES256 passkey for the signed-in user.
+pub async fn create_passkey(
+    options: &PasskeyRegistrationOptions,
+    name: String,
+) -> Result<PasskeyRegistration, String> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        use browser::{bytes, credentials, encode, js_error, resolve};
+        use wasm_bindgen::JsCast;
+        use web_sys::{
+            AuthenticatorAttestationResponse, AuthenticatorSelectionCriteria,
+            CredentialCreationOptions, PublicKeyCredentialCreationOptions,
+            PublicKeyCredentialDescriptor, PublicKeyCredentialParameters,
+            PublicKeyCredentialRpEntity, PublicKeyCredentialType, PublicKeyCredentialUserEntity,
+            UserVerificationRequirement,
+        };
+
+        let rp = PublicKeyCredentialRpEntity::new(&options.rp_name);
+        rp.set_id(&options.rp_id);
+        let user = PublicKeyCredentialUserEntity::new(
+            &options.user_name,
+            &options.user_name,
+            &bytes(&options.user_id)?,
+        );
+        let params = js_sys::Array::of1(&PublicKeyCredentialParameters::new(
+            -7,
+            PublicKeyCredentialType::PublicKey,
+        ));
+        let public_key = PublicKeyCredentialCreationOptions::new(
+            &bytes(&options.challenge)?,
+            &params,
+            &rp,
+            &user,
+        );
+
+        let selection = AuthenticatorSelectionCriteria::new();
+        selection.set_resident_key("required");
+        selection.set_user_verification(UserVerificationRequirement::Required);
+        public_key.set_authenticator_selection(&selection);
+
+        let exclude = js_sys::Array::new();
+        for id in &options.exclude_credentials {
+            exclude.push(&PublicKeyCredentialDescriptor::new(
+                &bytes(id)?,
+                PublicKeyCredentialType::PublicKey,
+            ));
+        }
+        public_key.set_exclude_credentials(&exclude);
+
+        let request = CredentialCreationOptions::new();
+        request.set_public_key(&public_key);
+        let credential = resolve(
+            credentials()?
+                .create_with_options(&request)
+                .map_err(js_error)?,
+        )
+        .await?;
+        let response = credential
+            .response()
+            .dyn_into::<AuthenticatorAttestationResponse>()
+            .map_err(|_| "The browser returned an unexpected response".to_string())?;
+        let spki = response
+            .get_public_key()
+            .map_err(js_error)?
+            .ok_or_else(|| "This authenticator's key type is not supported".to_string())?;
+
+        Ok(PasskeyRegistration {
+            name,
+            credential_id: encode(&credential.raw_id()),
+            client_data_json: encode(&response.client_data_json()),
+            authenticator_data: encode(&response.get_authenticator_data().map_err(js_error)?),
+            public_key: encode(&spki),
+            public_key_algorithm: response.get_public_key_algorithm().map_err(js_error)? as i64,
+        })
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let _ = (options, name);
+        Err("Passkeys are only available in the browser".to_string())
+    }
+}
+
+/// Asks the browser for any passkey registered for this site.
+pub async fn get_passkey_assertion(
+    options: &PasskeyLoginOptions,
+) -> Result<PasskeyAssertion, String> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        use browser::{bytes, credentials, encode, js_error, resolve};
+        use wasm_bindgen::JsCast;
+        use web_sys::{
+            AuthenticatorAssertionResponse, CredentialRequestOptions,
+            PublicKeyCredentialRequestOptions, UserVerificationRequirement,
+        };
+
+        let public_key = PublicKeyCredentialRequestOptions::new(&bytes(&options.challenge)?);
+        public_key.set_rp_id(&options.rp_id);
+        public_key.set_user_verification(UserVerificationRequirement::Required);
+        let request = CredentialRequestOptions::new();
+        request.set_public_key(&public_key);
+
+        let credential = resolve(
+            credentials()?
+                .get_with_options(&request)
+                .map_err(js_error)?,
+        )
+        .await?;
+        let response = credential
+            .response()
+            .dyn_into::<AuthenticatorAssertionResponse>()
+            .map_err(|_| "The browser returned an unexpected response".to_string())?;
+
+        Ok(PasskeyAssertion {
+            credential_id: encode(&credential.raw_id()),
+            client_data_json: encode(&response.client_data_json()),
+            authenticator_data: encode(&response.authenticator_data()),
+            signature: encode(&response.signature()),
+        })
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let _ = options;
+        Err("Passkeys are only available in the browser".to_string())
+    }
+}
@@ -0,0 +1,201 @@
+//! Client-side view of the admin session. The access and refresh tokens live in HttpOnly
+//! cookies; localStorage only keeps their expiry times so pages know whether to show admin
+//! controls and when to refresh.
+
+use shared::auth::{Role, SessionExpiry};
+
+#[cfg(target_arch = "wasm32")]
+const STORAGE_KEY: &str = "admin_session";
+/// Set while a tab is refreshing. A rotated refresh token can only be used once, so other
+/// requests and tabs wait for that refresh instead of sending the cookie it replaces.
+#[cfg(target_arch = "wasm32")]
+const REFRESH_LOCK_KEY: &str = "admin_session_refresh";
+/// A lock older than this was left by a tab that closed mid-refresh.
+#[cfg(target_arch = "wasm32")]
+const REFRESH_LOCK_MS: f64 = 10_000.0;
+
+/// Refresh this long before the access token runs out.
+pub const REFRESH_MARGIN_SECS: i64 = 60;
+
+/// Whether the access token should be refreshed before the next request.
+pub fn needs_refresh(expiry: &SessionExpiry, now: i64) -> bool {
+    expiry.access_expires_at - REFRESH_MARGIN_SECS <= now
+}
+
+pub fn store_session(expiry: &SessionExpiry) {
+    #[cfg(target_arch = "wasm32")]
+    if let Ok(Some(storage)) = web_sys::window().unwrap().local_storage() {
+        if let Ok(json) = serde_json::to_string(expiry) {
+            let _ = storage.set_item(STORAGE_KEY, &json);
+        }
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = expiry;
+}
+
+pub fn load_session() -> Option<SessionExpiry> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let storage = web_sys::window()?.local_storage().ok()??;
+        let json = storage.get_item(STORAGE_KEY).ok()??;
+        serde_json::from_str(&json).ok()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    None
+}
+
+pub fn clear_session() {
+    #[cfg(target_arch = "wasm32")]
+    if let Ok(Some(storage)) = web_sys::window().unwrap().local_storage() {
+        let _ = storage.remove_item(STORAGE_KEY);
+    }
+}
+
+/// True while the refresh token is still valid, i.e. the admin is signed in (the access
+/// token may still need refreshing).
+pub fn has_session() -> bool {
+    load_session()
+        .is_some_and(|expiry| expiry.refresh_expires_at > shared::auth::get_current_timestamp())
+}
+
+/// Role of the signed-in account, for hiding controls it isn't allowed to use.
+pub fn current_role() -> Option<Role> {
+    load_session().map(|expiry| expiry.role)
+}
+
+/// Makes sure the access cookie is good for the next request, rotating the refresh token
+/// if needed. Returns false when the admin has to sign in again.
+pub async fn ensure_session() -> bool {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let storage = web_sys::window().and_then(|w| w.local_storage().ok().flatten());
+        // Long enough to outwait a lock left behind by a closed tab
+        for _ in 0..40 {
+            let Some(expiry) = load_session() else {
+                return false;
+            };
+            let now = shared::auth::get_current_timestamp();
+            if expiry.refresh_expires_at <= now {
+                clear_session();
+                return false;
+            }
+            if !needs_refresh(&expiry, now) {
+                return true;
+            }
+
+            // Another request (or tab) is already refreshing; its response updates the
+            // stored expiry, so wait and look again.
+            let locked_at = storage
+                .as_ref()
+                .and_then(|s| s.get_item(REFRESH_LOCK_KEY).ok().flatten())
+                .and_then(|v| v.parse::<f64>().ok());
+            if locked_at.is_some_and(|t| js_sys::Date::now() - t < REFRESH_LOCK_MS) {
+                sleep_ms(300).await;
+                continue;
+            }
+            if let Some(storage) = &storage {
+                let _ = storage.set_item(REFRESH_LOCK_KEY, &js_sys::Date::now().to_string());
+            }
+            let resp = gloo_net::http::Request::post("/admin/refresh").send().await;
+            let refreshed = match resp {
+                Ok(resp) if resp.ok() => match resp.json::<SessionExpiry>().await {
+                    Ok(expiry) => {
+                        store_session(&expiry);
+                        true
+                    }
+                    Err(_) => false,
+                },
+                Ok(_) => {
+                    clear_session();
+                    false
+                }
+                Err(_) => false,
+            };
+            if let Some(storage) = &storage {
+                let _ = storage.remove_item(REFRESH_LOCK_KEY);
+            }
+            return refreshed;
+        }
+        false
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    false
+}
+
+/// Calls one of the JSON endpoints under `/admin`, refreshing the session first and sending
+/// `body` as JSON when given.
+/// `Ok(None)` means the endpoint answered without a body.
+pub async fn admin_request<R: serde::de::DeserializeOwned>(
+    method: &'static str,
+    url: &str,
+    body: Option<serde_json::Value>,
+) -> Result<Option<R>, String> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        use gloo_net::http::Request;
+
+        if !ensure_session().await {
+            return Err("Your session has expired. Please log in again.".to_string());
+        }
+        let builder = match method {
+            "GET" => Request::get(url),
+            "DELETE" => Request::delete(url),
+            _ => Request::post(url),
+        };
+        let resp = match body {
+            Some(body) => builder.json(&body).map_err(|e| e.to_string())?.send().await,
+            None => builder.send().await,
+        }
+        .map_err(|e| format!("Network error: {}", e))?;
+
+        if !resp.ok() {
+            let text = resp.text().await.unwrap_or_default();
+            return Err(if text.is_empty() {
+                format!("HTTP {}", resp.status())
+            } else {
+                text
+            });
+        }
+        if matches!(resp.status(), 201 | 204) {
+            return Ok(None);
+        }
+        resp.json::<R>().await.map(Some).map_err(|e| e.to_string())
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let _ = (method, url, body);
+        Err("Not available during server rendering".to_string())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn sleep_ms(ms: i32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        let _ = web_sys::window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms);
+    });
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+/// Server functions report a missing, expired or revoked session this way.
+pub fn is_auth_error(message: &str) -> bool {
+    message.contains("Invalid token")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_needs_refresh_near_expiry() {
+        let expiry = SessionExpiry {
+            access_expires_at: 1_000,
+            refresh_expires_at: 10_000,
+            role: Role::Admin,
+        };
+        assert!(!needs_refresh(&expiry, 1_000 - REFRESH_MARGIN_SECS - 1));
+        assert!(needs_refresh(&expiry, 1_000 - REFRESH_MARGIN_SECS));
+        assert!(needs_refresh(&expiry, 2_000));
+    }
+}
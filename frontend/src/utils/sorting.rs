@@ -100,6 +100,7 @@ mod tests {
     fn test_sort_articles_newest_first() {
         let mut articles = vec![
             Article {
+                id: None,
                 slug: "old-article".to_string(),
                 title: "Old Article".to_string(),
                 iso_date: "2020-07-16".to_string(),
@@ -111,8 +112,10 @@ mod tests {
                 excerpt: String::new(),
                 byline: None,
                 status: None,
+                image_variants: Default::default(),
             },
             Article {
+                id: None,
                 slug: "mid-article".to_string(),
                 title: "Mid Article".to_string(),
                 iso_date: "2025-05-21".to_string(),
@@ -124,8 +127,10 @@ mod tests {
                 excerpt: String::new(),
                 byline: None,
                 status: None,
+                image_variants: Default::default(),
             },
             Article {
+                id: None,
                 slug: "new-article".to_string(),
                 title: "New Article".to_string(),
                 iso_date: "2026-08-01".to_string(),
@@ -137,6 +142,7 @@ mod tests {
                 excerpt: String::new(),
                 byline: None,
                 status: None,
+                image_variants: Default::default(),
             },
         ];
 
@@ -161,6 +167,7 @@ mod tests {
     fn test_extract_highlight_articles() {
         let articles = vec![
             Article {
+                id: None,
                 slug: "article-1".to_string(),
                 title: "Article 1".to_string(),
                 iso_date: "2025-01-01".to_string(),
@@ -172,8 +179,10 @@ mod tests {
                 excerpt: String::new(),
                 byline: None,
                 status: None,
+                image_variants: Default::default(),
             },
             Article {
+                id: None,
                 slug: "article-2".to_string(),
                 title: "Article 2".to_string(),
                 iso_date: "2025-01-02".to_string(),
@@ -185,8 +194,10 @@ mod tests {
                 excerpt: String::new(),
                 byline: None,
                 status: None,
+                image_variants: Default::default(),
             },
             Article {
+                id: None,
                 slug: "article-3".to_string(),
                 title: "Article 3".to_string(),
                 iso_date: "2025-01-03".to_string(),
@@ -198,6 +209,7 @@ mod tests {
                 excerpt: String::new(),
                 byline: None,
                 status: None,
+                image_variants: Default::default(),
             },
         ];
 
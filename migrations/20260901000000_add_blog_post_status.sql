@@ -0,0 +1,5 @@
+-- Add status column to blog posts so they follow the same draft/scheduled/published rules as articles
+
+ALTER TABLE blog_posts ADD COLUMN status TEXT NOT NULL DEFAULT 'published';
+
+CREATE INDEX IF NOT EXISTS idx_blog_posts_status_published_at ON blog_posts(status, published_at DESC);
@@ -0,0 +1,16 @@
+-- Index and validate creative_works.published_at the same way as articles and blog_posts
+CREATE INDEX IF NOT EXISTS idx_creative_works_status_published_at ON creative_works(status, published_at DESC);
+
+CREATE TRIGGER check_creative_works_published_at_insert
+BEFORE INSERT ON creative_works
+WHEN NEW.published_at IS NOT NULL AND NEW.published_at != strftime('%Y-%m-%dT%H:%M:%fZ', NEW.published_at)
+BEGIN
+    SELECT RAISE(ABORT, 'published_at must be in %Y-%m-%dT%H:%M:%fZ format');
+END;
+
+CREATE TRIGGER check_creative_works_published_at_update
+BEFORE UPDATE ON creative_works
+WHEN NEW.published_at IS NOT NULL AND NEW.published_at IS NOT OLD.published_at AND NEW.published_at != strftime('%Y-%m-%dT%H:%M:%fZ', NEW.published_at)
+BEGIN
+    SELECT RAISE(ABORT, 'published_at must be in %Y-%m-%dT%H:%M:%fZ format');
+END;
@@ -0,0 +1,93 @@
+-- Full-text search over articles, blog posts and pages (SQLite FTS5)
+--
+-- `search_index` holds one row per document with HTML tags stripped from the body.
+-- Triggers only queue changed rows in `search_queue`; `search::ssr::sync_index` strips
+-- the HTML in Rust and moves them into the index. A row may already be queued, so each
+-- trigger clears it first: an upsert's conflict policy overrides `OR IGNORE` inside the
+-- trigger. Visibility (drafts, scheduled posts) is checked at query time.
+
+CREATE VIRTUAL TABLE search_index USING fts5(
+    kind UNINDEXED, -- 'article', 'blog', 'page'
+    slug UNINDEXED,
+    title,
+    excerpt,
+    body,
+    tokenize = 'porter unicode61 remove_diacritics 2'
+);
+
+CREATE TABLE search_queue (
+    kind TEXT NOT NULL,
+    slug TEXT NOT NULL,
+    PRIMARY KEY (kind, slug)
+);
+
+-- Backfill existing content
+INSERT INTO search_queue (kind, slug) SELECT 'article', slug FROM articles;
+INSERT INTO search_queue (kind, slug) SELECT 'blog', slug FROM blog_posts;
+INSERT INTO search_queue (kind, slug) SELECT 'page', slug FROM pages;
+
+-- Articles
+CREATE TRIGGER articles_search_insert
+AFTER INSERT ON articles
+BEGIN
+    DELETE FROM search_queue WHERE kind = 'article' AND slug = NEW.slug;
+    INSERT INTO search_queue (kind, slug) VALUES ('article', NEW.slug);
+END;
+
+CREATE TRIGGER articles_search_update
+AFTER UPDATE OF slug, title, excerpt, content ON articles
+BEGIN
+    DELETE FROM search_index WHERE kind = 'article' AND slug = OLD.slug;
+    DELETE FROM search_queue WHERE kind = 'article' AND slug = NEW.slug;
+    INSERT INTO search_queue (kind, slug) VALUES ('article', NEW.slug);
+END;
+
+CREATE TRIGGER articles_search_delete
+AFTER DELETE ON articles
+BEGIN
+    DELETE FROM search_index WHERE kind = 'article' AND slug = OLD.slug;
+END;
+
+-- Blog posts
+CREATE TRIGGER blog_posts_search_insert
+AFTER INSERT ON blog_posts
+BEGIN
+    DELETE FROM search_queue WHERE kind = 'blog' AND slug = NEW.slug;
+    INSERT INTO search_queue (kind, slug) VALUES ('blog', NEW.slug);
+END;
+
+CREATE TRIGGER blog_posts_search_update
+AFTER UPDATE OF slug, title, content ON blog_posts
+BEGIN
+    DELETE FROM search_index WHERE kind = 'blog' AND slug = OLD.slug;
+    DELETE FROM search_queue WHERE kind = 'blog' AND slug = NEW.slug;
+    INSERT INTO search_queue (kind, slug) VALUES ('blog', NEW.slug);
+END;
+
+CREATE TRIGGER blog_posts_search_delete
+AFTER DELETE ON blog_posts
+BEGIN
+    DELETE FROM search_index WHERE kind = 'blog' AND slug = OLD.slug;
+END;
+
+-- Pages
+CREATE TRIGGER pages_search_insert
+AFTER INSERT ON pages
+BEGIN
+    DELETE FROM search_queue WHERE kind = 'page' AND slug = NEW.slug;
+    INSERT INTO search_queue (kind, slug) VALUES ('page', NEW.slug);
+END;
+
+CREATE TRIGGER pages_search_update
+AFTER UPDATE OF slug, title, content ON pages
+BEGIN
+    DELETE FROM search_index WHERE kind = 'page' AND slug = OLD.slug;
+    DELETE FROM search_queue WHERE kind = 'page' AND slug = NEW.slug;
+    INSERT INTO search_queue (kind, slug) VALUES ('page', NEW.slug);
+END;
+
+CREATE TRIGGER pages_search_delete
+AFTER DELETE ON pages
+BEGIN
+    DELETE FROM search_index WHERE kind = 'page' AND slug = OLD.slug;
+END;
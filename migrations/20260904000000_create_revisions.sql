@@ -0,0 +1,33 @@
+-- Revision history for articles and pages. Every save appends a snapshot so edits
+-- can be diffed and rolled back.
+
+CREATE TABLE article_revisions (
+    -- Uses MACRO: UUID_V4_GENERATOR
+    id TEXT PRIMARY KEY DEFAULT (lower(hex(randomblob(4))) || '-' || lower(hex(randomblob(2))) || '-4' || substr(lower(hex(randomblob(2))),2) || '-' || substr('89ab', (random() & 3) + 1, 1) || substr(lower(hex(randomblob(2))),2) || '-' || lower(hex(randomblob(6)))),
+    article_id TEXT NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    author_sub TEXT NOT NULL, -- `sub` claim of the JWT that made the change
+    title TEXT NOT NULL,
+    content TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE INDEX idx_article_revisions_article_id ON article_revisions(article_id, created_at DESC);
+
+CREATE TABLE page_revisions (
+    -- Uses MACRO: UUID_V4_GENERATOR
+    id TEXT PRIMARY KEY DEFAULT (lower(hex(randomblob(4))) || '-' || lower(hex(randomblob(2))) || '-4' || substr(lower(hex(randomblob(2))),2) || '-' || substr('89ab', (random() & 3) + 1, 1) || substr(lower(hex(randomblob(2))),2) || '-' || lower(hex(randomblob(6)))),
+    page_id TEXT NOT NULL REFERENCES pages(id) ON DELETE CASCADE,
+    author_sub TEXT NOT NULL,
+    title TEXT NOT NULL,
+    content TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE INDEX idx_page_revisions_page_id ON page_revisions(page_id, created_at DESC);
+
+-- Seed each existing item with its current content so the first edit has a baseline
+INSERT INTO article_revisions (article_id, author_sub, title, content, created_at)
+SELECT id, 'system', title, content, updated_at FROM articles;
+
+INSERT INTO page_revisions (page_id, author_sub, title, content, created_at)
+SELECT id, 'system', title, content, updated_at FROM pages;
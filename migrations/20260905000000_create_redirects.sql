@@ -0,0 +1,11 @@
+-- Old slugs that should permanently redirect to the item now holding them.
+-- `target_id` points at the row's id so renaming twice still lands on the current slug.
+CREATE TABLE redirects (
+    kind TEXT NOT NULL, -- 'article'
+    old_slug TEXT NOT NULL,
+    target_id TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY (kind, old_slug)
+);
+
+CREATE INDEX idx_redirects_target_id ON redirects(kind, target_id);
@@ -0,0 +1,13 @@
+-- What the background scheduler published and when. `caught_up` marks posts whose
+-- publish time passed while the server was down and were published on startup.
+CREATE TABLE publish_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL, -- 'article', 'blog', 'creative'
+    target_id TEXT NOT NULL,
+    slug TEXT NOT NULL,
+    scheduled_for TEXT NOT NULL,
+    published_at DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    caught_up INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE INDEX idx_publish_log_published_at ON publish_log(published_at);
@@ -0,0 +1,10 @@
+-- Rotating refresh tokens. Every token issued for a session belongs to the same family
+-- (`session_jti`); presenting a token that was already rotated revokes the session.
+CREATE TABLE refresh_tokens (
+    token_hash TEXT PRIMARY KEY, -- SHA-256 of the cookie value
+    session_jti TEXT NOT NULL REFERENCES sessions(jti) ON DELETE CASCADE,
+    created_at DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    rotated_at DATETIME
+);
+
+CREATE INDEX idx_refresh_tokens_session_jti ON refresh_tokens(session_jti);
@@ -0,0 +1,27 @@
+-- Optional TOTP second factor. A row with `enabled_at` NULL is an enrollment that has
+-- not been confirmed with a code yet and is ignored at login.
+CREATE TABLE user_totp (
+    user_id TEXT PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    secret TEXT NOT NULL, -- base32, as shown to the authenticator app
+    last_used_step INTEGER, -- rejects replaying a code within its validity window
+    created_at DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    enabled_at DATETIME
+);
+
+-- One-time recovery codes, stored as SHA-256 hashes.
+CREATE TABLE recovery_codes (
+    code_hash TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    used_at DATETIME
+);
+
+CREATE INDEX idx_recovery_codes_user_id ON recovery_codes(user_id);
+
+-- Pending second login step: the password was correct, the code is still to come.
+CREATE TABLE login_challenges (
+    challenge_hash TEXT PRIMARY KEY, -- SHA-256 of the value handed to the client
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    expires_at DATETIME NOT NULL
+);
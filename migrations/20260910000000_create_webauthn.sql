@@ -0,0 +1,22 @@
+-- Passkeys (WebAuthn credentials). Only ES256 keys are accepted; `public_key` holds the
+-- uncompressed P-256 point.
+CREATE TABLE webauthn_credentials (
+    id TEXT PRIMARY KEY, -- base64url credential id
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    public_key BLOB NOT NULL,
+    sign_count INTEGER NOT NULL DEFAULT 0,
+    created_at DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    last_used_at DATETIME
+);
+
+CREATE INDEX idx_webauthn_credentials_user_id ON webauthn_credentials(user_id);
+
+-- Outstanding ceremony challenges. Registration challenges belong to the signed-in user;
+-- login challenges have no user until a credential answers them.
+CREATE TABLE webauthn_challenges (
+    challenge TEXT PRIMARY KEY, -- base64url
+    kind TEXT NOT NULL CHECK (kind IN ('register', 'login')),
+    user_id TEXT REFERENCES users(id) ON DELETE CASCADE,
+    expires_at DATETIME NOT NULL
+);
@@ -0,0 +1,5 @@
+-- New accounts start with the least privilege; accounts from before roles existed keep
+-- full access.
+ALTER TABLE users ADD COLUMN role TEXT NOT NULL DEFAULT 'contributor' CHECK (role IN ('admin', 'editor', 'contributor'));
+
+UPDATE users SET role = 'admin';
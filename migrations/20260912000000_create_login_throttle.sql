@@ -0,0 +1,20 @@
+-- Request counts for the persistent rate limiter, one fixed window per client and bucket.
+-- Unlike the in-memory governor these survive restarts.
+CREATE TABLE rate_limit_windows (
+    bucket TEXT NOT NULL,
+    key TEXT NOT NULL, -- client IP from TrustedProxyIpKeyExtractor
+    window_start INTEGER NOT NULL, -- unix seconds
+    hits INTEGER NOT NULL,
+    PRIMARY KEY (bucket, key)
+);
+
+-- Failed logins per client IP and per account. Once `failures` reaches the lockout
+-- threshold `locked_until` is set, doubling with every further failure.
+CREATE TABLE login_failures (
+    kind TEXT NOT NULL CHECK (kind IN ('ip', 'account')),
+    key TEXT NOT NULL, -- client IP or username
+    failures INTEGER NOT NULL,
+    last_failure_at DATETIME NOT NULL,
+    locked_until DATETIME,
+    PRIMARY KEY (kind, key)
+);
@@ -0,0 +1,25 @@
+-- Security audit log: who signed in, changed a password, or changed content and media,
+-- from which client IP. Rows can only be added; triggers reject edits and deletions.
+CREATE TABLE audit_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    created_at DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    action TEXT NOT NULL,
+    actor TEXT, -- user id; not a foreign key so events outlive deleted accounts
+    ip TEXT, -- client IP from extract_client_ip
+    target TEXT, -- slug, object name or username acted on
+    before_summary TEXT,
+    after_summary TEXT
+);
+
+CREATE INDEX idx_audit_events_action ON audit_events(action, id DESC);
+CREATE INDEX idx_audit_events_actor ON audit_events(actor, id DESC);
+
+CREATE TRIGGER audit_events_no_update BEFORE UPDATE ON audit_events
+BEGIN
+    SELECT RAISE(ABORT, 'audit_events is append-only');
+END;
+
+CREATE TRIGGER audit_events_no_delete BEFORE DELETE ON audit_events
+BEGIN
+    SELECT RAISE(ABORT, 'audit_events is append-only');
+END;
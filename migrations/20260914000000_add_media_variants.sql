@@ -0,0 +1,9 @@
+-- Uploaded files are recorded in media_items, with the resized copies made of images
+ALTER TABLE media_items ADD COLUMN object_name TEXT;
+ALTER TABLE media_items ADD COLUMN width INTEGER;
+ALTER TABLE media_items ADD COLUMN height INTEGER;
+-- JSON array of {"width", "format": "webp" | "jpeg", "url"}
+ALTER TABLE media_items ADD COLUMN variants TEXT NOT NULL DEFAULT '[]';
+
+CREATE UNIQUE INDEX idx_media_items_object_name ON media_items(object_name);
+CREATE INDEX idx_media_items_url ON media_items(url);
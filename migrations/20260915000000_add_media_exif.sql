@@ -0,0 +1,9 @@
+-- Camera details read from an upload's EXIF block (taken_at already exists)
+ALTER TABLE media_items ADD COLUMN camera TEXT;
+ALTER TABLE media_items ADD COLUMN lens TEXT;
+-- Base64 of the EXIF block as uploaded, kept so stripping can be undone; never served
+ALTER TABLE media_items ADD COLUMN original_exif TEXT;
+-- Whether the uploaded EXIF had GPS or serial-number tags
+ALTER TABLE media_items ADD COLUMN has_private_metadata INTEGER NOT NULL DEFAULT 0 CHECK (has_private_metadata IN (0, 1));
+-- Whether those tags are removed from the public file
+ALTER TABLE media_items ADD COLUMN strip_metadata INTEGER NOT NULL DEFAULT 1 CHECK (strip_metadata IN (0, 1));
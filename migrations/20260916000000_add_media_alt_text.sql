@@ -0,0 +1,3 @@
+-- The media library lists media_items rather than the store, newest first
+ALTER TABLE media_items ADD COLUMN alt_text TEXT;
+CREATE INDEX idx_media_items_created_at ON media_items(created_at);
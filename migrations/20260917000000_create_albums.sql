@@ -0,0 +1,31 @@
+-- Public galleries. Uploads default to the photography category, so an item only shows
+-- on /photography or /visual-art once it's been marked for the galleries.
+ALTER TABLE media_items ADD COLUMN show_in_gallery INTEGER NOT NULL DEFAULT 0 CHECK (show_in_gallery IN (0, 1));
+CREATE INDEX idx_media_items_gallery ON media_items(category, show_in_gallery, taken_at);
+
+-- Named collections of gallery items, in the order admins arrange them
+CREATE TABLE albums (
+    -- Uses MACRO: UUID_V4_GENERATOR
+    id TEXT PRIMARY KEY DEFAULT (lower(hex(randomblob(4))) || '-' || lower(hex(randomblob(2))) || '-4' || substr(lower(hex(randomblob(2))),2) || '-' || substr('89ab', (random() & 3) + 1, 1) || substr(lower(hex(randomblob(2))),2) || '-' || lower(hex(randomblob(6)))),
+    slug TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT,
+    category TEXT NOT NULL CHECK(category IN ('photography', 'visual_art', 'video', 'j_school')),
+    context TEXT NOT NULL DEFAULT 'personal' CHECK(context IN ('personal', 'professional')),
+    position INTEGER NOT NULL DEFAULT 0,
+    created_at DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    updated_at DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE INDEX idx_albums_category_position ON albums(category, position);
+
+CREATE TABLE album_items (
+    -- Uses MACRO: UUID_V4_GENERATOR
+    id TEXT PRIMARY KEY DEFAULT (lower(hex(randomblob(4))) || '-' || lower(hex(randomblob(2))) || '-4' || substr(lower(hex(randomblob(2))),2) || '-' || substr('89ab', (random() & 3) + 1, 1) || substr(lower(hex(randomblob(2))),2) || '-' || lower(hex(randomblob(6)))),
+    album_id TEXT NOT NULL REFERENCES albums(id) ON DELETE CASCADE,
+    media_id TEXT NOT NULL REFERENCES media_items(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL DEFAULT 0,
+    UNIQUE (album_id, media_id)
+);
+
+CREATE INDEX idx_album_items_position ON album_items(album_id, position);
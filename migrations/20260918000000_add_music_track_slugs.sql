@@ -0,0 +1,5 @@
+-- Tracks get their own pages at /music/<slug>
+ALTER TABLE music_tracks ADD COLUMN slug TEXT;
+UPDATE music_tracks SET slug = id WHERE slug IS NULL;
+CREATE UNIQUE INDEX idx_music_tracks_slug ON music_tracks(slug);
+CREATE INDEX idx_music_tracks_published_at ON music_tracks(published_at);
@@ -0,0 +1,3 @@
+-- Base64 of an uploaded XMP packet with GPS or serial-number properties. The public file
+-- drops the packet while metadata is stripped; this copy puts it back. Never served.
+ALTER TABLE media_items ADD COLUMN original_xmp TEXT;
@@ -12,7 +12,6 @@ fi
 
 # Check dependencies
 command -v cargo &> /dev/null || { echo "❌ cargo not found. Install Rust from https://rustup.rs/"; exit 1; }
-command -v sqlite3 &> /dev/null || { echo "❌ sqlite3 not found"; exit 1; }
 
 echo "✅ All dependencies found"
 echo ""
@@ -30,40 +29,14 @@ DB_FILE="${DB_FILE:-sqlite.db}"
 touch "$DB_FILE"
 chmod 600 "$DB_FILE"
 
-cargo sqlx migrate run -D "$DATABASE_URL" || true
+cargo run --quiet --bin admin -- migrate
 
 echo ""
 echo "👤 Creating default admin user..."
 # WARN: The seeded password below is 'demo-admin-2026!'.
 # Anyone reading the repository knows these default credentials. Check that this
 # dev instance isn't exposed to untrusted networks.
-# Generate hash dynamically
-if [ ! -x "hgen/target/release/hgen" ]; then
-  (cd hgen && cargo build --release --quiet)
-fi
-ADMIN_HASH=$(echo -n "demo-admin-2026!" | ./hgen/target/release/hgen 2>/dev/null | tail -n 1)
-
-if ! [[ "$ADMIN_HASH" =~ ^\$argon2 ]]; then
-  echo "❌ hgen failed or produced unexpected output"
-  exit 1
-fi
-
-# Fallback to python UUID or kernel uuid if uuidgen missing
-ADMIN_UUID=$(uuidgen 2>/dev/null || cat /proc/sys/kernel/random/uuid 2>/dev/null || python3 -c 'import uuid; print(uuid.uuid4())' 2>/dev/null || { echo "❌ Could not generate a UUID. Please install uuidgen."; exit 1; })
-SAFE_UUID=$(echo "$ADMIN_UUID" | tr -cd 'a-fA-F0-9-')
-
-if ! [[ "$SAFE_UUID" =~ ^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$ ]]; then
-  echo "❌ Invalid Admin UUID format generated: $SAFE_UUID"
-  exit 1
-fi
-
-ESCAPED_HASH="${ADMIN_HASH//\'/\'\'}"
-sqlite3 "$DB_FILE" <<EOF
-.param set @id '$SAFE_UUID'
-.param set @hash '$ESCAPED_HASH'
-INSERT INTO users (id, username, password_hash) VALUES (@id, 'admin', @hash) ON CONFLICT (username) DO NOTHING;
-EOF
-if [ $? -ne 0 ]; then
+if ! echo -n "demo-admin-2026!" | cargo run --quiet --bin admin -- user create admin; then
   echo "⚠️ Could not create user (may already exist)"
 fi
 
@@ -5,6 +5,213 @@ pub const ARGON2_M_COST: u32 = 19456;
 pub const ARGON2_T_COST: u32 = 2;
 pub const ARGON2_P_COST: u32 = 1;
 
+/// HttpOnly cookie carrying the short-lived access token.
+pub const ACCESS_COOKIE: &str = "admin_access";
+/// HttpOnly cookie carrying the rotating refresh token; only sent to `/admin`.
+pub const REFRESH_COOKIE: &str = "admin_refresh";
+
+/// Returned by login and refresh. The tokens themselves stay in HttpOnly cookies; the
+/// client keeps these unix timestamps to know when to refresh and whether it is signed in.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SessionExpiry {
+    pub access_expires_at: i64,
+    pub refresh_expires_at: i64,
+    /// Only used to decide which controls to show; the server checks the token's role.
+    #[serde(default)]
+    pub role: Role,
+}
+
+/// What an account may do, in increasing order of privilege: contributors write drafts,
+/// editors publish and delete content, admins also delete media and manage users.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    // Tokens and stored sessions from before roles existed fall back to the least privilege
+    #[default]
+    Contributor,
+    Editor,
+    Admin,
+}
+
+impl Role {
+    pub const ALL: [Role; 3] = [Role::Contributor, Role::Editor, Role::Admin];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Role::Contributor => "contributor",
+            Role::Editor => "editor",
+            Role::Admin => "admin",
+        }
+    }
+
+    pub fn can_publish(self) -> bool {
+        self >= Role::Editor
+    }
+
+    pub fn can_manage_users(self) -> bool {
+        self == Role::Admin
+    }
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Role::ALL
+            .into_iter()
+            .find(|role| role.as_str() == s)
+            .ok_or_else(|| format!("Unknown role: {}", s))
+    }
+}
+
+/// An account as listed on the user management page.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UserInfo {
+    pub id: String,
+    pub username: String,
+    pub role: Role,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CreateUserRequest {
+    pub username: String,
+    pub password: String,
+    pub role: Role,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct UpdateRoleRequest {
+    pub role: Role,
+}
+
+/// A client IP or account with recent failed logins, as listed on the lockouts page.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LockoutInfo {
+    /// `ip` or `account`.
+    pub kind: String,
+    /// The client IP or username.
+    pub key: String,
+    pub failures: i64,
+    /// Set while the lockout is in force.
+    pub locked_until: Option<String>,
+    pub last_failure_at: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct UnlockRequest {
+    pub kind: String,
+    pub key: String,
+}
+
+/// Returned by login (with `202 Accepted`) instead of a session when the user has
+/// two-factor authentication enabled. The challenge is exchanged at `/admin/login/totp`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TotpChallenge {
+    pub challenge: String,
+}
+
+/// Second login step: a TOTP code or an unused recovery code.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TotpLoginRequest {
+    pub challenge: String,
+    pub code: String,
+}
+
+/// A TOTP or recovery code confirming a change to two-factor settings.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TotpCodeRequest {
+    pub code: String,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TotpStatus {
+    pub enabled: bool,
+    pub recovery_codes_remaining: i64,
+}
+
+/// A pending enrollment: the secret for manual entry and the `otpauth://` URI that
+/// authenticator apps read from a QR code.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TotpSetup {
+    pub secret: String,
+    pub provisioning_uri: String,
+    /// `provisioning_uri` as an SVG QR code, drawn on the server.
+    pub qr_code_svg: String,
+}
+
+/// Freshly generated recovery codes; they are only ever shown once.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RecoveryCodes {
+    pub codes: Vec<String>,
+}
+
+/// Options for `navigator.credentials.create()`. Binary values are base64url without padding.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PasskeyRegistrationOptions {
+    pub challenge: String,
+    pub rp_id: String,
+    pub rp_name: String,
+    pub user_id: String,
+    pub user_name: String,
+    /// Credentials the user already has, so the authenticator doesn't register twice.
+    pub exclude_credentials: Vec<String>,
+}
+
+/// The parts of a new credential the server checks, as reported by the browser.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PasskeyRegistration {
+    pub name: String,
+    pub credential_id: String,
+    pub client_data_json: String,
+    pub authenticator_data: String,
+    /// DER SubjectPublicKeyInfo from `getPublicKey()`.
+    pub public_key: String,
+    /// COSE algorithm identifier from `getPublicKeyAlgorithm()`.
+    pub public_key_algorithm: i64,
+}
+
+/// Options for `navigator.credentials.get()`; any discoverable credential may answer.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PasskeyLoginOptions {
+    pub challenge: String,
+    pub rp_id: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PasskeyAssertion {
+    pub credential_id: String,
+    pub client_data_json: String,
+    pub authenticator_data: String,
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PasskeyInfo {
+    pub id: String,
+    pub name: String,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+}
+
 #[cfg(feature = "ssr")]
 static JWT_SECRET: OnceLock<Vec<u8>> = OnceLock::new();
 
@@ -70,7 +277,8 @@ pub fn is_token_expired(token: &str) -> bool {
     true
 }
 
-fn get_current_timestamp() -> i64 {
+/// Current unix time in seconds, on both the WASM client and the server.
+pub fn get_current_timestamp() -> i64 {
     #[cfg(target_arch = "wasm32")]
     {
         (js_sys::Date::now() / 1000.0) as i64
@@ -132,4 +340,20 @@ mod tests {
         let future_token = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.eyJzdWIiOiIxMjM0NTY3ODkwIiwiZXhwIjoyNTI0NjA4MDAwfQ.signature";
         assert!(!is_token_expired(future_token));
     }
+
+    #[test]
+    fn test_role_order_and_parsing() {
+        for role in Role::ALL {
+            assert_eq!(role.as_str().parse::<Role>(), Ok(role));
+        }
+        assert!(Role::Contributor < Role::Editor && Role::Editor < Role::Admin);
+        assert!(!Role::Contributor.can_publish());
+        assert!(Role::Editor.can_publish() && !Role::Editor.can_manage_users());
+        assert!("owner".parse::<Role>().is_err());
+
+        // Sessions stored before roles existed get the least privilege
+        let expiry: SessionExpiry =
+            serde_json::from_str(r#"{"access_expires_at":1,"refresh_expires_at":2}"#).unwrap();
+        assert_eq!(expiry.role, Role::Contributor);
+    }
 }
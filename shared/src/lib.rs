@@ -36,7 +36,7 @@ pub struct BlogPost {
     pub tags: Option<Vec<String>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum MediaCategory {
     Photography,
@@ -45,6 +45,24 @@ pub enum MediaCategory {
     JSchool,
 }
 
+impl MediaCategory {
+    pub const ALL: [MediaCategory; 4] = [
+        MediaCategory::Photography,
+        MediaCategory::VisualArt,
+        MediaCategory::Video,
+        MediaCategory::JSchool,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MediaCategory::Photography => "Photography",
+            MediaCategory::VisualArt => "Visual art",
+            MediaCategory::Video => "Video",
+            MediaCategory::JSchool => "J-School",
+        }
+    }
+}
+
 impl std::fmt::Display for MediaCategory {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -70,13 +88,24 @@ impl std::str::FromStr for MediaCategory {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum MediaContext {
     Personal,
     Professional,
 }
 
+impl MediaContext {
+    pub const ALL: [MediaContext; 2] = [MediaContext::Personal, MediaContext::Professional];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MediaContext::Personal => "Personal",
+            MediaContext::Professional => "Professional",
+        }
+    }
+}
+
 impl std::fmt::Display for MediaContext {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -178,3 +207,14 @@ pub struct PageContent {
     pub content: String,
     pub updated_at: Option<DateTime<Utc>>,
 }
+
+/// A ranked full-text search hit. `snippet` is HTML with matches wrapped in `<mark>`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SearchResult {
+    pub kind: String,
+    pub slug: String,
+    pub title: String,
+    pub url: String,
+    pub snippet: String,
+    pub rank: f64,
+}